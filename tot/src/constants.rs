@@ -34,8 +34,11 @@ pub const TOKEN_SYMBOL: &str = "TOT";
 
 /// 代币精度（小数位数）
 /// Solana代币使用u64存储，精度决定了最小单位
-/// 例如：精度为9时，1 TOT = 1,000,000,000 基础单位
-pub const TOKEN_DECIMALS: u8 = 9;
+/// 例如：精度为6时，1 TOT = 1,000,000 基础单位
+///
+/// 注意：202.7B总量在9位精度下为2.027×10^20，超出u64上限（约1.8×10^19），
+/// 因此使用6位精度
+pub const TOKEN_DECIMALS: u8 = 6;
 
 /// 总供应量
 /// 
@@ -43,22 +46,34 @@ pub const TOKEN_DECIMALS: u8 = 9;
 /// 
 /// 数学含义: 对应2027年，这是TOT的终极时间锚点
 /// 
-/// 存储说明: 实际存储值需要乘以 10^9 (decimals)
-/// 计算公式: 202.7B * 10^9 = 202,700,000,000,000,000,000
+/// 存储说明: 实际存储值需要乘以 10^6 (decimals)
+/// 计算公式: 202.7B * 10^6 = 202,700,000,000,000,000
 /// 
 /// 重要特性:
 /// - 一次性铸造，永不增发
 /// - 可以通过销毁减少供应量
 /// - 总供应量 = 五大池子分配之和
-pub const TOTAL_SUPPLY: u64 = 202_700_000_000_000_000_000;
+pub const TOTAL_SUPPLY: u64 = 202_700_000_000_000_000;
+
+/// Transfer Hook程序ID
+/// 
+/// Hook是独立部署的程序（见`transfer-hook/`），`initialize`把它写入Mint的Transfer Hook扩展。
+/// 
+/// 不能使用主程序自身作为Hook：主程序发起的transfer_checked CPI
+/// 会经Token-2022再次进入主程序，Solana运行时禁止这种间接重入。
+pub const TRANSFER_HOOK_PROGRAM_ID: Pubkey = transfer_hook_program::ID;
+
+/// Transfer Hook程序ID声明（与`transfer-hook/lib.rs`中的`declare_id!`一致）
+pub mod transfer_hook_program {
+    use super::*;
+    declare_id!("ToTHook111111111111111111111111111111111111");
+}
 
 /// 五大池子分配模块
 /// 
 /// 定义了TOT代币的五大池子分配方案，每个池子都有特定的用途和释放机制。
-/// 所有数值单位为基础单位（已乘以10^9），总和等于TOTAL_SUPPLY。
+/// 所有数值单位为基础单位（已乘以10^6），总和等于TOTAL_SUPPLY。
 pub mod allocation {
-    use super::*;
-    
     /// 胜利日基金池
     /// 
     /// 数量: 20.27B (占总供应量的10%)
@@ -75,7 +90,7 @@ pub mod allocation {
     /// - 这是"破釜沉舟"机制，确保团队与项目共进退
     /// 
     /// 释放机制: 时间锁，到期后一次性解锁
-    pub const VICTORY_FUND: u64 = 20_270_000_000_000_000_000;
+    pub const VICTORY_FUND: u64 = 20_270_000_000_000_000;
     
     /// 历史重铸池（初始流动性池）
     /// 
@@ -92,7 +107,7 @@ pub mod allocation {
     /// - 寓意：1949年的历史不可逆转，TOT的底池也永远不可撤资
     /// 
     /// 释放机制: 立即释放，用于创建流动性池
-    pub const HISTORY_LP: u64 = 19_490_000_000_000_000_000;
+    pub const HISTORY_LP: u64 = 19_490_000_000_000_000;
     
     /// 认知作战池（社区激励池）
     /// 
@@ -112,7 +127,7 @@ pub mod allocation {
     /// - 365天线性释放
     /// - 每天释放约0.1%作为当天的"作战经费"
     /// - 确保资金持续投入社区建设
-    pub const CYBER_ARMY: u64 = 14_500_000_000_000_000_000;
+    pub const CYBER_ARMY: u64 = 14_500_000_000_000_000;
     
     /// 外资统战池（机构投资者池）
     /// 
@@ -132,7 +147,7 @@ pub mod allocation {
     /// - 需要3-of-5多签才能释放
     /// - 确保机构资金的安全和合规
     /// - 寓意："挟洋自重"的逆向操作——让洋人不得不买我们的账
-    pub const GLOBAL_ALLIANCE: u64 = 7_040_000_000_000_000_000;
+    pub const GLOBAL_ALLIANCE: u64 = 7_040_000_000_000_000;
     
     /// 资产锚定池（RWA储备池）
     /// 
@@ -155,15 +170,13 @@ pub mod allocation {
     ///   * "新台币置换"（法币兑换）
     /// - 它不是币，它是未来的房产证
     /// - 通过RWA智能合约验证后才会解锁对应数量的TOT
-    pub const ASSET_ANCHOR: u64 = 141_400_000_000_000_000_000;
+    pub const ASSET_ANCHOR: u64 = 141_400_000_000_000_000;
 }
 
 /// 时间常量模块
 /// 
 /// 定义了系统中使用的时间相关常量，包括解锁时间、释放周期等。
 pub mod time {
-    use super::*;
-    
     /// 胜利日解锁时间
     /// 
     /// 数值: 2027年1月1日 00:00:00 UTC 的 Unix 时间戳
@@ -202,6 +215,28 @@ pub mod time {
     /// - 时间单位转换
     /// - 持有时间折扣计算
     pub const SECONDS_PER_DAY: i64 = 86400;
+
+    /// 权限移交最大时间锁
+    ///
+    /// 数值: 30天（以秒为单位）
+    /// 计算: 30 * 86400 = 2,592,000 秒
+    ///
+    /// 用途:
+    /// - 限制`set_authority_transfer_delay`可设置的最大延迟
+    /// - 防止误设过长的延迟导致权限长期无法移交
+    ///
+    /// 说明:
+    /// - 延迟为0表示提议后可立即接受（仍需新地址签名）
+    pub const MAX_AUTHORITY_TRANSFER_DELAY: i64 = 30 * SECONDS_PER_DAY;
+    
+    /// 追回前最短冻结时间
+    /// 
+    /// 数值: 30 天
+    /// 
+    /// 说明:
+    /// - 持有者账户冻结满30天后才能通过Permanent Delegate追回代币
+    /// - 给持有者留出申诉和解冻的时间窗口
+    pub const MIN_CLAWBACK_FROZEN_PERIOD: i64 = 30 * SECONDS_PER_DAY;
 }
 
 /// 动态税收模型参数模块
@@ -209,8 +244,6 @@ pub mod time {
 /// 定义了TOT动态重力场税收模型（TOT-DGTM）的所有参数。
 /// 这些参数控制着税率计算的核心逻辑。
 pub mod tax {
-    use super::*;
-    
     /// 基础税率
     /// 
     /// 数值: 2% (以 basis points 表示, 200 = 2%)
@@ -321,6 +354,18 @@ pub mod tax {
     /// - 防止市场恐慌导致的流动性枯竭
     pub const PANIC_TAX_BPS: u16 = 3000;
     
+    /// 买入税率
+    /// 
+    /// 数值: 5% (500 basis points)
+    /// 
+    /// 说明:
+    /// - 从已登记的交易场所（DEX池、销售合约等）转入代币时，代替基础税率作为买入的基础税率
+    /// - 买入同样适用持有时间折扣和大额交易附加税
+    /// - 未登记任何交易场所时不会生效
+    /// 
+    /// 用途: `TaxConfig.buy_tax_bps`的初始值，管理员可通过update_tax_config调整
+    pub const BUY_TAX_BPS: u16 = 500;
+    
     /// 流动性注入比例
     /// 
     /// 数值: 80% (8000 basis points)
@@ -352,8 +397,6 @@ pub mod tax {
     /// 
     /// 定义了税收的最终分配方案。税收会按照以下比例分配：
     pub mod distribution {
-        use super::*;
-        
         /// 销毁比例
         /// 
         /// 数值: 40% (4000 basis points)
@@ -423,8 +466,6 @@ pub mod tax {
 /// 定义了用于派生程序派生地址（PDA）的种子字符串。
 /// 这些种子用于创建确定性的账户地址，确保每次部署都能找到相同的账户。
 pub mod seeds {
-    use super::*;
-    
     /// 全局配置账户种子
     /// 用于派生TotConfig账户的PDA地址
     pub const CONFIG_SEED: &[u8] = b"tot_config";
@@ -447,14 +488,116 @@ pub mod seeds {
     /// 用于派生Mint Authority的PDA地址（如果使用PDA作为Mint Authority）
     pub const MINT_AUTHORITY_SEED: &[u8] = b"tot_mint_auth";
     
+    /// 元数据权限账户种子
+    /// 用于派生TokenMetadata update_authority的PDA地址
+    pub const METADATA_AUTHORITY_SEED: &[u8] = b"tot_metadata_auth";
+    
+    /// 永久代理账户种子
+    /// 用于派生Permanent Delegate的PDA地址
+    pub const PERMANENT_DELEGATE_SEED: &[u8] = b"tot_perm_delegate";
+    
+    /// 追回记录账户种子
+    /// 用于派生ClawbackRecord账户的PDA地址
+    /// 注意：实际使用时需要结合案件编号（u64小端字节）一起派生
+    pub const CLAWBACK_SEED: &[u8] = b"tot_clawback";
+    
+    /// 支付回执账户种子
+    /// 用于派生PaymentReceipt账户的PDA地址
+    /// 注意：实际使用时需要结合付款方地址和32字节支付引用一起派生
+    pub const PAYMENT_RECEIPT_SEED: &[u8] = b"tot_payment_receipt";
+    
+    /// 代币流账户种子
+    /// 用于派生StreamAccount账户的PDA地址
+    /// 注意：实际使用时需要结合发送者地址和流编号（u64小端字节）一起派生
+    pub const STREAM_SEED: &[u8] = b"tot_stream";
+    
+    /// P2P挂单账户种子
+    /// 用于派生TradeOffer账户的PDA地址
+    /// 注意：实际使用时需要结合挂单方地址和挂单编号（u64小端字节）一起派生
+    pub const OFFER_SEED: &[u8] = b"tot_offer";
+    
+    /// 消费回执账户种子
+    /// 用于派生ConsumeReceipt账户的PDA地址
+    /// 注意：实际使用时需要结合用户地址和客户端随机数（u64小端字节）一起派生
+    pub const CONSUME_RECEIPT_SEED: &[u8] = b"tot_consume_receipt";
+    
+    /// 价格目录账户种子
+    /// 用于派生PriceCatalog账户的PDA地址（全局唯一）
+    pub const PRICE_CATALOG_SEED: &[u8] = b"tot_price_catalog";
+    
+    /// 奖励金库账户种子
+    /// 用于派生RewardsVault账户的PDA地址（全局唯一），同时作为金库代币账户的所有者
+    pub const REWARDS_VAULT_SEED: &[u8] = b"tot_rewards_vault";
+    
+    /// 平台预算账户种子
+    /// 用于派生PlatformBudget账户的PDA地址（全局唯一）
+    pub const PLATFORM_BUDGET_SEED: &[u8] = b"tot_platform_budget";
+    
+    /// 平台转账接收者额度账户种子
+    /// 用于派生PlatformRecipientUsage账户的PDA地址
+    /// 注意：实际使用时需要结合接收者钱包地址一起派生
+    pub const PLATFORM_RECIPIENT_SEED: &[u8] = b"tot_platform_recipient";
+    
+    /// 订阅账户种子
+    /// 用于派生Subscription账户的PDA地址
+    /// 注意：实际使用时需要结合用户地址和订阅ID（u64小端字节）一起派生
+    pub const SUBSCRIPTION_SEED: &[u8] = b"tot_subscription";
+    
+    /// 订阅扣款代理种子
+    /// 用于派生用户代币账户的Delegate PDA（全局唯一，所有订阅共用，无账户数据）
+    pub const SUBSCRIPTION_DELEGATE_SEED: &[u8] = b"tot_sub_delegate";
+    
+    /// 商户账户种子
+    /// 用于派生MerchantAccount账户的PDA地址
+    /// 注意：实际使用时需要结合商户钱包地址一起派生
+    pub const MERCHANT_SEED: &[u8] = b"tot_merchant";
+    
+    /// 发票账户种子
+    /// 用于派生Invoice账户的PDA地址
+    /// 注意：实际使用时需要结合商户钱包地址和发票ID（u64小端字节）一起派生
+    pub const INVOICE_SEED: &[u8] = b"tot_invoice";
+    
+    /// 合作程序登记账户种子
+    /// 用于派生PartnerProgram账户的PDA地址
+    /// 注意：实际使用时需要结合合作程序ID一起派生
+    pub const PARTNER_SEED: &[u8] = b"tot_partner";
+    
+    /// 合作程序签名PDA种子
+    /// 合作程序用本种子在**自己的**程序ID下派生签名PDA，CPI调用TOT时以invoke_signed签名，
+    /// 证明调用来自该合作程序
+    pub const PARTNER_AUTHORITY_SEED: &[u8] = b"tot_partner_authority";
+    
+    /// 积分配置账户种子
+    /// 用于派生PointsConfig账户的PDA地址（全局唯一）
+    pub const POINTS_CONFIG_SEED: &[u8] = b"tot_points_config";
+    
+    /// 积分账户种子
+    /// 用于派生PointsAccount账户的PDA地址
+    /// 注意：实际使用时需要结合持有者钱包地址一起派生
+    pub const POINTS_SEED: &[u8] = b"tot_points";
+    
+    /// 兑换券核销记录账户种子
+    /// 用于派生VoucherRedemption账户的PDA地址
+    /// 注意：实际使用时需要结合兑换券随机数（u64小端字节）一起派生
+    pub const VOUCHER_SEED: &[u8] = b"tot_voucher";
+    
+    /// Extra Account Metas账户种子
+    /// 用于派生Transfer Hook额外账户列表的PDA地址（spl-transfer-hook-interface规定）
+    /// 注意：实际使用时需要结合Mint地址一起派生
+    pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+    
     /// 税率配置账户种子
     /// 用于派生TaxConfig账户的PDA地址
     pub const TAX_CONFIG_SEED: &[u8] = b"tot_tax_config";
     
     /// Transfer Hook配置账户种子
-    /// 用于派生TransferHookConfig账户的PDA地址（在主程序中）
+    /// 用于派生TransferHookConfig账户的PDA地址（在Hook程序中）
     pub const HOOK_CONFIG_SEED: &[u8] = b"hook_config";
     
+    /// 转账守卫账户种子
+    /// 用于派生TransferGuard账户的PDA地址（标记主程序发起的转账，供Transfer Hook识别）
+    pub const TRANSFER_GUARD_SEED: &[u8] = b"tot_transfer_guard";
+    
     /// 资产账户种子
     /// 用于派生AssetAccount账户的PDA地址
     /// 注意：实际使用时需要结合资产ID一起派生
@@ -491,8 +634,6 @@ pub const BASIS_POINTS: u64 = 10000;
 /// 
 /// 注意: Anchor会自动添加8字节的discriminator，所以实际账户大小 = 8 + 结构体大小
 pub mod size {
-    use super::*;
-    
     /// 全局配置账户大小
     /// 
     /// 总大小: 8 + 32 + 32 + 32 + 32 + 1 + 8 + 8 + 8 + 8 + 1 + 128 = 286 字节
@@ -588,8 +729,6 @@ pub mod size {
 /// 
 /// 定义了系统中各种业务限制的最大值，用于防止资源滥用和确保系统安全。
 pub mod limits {
-    use super::*;
-    
     /// 最大冻结原因长度
     /// 
     /// 数值: 100 字符
@@ -621,6 +760,19 @@ pub mod limits {
     /// - 系统合约地址
     pub const MAX_TAX_EXEMPT_ADDRESSES: usize = 50;
     
+    /// 最大交易场所数量
+    /// 
+    /// 数值: 20个地址
+    /// 
+    /// 说明:
+    /// - `TaxConfig.venue_addresses`中最多可登记的地址数量
+    /// - 从这些地址转入代币视为买入，按买入税率计税
+    /// 
+    /// 典型用途:
+    /// - DEX池的代币账户所有者
+    /// - 代币销售合约
+    pub const MAX_TRADING_VENUES: usize = 20;
+    
     /// 最大税率层级数
     /// 
     /// 数值: 10 个层级
@@ -633,4 +785,76 @@ pub mod limits {
     /// - 预留未来功能扩展
     /// - 限制配置复杂度
     pub const MAX_TAX_TIERS: usize = 10;
+    
+    /// 最大元数据自定义字段名长度
+    /// 
+    /// 数值: 32 字符
+    /// 
+    /// 说明:
+    /// - 添加自定义元数据字段（如"tax_policy"）时，字段名的最大长度
+    /// 
+    /// 用途:
+    /// - 限制Mint账户扩容的大小
+    pub const MAX_METADATA_KEY_LEN: usize = 32;
+    
+    /// 最大元数据字段值长度
+    /// 
+    /// 数值: 200 字符
+    /// 
+    /// 说明:
+    /// - 名称、符号、URI和自定义字段值的最大长度
+    /// - 足以容纳常见的Arweave/IPFS链接
+    /// 
+    /// 用途:
+    /// - 限制Mint账户扩容的大小
+    /// - 防止恶意输入
+    pub const MAX_METADATA_VALUE_LEN: usize = 200;
+    
+    /// 批量转账最大接收者数量
+    /// 
+    /// 数值: 8 个接收者
+    /// 
+    /// 说明:
+    /// - 每个接收者占用2个remaining_accounts（代币账户、持有者账户）和40字节指令数据
+    /// - 每个接收者一次transfer_checked CPI（含Transfer Hook）约消耗40,000-60,000 CU
+    /// - 8个接收者需要通过ComputeBudget将上限提高到约600,000 CU
+    /// - 交易大小接近1232字节上限，建议配合地址查找表（ALT）使用
+    /// 
+    /// 用途:
+    /// - 保证批量转账在单笔交易的计算和大小限制内完成
+    pub const MAX_BATCH_TRANSFER_RECIPIENTS: usize = 8;
+    
+    /// 价格目录最大条目数
+    /// 
+    /// 数值: 64 个条目
+    /// 
+    /// 说明:
+    /// - `PriceCatalog.entries`中最多可登记的（消费类型, SKU）价格数量
+    /// - 每个条目50字节，64个条目约3.2KB
+    /// 
+    /// 用途:
+    /// - 控制价格目录账户大小
+    pub const MAX_CATALOG_ENTRIES: usize = 64;
+    
+    /// 平台热钱包签名者最大数量
+    /// 
+    /// 数值: 10 个地址
+    /// 
+    /// 说明:
+    /// - `PlatformBudget.signers`中可登记的、允许调用`platform_transfer`的热钱包数量
+    /// 
+    /// 用途:
+    /// - 控制平台预算账户大小
+    pub const MAX_PLATFORM_SIGNERS: usize = 10;
+    
+    /// 积分排行榜最大返回条数
+    /// 
+    /// 数值: 20 条
+    /// 
+    /// 说明:
+    /// - `get_points_leaderboard`每条返回48字节，20条约1KB
+    /// 
+    /// 用途:
+    /// - 不超过交易返回数据的1024字节上限
+    pub const MAX_POINTS_LEADERBOARD: usize = 20;
 }
@@ -418,10 +418,12 @@ pub enum TotError {
     /// 触发场景:
     /// - Transfer Hook程序验证失败
     /// - Transfer Hook执行出错
+    /// - 主程序发起转账时，remaining_accounts中缺少Hook程序或Extra Account Meta List
     /// 
     /// 解决方案:
     /// - 检查Transfer Hook程序的配置
     /// - 确认转账符合Hook的要求
+    /// - 按Extra Account Meta List解析Hook账户并放入remaining_accounts
     #[msg("Transfer hook validation failed")]
     TransferHookFailed,
     
@@ -540,11 +542,11 @@ pub enum TotError {
     /// 操作已暂停
     /// 
     /// 触发场景:
-    /// - 系统处于暂停状态时尝试执行某些操作
-    /// - 恐慌模式激活
+    /// - 指令所属的指令族（PauseFlag）已被管理员暂停
+    /// - 暂停尚未到达自动恢复时间
     /// 
     /// 解决方案:
-    /// - 等待系统恢复
+    /// - 等待自动恢复时间或管理员手动恢复
     /// - 联系管理员了解暂停原因
     #[msg("Operation paused")]
     OperationPaused,
@@ -689,4 +691,751 @@ pub enum TotError {
     /// - 确认时间参数在合理范围内
     #[msg("Invalid time parameter")]
     InvalidTimeParameter,
+
+    // ============================================
+    // 权限移交错误 (6120-6129)
+    // ============================================
+
+    /// 没有进行中的权限移交
+    /// 
+    /// 触发场景:
+    /// - 未调用`propose_authority`就调用`accept_authority`或取消
+    /// - 移交已被接受或取消
+    /// 
+    /// 解决方案:
+    /// - 先由当前管理员提议新的权限地址
+    /// - 检查pending_authority字段
+    #[msg("No pending authority transfer")]
+    NoPendingAuthority,
+
+    /// 签名者不是待接受的新权限地址
+    /// 
+    /// 触发场景:
+    /// - 接受权限的签名者与提议的新地址不一致
+    /// 
+    /// 解决方案:
+    /// - 使用提议时指定的新地址签名
+    /// - 如果提议地址有误，由当前管理员取消后重新提议
+    #[msg("Signer is not the pending authority")]
+    PendingAuthorityMismatch,
+
+    /// 权限移交时间锁未到期
+    /// 
+    /// 触发场景:
+    /// - 当前时间早于提议时计算的生效时间
+    /// 
+    /// 解决方案:
+    /// - 等待时间锁到期后再接受
+    /// - 检查authority_transfer_eta字段
+    #[msg("Authority transfer is still timelocked")]
+    AuthorityTransferTimelocked,
+
+    /// 无效的权限移交延迟
+    /// 
+    /// 触发场景:
+    /// - 延迟为负数
+    /// - 延迟超过MAX_AUTHORITY_TRANSFER_DELAY（30天）
+    /// 
+    /// 解决方案:
+    /// - 使用0到30天之间的延迟（以秒为单位）
+    #[msg("Invalid authority transfer delay")]
+    InvalidAuthorityTransferDelay,
+
+    // ============================================
+    // 铸造权限错误 (6130-6139)
+    // ============================================
+
+    /// 代币尚未全部铸造
+    /// 
+    /// 触发场景:
+    /// - 在mint_to_pools完成前调用renounce_mint_authority
+    /// - config.total_minted不等于TOTAL_SUPPLY
+    /// 
+    /// 解决方案:
+    /// - 先调用mint_to_pools铸造全部代币
+    #[msg("Minting is not complete")]
+    MintingNotComplete,
+
+    /// 铸造权限已放弃
+    /// 
+    /// 触发场景:
+    /// - 重复调用renounce_mint_authority
+    /// - 放弃后尝试迁移铸造权限
+    /// 
+    /// 解决方案:
+    /// - 铸造权限放弃不可逆，无需再次操作
+    #[msg("Mint authority has already been renounced")]
+    MintAuthorityAlreadyRenounced,
+
+    // ============================================
+    // 元数据错误 (6140-6149)
+    // ============================================
+
+    /// 签名者不是元数据权限
+    /// 
+    /// 触发场景:
+    /// - 签名者不是Mint的Metadata Pointer权限持有者
+    /// 
+    /// 解决方案:
+    /// - 使用Metadata Pointer权限地址签名
+    /// - 如需更换，通过propose_token_authority移交MetadataPointer权限
+    #[msg("Signer is not the metadata authority")]
+    InvalidMetadataAuthority,
+
+    /// 元数据字段过长
+    /// 
+    /// 触发场景:
+    /// - 字段值超过MAX_METADATA_VALUE_LEN（200字符）
+    /// - 自定义字段名为空或超过MAX_METADATA_KEY_LEN（32字符）
+    /// 
+    /// 解决方案:
+    /// - 缩短字段内容，较长的信息放到URI指向的链下JSON中
+    #[msg("Metadata field is too long")]
+    MetadataFieldTooLong,
+
+    // ============================================
+    // 追回错误 (6150-6159)
+    // ============================================
+
+    /// 冻结时间不足，不能追回
+    /// 
+    /// 触发场景:
+    /// - 持有者冻结时间不足MIN_CLAWBACK_FROZEN_PERIOD（30天）
+    /// - 持有者在frozen_at字段加入前被冻结（frozen_at为0）
+    /// 
+    /// 解决方案:
+    /// - 等待冻结满30天后再执行追回
+    /// - frozen_at为0时，先解冻再重新冻结以记录冻结时间
+    #[msg("Holder has not been frozen long enough for clawback")]
+    ClawbackFrozenPeriodNotElapsed,
+
+    /// 转移追回缺少接收账户
+    /// 
+    /// 触发场景:
+    /// - 追回方式为Transfer但未提供destination_token_account
+    /// 
+    /// 解决方案:
+    /// - 提供接收代币账户，或改用Burn方式
+    #[msg("Clawback transfer requires a destination token account")]
+    ClawbackDestinationRequired,
+
+    // ============================================
+    // Transfer Hook规则错误 (6160-6169)
+    // ============================================
+
+    /// 恐慌模式下Hook拒绝大额卖出
+    /// 
+    /// 触发场景:
+    /// - 恐慌模式开启时，通过普通Token-2022转账（DEX、钱包）卖出
+    /// - 卖出金额达到恐慌阈值（panic_threshold_bps）
+    /// 
+    /// 解决方案:
+    /// - 使用transfer_with_tax卖出并缴纳恐慌税
+    /// - 拆分为低于阈值的小额卖出
+    #[msg("Sell exceeds the panic limit for hook transfers")]
+    HookSellLimitExceeded,
+
+    // ============================================
+    // 批量转账错误 (6170-6179)
+    // ============================================
+
+    /// 批量转账接收者数量无效
+    /// 
+    /// 触发场景:
+    /// - 接收者列表为空
+    /// - 接收者数量超过MAX_BATCH_TRANSFER_RECIPIENTS（8）
+    /// 
+    /// 解决方案:
+    /// - 将接收者拆分到多笔batch_transfer交易中
+    #[msg("Invalid number of batch transfer recipients")]
+    InvalidBatchSize,
+
+    /// 批量转账账户与接收者不匹配
+    /// 
+    /// 触发场景:
+    /// - remaining_accounts数量少于接收者数量的2倍
+    /// - 代币账户所有者与接收者地址不一致
+    /// - 持有者账户不是对应接收者的持有者PDA
+    /// 
+    /// 解决方案:
+    /// - 按接收者顺序依次传入[代币账户, 持有者PDA]
+    #[msg("Batch transfer accounts do not match recipients")]
+    BatchAccountsMismatch,
+
+    // ============================================
+    // 支付引用错误 (6180-6189)
+    // ============================================
+
+    /// 支付引用已被使用
+    /// 
+    /// 触发场景:
+    /// - 使用同一引用重复提交转账（如后端超时后重试已成功的请求）
+    /// 
+    /// 解决方案:
+    /// - 查询回执PDA确认之前的付款已完成，不要再次付款
+    #[msg("Payment reference has already been used")]
+    DuplicatePaymentReference,
+
+    /// 缺少或错误的支付回执账户
+    /// 
+    /// 触发场景:
+    /// - 提供了支付引用但未传入payment_receipt或system_program
+    /// - payment_receipt不是`["tot_payment_receipt", payer, reference]`派生的PDA
+    /// 
+    /// 解决方案:
+    /// - 按引用派生回执PDA并与System Program一起传入
+    #[msg("Payment receipt account is missing or invalid")]
+    InvalidPaymentReceipt,
+
+    // ============================================
+    // 代币流错误 (6190-6199)
+    // ============================================
+
+    /// 代币流时间安排无效
+    /// 
+    /// 触发场景:
+    /// - 结束时间不晚于开始时间
+    /// - 结束时间早于当前时间
+    /// 
+    /// 解决方案:
+    /// - 设置 start_time < end_time 且 end_time 在未来
+    #[msg("Invalid stream schedule")]
+    InvalidStreamSchedule,
+
+    /// 代币流暂无可提取的数量
+    /// 
+    /// 触发场景:
+    /// - 流尚未开始
+    /// - 已释放的部分已全部提取
+    /// 
+    /// 解决方案:
+    /// - 等待更多代币释放后再提取
+    #[msg("Nothing to withdraw from stream")]
+    StreamNothingToWithdraw,
+
+    /// 代币流已取消
+    /// 
+    /// 触发场景:
+    /// - 重复取消同一个流
+    /// 
+    /// 解决方案:
+    /// - 无需再次取消，接收者仍可提取取消前已释放的部分
+    #[msg("Stream has already been cancelled")]
+    StreamAlreadyCancelled,
+
+    // ============================================
+    // P2P挂单错误 (6200-6209)
+    // ============================================
+
+    /// 挂单条件无效
+    /// 
+    /// 触发场景:
+    /// - 要价数量为0
+    /// - 要价代币是TOT本身
+    /// 
+    /// 解决方案:
+    /// - 使用其他SPL代币（SOL请使用Wrapped SOL）并设置大于0的要价
+    #[msg("Invalid offer terms")]
+    InvalidOfferTerms,
+
+    /// 挂单已关闭
+    /// 
+    /// 触发场景:
+    /// - 挂单已被取消
+    /// - 挂单已全部成交
+    /// 
+    /// 解决方案:
+    /// - 选择其他仍在挂出的订单
+    #[msg("Offer is closed")]
+    OfferClosed,
+
+    /// 成交数量超过挂单剩余数量
+    /// 
+    /// 触发场景:
+    /// - 吃单数量大于挂单中剩余的TOT
+    /// 
+    /// 解决方案:
+    /// - 查询挂单剩余数量后减少吃单数量
+    #[msg("Fill amount exceeds offer remaining amount")]
+    OfferFillExceedsRemaining,
+
+    // ============================================
+    // 交易场所错误 (6210-6219)
+    // ============================================
+
+    /// 交易场所已登记
+    /// 
+    /// 触发场景:
+    /// - 重复登记同一个交易场所地址
+    /// 
+    /// 解决方案:
+    /// - 检查TaxConfig.venue_addresses，无需重复登记
+    #[msg("Trading venue already registered")]
+    VenueAlreadyRegistered,
+
+    /// 交易场所未登记
+    /// 
+    /// 触发场景:
+    /// - 移除不在交易场所列表中的地址
+    /// 
+    /// 解决方案:
+    /// - 确认地址是否正确
+    #[msg("Trading venue not registered")]
+    VenueNotRegistered,
+
+    /// 交易场所数量过多
+    /// 
+    /// 触发场景:
+    /// - 登记超过MAX_TRADING_VENUES (20)个交易场所
+    /// 
+    /// 解决方案:
+    /// - 先移除不再使用的交易场所
+    #[msg("Too many trading venues")]
+    TooManyVenues,
+
+    // ============================================
+    // 池子释放错误 (6220-6229)
+    // ============================================
+
+    /// 池子需要多签
+    /// 
+    /// 触发场景:
+    /// - 通过release_pool_tokens释放requires_multisig为true的池子（外资统战池）
+    /// 
+    /// 解决方案:
+    /// - 通过多签流程释放该池子
+    #[msg("Pool requires multisig approval")]
+    PoolRequiresMultisig,
+
+    // ============================================
+    // 消费回执错误 (6230-6239)
+    // ============================================
+
+    /// 消费回执账户无效
+    /// 
+    /// 触发场景:
+    /// - 提供了回执参数，但未传入回执账户或System Program
+    /// - 回执账户地址不是由用户和随机数派生的PDA
+    /// 
+    /// 解决方案:
+    /// - 按`["tot_consume_receipt", user, nonce]`派生回执地址并传入System Program
+    #[msg("Invalid consume receipt account")]
+    InvalidConsumeReceipt,
+
+    /// 消费随机数已使用
+    /// 
+    /// 触发场景:
+    /// - 同一用户使用已存在回执的随机数再次消费
+    /// 
+    /// 解决方案:
+    /// - 为每次消费生成新的随机数
+    /// - 重试前先查询回执是否已存在，已存在说明上一次消费已成功
+    #[msg("Consume nonce already used")]
+    DuplicateConsumeNonce,
+
+    /// 消费回执已核销
+    /// 
+    /// 触发场景:
+    /// - 重复核销同一张回执
+    /// 
+    /// 解决方案:
+    /// - 回执只能核销一次，检查is_redeemed字段
+    #[msg("Consume receipt already redeemed")]
+    ConsumeReceiptAlreadyRedeemed,
+
+    /// 消费回执尚未核销
+    /// 
+    /// 触发场景:
+    /// - 关闭尚未核销的回执
+    /// 
+    /// 解决方案:
+    /// - 先调用redeem_consume_receipt核销
+    #[msg("Consume receipt not redeemed")]
+    ConsumeReceiptNotRedeemed,
+
+    // ============================================
+    // 价格目录错误 (6240-6249)
+    // ============================================
+
+    /// 商品未在价格目录中登记
+    /// 
+    /// 触发场景:
+    /// - 消费类型在价格目录中有定价，但传入的SKU没有对应条目
+    /// - 删除不存在的价格条目
+    /// 
+    /// 解决方案:
+    /// - 检查消费类型和SKU是否正确
+    #[msg("Item not listed in price catalog")]
+    PriceNotListed,
+
+    /// 商品已下架
+    /// 
+    /// 触发场景:
+    /// - 消费价格条目is_active为false的商品
+    /// 
+    /// 解决方案:
+    /// - 等待管理员重新上架
+    #[msg("Item is not active in price catalog")]
+    PriceInactive,
+
+    /// 消费金额与目录价格不符
+    /// 
+    /// 触发场景:
+    /// - 消费金额不等于目录价格（已扣除持有者折扣）
+    /// 
+    /// 解决方案:
+    /// - 重新读取价格目录后按最新价格消费
+    #[msg("Amount does not match catalog price")]
+    PriceMismatch,
+
+    /// 价格目录已满
+    /// 
+    /// 触发场景:
+    /// - 登记超过MAX_CATALOG_ENTRIES (64)个价格条目
+    /// 
+    /// 解决方案:
+    /// - 先删除不再使用的价格条目
+    #[msg("Price catalog is full")]
+    CatalogFull,
+
+    /// 价格条目参数无效
+    /// 
+    /// 触发场景:
+    /// - 价格为0且未登记受益人
+    /// - 持有者折扣超过10000 basis points（100%）
+    /// 
+    /// 解决方案:
+    /// - 检查价格和折扣参数
+    #[msg("Invalid price entry")]
+    InvalidPriceEntry,
+
+    // ========================================
+    // 收入分成错误 (6250-6259)
+    // ========================================
+
+    /// 收入分成比例无效
+    /// 
+    /// 触发场景:
+    /// - 销毁、财库、受益人比例之和不等于10000 basis points
+    /// 
+    /// 解决方案:
+    /// - 调整比例使三项之和为100%
+    #[msg("Revenue split must sum to 10000 basis points")]
+    InvalidRevenueSplit,
+
+    /// 缺少受益人代币账户
+    /// 
+    /// 触发场景:
+    /// - 消费的商品登记了受益人且分成包含受益人份额，但未传入受益人代币账户
+    /// 
+    /// 解决方案:
+    /// - 传入受益人的TOT代币账户
+    #[msg("Beneficiary token account is required")]
+    BeneficiaryAccountMissing,
+
+    /// 受益人不匹配
+    /// 
+    /// 触发场景:
+    /// - 受益人代币账户的所有者不是价格目录为该商品登记的受益人
+    /// 
+    /// 解决方案:
+    /// - 按价格目录中的受益人地址传入代币账户
+    #[msg("Beneficiary does not match price catalog")]
+    BeneficiaryMismatch,
+
+    // ========================================
+    // 奖励兑换券错误 (6260-6269)
+    // ========================================
+
+    /// 兑换券签名公钥未设置
+    /// 
+    /// 触发场景:
+    /// - config.voucher_signer为Pubkey::default()时兑换
+    /// 
+    /// 解决方案:
+    /// - 管理员先调用set_voucher_signer
+    #[msg("Voucher signer is not configured")]
+    VoucherSignerNotSet,
+
+    /// 兑换券签名无效
+    /// 
+    /// 触发场景:
+    /// - 兑换指令前一条不是Ed25519签名验证指令
+    /// - 签名公钥不是config.voucher_signer
+    /// - 签名的消息与兑换券内容不一致
+    /// 
+    /// 解决方案:
+    /// - 在兑换指令之前紧邻放置Ed25519签名验证指令，数据内联在该指令中
+    #[msg("Invalid voucher signature")]
+    InvalidVoucherSignature,
+
+    /// 兑换券已过期
+    /// 
+    /// 触发场景:
+    /// - 当前时间晚于兑换券的expires_at
+    /// 
+    /// 解决方案:
+    /// - 请平台重新签发兑换券
+    #[msg("Voucher has expired")]
+    VoucherExpired,
+
+    /// 兑换券接收者不匹配
+    /// 
+    /// 触发场景:
+    /// - 提交者或接收代币账户所有者不是兑换券中的recipient
+    /// 
+    /// 解决方案:
+    /// - 由兑换券接收者本人提交，并传入自己的代币账户
+    #[msg("Voucher recipient mismatch")]
+    VoucherRecipientMismatch,
+
+    // ========================================
+    // 平台预算错误 (6270-6279)
+    // ========================================
+
+    /// 超出平台周期预算
+    /// 
+    /// 触发场景:
+    /// - 本周期平台转账累计数量加上本次数量超过period_cap
+    /// 
+    /// 解决方案:
+    /// - 等待下一个周期，或由管理员调高预算
+    #[msg("Platform transfer exceeds period budget")]
+    PlatformBudgetExceeded,
+
+    /// 超出单个接收者的周期额度
+    /// 
+    /// 触发场景:
+    /// - 本周期向同一接收者的平台转账累计数量超过per_recipient_cap
+    /// 
+    /// 解决方案:
+    /// - 等待下一个周期，或由管理员调高单个接收者额度
+    #[msg("Platform transfer exceeds per-recipient budget")]
+    RecipientBudgetExceeded,
+
+    /// 平台预算参数无效
+    /// 
+    /// 触发场景:
+    /// - 周期长度、周期预算或单个接收者额度为0
+    /// - 单个接收者额度大于周期预算
+    /// 
+    /// 解决方案:
+    /// - 检查预算参数
+    #[msg("Invalid platform budget parameters")]
+    InvalidPlatformBudget,
+
+    /// 平台签名者列表已满
+    /// 
+    /// 触发场景:
+    /// - 登记超过MAX_PLATFORM_SIGNERS (10)个热钱包
+    /// 
+    /// 解决方案:
+    /// - 先移除不再使用的热钱包
+    #[msg("Too many platform signers")]
+    TooManyPlatformSigners,
+
+    /// 平台签名者已登记
+    /// 
+    /// 触发场景:
+    /// - 重复登记同一个热钱包
+    #[msg("Platform signer already registered")]
+    PlatformSignerAlreadyRegistered,
+
+    /// 平台签名者未登记
+    /// 
+    /// 触发场景:
+    /// - 移除未登记的热钱包
+    #[msg("Platform signer not registered")]
+    PlatformSignerNotRegistered,
+
+    // ========================================
+    // 订阅错误 (6280-6289)
+    // ========================================
+
+    /// 订阅计划参数无效
+    /// 
+    /// 触发场景:
+    /// - 每期金额、周期长度或最大期数为0
+    /// - 授权总额（每期金额 × 最大期数）溢出
+    /// 
+    /// 解决方案:
+    /// - 检查订阅计划参数
+    #[msg("Invalid subscription plan")]
+    InvalidSubscriptionPlan,
+
+    /// 订阅不处于生效状态
+    /// 
+    /// 触发场景:
+    /// - 对已停止（余额不足、冻结）或已完成的订阅扣款
+    /// 
+    /// 解决方案:
+    /// - 用户取消后重新订阅
+    #[msg("Subscription is not active")]
+    SubscriptionNotActive,
+
+    /// 订阅尚未到期
+    /// 
+    /// 触发场景:
+    /// - 当前时间早于下一期扣款时间
+    /// 
+    /// 解决方案:
+    /// - 等待next_charge_at之后再调用
+    #[msg("Subscription charge is not due yet")]
+    SubscriptionNotDue,
+
+    // ========================================
+    // 商户与发票错误 (6290-6299)
+    // ========================================
+
+    /// 商户未启用
+    /// 
+    /// 触发场景:
+    /// - 被停用的商户创建发票
+    /// - 支付被停用商户的发票
+    /// 
+    /// 解决方案:
+    /// - 联系管理员重新启用商户
+    #[msg("Merchant is not active")]
+    MerchantNotActive,
+
+    /// 商户税率无效
+    /// 
+    /// 触发场景:
+    /// - 商户税率上限超过MAX_TAX_BPS
+    /// 
+    /// 解决方案:
+    /// - 使用0（免税）到MAX_TAX_BPS之间的税率上限
+    #[msg("Invalid merchant tax rate")]
+    InvalidMerchantTaxRate,
+
+    /// 发票参数无效
+    /// 
+    /// 触发场景:
+    /// - 发票金额为0
+    /// - 到期时间早于当前时间
+    /// 
+    /// 解决方案:
+    /// - 检查发票金额和到期时间
+    #[msg("Invalid invoice")]
+    InvalidInvoice,
+
+    /// 发票不是待支付状态
+    /// 
+    /// 触发场景:
+    /// - 重复支付已支付的发票
+    /// 
+    /// 解决方案:
+    /// - 检查发票状态
+    #[msg("Invoice is not open")]
+    InvoiceNotOpen,
+
+    /// 发票已过期
+    /// 
+    /// 触发场景:
+    /// - 在到期时间之后支付发票
+    /// 
+    /// 解决方案:
+    /// - 联系商户重新开具发票
+    #[msg("Invoice has expired")]
+    InvoiceExpired,
+
+    // ========================================
+    // 合作程序错误 (6300-6309)
+    // ========================================
+
+    /// 合作程序未授权
+    /// 
+    /// 触发场景:
+    /// - 合作程序已停用
+    /// - 合作程序没有该CPI入口的权限
+    /// - 签名账户不是合作程序的签名PDA
+    /// 
+    /// 解决方案:
+    /// - 联系管理员登记或更新合作程序权限
+    /// - 合作程序使用`["tot_partner_authority"]`在自己的程序ID下派生签名PDA
+    #[msg("Partner program is not authorized")]
+    PartnerNotAuthorized,
+
+    /// 合作程序登记参数无效
+    /// 
+    /// 触发场景:
+    /// - 权限位包含未定义的位
+    /// - 程序ID为默认值
+    /// 
+    /// 解决方案:
+    /// - 检查程序ID和权限位
+    #[msg("Invalid partner program registration")]
+    InvalidPartnerProgram,
+
+    // ========================================
+    // 积分错误 (6310-6319)
+    // ========================================
+
+    /// 积分余额不足
+    /// 
+    /// 触发场景:
+    /// - 兑换商品或税率折扣所需积分超过积分余额
+    /// 
+    /// 解决方案:
+    /// - 通过消费、持有或拍卖继续赚取积分
+    #[msg("Insufficient points")]
+    InsufficientPoints,
+
+    /// 积分配置无效
+    /// 
+    /// 触发场景:
+    /// - 税率折扣超过MAX_TAX_BPS
+    /// - 启用税率折扣兑换但时长为0
+    /// 
+    /// 解决方案:
+    /// - 检查积分配置参数
+    #[msg("Invalid points configuration")]
+    InvalidPointsConfig,
+
+    /// 积分兑换不可用
+    /// 
+    /// 触发场景:
+    /// - 兑换的商品不在价格目录中、已下架或价格为0
+    /// - 积分兑换或税率折扣兑换未启用（比例为0）
+    /// 
+    /// 解决方案:
+    /// - 检查价格目录和积分配置
+    #[msg("Points redemption is unavailable")]
+    PointsRedemptionUnavailable,
+
+    /// 没有可领取的持有积分
+    /// 
+    /// 触发场景:
+    /// - 距上次领取不足一天
+    /// - 持有积分比例为0
+    /// 
+    /// 解决方案:
+    /// - 持有满一天后再领取
+    #[msg("No holding points to claim")]
+    NoPointsToClaim,
+
+    // ========================================
+    // 配置迁移错误 (6320-6329)
+    // ========================================
+
+    /// 配置账户已是当前布局
+    /// 
+    /// 触发场景:
+    /// - 对新部署或已迁移的配置账户调用`migrate_config`
+    /// 
+    /// 解决方案:
+    /// - 无需迁移
+    #[msg("Config account already uses the current layout")]
+    ConfigAlreadyMigrated,
+
+    /// 旧配置账户数据无效
+    /// 
+    /// 触发场景:
+    /// - 账户大小不是旧布局的大小或discriminator不匹配
+    /// - 旧布局的预留空间不全为0，无法确定其中数据的含义
+    /// 
+    /// 解决方案:
+    /// - 检查传入的配置账户
+    #[msg("Legacy config account data is invalid")]
+    InvalidLegacyConfig,
 }
@@ -4,17 +4,20 @@
 // ============================================
 
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use anchor_spl::token_interface::{
-    self, TokenAccount, TokenInterface, TransferChecked, Mint,
+    TokenAccount, TokenInterface, TransferChecked, Mint,
 };
 
-use crate::state::config::TotConfig;
-use crate::constants::seeds;
+use crate::state::config::{TotConfig, PauseFlag};
+use crate::constants::{seeds, time};
 use crate::errors::TotError;
+use crate::utils::token_transfer::transfer_checked_with_hook;
+use crate::utils::config_migration::migrate_legacy_config_data;
 
-/// 更新管理员
+/// 提议新管理员
 #[derive(Accounts)]
-pub struct UpdateAuthority<'info> {
+pub struct ProposeAuthority<'info> {
     #[account(
         constraint = authority.key() == config.authority @ TotError::Unauthorized
     )]
@@ -28,22 +31,88 @@ pub struct UpdateAuthority<'info> {
     pub config: Account<'info, TotConfig>,
 }
 
-/// 更新管理员处理器
-pub fn update_authority_handler(
-    ctx: Context<UpdateAuthority>,
+/// 提议新管理员处理器
+/// 
+/// 权限移交的第一步：只记录待接受的新地址和最早生效时间，
+/// 当前管理员在新地址签名接受之前仍然保有全部权限。
+/// 重复提议会覆盖之前的提议并重新计算生效时间。
+pub fn propose_authority_handler(
+    ctx: Context<ProposeAuthority>,
     new_authority: Pubkey,
 ) -> Result<()> {
     let config = &mut ctx.accounts.config;
-    let old_authority = config.authority;
     let clock = Clock::get()?;
     let timestamp = clock.unix_timestamp;
     
     require!(
-        new_authority != Pubkey::default(),
+        new_authority != Pubkey::default() && new_authority != config.authority,
         TotError::InvalidNewAuthority
     );
-    
+
+    let eta = timestamp
+        .checked_add(config.authority_transfer_delay_at(timestamp))
+        .ok_or(TotError::MathOverflow)?;
+
+    config.pending_authority = new_authority;
+    config.authority_transfer_eta = eta;
+
+    msg!(
+        "Authority transfer proposed: {} -> {}, eta {}",
+        config.authority,
+        new_authority,
+        eta
+    );
+
+    emit!(AuthorityTransferProposed {
+        current_authority: config.authority,
+        pending_authority: new_authority,
+        eta,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 接受管理员权限
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    /// 新管理员（必须是提议中的地址）
+    #[account(
+        constraint = new_authority.key() == config.pending_authority @ TotError::PendingAuthorityMismatch
+    )]
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+}
+
+/// 接受管理员权限处理器
+/// 
+/// 权限移交的第二步：由新地址签名，时间锁到期后正式替换管理员。
+pub fn accept_authority_handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let clock = Clock::get()?;
+    let timestamp = clock.unix_timestamp;
+
+    require!(
+        config.pending_authority != Pubkey::default(),
+        TotError::NoPendingAuthority
+    );
+    require!(
+        timestamp >= config.authority_transfer_eta,
+        TotError::AuthorityTransferTimelocked
+    );
+
+    let old_authority = config.authority;
+    let new_authority = config.pending_authority;
+
     config.authority = new_authority;
+    config.pending_authority = Pubkey::default();
+    config.authority_transfer_eta = 0;
 
     msg!(
         "Authority updated from {} to {}",
@@ -60,6 +129,119 @@ pub fn update_authority_handler(
     Ok(())
 }
 
+/// 取消管理员移交
+#[derive(Accounts)]
+pub struct CancelAuthorityTransfer<'info> {
+    #[account(
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+}
+
+/// 取消管理员移交处理器
+pub fn cancel_authority_transfer_handler(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let clock = Clock::get()?;
+    let timestamp = clock.unix_timestamp;
+
+    require!(
+        config.pending_authority != Pubkey::default(),
+        TotError::NoPendingAuthority
+    );
+
+    let cancelled_authority = config.pending_authority;
+    config.pending_authority = Pubkey::default();
+    config.authority_transfer_eta = 0;
+
+    msg!("Authority transfer to {} cancelled", cancelled_authority);
+
+    emit!(AuthorityTransferCancelled {
+        authority: config.authority,
+        cancelled_authority,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 设置权限移交延迟
+#[derive(Accounts)]
+pub struct SetAuthorityTransferDelay<'info> {
+    #[account(
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+}
+
+/// 设置权限移交延迟处理器
+/// 
+/// 延迟对管理员、Transfer Hook管理员和Token-2022权限的移交统一生效，
+/// 只影响之后的提议。
+/// 
+/// 延长（或不变）立即生效，并取消待生效的缩短；
+/// 缩短在当前生效的延迟过去之后才生效，期间提议仍按原延迟计算。
+pub fn set_authority_transfer_delay_handler(
+    ctx: Context<SetAuthorityTransferDelay>,
+    delay_seconds: i64,
+) -> Result<()> {
+    require!(
+        (0..=time::MAX_AUTHORITY_TRANSFER_DELAY).contains(&delay_seconds),
+        TotError::InvalidAuthorityTransferDelay
+    );
+
+    let config = &mut ctx.accounts.config;
+    let clock = Clock::get()?;
+    let timestamp = clock.unix_timestamp;
+
+    // 先落定已到期的缩短，之后以当前生效的延迟为准
+    let old_delay = config.authority_transfer_delay_at(timestamp);
+    config.authority_transfer_delay = old_delay;
+    config.pending_authority_transfer_delay = 0;
+    config.authority_transfer_delay_eta = 0;
+
+    let effective_at = if delay_seconds >= old_delay {
+        config.authority_transfer_delay = delay_seconds;
+        timestamp
+    } else {
+        let eta = timestamp
+            .checked_add(old_delay)
+            .ok_or(TotError::MathOverflow)?;
+        config.pending_authority_transfer_delay = delay_seconds;
+        config.authority_transfer_delay_eta = eta;
+        eta
+    };
+
+    msg!(
+        "Authority transfer delay updated from {} to {} seconds, effective at {}",
+        old_delay,
+        delay_seconds,
+        effective_at
+    );
+
+    emit!(AuthorityTransferDelayUpdated {
+        old_delay,
+        new_delay: delay_seconds,
+        effective_at,
+        timestamp,
+    });
+
+    Ok(())
+}
+
 /// 暂停/恢复系统
 #[derive(Accounts)]
 pub struct SetPaused<'info> {
@@ -77,20 +259,99 @@ pub struct SetPaused<'info> {
 }
 
 /// 设置暂停状态处理器
+/// 
+/// 按指令族设置或解除暂停。暂停时可以指定自动恢复时间，
+/// 到期后该指令族自动恢复，无需管理员再发交易。
+/// 解除暂停会同时清除对应的自动恢复时间。
 pub fn set_paused_handler(
     ctx: Context<SetPaused>,
+    operations: Vec<PauseFlag>,
     paused: bool,
+    resume_at: Option<i64>,
 ) -> Result<()> {
+    require!(!operations.is_empty(), TotError::InvalidParameter);
+
     let config = &mut ctx.accounts.config;
     let clock = Clock::get()?;
     let timestamp = clock.unix_timestamp;
-    
-    config.panic_mode = paused;
 
-    msg!("System paused status: {}", paused);
+    // 自动恢复时间只在暂停时有意义，且必须晚于当前时间
+    let resume_at = if paused {
+        match resume_at {
+            Some(resume_at) => {
+                require!(resume_at > timestamp, TotError::InvalidTimeParameter);
+                resume_at
+            }
+            None => 0,
+        }
+    } else {
+        require!(resume_at.is_none(), TotError::InvalidParameter);
+        0
+    };
+
+    let mut mask: u16 = 0;
+    for flag in operations.iter() {
+        mask |= flag.bit();
+        config.pause_resume_at[*flag as usize] = resume_at;
+    }
+
+    if paused {
+        config.paused_operations |= mask;
+    } else {
+        config.paused_operations &= !mask;
+    }
+
+    msg!(
+        "Paused operations updated: mask={:#09b}, paused={}, resume_at={}",
+        mask,
+        paused,
+        resume_at
+    );
 
     emit!(SystemPausedEvent {
+        operations: mask,
         paused,
+        resume_at,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 设置恐慌税率模式
+#[derive(Accounts)]
+pub struct SetPanicMode<'info> {
+    #[account(
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+}
+
+/// 设置恐慌税率模式处理器
+/// 
+/// 恐慌模式只影响卖出税率（见`TaxCalculator::calculate_tax`），
+/// 与暂停位图相互独立，不会拒绝任何操作。
+pub fn set_panic_mode_handler(
+    ctx: Context<SetPanicMode>,
+    enabled: bool,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let clock = Clock::get()?;
+    let timestamp = clock.unix_timestamp;
+
+    config.panic_mode = enabled;
+
+    msg!("Panic mode: {}", enabled);
+
+    emit!(PanicModeUpdated {
+        enabled,
         timestamp,
     });
 
@@ -149,12 +410,16 @@ pub struct EmergencyWithdraw<'info> {
 ///   );
 ///   ```
 /// - 当前设计允许在紧急情况下从任何账户提取，提供更大的灵活性
-pub fn emergency_withdraw_handler(
-    ctx: Context<EmergencyWithdraw>,
+pub fn emergency_withdraw_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, EmergencyWithdraw<'info>>,
     amount: u64,
 ) -> Result<()> {
-    // 紧急提取需要系统处于暂停状态
-    require!(ctx.accounts.config.panic_mode, TotError::SystemNotPaused);
+    // 紧急提取需要转账处于暂停状态
+    let timestamp = Clock::get()?.unix_timestamp;
+    require!(
+        ctx.accounts.config.is_paused(PauseFlag::Transfers, timestamp),
+        TotError::SystemNotPaused
+    );
     
     // 验证源账户余额是否足够
     require!(
@@ -172,15 +437,13 @@ pub fn emergency_withdraw_handler(
         },
     );
 
-    token_interface::transfer_checked(
+    transfer_checked_with_hook(
         transfer_ctx,
+        ctx.remaining_accounts,
         amount,
         ctx.accounts.mint.decimals,
     )?;
 
-    let clock = Clock::get()?;
-    let timestamp = clock.unix_timestamp;
-
     msg!("Emergency withdrawal: {} tokens", amount);
 
     emit!(EmergencyWithdrawEvent {
@@ -257,6 +520,247 @@ pub fn set_tws_treasury_handler(
     Ok(())
 }
 
+/// 设置合规权限
+#[derive(Accounts)]
+pub struct SetComplianceAuthority<'info> {
+    #[account(
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+}
+
+/// 设置合规权限处理器
+/// 
+/// 合规权限可以与系统管理员一起执行`clawback`。
+/// 传入Pubkey::default()表示撤销合规权限。
+/// 
+/// # 参数
+/// * `ctx` - 管理员操作上下文
+/// * `compliance_authority` - 新的合规权限地址
+/// 
+/// # 返回值
+/// * `Result<()>` - 成功返回Ok(())
+pub fn set_compliance_authority_handler(
+    ctx: Context<SetComplianceAuthority>,
+    compliance_authority: Pubkey,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let old_authority = config.compliance_authority;
+    config.compliance_authority = compliance_authority;
+
+    msg!(
+        "Compliance authority updated from {} to {}",
+        old_authority,
+        compliance_authority
+    );
+
+    emit!(ComplianceAuthorityUpdated {
+        old_authority,
+        new_authority: compliance_authority,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 设置消费回执核销权限
+#[derive(Accounts)]
+pub struct SetReceiptAuthority<'info> {
+    #[account(
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+}
+
+/// 设置消费回执核销权限处理器
+/// 
+/// 回执核销权限可以与系统管理员一起核销和关闭消费回执。
+/// 传入Pubkey::default()表示撤销回执核销权限。
+/// 
+/// # 参数
+/// * `ctx` - 管理员操作上下文
+/// * `receipt_authority` - 新的回执核销权限地址
+/// 
+/// # 返回值
+/// * `Result<()>` - 成功返回Ok(())
+pub fn set_receipt_authority_handler(
+    ctx: Context<SetReceiptAuthority>,
+    receipt_authority: Pubkey,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let old_authority = config.receipt_authority;
+    config.receipt_authority = receipt_authority;
+
+    msg!(
+        "Receipt authority updated from {} to {}",
+        old_authority,
+        receipt_authority
+    );
+
+    emit!(ReceiptAuthorityUpdated {
+        old_authority,
+        new_authority: receipt_authority,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 设置奖励兑换券签名公钥
+#[derive(Accounts)]
+pub struct SetVoucherSigner<'info> {
+    #[account(
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+}
+
+/// 设置奖励兑换券签名公钥处理器
+/// 
+/// 轮换签名公钥后，旧公钥签发但尚未兑换的兑换券全部失效。
+/// 传入Pubkey::default()表示关闭兑换券功能。
+/// 
+/// # 参数
+/// * `ctx` - 管理员操作上下文
+/// * `voucher_signer` - 新的兑换券签名公钥
+/// 
+/// # 返回值
+/// * `Result<()>` - 成功返回Ok(())
+pub fn set_voucher_signer_handler(
+    ctx: Context<SetVoucherSigner>,
+    voucher_signer: Pubkey,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let old_signer = config.voucher_signer;
+    config.voucher_signer = voucher_signer;
+
+    msg!(
+        "Voucher signer updated from {} to {}",
+        old_signer,
+        voucher_signer
+    );
+
+    emit!(VoucherSignerUpdated {
+        old_signer,
+        new_signer: voucher_signer,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 迁移配置账户布局
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// 系统管理员（支付扩容租金，在处理器中按旧布局中的管理员地址校验）
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// 全局配置
+    ///
+    /// CHECK: 旧布局无法按TotConfig反序列化，地址由seeds约束，数据在处理器中校验
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        owner = crate::ID
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 迁移配置账户布局处理器
+/// 
+/// 把版本1布局（只有128字节预留空间）的配置账户扩容到`TotConfig::LEN`，
+/// 保留原有字段，新增字段取`initialize`的初始值，并补足租金。
+/// 升级程序后、执行其他指令前由管理员执行一次。
+/// 
+/// # 参数
+/// * `ctx` - 迁移上下文
+/// 
+/// # 返回值
+/// * `Result<()>` - 成功返回Ok(())
+/// 
+/// # 错误
+/// * `TotError::ConfigAlreadyMigrated` - 配置账户已是当前布局
+/// * `TotError::InvalidLegacyConfig` - 配置账户数据不是版本1布局
+/// * `TotError::Unauthorized` - 签名者不是配置中的管理员
+pub fn migrate_config_handler(ctx: Context<MigrateConfig>) -> Result<()> {
+    let config_info = ctx.accounts.config.to_account_info();
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let old_len = config_info.data_len();
+    let migrated = migrate_legacy_config_data(&config_info.try_borrow_data()?)?;
+    let config = TotConfig::try_deserialize(&mut &migrated[..])?;
+    require!(
+        ctx.accounts.authority.key() == config.authority,
+        TotError::Unauthorized
+    );
+
+    // 补足租金
+    let required = Rent::get()?.minimum_balance(TotConfig::LEN);
+    let current = config_info.lamports();
+    if required > current {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: config_info.clone(),
+                },
+            ),
+            required - current,
+        )?;
+    }
+
+    config_info.realloc(TotConfig::LEN, false)?;
+    config_info.try_borrow_mut_data()?.copy_from_slice(&migrated);
+
+    msg!(
+        "Config migrated from {} to {} bytes, version {}",
+        old_len,
+        TotConfig::LEN,
+        config.version
+    );
+
+    emit!(ConfigMigrated {
+        old_len: old_len as u32,
+        new_len: TotConfig::LEN as u32,
+        version: config.version,
+        timestamp,
+    });
+
+    Ok(())
+}
+
 /// 管理员更新事件
 #[event]
 pub struct AuthorityUpdated {
@@ -265,10 +769,48 @@ pub struct AuthorityUpdated {
     pub timestamp: i64,
 }
 
+/// 管理员移交提议事件
+#[event]
+pub struct AuthorityTransferProposed {
+    pub current_authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+/// 管理员移交取消事件
+#[event]
+pub struct AuthorityTransferCancelled {
+    pub authority: Pubkey,
+    pub cancelled_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// 权限移交延迟更新事件
+#[event]
+pub struct AuthorityTransferDelayUpdated {
+    pub old_delay: i64,
+    pub new_delay: i64,
+    /// 新延迟的生效时间（延长时为当前时间）
+    pub effective_at: i64,
+    pub timestamp: i64,
+}
+
 /// 系统暂停事件
 #[event]
 pub struct SystemPausedEvent {
+    /// 本次变更的指令族位图
+    pub operations: u16,
     pub paused: bool,
+    /// 自动恢复时间（0表示不自动恢复）
+    pub resume_at: i64,
+    pub timestamp: i64,
+}
+
+/// 恐慌税率模式更新事件
+#[event]
+pub struct PanicModeUpdated {
+    pub enabled: bool,
     pub timestamp: i64,
 }
 
@@ -288,3 +830,36 @@ pub struct TwsTreasuryUpdated {
     pub new_treasury: Pubkey,
     pub timestamp: i64,
 }
+
+/// 合规权限更新事件
+#[event]
+pub struct ComplianceAuthorityUpdated {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// 消费回执核销权限更新事件
+#[event]
+pub struct ReceiptAuthorityUpdated {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// 奖励兑换券签名公钥更新事件
+#[event]
+pub struct VoucherSignerUpdated {
+    pub old_signer: Pubkey,
+    pub new_signer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// 配置账户布局迁移事件
+#[event]
+pub struct ConfigMigrated {
+    pub old_len: u32,
+    pub new_len: u32,
+    pub version: u8,
+    pub timestamp: i64,
+}
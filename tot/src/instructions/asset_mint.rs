@@ -5,17 +5,25 @@
 
 use anchor_lang::prelude::*;
 
-use crate::state::config::TotConfig;
+use crate::state::config::{TotConfig, PauseFlag};
 use crate::state::asset::{AssetAccount, AssetLocation};
 use crate::constants::seeds;
 use crate::errors::TotError;
 
 /// 资产上链账户结构
 #[derive(Accounts)]
-#[instruction(asset_id: String)]
+#[instruction(
+    asset_id: String,
+    asset_type: u8,
+    owner: Pubkey,
+    location: crate::state::AssetLocation,
+    value: u64,
+    metadata_uri: Option<String>
+)]
 pub struct MintAsset<'info> {
-    /// 管理员（签名者，需要验证权限）
+    /// 管理员（签名者，需要验证权限，支付租金）
     #[account(
+        mut,
         constraint = authority.key() == config.authority @ TotError::Unauthorized
     )]
     pub authority: Signer<'info>,
@@ -105,6 +113,9 @@ pub fn mint_asset_handler(
     // 验证阶段
     // ========================================
     
+    // 暂停检查: 资产上链指令族被暂停时拒绝执行
+    ctx.accounts.config.require_not_paused(PauseFlag::AssetMinting, timestamp)?;
+
     // 验证1: 资产ID不能为空
     require!(!asset_id.is_empty(), TotError::InvalidParameter);
     
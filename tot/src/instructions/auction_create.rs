@@ -5,16 +5,17 @@
 
 use anchor_lang::prelude::*;
 
-use crate::state::config::TotConfig;
+use crate::state::config::{TotConfig, PauseFlag};
 use crate::state::auction::AuctionAccount;
 use crate::constants::seeds;
 use crate::errors::TotError;
 
 /// 拍卖创建账户结构
 #[derive(Accounts)]
-#[instruction(asset_id: String)]
+#[instruction(asset_id: String, start_price: u64, taunt_message: String)]
 pub struct CreateAuction<'info> {
-    /// 创建者（签名者）
+    /// 创建者（签名者，支付租金）
+    #[account(mut)]
     pub creator: Signer<'info>,
 
     /// 拍卖账户（PDA）
@@ -93,6 +94,9 @@ pub fn create_auction_handler(
     // 验证阶段
     // ========================================
     
+    // 暂停检查: 拍卖指令族被暂停时拒绝执行
+    ctx.accounts.config.require_not_paused(PauseFlag::Auctions, timestamp)?;
+
     // 验证1: 资产ID不能为空
     require!(!asset_id.is_empty(), TotError::InvalidParameter);
     
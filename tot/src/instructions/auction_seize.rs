@@ -5,15 +5,17 @@
 
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{
-    self, Mint, TokenAccount, TokenInterface, TransferChecked,
+    Mint, TokenAccount, TokenInterface, TransferChecked,
 };
 
-use crate::state::config::TotConfig;
+use crate::state::config::{TotConfig, PauseFlag};
 use crate::state::auction::AuctionAccount;
 use crate::state::holder::HolderAccount;
+use crate::state::points::{PointsConfig, PointsAccount, PointsSource};
+use crate::instructions::points::award_points;
 use crate::constants::seeds;
 use crate::errors::TotError;
-use crate::utils::validation::validate_transfer_amount;
+use crate::utils::token_transfer::transfer_checked_with_hook;
 
 /// 拍卖夺取账户结构
 #[derive(Accounts)]
@@ -57,8 +59,9 @@ pub struct SeizeAuction<'info> {
     #[account(mut)]
     pub mint: InterfaceAccount<'info, Mint>,
 
-    /// 全局配置
+    /// 全局配置（累计协议统计）
     #[account(
+        mut,
         seeds = [seeds::CONFIG_SEED],
         bump
     )]
@@ -74,6 +77,21 @@ pub struct SeizeAuction<'info> {
 
     /// Token 程序
     pub token_program: Interface<'info, TokenInterface>,
+
+    /// 积分配置（与新所有者积分账户同时传入时发放夺取积分）
+    #[account(
+        seeds = [seeds::POINTS_CONFIG_SEED],
+        bump = points_config.bump
+    )]
+    pub points_config: Option<Account<'info, PointsConfig>>,
+
+    /// 新所有者积分账户（可选）
+    #[account(
+        mut,
+        seeds = [seeds::POINTS_SEED, new_owner.key().as_ref()],
+        bump = new_owner_points.bump
+    )]
+    pub new_owner_points: Option<Account<'info, PointsAccount>>,
 }
 
 /// 拍卖夺取处理器
@@ -102,6 +120,7 @@ pub struct SeizeAuction<'info> {
 /// - 5%给财库（免税，因为是向TWS官方消费）
 /// - 95%给上一任房主（免税，因为是平台资产转移）
 /// - 会更新新所有者的消费统计
+/// - 传入积分配置和新所有者积分账户时发放夺取积分
 /// 
 /// # 使用示例
 /// ```rust
@@ -113,8 +132,8 @@ pub struct SeizeAuction<'info> {
 ///     .accounts({...})
 ///     .rpc();
 /// ```
-pub fn seize_auction_handler(
-    ctx: Context<SeizeAuction>,
+pub fn seize_auction_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, SeizeAuction<'info>>,
     bid_message: String,
 ) -> Result<()> {
     // 获取账户和配置引用
@@ -128,6 +147,9 @@ pub fn seize_auction_handler(
     // 验证阶段
     // ========================================
     
+    // 暂停检查: 拍卖指令族被暂停时拒绝执行
+    config.require_not_paused(PauseFlag::Auctions, timestamp)?;
+
     // 验证1: 留言长度不能超过100字符
     require!(bid_message.len() <= 100, TotError::StringTooLong);
     
@@ -180,8 +202,9 @@ pub fn seize_auction_handler(
         },
     );
 
-    token_interface::transfer_checked(
+    transfer_checked_with_hook(
         transfer_to_treasury_ctx,
+        ctx.remaining_accounts,
         fee_amount,
         mint_decimals,
     )?;
@@ -202,8 +225,9 @@ pub fn seize_auction_handler(
         },
     );
 
-    token_interface::transfer_checked(
+    transfer_checked_with_hook(
         transfer_to_old_owner_ctx,
+        ctx.remaining_accounts,
         payout_amount,
         mint_decimals,
     )?;
@@ -228,6 +252,19 @@ pub fn seize_auction_handler(
         holder_info.record_consume(min_required, timestamp)?;
     }
 
+    // 累计全局拍卖手续费统计
+    let config = &mut ctx.accounts.config;
+    config.total_auction_fees = config.total_auction_fees
+        .checked_add(fee_amount)
+        .ok_or(TotError::MathOverflow)?;
+
+    // 发放夺取积分（传入积分配置和积分账户时）
+    if let (Some(points_config), Some(new_owner_points)) =
+        (ctx.accounts.points_config.as_ref(), ctx.accounts.new_owner_points.as_mut())
+    {
+        award_points(new_owner_points, points_config.seize_points, PointsSource::Seize, timestamp)?;
+    }
+
     // ========================================
     // 发出拍卖夺取事件
     // ========================================
@@ -0,0 +1,370 @@
+// ============================================
+// 文件: src/instructions/batch_transfer.rs
+// 批量转账/空投指令
+// ============================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, Mint, TokenAccount, TokenInterface, TransferChecked, Burn,
+};
+
+use crate::state::config::{TotConfig, PauseFlag};
+use crate::state::tax::TaxConfig;
+use crate::state::holder::HolderAccount;
+use crate::constants::{seeds, limits::MAX_BATCH_TRANSFER_RECIPIENTS};
+use crate::errors::TotError;
+use crate::utils::tax_calculator::*;
+use crate::utils::validation::validate_transfer_amount;
+use crate::instructions::transfer::TransferWithTaxEvent;
+use crate::utils::token_transfer::transfer_checked_with_hook;
+
+/// 批量转账条目
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct BatchTransferEntry {
+    /// 接收者钱包地址（代币账户的所有者）
+    pub recipient: Pubkey,
+
+    /// 转账金额（原始金额，未扣除税收）
+    pub amount: u64,
+}
+
+/// 批量转账账户结构
+///
+/// 接收者账户通过`remaining_accounts`传入，每个接收者按顺序占两个位置：
+/// 1. 接收者代币账户（可写）
+/// 2. 接收者持有者PDA `["tot_holder", recipient]`（可写，可以尚未初始化）
+///
+/// 接收者账户之后是Transfer Hook需要的账户（见`utils::token_transfer`），
+/// 接收者的持有者PDA也会被Hook读取，因此Hook账户从整个`remaining_accounts`中查找。
+#[derive(Accounts)]
+pub struct BatchTransfer<'info> {
+    /// 发送者（签名者）
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// 发送者代币账户
+    #[account(
+        mut,
+        constraint = sender_token_account.owner == sender.key() @ TotError::InvalidOwner,
+        constraint = sender_token_account.mint == mint.key() @ TotError::InvalidMint
+    )]
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// TOT Mint
+    #[account(
+        mut,
+        address = config.mint @ TotError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// 全局配置（累计协议统计）
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 税率配置
+    #[account(
+        seeds = [seeds::TAX_CONFIG_SEED],
+        bump
+    )]
+    pub tax_config: Account<'info, TaxConfig>,
+
+    /// 发送者持有者信息
+    #[account(
+        seeds = [seeds::HOLDER_SEED, sender.key().as_ref()],
+        bump = sender_holder_info.bump
+    )]
+    pub sender_holder_info: Account<'info, HolderAccount>,
+
+    /// 税收收集账户（流动性池）
+    #[account(
+        mut,
+        constraint = tax_collector.mint == mint.key() @ TotError::InvalidMint
+    )]
+    pub tax_collector: InterfaceAccount<'info, TokenAccount>,
+
+    /// Token 程序
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// 批量转账处理器
+///
+/// 一笔交易内向多个接收者转账，用于营销、社区奖励发放和空投。
+/// 每个接收者执行与`transfer_with_tax`相同的免税、冻结和税率规则（按非卖出转账处理）。
+///
+/// # 功能流程
+///
+/// 1. **验证阶段**: 检查接收者数量、账户数量、暂停状态、总金额和余额
+/// 2. **逐个转账**: 对每个接收者执行免税检查、冻结检查、税率计算，转出净金额
+/// 3. **更新统计**: 更新已初始化的接收者持有者账户的买入统计
+/// 4. **税收处理**: 所有接收者的税收合并为一次销毁和一次转入税收收集账户
+/// 5. **发出事件**: 每个接收者一条`TransferWithTaxEvent`，最后一条`BatchTransferEvent`汇总
+///
+/// # 参数
+/// * `ctx` - 批量转账上下文，接收者账户和Hook账户在`remaining_accounts`中
+/// * `recipients` - 接收者和金额列表
+///
+/// # 返回值
+/// * `Result<()>` - 成功返回Ok(())，任一接收者失败则整笔交易回滚
+///
+/// # 限制
+///
+/// - 每笔最多`MAX_BATCH_TRANSFER_RECIPIENTS`（8）个接收者
+/// - 8个接收者需要约600,000 CU，客户端应在交易前加入ComputeBudget指令
+/// - 交易大小接近上限，接收者较多时建议使用地址查找表
+///
+/// # 注意事项
+///
+/// - 同一接收者可以出现多次，持有者统计按顺序累加
+/// - 接收者持有者账户尚未初始化时跳过其冻结检查和统计更新
+/// - 持有时间折扣按发送者计算，同一批次内所有接收者使用相同的持有者信息
+/// - 发送者是已登记的交易场所时按买入计税，折扣按各接收者计算，税额记入接收者的已缴税额
+pub fn batch_transfer_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchTransfer<'info>>,
+    recipients: Vec<BatchTransferEntry>,
+) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let tax_config = &ctx.accounts.tax_config;
+    let sender_holder = &ctx.accounts.sender_holder_info;
+    let remaining_accounts = ctx.remaining_accounts;
+    let hook_accounts = remaining_accounts;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let sender_key = ctx.accounts.sender.key();
+    let mint_key = ctx.accounts.mint.key();
+    let mint_decimals = ctx.accounts.mint.decimals;
+    let circulating_supply = config.circulating_supply(ctx.accounts.mint.supply);
+
+    // ========================================
+    // 验证阶段
+    // ========================================
+
+    require!(
+        !recipients.is_empty() && recipients.len() <= MAX_BATCH_TRANSFER_RECIPIENTS,
+        TotError::InvalidBatchSize
+    );
+    require!(
+        remaining_accounts.len() >= recipients.len() * 2,
+        TotError::BatchAccountsMismatch
+    );
+
+    config.require_not_paused(PauseFlag::Transfers, timestamp)?;
+
+    // 一次性校验总金额和余额，避免部分接收者转账后才发现余额不足
+    let mut total_amount: u64 = 0;
+    for entry in recipients.iter() {
+        validate_transfer_amount(entry.amount)?;
+        total_amount = total_amount
+            .checked_add(entry.amount)
+            .ok_or(TotError::MathOverflow)?;
+    }
+    require!(
+        ctx.accounts.sender_token_account.amount >= total_amount,
+        TotError::InsufficientBalance
+    );
+
+    let sender_exempt = tax_config.is_exempt(&sender_key);
+    // 销售合约等已登记的交易场所发放代币时按接收者买入计税
+    let is_buy = tax_config.is_venue(&sender_key);
+
+    // ========================================
+    // 逐个接收者转账
+    // ========================================
+
+    let mut total_tax: u64 = 0;
+    let mut total_net: u64 = 0;
+    let mut total_burn: u64 = 0;
+
+    for (entry, accounts) in recipients.iter().zip(remaining_accounts[..recipients.len() * 2].chunks(2)) {
+        let receiver_token_info = &accounts[0];
+        let receiver_holder_info = &accounts[1];
+
+        let receiver_token_account =
+            InterfaceAccount::<TokenAccount>::try_from(receiver_token_info)?;
+        require!(
+            receiver_token_account.owner == entry.recipient,
+            TotError::BatchAccountsMismatch
+        );
+        require!(
+            receiver_token_account.mint == mint_key,
+            TotError::InvalidMint
+        );
+
+        // 持有者账户必须是该接收者的持有者PDA，地址一致后账户为空才表示没有持有者记录，
+        // 否则传入任意账户即可跳过冻结检查、持有时间折扣和统计
+        let (receiver_holder_key, _) = Pubkey::find_program_address(
+            &[seeds::HOLDER_SEED, entry.recipient.as_ref()],
+            ctx.program_id,
+        );
+        require!(
+            receiver_holder_info.key() == receiver_holder_key,
+            TotError::BatchAccountsMismatch
+        );
+        let mut receiver_holder =
+            HolderAccount::load_optional(receiver_holder_info, ctx.program_id)?;
+
+        let is_exempt = sender_exempt || tax_config.is_exempt(&entry.recipient);
+
+        // 免税转账不做冻结检查和税率计算，与transfer_with_tax一致
+        let (tax_amount, net_amount, tax_rate_bps) = if is_exempt {
+            (0, entry.amount, 0)
+        } else {
+            require!(!sender_holder.is_frozen, TotError::HolderFrozen);
+            if let Some(ref holder) = receiver_holder {
+                require!(!holder.is_frozen, TotError::HolderFrozen);
+            }
+
+            // 买入时持有时间折扣按接收者计算
+            let discount_holder = if is_buy {
+                receiver_holder.as_ref()
+            } else {
+                Some(&**sender_holder)
+            };
+            let calculation = TaxCalculator::calculate_tax(
+                entry.amount,
+                discount_holder,
+                circulating_supply,
+                timestamp,
+                is_buy,
+                false, // 批量转账不是卖出
+                config.panic_mode,
+                tax_config,
+            )?;
+            (calculation.tax_amount, calculation.net_amount, calculation.final_tax_bps)
+        };
+
+        if net_amount > 0 {
+            let transfer_ctx = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.sender_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: receiver_token_info.clone(),
+                    authority: ctx.accounts.sender.to_account_info(),
+                },
+            );
+            transfer_checked_with_hook(transfer_ctx, hook_accounts, net_amount, mint_decimals)?;
+        }
+
+        let burned = if tax_amount > 0 {
+            TaxDistribution::calculate(tax_amount)?.to_burn
+        } else {
+            0
+        };
+
+        // 更新接收者统计（接收代币相当于买入操作）
+        if let Some(ref mut holder) = receiver_holder {
+            holder.record_buy(net_amount, if is_buy { tax_amount } else { 0 }, timestamp)?;
+            if holder.token_account == Pubkey::default() {
+                holder.token_account = receiver_token_info.key();
+            }
+            holder.store(receiver_holder_info)?;
+        }
+
+        total_tax = total_tax.checked_add(tax_amount).ok_or(TotError::MathOverflow)?;
+        total_net = total_net.checked_add(net_amount).ok_or(TotError::MathOverflow)?;
+        total_burn = total_burn.checked_add(burned).ok_or(TotError::MathOverflow)?;
+
+        emit!(TransferWithTaxEvent {
+            from: sender_key,
+            to: entry.recipient,
+            amount: entry.amount,
+            tax_amount,
+            net_amount,
+            tax_rate_bps,
+            burned,
+            reference: None,
+            timestamp,
+        });
+    }
+
+    // ========================================
+    // 合并处理税收
+    // ========================================
+    //
+    // 所有接收者的税收合并为一次销毁和一次转账，节省CPI次数。
+
+    if total_burn > 0 {
+        let burn_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.sender_token_account.to_account_info(),
+                authority: ctx.accounts.sender.to_account_info(),
+            },
+        );
+        token_interface::burn(burn_ctx, total_burn)?;
+    }
+
+    let total_collected = total_tax
+        .checked_sub(total_burn)
+        .ok_or(TotError::MathUnderflow)?;
+
+    if total_collected > 0 {
+        let tax_transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.sender_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.tax_collector.to_account_info(),
+                authority: ctx.accounts.sender.to_account_info(),
+            },
+        );
+        transfer_checked_with_hook(tax_transfer_ctx, hook_accounts, total_collected, mint_decimals)?;
+    }
+
+    // 累计全局收税和销毁统计
+    ctx.accounts.config.record_tax(total_tax, total_burn)?;
+
+    emit!(BatchTransferEvent {
+        from: sender_key,
+        recipient_count: recipients.len() as u8,
+        total_amount,
+        total_tax,
+        total_net,
+        total_burned: total_burn,
+        total_collected,
+        timestamp,
+    });
+
+    msg!(
+        "Batch transfer: {} recipients, amount={}, tax={}",
+        recipients.len(),
+        total_amount,
+        total_tax
+    );
+    Ok(())
+}
+
+/// 批量转账汇总事件
+///
+/// 每笔批量转账在所有接收者的`TransferWithTaxEvent`之后发出一条。
+#[event]
+pub struct BatchTransferEvent {
+    /// 发送者地址
+    pub from: Pubkey,
+
+    /// 接收者数量
+    pub recipient_count: u8,
+
+    /// 原始转账总额（未扣除税收）
+    pub total_amount: u64,
+
+    /// 税收总额
+    pub total_tax: u64,
+
+    /// 接收者实际收到的总额
+    pub total_net: u64,
+
+    /// 销毁的代币数量
+    pub total_burned: u64,
+
+    /// 转入税收收集账户的数量
+    pub total_collected: u64,
+
+    /// 交易时间戳
+    pub timestamp: i64,
+}
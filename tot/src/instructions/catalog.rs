@@ -0,0 +1,263 @@
+// ============================================
+// 文件: src/instructions/catalog.rs
+// 价格目录管理指令
+// ============================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::config::TotConfig;
+use crate::state::catalog::{PriceCatalog, PriceEntry, RevenueSplit};
+use crate::instructions::consume::ConsumeType;
+use crate::constants::seeds;
+use crate::errors::TotError;
+
+/// 初始化价格目录账户结构
+#[derive(Accounts)]
+pub struct InitializePriceCatalog<'info> {
+    /// 系统管理员（支付租金）
+    #[account(
+        mut,
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 价格目录PDA
+    #[account(
+        init,
+        payer = authority,
+        space = PriceCatalog::LEN,
+        seeds = [seeds::PRICE_CATALOG_SEED],
+        bump
+    )]
+    pub price_catalog: Account<'info, PriceCatalog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 初始化价格目录处理器
+///
+/// 创建空的全局价格目录。目录为空时所有消费类型都不限制金额，
+/// 管理员为某个消费类型登记价格后，该类型的消费才按目录校验。
+pub fn initialize_price_catalog_handler(ctx: Context<InitializePriceCatalog>) -> Result<()> {
+    let catalog = &mut ctx.accounts.price_catalog;
+    catalog.entries = Vec::new();
+    catalog.splits = Default::default();
+    catalog.last_updated = Clock::get()?.unix_timestamp;
+    catalog.bump = ctx.bumps.price_catalog;
+
+    msg!("Price catalog initialized");
+    Ok(())
+}
+
+/// 价格目录维护账户结构（设置/删除价格、设置收入分成共用）
+#[derive(Accounts)]
+pub struct ManagePriceCatalog<'info> {
+    /// 系统管理员
+    #[account(
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 价格目录PDA
+    #[account(
+        mut,
+        seeds = [seeds::PRICE_CATALOG_SEED],
+        bump = price_catalog.bump
+    )]
+    pub price_catalog: Account<'info, PriceCatalog>,
+}
+
+/// 设置目录价格处理器
+///
+/// 新增或覆盖（消费类型, SKU）的价格条目，并发出`CatalogPriceUpdated`事件，
+/// 前端据此更新缓存的价格表。
+///
+/// # 参数
+/// * `consume_type` - 消费类型
+/// * `sku` - 商品SKU（0表示该消费类型的默认价格）
+/// * `price` - 价格（基础单位，0表示不限金额，此时必须登记受益人）
+/// * `discount_bps` - 持有者折扣（basis points，不能超过10000）
+/// * `min_holding_days` - 享受折扣所需的最少持有天数
+/// * `is_active` - 是否上架（下架后该条目的消费会被拒绝）
+/// * `beneficiary` - 收入分成受益人（Pubkey::default()表示不登记）
+pub fn set_catalog_price_handler(
+    ctx: Context<ManagePriceCatalog>,
+    consume_type: ConsumeType,
+    sku: u32,
+    price: u64,
+    discount_bps: u16,
+    min_holding_days: u16,
+    is_active: bool,
+    beneficiary: Pubkey,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let catalog = &mut ctx.accounts.price_catalog;
+
+    let entry = PriceEntry {
+        consume_type: consume_type as u8,
+        sku,
+        price,
+        discount_bps,
+        min_holding_days,
+        is_active,
+        beneficiary,
+    };
+    let is_new = catalog.upsert(entry)?;
+    catalog.last_updated = timestamp;
+
+    emit!(CatalogPriceUpdated {
+        consume_type: consume_type as u8,
+        sku,
+        price,
+        discount_bps,
+        min_holding_days,
+        is_active,
+        beneficiary,
+        is_new,
+        updated_by: ctx.accounts.authority.key(),
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 删除目录价格处理器
+///
+/// 删除（消费类型, SKU）的价格条目。某个消费类型的条目全部删除后，
+/// 该类型的消费不再按目录校验金额。
+pub fn remove_catalog_price_handler(
+    ctx: Context<ManagePriceCatalog>,
+    consume_type: ConsumeType,
+    sku: u32,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let catalog = &mut ctx.accounts.price_catalog;
+
+    catalog.remove(consume_type as u8, sku)?;
+    catalog.last_updated = timestamp;
+
+    emit!(CatalogPriceRemoved {
+        consume_type: consume_type as u8,
+        sku,
+        removed_by: ctx.accounts.authority.key(),
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 设置收入分成处理器
+///
+/// 设置消费类型的收入分成（销毁/财库/受益人），三项之和必须为10000；
+/// 三项全部为0表示取消分成，该类型的消费全额进入财库。
+///
+/// # 参数
+/// * `consume_type` - 消费类型
+/// * `burn_bps` - 销毁比例
+/// * `treasury_bps` - 财库比例
+/// * `beneficiary_bps` - 受益人比例（商品未登记受益人时并入财库）
+pub fn set_revenue_split_handler(
+    ctx: Context<ManagePriceCatalog>,
+    consume_type: ConsumeType,
+    burn_bps: u16,
+    treasury_bps: u16,
+    beneficiary_bps: u16,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let split = RevenueSplit {
+        burn_bps,
+        treasury_bps,
+        beneficiary_bps,
+    };
+    if split.is_configured() {
+        split.validate()?;
+    }
+
+    let catalog = &mut ctx.accounts.price_catalog;
+    let slot = catalog.splits
+        .get_mut(consume_type as usize)
+        .ok_or(TotError::InvalidRevenueSplit)?;
+    *slot = split;
+    catalog.last_updated = timestamp;
+
+    emit!(RevenueSplitUpdated {
+        consume_type: consume_type as u8,
+        burn_bps,
+        treasury_bps,
+        beneficiary_bps,
+        updated_by: ctx.accounts.authority.key(),
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 目录价格更新事件
+#[event]
+pub struct CatalogPriceUpdated {
+    /// 消费类型
+    pub consume_type: u8,
+    /// 商品SKU
+    pub sku: u32,
+    /// 价格
+    pub price: u64,
+    /// 持有者折扣（basis points）
+    pub discount_bps: u16,
+    /// 享受折扣所需的最少持有天数
+    pub min_holding_days: u16,
+    /// 是否上架
+    pub is_active: bool,
+    /// 收入分成受益人
+    pub beneficiary: Pubkey,
+    /// 是否为新增条目
+    pub is_new: bool,
+    /// 操作者
+    pub updated_by: Pubkey,
+    /// 更新时间
+    pub timestamp: i64,
+}
+
+/// 目录价格删除事件
+#[event]
+pub struct CatalogPriceRemoved {
+    /// 消费类型
+    pub consume_type: u8,
+    /// 商品SKU
+    pub sku: u32,
+    /// 操作者
+    pub removed_by: Pubkey,
+    /// 删除时间
+    pub timestamp: i64,
+}
+
+/// 收入分成更新事件
+#[event]
+pub struct RevenueSplitUpdated {
+    /// 消费类型
+    pub consume_type: u8,
+    /// 销毁比例（basis points）
+    pub burn_bps: u16,
+    /// 财库比例（basis points）
+    pub treasury_bps: u16,
+    /// 受益人比例（basis points）
+    pub beneficiary_bps: u16,
+    /// 操作者
+    pub updated_by: Pubkey,
+    /// 更新时间
+    pub timestamp: i64,
+}
@@ -0,0 +1,331 @@
+// ============================================
+// 文件: src/instructions/clawback.rs
+// 追回指令 - 通过Permanent Delegate转移或销毁冻结账户的代币
+// ============================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use anchor_lang::solana_program::program::invoke;
+use spl_token_2022::instruction::{self as token_2022_instruction, AuthorityType};
+
+use crate::state::config::TotConfig;
+use crate::state::holder::HolderAccount;
+use crate::state::clawback::{ClawbackRecord, ClawbackAction};
+use crate::constants::{seeds, time};
+use crate::errors::TotError;
+use crate::utils::token_transfer::transfer_checked_with_hook;
+
+/// 追回账户结构
+#[derive(Accounts)]
+#[instruction(case_id: u64)]
+pub struct Clawback<'info> {
+    /// 执行者（系统管理员或合规权限）
+    ///
+    /// 同时支付追回记录账户的租金。
+    #[account(
+        mut,
+        constraint = operator.key() == config.authority
+            || (config.compliance_authority != Pubkey::default()
+                && operator.key() == config.compliance_authority)
+            @ TotError::Unauthorized
+    )]
+    pub operator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 被追回的持有者信息（必须处于冻结状态）
+    #[account(
+        seeds = [seeds::HOLDER_SEED, holder_info.owner.as_ref()],
+        bump = holder_info.bump,
+        constraint = holder_info.is_frozen @ TotError::HolderNotFrozen
+    )]
+    pub holder_info: Account<'info, HolderAccount>,
+
+    /// 被追回的代币账户
+    #[account(
+        mut,
+        token::mint = mint,
+        constraint = source_token_account.owner == holder_info.owner @ TotError::TokenAccountMismatch
+    )]
+    pub source_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 接收代币账户（ClawbackAction::Transfer时必须提供）
+    #[account(
+        mut,
+        token::mint = mint
+    )]
+    pub destination_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// TOT Mint
+    #[account(
+        mut,
+        address = config.mint @ TotError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// 永久代理PDA
+    /// CHECK: 仅作为Permanent Delegate签名，地址由seeds约束
+    #[account(
+        seeds = [seeds::PERMANENT_DELEGATE_SEED],
+        bump
+    )]
+    pub permanent_delegate: UncheckedAccount<'info>,
+
+    /// 追回记录（每个案件编号只能创建一次）
+    #[account(
+        init,
+        payer = operator,
+        space = ClawbackRecord::LEN,
+        seeds = [seeds::CLAWBACK_SEED, case_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub clawback_record: Account<'info, ClawbackRecord>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 追回处理器
+///
+/// 由永久代理PDA签名，把冻结持有者的代币转移到指定账户或直接销毁，
+/// 并创建追回记录、更新全局追回统计。
+///
+/// # 限制
+/// - 只有系统管理员（多签）或合规权限可以执行
+/// - 持有者必须已冻结满`MIN_CLAWBACK_FROZEN_PERIOD`
+/// - 每个案件编号只能使用一次
+pub fn clawback_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, Clawback<'info>>,
+    case_id: u64,
+    action: ClawbackAction,
+    amount: u64,
+    reason_hash: [u8; 32],
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let holder_info = &ctx.accounts.holder_info;
+
+    // 冻结时长检查: 冻结满最短时间后才能追回，给持有者留出申诉窗口
+    let frozen_for = timestamp
+        .checked_sub(holder_info.frozen_at)
+        .ok_or(TotError::MathOverflow)?;
+    require!(
+        holder_info.frozen_at > 0 && frozen_for >= time::MIN_CLAWBACK_FROZEN_PERIOD,
+        TotError::ClawbackFrozenPeriodNotElapsed
+    );
+
+    require!(amount > 0, TotError::InvalidAmount);
+    require!(
+        ctx.accounts.source_token_account.amount >= amount,
+        TotError::InsufficientBalance
+    );
+
+    let delegate_seeds: &[&[u8]] = &[
+        seeds::PERMANENT_DELEGATE_SEED,
+        &[ctx.bumps.permanent_delegate],
+    ];
+    let signer_seeds = &[delegate_seeds];
+
+    let destination = match action {
+        ClawbackAction::Transfer => {
+            let destination_token_account = ctx.accounts.destination_token_account
+                .as_ref()
+                .ok_or(TotError::ClawbackDestinationRequired)?;
+
+            transfer_checked_with_hook(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.source_token_account.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: destination_token_account.to_account_info(),
+                        authority: ctx.accounts.permanent_delegate.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                ctx.remaining_accounts,
+                amount,
+                ctx.accounts.mint.decimals,
+            )?;
+
+            destination_token_account.key()
+        }
+        ClawbackAction::Burn => {
+            token_interface::burn(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        from: ctx.accounts.source_token_account.to_account_info(),
+                        authority: ctx.accounts.permanent_delegate.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+
+            Pubkey::default()
+        }
+    };
+
+    // 记录追回
+    let record = &mut ctx.accounts.clawback_record;
+    record.case_id = case_id;
+    record.holder = holder_info.owner;
+    record.source_token_account = ctx.accounts.source_token_account.key();
+    record.destination_token_account = destination;
+    record.action = action;
+    record.amount = amount;
+    record.reason_hash = reason_hash;
+    record.operator = ctx.accounts.operator.key();
+    record.freeze_reason = holder_info.freeze_reason;
+    record.frozen_at = holder_info.frozen_at;
+    record.executed_at = timestamp;
+    record.bump = ctx.bumps.clawback_record;
+
+    // 更新全局统计
+    let config = &mut ctx.accounts.config;
+    config.total_clawed_back = config.total_clawed_back
+        .checked_add(amount)
+        .ok_or(TotError::MathOverflow)?;
+    config.clawback_count = config.clawback_count
+        .checked_add(1)
+        .ok_or(TotError::MathOverflow)?;
+    if action == ClawbackAction::Burn {
+        config.total_burned = config.total_burned
+            .checked_add(amount)
+            .ok_or(TotError::MathOverflow)?;
+    }
+
+    msg!(
+        "Clawback case {}: {:?} {} from {}",
+        case_id,
+        action,
+        amount,
+        holder_info.owner
+    );
+
+    emit!(ClawbackExecuted {
+        case_id,
+        holder: holder_info.owner,
+        action,
+        amount,
+        destination,
+        reason_hash,
+        operator: ctx.accounts.operator.key(),
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 迁移永久代理账户结构
+///
+/// 用于旧版本`initialize`部署的Mint：Permanent Delegate仍是部署者钱包，
+/// 通过此指令一次性移交给`PERMANENT_DELEGATE_SEED` PDA。
+#[derive(Accounts)]
+pub struct MigratePermanentDelegate<'info> {
+    /// 系统管理员
+    #[account(
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// 当前的Permanent Delegate
+    ///
+    /// 实际是否为永久代理由Token-2022程序验证。
+    pub current_delegate: Signer<'info>,
+
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// TOT Mint账户
+    /// CHECK: 地址与config.mint一致，数据由Token-2022程序验证和修改
+    #[account(
+        mut,
+        address = config.mint @ TotError::InvalidMint
+    )]
+    pub mint: AccountInfo<'info>,
+
+    /// 永久代理PDA
+    /// CHECK: 仅作为新的Permanent Delegate地址，地址由seeds约束
+    #[account(
+        seeds = [seeds::PERMANENT_DELEGATE_SEED],
+        bump
+    )]
+    pub permanent_delegate: UncheckedAccount<'info>,
+
+    /// Token-2022 程序
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// 迁移永久代理处理器
+///
+/// 迁移后只能通过`clawback`使用永久代理权限。
+pub fn migrate_permanent_delegate_handler(ctx: Context<MigratePermanentDelegate>) -> Result<()> {
+    let mint = &ctx.accounts.mint;
+    let current_delegate = &ctx.accounts.current_delegate;
+    let permanent_delegate = ctx.accounts.permanent_delegate.key();
+
+    invoke(
+        &token_2022_instruction::set_authority(
+            &ctx.accounts.token_program.key(),
+            &mint.key(),
+            Some(&permanent_delegate),
+            AuthorityType::PermanentDelegate,
+            &current_delegate.key(),
+            &[],
+        )?,
+        &[
+            mint.to_account_info(),
+            current_delegate.to_account_info(),
+        ],
+    )?;
+
+    msg!(
+        "Permanent delegate migrated from {} to program PDA {}",
+        current_delegate.key(),
+        permanent_delegate
+    );
+
+    emit!(PermanentDelegateMigrated {
+        old_delegate: current_delegate.key(),
+        permanent_delegate,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// 追回执行事件
+#[event]
+pub struct ClawbackExecuted {
+    pub case_id: u64,
+    pub holder: Pubkey,
+    pub action: ClawbackAction,
+    pub amount: u64,
+    /// 接收代币账户（销毁时为Pubkey::default()）
+    pub destination: Pubkey,
+    pub reason_hash: [u8; 32],
+    pub operator: Pubkey,
+    pub timestamp: i64,
+}
+
+/// 永久代理迁移事件
+#[event]
+pub struct PermanentDelegateMigrated {
+    pub old_delegate: Pubkey,
+    /// 程序PDA地址
+    pub permanent_delegate: Pubkey,
+    pub timestamp: i64,
+}
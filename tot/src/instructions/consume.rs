@@ -5,14 +5,20 @@
 
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{
-    self, Mint, TokenAccount, TokenInterface, TransferChecked,
+    self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked,
 };
 
-use crate::state::config::TotConfig;
+use crate::state::config::{TotConfig, PauseFlag};
 use crate::state::holder::HolderAccount;
+use crate::state::catalog::{PriceCatalog, RevenueDistribution};
+use crate::state::receipt::{PaymentReceipt, PaymentKind, ConsumeReceipt};
+use crate::state::points::{PointsConfig, PointsAccount, PointsSource};
+use crate::instructions::receipt::{create_payment_receipt, create_consume_receipt};
+use crate::instructions::points::award_points;
 use crate::constants::seeds;
 use crate::errors::TotError;
 use crate::utils::validation::validate_transfer_amount;
+use crate::utils::token_transfer::transfer_checked_with_hook;
 
 /// 消费类型枚举
 /// 
@@ -35,6 +41,17 @@ pub enum ConsumeType {
     PredictionFee = 6,
 }
 
+/// 消费回执参数
+///
+/// 提供时`consume_to_treasury`会创建`ConsumeReceipt` PDA，供后端核销。
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ConsumeReceiptArgs {
+    /// 客户端随机数（同一用户下唯一，参与PDA派生）
+    pub nonce: u64,
+    /// 商品引用（如地图操作或拍卖资产ID的哈希）
+    pub item_ref: [u8; 32],
+}
+
 /// 消费转账账户结构
 #[derive(Accounts)]
 pub struct ConsumeToTreasury<'info> {
@@ -62,8 +79,9 @@ pub struct ConsumeToTreasury<'info> {
     #[account(mut)]
     pub mint: InterfaceAccount<'info, Mint>,
 
-    /// 全局配置
+    /// 全局配置（消费进入财库的代币计入锁定供应量，累计消费统计）
     #[account(
+        mut,
         seeds = [seeds::CONFIG_SEED],
         bump
     )]
@@ -77,8 +95,50 @@ pub struct ConsumeToTreasury<'info> {
     )]
     pub user_holder_info: Option<Account<'info, HolderAccount>>,
 
+    /// 价格目录（校验消费金额，提供收入分成配置）
+    #[account(
+        seeds = [seeds::PRICE_CATALOG_SEED],
+        bump = price_catalog.bump
+    )]
+    pub price_catalog: Account<'info, PriceCatalog>,
+
+    /// 受益人代币账户（商品登记了受益人且分成包含受益人份额时必须传入）
+    #[account(
+        mut,
+        constraint = beneficiary_token_account.mint == mint.key() @ TotError::InvalidMint
+    )]
+    pub beneficiary_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
     /// Token 程序
     pub token_program: Interface<'info, TokenInterface>,
+
+    /// 支付回执PDA（提供支付引用时必须传入）
+    /// CHECK: 由create_payment_receipt校验地址并创建
+    #[account(mut)]
+    pub payment_receipt: Option<UncheckedAccount<'info>>,
+
+    /// 消费回执PDA（提供回执参数时必须传入）
+    /// CHECK: 由create_consume_receipt校验地址并创建
+    #[account(mut)]
+    pub consume_receipt: Option<UncheckedAccount<'info>>,
+
+    /// System Program（提供支付引用或回执参数时必须传入）
+    pub system_program: Option<Program<'info, System>>,
+
+    /// 积分配置（与用户积分账户同时传入时按消费类型发放积分）
+    #[account(
+        seeds = [seeds::POINTS_CONFIG_SEED],
+        bump = points_config.bump
+    )]
+    pub points_config: Option<Account<'info, PointsConfig>>,
+
+    /// 用户积分账户（可选）
+    #[account(
+        mut,
+        seeds = [seeds::POINTS_SEED, user.key().as_ref()],
+        bump = user_points.bump
+    )]
+    pub user_points: Option<Account<'info, PointsAccount>>,
 }
 
 /// 消费转账处理器
@@ -90,14 +150,17 @@ pub struct ConsumeToTreasury<'info> {
 /// 
 /// 1. **验证阶段**: 检查系统状态、账户状态、金额有效性
 /// 2. **财库验证**: 验证接收者是否为配置的TWS财库地址
-/// 3. **执行转账**: 将全额金额转给TWS财库（无税收）
+/// 3. **执行分成**: 按消费类型的收入分成销毁、转入TWS财库、转给受益人（无税收）
 /// 4. **更新统计**: 更新用户的消费统计信息
 /// 5. **发出事件**: 记录消费信息到链上日志
 /// 
 /// # 参数
 /// * `ctx` - 消费转账上下文，包含所有必需的账户
-/// * `amount` - 消费金额（全额转账，无税收）
+/// * `amount` - 消费金额（无税收，按收入分成分配）
 /// * `consume_type` - 消费类型（地图操作、祖籍标记等）
+/// * `sku` - 商品SKU（0表示该消费类型的默认价格）
+/// * `reference` - 支付引用（可选），写入事件并创建回执PDA，同一引用只能使用一次
+/// * `receipt` - 消费回执参数（可选），创建`ConsumeReceipt` PDA供后端核销
 /// 
 /// # 返回值
 /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
@@ -108,6 +171,9 @@ pub struct ConsumeToTreasury<'info> {
 /// - 系统处于恐慌模式时，消费仍可进行（消费不受限制）
 /// - 消费不收取税收，全额转账给TWS财库
 /// - 消费会更新用户的消费统计
+/// - 价格目录为该消费类型登记了价格时，金额必须等于目录价格（持有天数达标时按折扣价）
+/// - 未配置收入分成的消费类型全额进入财库；商品未登记受益人时受益人份额并入财库
+/// - 传入积分配置和用户积分账户时，按该消费类型的比例发放积分
 /// 
 /// # 使用示例
 /// ```rust
@@ -115,30 +181,66 @@ pub struct ConsumeToTreasury<'info> {
 /// program.methods
 ///     .consumeToTreasury(
 ///         new anchor.BN(100000000), // 消费100个代币
-///         { mapAction: {} } // 地图操作
+///         { mapAction: {} }, // 地图操作
+///         0, // 默认SKU
+///         null, // 不使用支付引用
+///         { nonce: new anchor.BN(1), itemRef: itemHash } // 创建消费回执
 ///     )
 ///     .accounts({...})
 ///     .rpc();
 /// ```
-pub fn consume_to_treasury_handler(
-    ctx: Context<ConsumeToTreasury>,
+pub fn consume_to_treasury_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ConsumeToTreasury<'info>>,
     amount: u64,
     consume_type: ConsumeType,
+    sku: u32,
+    reference: Option<[u8; 32]>,
+    receipt: Option<ConsumeReceiptArgs>,
+) -> Result<()> {
+    process_consume(
+        ctx.accounts,
+        ctx.program_id,
+        ctx.remaining_accounts,
+        amount,
+        consume_type,
+        sku,
+        reference,
+        receipt,
+    )
+}
+
+/// 消费转账的共享实现
+///
+/// `consume_to_treasury`和合作程序CPI入口`partner_consume`共用。
+/// `hook_accounts`为Transfer Hook需要的账户（指令的`remaining_accounts`）。
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn process_consume<'info>(
+    accounts: &mut ConsumeToTreasury<'info>,
+    program_id: &Pubkey,
+    hook_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    consume_type: ConsumeType,
+    sku: u32,
+    reference: Option<[u8; 32]>,
+    receipt: Option<ConsumeReceiptArgs>,
 ) -> Result<()> {
     // 获取账户和配置引用
-    let config = &ctx.accounts.config;
+    let config = &accounts.config;
     let clock = Clock::get()?;
     let timestamp = clock.unix_timestamp;
 
     // 缓存常用值以减少重复访问
-    let user_key = ctx.accounts.user.key();
-    let treasury_owner = ctx.accounts.treasury_token_account.owner;
-    let mint_decimals = ctx.accounts.mint.decimals;
+    let user_key = accounts.user.key();
+    let treasury_owner = accounts.treasury_token_account.owner;
+    let mint_decimals = accounts.mint.decimals;
 
     // ========================================
     // 验证阶段
     // ========================================
     
+    // 暂停检查: 消费指令族被暂停时拒绝执行
+    config.require_not_paused(PauseFlag::Consume, timestamp)?;
+
     // 验证1: 检查消费金额是否有效（必须大于0）
     validate_transfer_amount(amount)?;
 
@@ -157,10 +259,30 @@ pub fn consume_to_treasury_handler(
     );
 
     // 验证4: 检查用户账户是否被冻结（如果存在持有者账户）
-    if let Some(ref user_holder) = ctx.accounts.user_holder_info {
+    if let Some(ref user_holder) = accounts.user_holder_info {
         require!(!user_holder.is_frozen, TotError::HolderFrozen);
     }
 
+    // 验证5: 按价格目录校验金额
+    // 该消费类型未登记任何价格时不限制金额（如预测下注）
+    let holding_days = accounts.user_holder_info
+        .as_ref()
+        .map(|holder| holder.get_holding_days(timestamp));
+    if let Some(expected) = accounts.price_catalog
+        .expected_price(consume_type as u8, sku, holding_days)?
+    {
+        require!(amount == expected, TotError::PriceMismatch);
+    }
+
+    // 验证6: 解析收入分成和受益人
+    let (distribution, beneficiary) = resolve_revenue_distribution(
+        &accounts.price_catalog,
+        consume_type as u8,
+        sku,
+        amount,
+        accounts.beneficiary_token_account.as_ref(),
+    )?;
+
     // ========================================
     // 余额验证（在转账前验证，避免无效转账浪费gas）
     // ========================================
@@ -168,32 +290,69 @@ pub fn consume_to_treasury_handler(
     // 验证用户账户余额是否足够支付消费金额。
     // 消费是全额转账，不需要扣除税收。
     require!(
-        ctx.accounts.user_token_account.amount >= amount,
+        accounts.user_token_account.amount >= amount,
         TotError::InsufficientBalance
     );
 
     // ========================================
-    // 执行转账 - 全额金额给TWS财库
+    // 执行分成 - 销毁 / TWS财库 / 受益人
     // ========================================
     // 
-    // 将全额金额转给TWS财库，不收取任何税收。
-    
-    let transfer_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        TransferChecked {
-            from: ctx.accounts.user_token_account.to_account_info(),
-            mint: ctx.accounts.mint.to_account_info(),
-            to: ctx.accounts.treasury_token_account.to_account_info(),
-            authority: ctx.accounts.user.to_account_info(),
-        },
-    );
+    // 三部分在同一指令内完成，任一步失败整笔消费回滚。不收取任何税收。
 
-    // 执行转账（使用transfer_checked确保金额和精度正确）
-    token_interface::transfer_checked(
-        transfer_ctx,
-        amount,  // 全额转账，无税收
-        mint_decimals,  // 代币精度
-    )?;
+    // 分成1: 销毁部分（直接从用户账户销毁）
+    if distribution.to_burn > 0 {
+        token_interface::burn(
+            CpiContext::new(
+                accounts.token_program.to_account_info(),
+                Burn {
+                    mint: accounts.mint.to_account_info(),
+                    from: accounts.user_token_account.to_account_info(),
+                    authority: accounts.user.to_account_info(),
+                },
+            ),
+            distribution.to_burn,
+        )?;
+    }
+
+    // 分成2: 财库部分（使用transfer_checked确保金额和精度正确）
+    if distribution.to_treasury > 0 {
+        transfer_checked_with_hook(
+            CpiContext::new(
+                accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: accounts.user_token_account.to_account_info(),
+                    mint: accounts.mint.to_account_info(),
+                    to: accounts.treasury_token_account.to_account_info(),
+                    authority: accounts.user.to_account_info(),
+                },
+            ),
+            hook_accounts,
+            distribution.to_treasury,
+            mint_decimals,
+        )?;
+    }
+
+    // 分成3: 受益人部分
+    if distribution.to_beneficiary > 0 {
+        let beneficiary_account = accounts.beneficiary_token_account
+            .as_ref()
+            .ok_or(TotError::BeneficiaryAccountMissing)?;
+        transfer_checked_with_hook(
+            CpiContext::new(
+                accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: accounts.user_token_account.to_account_info(),
+                    mint: accounts.mint.to_account_info(),
+                    to: beneficiary_account.to_account_info(),
+                    authority: accounts.user.to_account_info(),
+                },
+            ),
+            hook_accounts,
+            distribution.to_beneficiary,
+            mint_decimals,
+        )?;
+    }
 
     // ========================================
     // 更新用户统计信息
@@ -201,10 +360,79 @@ pub fn consume_to_treasury_handler(
     // 
     // 更新用户的消费统计，记录消费金额和时间。
     
-    if let Some(ref mut user_holder) = ctx.accounts.user_holder_info {
+    if let Some(ref mut user_holder) = accounts.user_holder_info {
         user_holder.record_consume(amount, timestamp)?;
     }
 
+    // 进入财库的代币不再流通（按财库实际收到的数量，扣除Transfer Fee预扣后）
+    let treasury_balance_before = accounts.treasury_token_account.amount;
+    accounts.treasury_token_account.reload()?;
+    let received = accounts.treasury_token_account.amount
+        .saturating_sub(treasury_balance_before);
+    let config = &mut accounts.config;
+    config.locked_supply = config.locked_supply.saturating_add(received);
+
+    // 累计全局消费统计
+    config.record_consume(consume_type as u8, amount)?;
+    config.record_burn(distribution.to_burn)?;
+
+    // 发放消费积分（传入积分配置和积分账户时）
+    if let (Some(points_config), Some(user_points)) =
+        (accounts.points_config.as_ref(), accounts.user_points.as_mut())
+    {
+        let points = points_config.consume_points(consume_type as u8, amount)?;
+        award_points(user_points, points, PointsSource::Consume, timestamp)?;
+    }
+
+    // ========================================
+    // 创建支付回执（提供支付引用时）
+    // ========================================
+    
+    if let Some(reference) = reference {
+        create_payment_receipt(
+            program_id,
+            accounts.payment_receipt.as_ref(),
+            accounts.system_program.as_ref(),
+            accounts.user.to_account_info(),
+            PaymentReceipt {
+                reference,
+                payer: user_key,
+                recipient: treasury_owner,
+                kind: PaymentKind::Consume,
+                amount,
+                created_at: timestamp,
+                bump: 0,
+            },
+        )?;
+    }
+
+    // ========================================
+    // 创建消费回执（提供回执参数时）
+    // ========================================
+    // 
+    // 回执PDA按（用户, 随机数）派生，同一随机数重复提交会在这里失败，整笔消费回滚。
+    
+    if let Some(args) = receipt {
+        create_consume_receipt(
+            program_id,
+            accounts.consume_receipt.as_ref(),
+            accounts.system_program.as_ref(),
+            accounts.user.to_account_info(),
+            ConsumeReceipt {
+                user: user_key,
+                nonce: args.nonce,
+                amount,
+                consume_type: consume_type as u8,
+                item_ref: args.item_ref,
+                slot: clock.slot,
+                created_at: timestamp,
+                is_redeemed: false,
+                redeemed_at: 0,
+                bump: 0,
+            },
+        )?;
+    }
+
     // ========================================
     // 发出消费事件
     // ========================================
@@ -217,12 +445,59 @@ pub fn consume_to_treasury_handler(
         treasury: treasury_owner,
         amount,
         consume_type: consume_type as u8,
+        sku,
+        burned: distribution.to_burn,
+        treasury_amount: distribution.to_treasury,
+        beneficiary,
+        beneficiary_amount: distribution.to_beneficiary,
+        reference,
         timestamp,
     });
 
     Ok(())
 }
 
+/// 按价格目录解析消费的收入分成和受益人
+///
+/// consume_to_treasury和charge_subscription共用。受益人只能是价格目录为该商品登记的地址，
+/// 调用方不能自行指定；商品未登记受益人时受益人份额并入财库，未配置分成的消费类型全额进入财库。
+///
+/// # 错误
+/// * `TotError::BeneficiaryAccountMissing` - 商品登记了受益人但未传入其代币账户
+/// * `TotError::BeneficiaryMismatch` - 传入的代币账户不属于登记的受益人
+pub(crate) fn resolve_revenue_distribution(
+    price_catalog: &PriceCatalog,
+    consume_type: u8,
+    sku: u32,
+    amount: u64,
+    beneficiary_token_account: Option<&InterfaceAccount<TokenAccount>>,
+) -> Result<(RevenueDistribution, Option<Pubkey>)> {
+    let split = price_catalog.split_for(consume_type);
+    if !split.is_configured() {
+        let distribution = RevenueDistribution {
+            to_treasury: amount,
+            ..Default::default()
+        };
+        return Ok((distribution, None));
+    }
+
+    let beneficiary = if split.beneficiary_bps > 0 {
+        price_catalog.beneficiary_for(consume_type, sku)
+    } else {
+        None
+    };
+    if let Some(expected_beneficiary) = beneficiary {
+        let beneficiary_account = beneficiary_token_account
+            .ok_or(TotError::BeneficiaryAccountMissing)?;
+        require!(
+            beneficiary_account.owner == expected_beneficiary,
+            TotError::BeneficiaryMismatch
+        );
+    }
+    let distribution = split.distribute(amount, beneficiary.is_some())?;
+    Ok((distribution, beneficiary))
+}
+
 /// 消费转账事件
 /// 
 /// 每次消费转账都会发出此事件，记录完整的消费信息。
@@ -235,12 +510,172 @@ pub struct ConsumeToTreasuryEvent {
     /// TWS财库地址
     pub treasury: Pubkey,
     
-    /// 消费金额（全额转账，无税收）
+    /// 消费金额（无税收，含销毁和受益人份额）
     pub amount: u64,
     
     /// 消费类型（0=地图操作, 1=祖籍标记, 2=其他）
     pub consume_type: u8,
     
+    /// 商品SKU（0表示默认价格）
+    pub sku: u32,
+    
+    /// 销毁数量
+    pub burned: u64,
+    
+    /// 转入TWS财库数量
+    pub treasury_amount: u64,
+    
+    /// 受益人（未分给受益人时为None）
+    pub beneficiary: Option<Pubkey>,
+    
+    /// 转给受益人数量
+    pub beneficiary_amount: u64,
+    
+    /// 支付引用（未提供时为None）
+    pub reference: Option<[u8; 32]>,
+    
     /// 交易时间戳
     pub timestamp: i64,
 }
+
+/// 核销消费回执账户结构
+#[derive(Accounts)]
+pub struct RedeemConsumeReceipt<'info> {
+    /// 核销者（系统管理员或回执核销权限）
+    #[account(
+        constraint = config.is_receipt_redeemer(&redeemer.key()) @ TotError::Unauthorized
+    )]
+    pub redeemer: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 消费回执
+    #[account(
+        mut,
+        seeds = [
+            seeds::CONSUME_RECEIPT_SEED,
+            consume_receipt.user.as_ref(),
+            consume_receipt.nonce.to_le_bytes().as_ref(),
+        ],
+        bump = consume_receipt.bump
+    )]
+    pub consume_receipt: Account<'info, ConsumeReceipt>,
+}
+
+/// 核销消费回执处理器
+///
+/// 后端确认已为该笔消费提供服务（如执行地图操作、创建拍卖）后调用，
+/// 回执只能核销一次。
+pub fn redeem_consume_receipt_handler(ctx: Context<RedeemConsumeReceipt>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let receipt = &mut ctx.accounts.consume_receipt;
+
+    require!(!receipt.is_redeemed, TotError::ConsumeReceiptAlreadyRedeemed);
+
+    receipt.is_redeemed = true;
+    receipt.redeemed_at = timestamp;
+
+    emit!(ConsumeReceiptRedeemed {
+        receipt: receipt.key(),
+        user: receipt.user,
+        nonce: receipt.nonce,
+        redeemer: ctx.accounts.redeemer.key(),
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 关闭消费回执账户结构
+#[derive(Accounts)]
+pub struct CloseConsumeReceipt<'info> {
+    /// 关闭者（消费用户本人、系统管理员或回执核销权限）
+    #[account(
+        constraint = closer.key() == consume_receipt.user
+            || config.is_receipt_redeemer(&closer.key())
+            @ TotError::Unauthorized
+    )]
+    pub closer: Signer<'info>,
+
+    /// 消费用户（接收退还的租金）
+    /// CHECK: 地址必须等于回执中记录的用户
+    #[account(
+        mut,
+        address = consume_receipt.user @ TotError::InvalidOwner
+    )]
+    pub user: UncheckedAccount<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 消费回执（关闭后租金退还给用户）
+    #[account(
+        mut,
+        close = user,
+        seeds = [
+            seeds::CONSUME_RECEIPT_SEED,
+            consume_receipt.user.as_ref(),
+            consume_receipt.nonce.to_le_bytes().as_ref(),
+        ],
+        bump = consume_receipt.bump
+    )]
+    pub consume_receipt: Account<'info, ConsumeReceipt>,
+}
+
+/// 关闭消费回执处理器
+///
+/// 只有已核销的回执可以关闭，租金退还给消费用户。
+///
+/// # 注意事项
+/// - 关闭后同一随机数可以再次用于消费，后端应以核销事件或自己的记录防止重复提供服务
+pub fn close_consume_receipt_handler(ctx: Context<CloseConsumeReceipt>) -> Result<()> {
+    let receipt = &ctx.accounts.consume_receipt;
+
+    require!(receipt.is_redeemed, TotError::ConsumeReceiptNotRedeemed);
+
+    emit!(ConsumeReceiptClosed {
+        receipt: receipt.key(),
+        user: receipt.user,
+        nonce: receipt.nonce,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// 消费回执核销事件
+#[event]
+pub struct ConsumeReceiptRedeemed {
+    /// 回执账户
+    pub receipt: Pubkey,
+    /// 消费用户
+    pub user: Pubkey,
+    /// 客户端随机数
+    pub nonce: u64,
+    /// 核销者
+    pub redeemer: Pubkey,
+    /// 核销时间
+    pub timestamp: i64,
+}
+
+/// 消费回执关闭事件
+#[event]
+pub struct ConsumeReceiptClosed {
+    /// 回执账户
+    pub receipt: Pubkey,
+    /// 消费用户
+    pub user: Pubkey,
+    /// 客户端随机数
+    pub nonce: u64,
+    /// 关闭时间
+    pub timestamp: i64,
+}
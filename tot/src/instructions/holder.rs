@@ -8,7 +8,6 @@ use crate::state::config::TotConfig;
 use crate::state::holder::HolderAccount;
 use crate::constants::seeds;
 use crate::errors::TotError;
-use crate::utils::validation::validate_freeze_reason;
 
 /// 初始化持有者信息
 /// 当用户首次接收 TOT 代币时调用
@@ -52,6 +51,7 @@ pub fn initialize_holder_handler(ctx: Context<InitializeHolder>) -> Result<()> {
     holder_info.total_tax_paid = 0;
     holder_info.is_frozen = false;
     holder_info.freeze_reason = 0;
+    holder_info.frozen_at = 0;
     holder_info.bump = ctx.bumps.holder_info;
 
     msg!("Holder info initialized for: {}", holder_info.owner);
@@ -93,6 +93,7 @@ pub fn freeze_holder_handler(
     
     holder_info.is_frozen = true;
     holder_info.freeze_reason = reason_code;
+    holder_info.frozen_at = timestamp;
 
     msg!("Account frozen: {} - Reason code: {}", holder_info.owner, reason_code);
     
@@ -137,6 +138,7 @@ pub fn unfreeze_holder_handler(ctx: Context<UnfreezeHolder>) -> Result<()> {
     
     holder_info.is_frozen = false;
     holder_info.freeze_reason = 0;
+    holder_info.frozen_at = 0;
 
     msg!("Account unfrozen: {}", holder_info.owner);
     
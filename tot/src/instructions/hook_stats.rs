@@ -0,0 +1,155 @@
+// ============================================
+// 文件: src/instructions/hook_stats.rs
+// 转账守卫初始化与Transfer Hook持有者统计指令
+// ============================================
+
+use anchor_lang::prelude::*;
+
+use crate::state::config::TotConfig;
+use crate::state::holder::HolderAccount;
+use crate::state::transfer_guard::TransferGuard;
+use crate::constants::{seeds, TRANSFER_HOOK_PROGRAM_ID};
+use crate::errors::TotError;
+
+/// Transfer Hook上报的一笔转账
+///
+/// 由Hook从Execute指令的代币账户中读取，金额均为基础单位。
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct HookTransferRecord {
+    /// 转出代币账户的所有者
+    pub sender: Pubkey,
+    /// 转入代币账户的所有者
+    pub receiver: Pubkey,
+    /// 转账金额
+    pub amount: u64,
+    /// Transfer Fee扩展预扣的税额
+    pub fee: u64,
+    /// 转出后发送者代币账户的余额
+    pub sender_remaining: u64,
+    /// 是否转入流动性池（卖出）
+    pub is_sell: bool,
+    /// 是否从流动性池转出（买入）
+    pub is_buy: bool,
+}
+
+/// 初始化转账守卫账户结构
+#[derive(Accounts)]
+pub struct InitializeTransferGuard<'info> {
+    /// 系统管理员（支付租金）
+    #[account(
+        mut,
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 转账守卫PDA
+    #[account(
+        init,
+        payer = authority,
+        space = TransferGuard::LEN,
+        seeds = [seeds::TRANSFER_GUARD_SEED],
+        bump
+    )]
+    pub transfer_guard: Account<'info, TransferGuard>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 初始化转账守卫处理器
+///
+/// 创建转账守卫账户后，Transfer Hook开始为钱包和DEX发起的转账回调
+/// `record_hook_transfer`更新持有者统计。
+/// 应在Hook程序的额外账户列表登记守卫账户（`update_extra_account_meta_list`）之后执行。
+pub fn initialize_transfer_guard_handler(ctx: Context<InitializeTransferGuard>) -> Result<()> {
+    let transfer_guard = &mut ctx.accounts.transfer_guard;
+    transfer_guard.in_progress = false;
+    transfer_guard.bump = ctx.bumps.transfer_guard;
+
+    msg!("Transfer guard initialized");
+    Ok(())
+}
+
+/// Transfer Hook上报转账账户结构
+#[derive(Accounts)]
+#[instruction(record: HookTransferRecord)]
+pub struct RecordHookTransfer<'info> {
+    /// Hook配置PDA（Hook程序invoke_signed签名）
+    #[account(
+        constraint = hook_authority.key() == hook_authority_address() @ TotError::Unauthorized
+    )]
+    pub hook_authority: Signer<'info>,
+
+    /// 发送者持有者信息
+    ///
+    /// CHECK: 地址由seeds约束，可能尚未初始化，在处理器中按需反序列化
+    #[account(
+        mut,
+        seeds = [seeds::HOLDER_SEED, record.sender.as_ref()],
+        bump
+    )]
+    pub sender_holder_info: UncheckedAccount<'info>,
+
+    /// 接收者持有者信息
+    ///
+    /// CHECK: 地址由seeds约束，可能尚未初始化，在处理器中按需反序列化
+    #[account(
+        mut,
+        seeds = [seeds::HOLDER_SEED, record.receiver.as_ref()],
+        bump
+    )]
+    pub receiver_holder_info: UncheckedAccount<'info>,
+}
+
+/// Transfer Hook上报转账处理器
+///
+/// 钱包和DEX发起的转账不经过主程序指令，由Hook在Execute中回调本指令，
+/// 按`transfer_with_tax`的规则更新持有者统计：
+/// - 卖出时更新发送者的卖出统计（税额为Transfer Fee扩展预扣的部分）
+/// - 接收者按实际到账金额记录买入，从流动性池买入时税额计入接收者
+///
+/// # 注意事项
+/// - 只接受Hook配置PDA的签名，Hook只在Token-2022转账过程中且不是主程序发起时回调
+/// - 没有持有者账户的一方跳过，不创建账户
+/// - 冻结和暂停检查已在Hook中完成
+pub fn record_hook_transfer_handler(
+    ctx: Context<RecordHookTransfer>,
+    record: HookTransferRecord,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    if record.is_sell {
+        let sender_info = &ctx.accounts.sender_holder_info;
+        if let Some(mut holder) = HolderAccount::load_optional(sender_info, ctx.program_id)? {
+            holder.record_sell(record.amount, record.fee, record.sender_remaining, timestamp)?;
+            holder.store(sender_info)?;
+        }
+    }
+
+    // 转给自己不计入买入
+    if record.receiver != record.sender {
+        let receiver_info = &ctx.accounts.receiver_holder_info;
+        if let Some(mut holder) = HolderAccount::load_optional(receiver_info, ctx.program_id)? {
+            let net_amount = record.amount
+                .checked_sub(record.fee)
+                .ok_or(TotError::MathUnderflow)?;
+            holder.record_buy(net_amount, if record.is_buy { record.fee } else { 0 }, timestamp)?;
+            holder.store(receiver_info)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Hook程序回调主程序时使用的签名PDA
+///
+/// 即Hook配置账户`["hook_config"]`（在Hook程序下派生）。
+pub fn hook_authority_address() -> Pubkey {
+    Pubkey::find_program_address(&[seeds::HOOK_CONFIG_SEED], &TRANSFER_HOOK_PROGRAM_ID).0
+}
@@ -10,7 +10,7 @@
 //! 3. 初始化Transfer Fee扩展（交易税功能）
 //! 4. 初始化Permanent Delegate扩展（永久代理权）
 //! 5. 初始化Metadata Pointer扩展（可更新元数据）
-//! 6. 初始化Transfer Hook扩展（自定义转账逻辑，已合并到主程序）
+//! 6. 初始化Transfer Hook扩展（自定义转账逻辑，指向独立部署的Hook程序）
 //! 7. 初始化Mint（设置精度、权限等）
 //! 8. 初始化全局配置账户
 //! 
@@ -27,9 +27,14 @@
 
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::TokenInterface;
-use solana_program::program::invoke;
+use anchor_lang::solana_program::program::invoke;
 use spl_token_2022::{
-    extension::ExtensionType,
+    extension::{
+        ExtensionType,
+        metadata_pointer::instruction as metadata_pointer_instruction,
+        transfer_fee::instruction as transfer_fee_instruction,
+        transfer_hook::instruction as transfer_hook_instruction,
+    },
     state::Mint,
     instruction as token_2022_instruction,
 };
@@ -162,7 +167,7 @@ pub struct Initialize<'info> {
 ///         systemProgram: SystemProgram.programId,
 ///         rent: SYSVAR_RENT_PUBKEY,
 ///     })
-///     // 注意：Transfer Hook已合并到主程序，主程序ID自动作为transfer hook program ID
+///     // 注意：Transfer Hook指向独立部署的Hook程序（TRANSFER_HOOK_PROGRAM_ID）
 ///     .signers([mintKeypair])
 ///     .rpc();
 /// ```
@@ -238,7 +243,7 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     let max_fee: u64 = u64::MAX; // 无上限，允许大额转账
     
     invoke(
-        &token_2022_instruction::initialize_transfer_fee_config(
+        &transfer_fee_instruction::initialize_transfer_fee_config(
             &token_program.key(),
             &mint.key(),
             Some(&authority.key()), // transfer_fee_config_authority: 可以更新税率的权限
@@ -266,15 +271,20 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     // - 这是TOT的"核按钮"，必须谨慎使用
     // 
     // 重要:
-    // - Permanent Delegate一旦设置，无法更改
-    // - 建议使用多签钱包作为permanent_delegate
-    // - 只有permanent_delegate可以执行强制操作
-    
+    // - Permanent Delegate设置为程序PDA，只能通过clawback指令使用
+    // - clawback要求多签或合规权限、账户冻结满最短时间，并留下链上记录
+    // - 任何钱包都无法绕过程序直接强制转移
+    
+    let (permanent_delegate, _) = Pubkey::find_program_address(
+        &[seeds::PERMANENT_DELEGATE_SEED],
+        ctx.program_id,
+    );
+
     invoke(
         &token_2022_instruction::initialize_permanent_delegate(
             &token_program.key(),
             &mint.key(),
-            &authority.key(), // permanent_delegate: 永久代理地址（设置为管理员）
+            &permanent_delegate, // permanent_delegate: 永久代理地址（程序PDA）
         )?,
         &[
             mint.to_account_info(),
@@ -298,11 +308,11 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     // - 保持元数据的灵活性和可更新性
     
     invoke(
-        &token_2022_instruction::initialize_metadata_pointer(
+        &metadata_pointer_instruction::initialize(
             &token_program.key(),
             &mint.key(),
-            Some(&authority.key()), // metadata_authority: 可以更新元数据的权限
-            Some(&mint.key()),      // metadata地址: 元数据存储在mint账户本身
+            Some(authority.key()), // metadata_authority: 可以更新元数据的权限
+            Some(mint.key()),      // metadata地址: 元数据存储在mint账户本身
         )?,
         &[
             mint.to_account_info(),
@@ -318,19 +328,19 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     // 
     // 配置说明:
     // - hook_authority: 可以更新Transfer Hook配置的权限（保留给管理员）
-    // - hook_program_id: Transfer Hook程序的地址（使用主程序ID）
+    // - hook_program_id: 独立部署的Transfer Hook程序（TRANSFER_HOOK_PROGRAM_ID）
     // 
     // 注意:
-    // - Transfer Hook已合并到主程序，主程序ID同时作为transfer hook program ID
-    // - 主程序同时处理自己的指令和transfer hook的execute指令
-    // - 在初始化时，主程序ID会自动设置为transfer hook program ID
+    // - Hook不能是主程序自身：主程序发起的转账CPI会经Token-2022再次进入主程序，
+    //   Solana禁止这种间接重入
+    // - 主程序发起转账时，Hook所需账户通过remaining_accounts传入
     
     invoke(
-        &spl_token_2022::instruction::initialize_transfer_hook(
+        &transfer_hook_instruction::initialize(
             &token_program.key(),
             &mint.key(),
-            Some(&authority.key()),      // hook_authority: 可以更新Transfer Hook配置的权限
-            Some(&ctx.program_id),       // hook_program_id: 使用主程序ID作为transfer hook program ID
+            Some(authority.key()),          // hook_authority: 可以更新Transfer Hook配置的权限
+            Some(TRANSFER_HOOK_PROGRAM_ID), // hook_program_id: 独立部署的Transfer Hook程序
         )?,
         &[
             mint.to_account_info(),
@@ -345,22 +355,29 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     // 这设置了代币的基本参数：精度、Mint Authority、Freeze Authority等。
     // 
     // 配置说明:
-    // - mint_authority: 可以铸造新代币的权限（保留给管理员，后续可以销毁）
+    // - mint_authority: 可以铸造新代币的权限（程序PDA，铸造完成后可以放弃）
     // - freeze_authority: 可以冻结账户的权限（保留给管理员）
-    // - decimals: 代币精度（9位小数）
+    // - decimals: 代币精度（6位小数）
     // 
     // 重要:
     // - 一旦Mint初始化完成，扩展结构就固定了，无法更改
-    // - Mint Authority可以在后续销毁，确保总量不再增加
+    // - Mint Authority可以通过renounce_mint_authority放弃，确保总量不再增加
     // - Freeze Authority保留，用于合规和紧急情况
     
+    // 铸造权限交给程序PDA，只有本程序（mint_to_pools）能够铸造，
+    // 持有者可以在链上验证部署者钱包无法增发
+    let (mint_authority, _) = Pubkey::find_program_address(
+        &[seeds::MINT_AUTHORITY_SEED],
+        ctx.program_id,
+    );
+
     invoke(
         &token_2022_instruction::initialize_mint2(
             &token_program.key(),
             &mint.key(),
-            &authority.key(),       // mint_authority: 铸造权限
+            &mint_authority,        // mint_authority: 铸造权限（程序PDA）
             Some(&authority.key()), // freeze_authority: 冻结权限
-            TOKEN_DECIMALS,         // 精度: 9位小数
+            TOKEN_DECIMALS,         // 精度: 6位小数
         )?,
         &[
             mint.to_account_info(),
@@ -371,8 +388,9 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     // 步骤8: 初始化 Token Metadata (使用Metadata Interface)
     // ========================================
     // 
-    // 注意: 这里需要使用spl-token-metadata-interface
-    // 如果项目中没有这个依赖，可以暂时注释掉，后续添加
+    // 注意: TokenMetadata需要扩容Mint账户，且URI在部署时通常尚未确定，
+    // 因此不在此处写入，由单独的initialize_token_metadata指令完成
+    // （见token_metadata模块）
     // 
     // Metadata可以包含：
     // - 代币名称、符号
@@ -433,13 +451,43 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     config.tws_treasury = mint.key();
     
     // 初始化系统状态
-    config.panic_mode = false;                    // 未启用恐慌模式
+    config.panic_mode = false;                    // 未启用恐慌税率模式
     config.initialized_at = clock.unix_timestamp; // 记录初始化时间
     config.total_minted = 0;                      // 尚未铸造任何代币
     config.total_burned = 0;                      // 尚未销毁任何代币
     config.total_tax_collected = 0;              // 尚未收取任何税收
-    config.version = 1;                          // 初始版本为1
-    
+    config.version = TotConfig::CURRENT_VERSION; // 当前布局版本
+
+    // 初始化权限移交状态（无进行中的移交，不设时间锁）
+    config.pending_authority = Pubkey::default();
+    config.authority_transfer_eta = 0;
+    config.authority_transfer_delay = 0;
+    config.pending_token_authority = Pubkey::default();
+    config.pending_token_authority_kinds = 0;
+    config.token_authority_transfer_eta = 0;
+
+    // 初始化暂停状态（所有指令族均未暂停）
+    config.paused_operations = 0;
+    config.pause_resume_at = [0; 8];
+    config.mint_authority_renounced = false;
+
+    // 初始化追回状态（未设置合规权限，无追回记录）
+    config.compliance_authority = Pubkey::default();
+    config.total_clawed_back = 0;
+    config.clawback_count = 0;
+    config.locked_supply = 0;
+    config.locked_supply_refreshed_at = 0;
+
+    // 初始化协议统计
+    config.total_consumed = [0; 8];
+    config.total_platform_distributed = 0;
+    config.total_auction_fees = 0;
+    config.total_pool_released = 0;
+    config.receipt_authority = Pubkey::default();
+    config.voucher_signer = Pubkey::default();
+    config.pending_authority_transfer_delay = 0;
+    config.authority_transfer_delay_eta = 0;
+
     // 输出初始化信息（用于调试和审计）
     msg!("TOT Token 初始化完成!");
     msg!("Mint: {}", mint.key());
@@ -0,0 +1,592 @@
+// ============================================
+// 文件: src/instructions/merchant.rs
+// 商户登记与发票指令 - 登记、开票、支付、关闭
+// ============================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::state::config::{TotConfig, PauseFlag};
+use crate::state::tax::TaxConfig;
+use crate::state::holder::HolderAccount;
+use crate::state::merchant::{MerchantAccount, Invoice, InvoiceStatus};
+use crate::constants::seeds;
+use crate::errors::TotError;
+use crate::utils::tax_calculator::*;
+use crate::utils::token_transfer::transfer_checked_with_hook;
+
+/// 登记商户账户结构
+#[derive(Accounts)]
+#[instruction(merchant: Pubkey)]
+pub struct RegisterMerchant<'info> {
+    /// 系统管理员（支付租金）
+    #[account(
+        mut,
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 商户账户PDA
+    #[account(
+        init,
+        payer = authority,
+        space = MerchantAccount::LEN,
+        seeds = [seeds::MERCHANT_SEED, merchant.as_ref()],
+        bump
+    )]
+    pub merchant_account: Account<'info, MerchantAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 登记商户处理器
+///
+/// # 参数
+/// * `merchant` - 商户钱包地址
+/// * `tax_cap_bps` - 税率上限（`None`标准税率，`Some(0)`免税，`Some(n)`最终税率不超过n）
+pub fn register_merchant_handler(
+    ctx: Context<RegisterMerchant>,
+    merchant: Pubkey,
+    tax_cap_bps: Option<u16>,
+) -> Result<()> {
+    MerchantAccount::validate_tax_cap(tax_cap_bps)?;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let merchant_account = &mut ctx.accounts.merchant_account;
+    merchant_account.merchant = merchant;
+    merchant_account.tax_cap_bps = tax_cap_bps;
+    merchant_account.is_active = true;
+    merchant_account.invoice_count = 0;
+    merchant_account.total_received = 0;
+    merchant_account.registered_at = timestamp;
+    merchant_account.bump = ctx.bumps.merchant_account;
+
+    msg!("Registered merchant: {}", merchant);
+
+    emit!(MerchantUpdated {
+        merchant,
+        tax_cap_bps,
+        is_active: true,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 更新商户账户结构
+#[derive(Accounts)]
+pub struct UpdateMerchant<'info> {
+    /// 系统管理员
+    #[account(
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 商户账户PDA
+    #[account(
+        mut,
+        seeds = [seeds::MERCHANT_SEED, merchant_account.merchant.as_ref()],
+        bump = merchant_account.bump
+    )]
+    pub merchant_account: Account<'info, MerchantAccount>,
+}
+
+/// 更新商户处理器
+///
+/// 修改税率上限或启用状态，对之后支付的发票立即生效。
+pub fn update_merchant_handler(
+    ctx: Context<UpdateMerchant>,
+    tax_cap_bps: Option<u16>,
+    is_active: bool,
+) -> Result<()> {
+    MerchantAccount::validate_tax_cap(tax_cap_bps)?;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let merchant_account = &mut ctx.accounts.merchant_account;
+    merchant_account.tax_cap_bps = tax_cap_bps;
+    merchant_account.is_active = is_active;
+
+    emit!(MerchantUpdated {
+        merchant: merchant_account.merchant,
+        tax_cap_bps,
+        is_active,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 开具发票账户结构
+#[derive(Accounts)]
+#[instruction(invoice_id: u64)]
+pub struct CreateInvoice<'info> {
+    /// 商户（签名者，支付租金）
+    #[account(mut)]
+    pub merchant: Signer<'info>,
+
+    /// 商户账户（必须已登记）
+    #[account(
+        mut,
+        seeds = [seeds::MERCHANT_SEED, merchant.key().as_ref()],
+        bump = merchant_account.bump
+    )]
+    pub merchant_account: Account<'info, MerchantAccount>,
+
+    /// 发票账户
+    #[account(
+        init,
+        payer = merchant,
+        space = Invoice::LEN,
+        seeds = [seeds::INVOICE_SEED, merchant.key().as_ref(), invoice_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub invoice: Account<'info, Invoice>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 开具发票处理器
+///
+/// # 参数
+/// * `invoice_id` - 发票ID（同一商户下唯一）
+/// * `amount` - 发票金额（商户应收到的净额）
+/// * `due_at` - 到期时间
+/// * `reference` - 商户订单引用
+pub fn create_invoice_handler(
+    ctx: Context<CreateInvoice>,
+    invoice_id: u64,
+    amount: u64,
+    due_at: i64,
+    reference: [u8; 32],
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    require!(ctx.accounts.merchant_account.is_active, TotError::MerchantNotActive);
+    require!(amount > 0 && due_at > timestamp, TotError::InvalidInvoice);
+
+    let merchant_account = &mut ctx.accounts.merchant_account;
+    merchant_account.invoice_count = merchant_account.invoice_count
+        .checked_add(1)
+        .ok_or(TotError::MathOverflow)?;
+
+    let invoice = &mut ctx.accounts.invoice;
+    invoice.merchant = ctx.accounts.merchant.key();
+    invoice.invoice_id = invoice_id;
+    invoice.amount = amount;
+    invoice.due_at = due_at;
+    invoice.reference = reference;
+    invoice.status = InvoiceStatus::Open;
+    invoice.payer = Pubkey::default();
+    invoice.tax_amount = 0;
+    invoice.created_at = timestamp;
+    invoice.paid_at = 0;
+    invoice.bump = ctx.bumps.invoice;
+
+    emit!(InvoiceCreated {
+        invoice: invoice.key(),
+        merchant: invoice.merchant,
+        invoice_id,
+        amount,
+        due_at,
+        reference,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 支付发票账户结构
+#[derive(Accounts)]
+pub struct PayInvoice<'info> {
+    /// 付款人（签名者）
+    pub payer: Signer<'info>,
+
+    /// 付款人代币账户
+    #[account(
+        mut,
+        constraint = payer_token_account.owner == payer.key() @ TotError::InvalidOwner,
+        constraint = payer_token_account.mint == mint.key() @ TotError::InvalidMint
+    )]
+    pub payer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 付款人持有者信息（冻结检查和持有时间折扣）
+    #[account(
+        seeds = [seeds::HOLDER_SEED, payer.key().as_ref()],
+        bump = payer_holder_info.bump
+    )]
+    pub payer_holder_info: Account<'info, HolderAccount>,
+
+    /// 发票账户
+    #[account(
+        mut,
+        seeds = [seeds::INVOICE_SEED, invoice.merchant.as_ref(), invoice.invoice_id.to_le_bytes().as_ref()],
+        bump = invoice.bump
+    )]
+    pub invoice: Account<'info, Invoice>,
+
+    /// 商户账户
+    #[account(
+        mut,
+        seeds = [seeds::MERCHANT_SEED, invoice.merchant.as_ref()],
+        bump = merchant_account.bump
+    )]
+    pub merchant_account: Account<'info, MerchantAccount>,
+
+    /// 商户代币账户
+    #[account(
+        mut,
+        constraint = merchant_token_account.owner == invoice.merchant @ TotError::InvalidOwner,
+        constraint = merchant_token_account.mint == mint.key() @ TotError::InvalidMint
+    )]
+    pub merchant_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 商户持有者信息（可选，存在时更新买入统计）
+    #[account(
+        mut,
+        seeds = [seeds::HOLDER_SEED, invoice.merchant.as_ref()],
+        bump = merchant_holder_info.bump
+    )]
+    pub merchant_holder_info: Option<Account<'info, HolderAccount>>,
+
+    /// TOT Mint
+    #[account(
+        mut,
+        address = config.mint @ TotError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// 全局配置（累计协议统计）
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 税率配置
+    #[account(
+        seeds = [seeds::TAX_CONFIG_SEED],
+        bump
+    )]
+    pub tax_config: Account<'info, TaxConfig>,
+
+    /// 税收收集账户（流动性池）
+    #[account(
+        mut,
+        constraint = tax_collector.mint == mint.key() @ TotError::InvalidMint
+    )]
+    pub tax_collector: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// 支付发票处理器
+///
+/// 按`transfer_exact_with_tax`的规则支付：商户收到发票金额，税额另外从付款人扣除。
+///
+/// # 税收规则
+///
+/// - 付款人和商户都不能处于冻结状态（免税支付同样检查）
+/// - 付款人或商户在免税列表中，或商户税率上限为0时，全额到账
+/// - 否则税率按付款人的持有时间计算（非买入、非卖出）
+/// - 商户设置了税率上限且计算出的税率更高时，按上限税率收税
+/// - 税收40%销毁、其余转入税收收集账户
+pub fn pay_invoice_handler<'info>(ctx: Context<'_, '_, '_, 'info, PayInvoice<'info>>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let config = &ctx.accounts.config;
+    let tax_config = &ctx.accounts.tax_config;
+    let invoice = &ctx.accounts.invoice;
+    let merchant_account = &ctx.accounts.merchant_account;
+    let payer_key = ctx.accounts.payer.key();
+    let merchant_key = invoice.merchant;
+    let amount = invoice.amount;
+
+    // ========================================
+    // 验证阶段
+    // ========================================
+
+    config.require_not_paused(PauseFlag::Transfers, timestamp)?;
+    require!(invoice.status == InvoiceStatus::Open, TotError::InvoiceNotOpen);
+    require!(merchant_account.is_active, TotError::MerchantNotActive);
+    require!(timestamp <= invoice.due_at, TotError::InvoiceExpired);
+
+    // 冻结检查在免税判断之前，冻结的持有者不能借免税商户转移资金
+    let payer_holder = &ctx.accounts.payer_holder_info;
+    require!(!payer_holder.is_frozen, TotError::HolderFrozen);
+    if let Some(ref merchant_holder) = ctx.accounts.merchant_holder_info {
+        require!(!merchant_holder.is_frozen, TotError::HolderFrozen);
+    }
+
+    // ========================================
+    // 税额计算（不含税模式，商户收到发票金额）
+    // ========================================
+
+    let is_exempt = merchant_account.is_tax_exempt()
+        || tax_config.is_exempt(&payer_key)
+        || tax_config.is_exempt(&merchant_key);
+
+    let (tax_amount, tax_rate_bps) = if is_exempt {
+        (0, 0)
+    } else {
+        let calculation = TaxCalculator::calculate_tax_exclusive(
+            amount,
+            Some(payer_holder),
+            config.circulating_supply(ctx.accounts.mint.supply),
+            timestamp,
+            false, // 发票支付不是买入
+            false, // 发票支付不是卖出
+            config.panic_mode,
+            tax_config,
+        )?;
+
+        match merchant_account.tax_cap_bps {
+            Some(cap) if calculation.final_tax_bps > cap => {
+                let gross_amount = TaxCalculator::calculate_gross_amount(amount, cap)?;
+                let capped_tax = gross_amount
+                    .checked_sub(amount)
+                    .ok_or(TotError::MathUnderflow)?;
+                (capped_tax, cap)
+            }
+            _ => (calculation.tax_amount, calculation.final_tax_bps),
+        }
+    };
+
+    let gross_amount = amount
+        .checked_add(tax_amount)
+        .ok_or(TotError::MathOverflow)?;
+    require!(
+        ctx.accounts.payer_token_account.amount >= gross_amount,
+        TotError::InsufficientBalance
+    );
+
+    // ========================================
+    // 执行转账 - 发票金额给商户
+    // ========================================
+
+    let mint_decimals = ctx.accounts.mint.decimals;
+
+    transfer_checked_with_hook(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.payer_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.merchant_token_account.to_account_info(),
+                authority: ctx.accounts.payer.to_account_info(),
+            },
+        ),
+        ctx.remaining_accounts,
+        amount,
+        mint_decimals,
+    )?;
+
+    // ========================================
+    // 处理税收分配
+    // ========================================
+
+    let burned = if tax_amount > 0 {
+        let tax_dist = TaxDistribution::calculate(tax_amount)?;
+
+        if tax_dist.to_burn > 0 {
+            token_interface::burn(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        from: ctx.accounts.payer_token_account.to_account_info(),
+                        authority: ctx.accounts.payer.to_account_info(),
+                    },
+                ),
+                tax_dist.to_burn,
+            )?;
+        }
+
+        let remaining_tax = tax_amount
+            .checked_sub(tax_dist.to_burn)
+            .ok_or(TotError::MathUnderflow)?;
+        if remaining_tax > 0 {
+            transfer_checked_with_hook(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.payer_token_account.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.tax_collector.to_account_info(),
+                        authority: ctx.accounts.payer.to_account_info(),
+                    },
+                ),
+                ctx.remaining_accounts,
+                remaining_tax,
+                mint_decimals,
+            )?;
+        }
+
+        ctx.accounts.config.record_tax(tax_amount, tax_dist.to_burn)?;
+        tax_dist.to_burn
+    } else {
+        0
+    };
+
+    // ========================================
+    // 更新发票与统计
+    // ========================================
+
+    // 商户收款相当于买入操作，税收由付款人支付
+    if let Some(ref mut merchant_holder) = ctx.accounts.merchant_holder_info {
+        merchant_holder.record_buy(amount, 0, timestamp)?;
+        if merchant_holder.token_account == Pubkey::default() {
+            merchant_holder.token_account = ctx.accounts.merchant_token_account.key();
+        }
+    }
+
+    let merchant_account = &mut ctx.accounts.merchant_account;
+    merchant_account.total_received = merchant_account.total_received
+        .checked_add(amount)
+        .ok_or(TotError::MathOverflow)?;
+
+    let invoice = &mut ctx.accounts.invoice;
+    invoice.status = InvoiceStatus::Paid;
+    invoice.payer = payer_key;
+    invoice.tax_amount = tax_amount;
+    invoice.paid_at = timestamp;
+
+    emit!(InvoicePaid {
+        invoice: invoice.key(),
+        merchant: merchant_key,
+        invoice_id: invoice.invoice_id,
+        payer: payer_key,
+        amount,
+        tax_amount,
+        tax_rate_bps,
+        burned,
+        reference: invoice.reference,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 关闭发票账户结构
+#[derive(Accounts)]
+pub struct CloseInvoice<'info> {
+    /// 商户（签名者，收回租金）
+    #[account(mut)]
+    pub merchant: Signer<'info>,
+
+    /// 发票账户（关闭后租金退还商户）
+    #[account(
+        mut,
+        close = merchant,
+        has_one = merchant @ TotError::Unauthorized,
+        seeds = [seeds::INVOICE_SEED, merchant.key().as_ref(), invoice.invoice_id.to_le_bytes().as_ref()],
+        bump = invoice.bump
+    )]
+    pub invoice: Account<'info, Invoice>,
+}
+
+/// 关闭发票处理器
+///
+/// 已支付的发票关闭后归档（支付记录保留在`InvoicePaid`事件中），
+/// 未支付的发票关闭即作废。
+pub fn close_invoice_handler(ctx: Context<CloseInvoice>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let invoice = &ctx.accounts.invoice;
+
+    emit!(InvoiceClosed {
+        invoice: invoice.key(),
+        merchant: invoice.merchant,
+        invoice_id: invoice.invoice_id,
+        status: invoice.status as u8,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 商户登记/更新事件
+#[event]
+pub struct MerchantUpdated {
+    /// 商户钱包
+    pub merchant: Pubkey,
+    /// 税率上限
+    pub tax_cap_bps: Option<u16>,
+    /// 是否启用
+    pub is_active: bool,
+    /// 更新时间
+    pub timestamp: i64,
+}
+
+/// 发票开具事件
+#[event]
+pub struct InvoiceCreated {
+    /// 发票账户
+    pub invoice: Pubkey,
+    /// 商户钱包
+    pub merchant: Pubkey,
+    /// 发票ID
+    pub invoice_id: u64,
+    /// 发票金额
+    pub amount: u64,
+    /// 到期时间
+    pub due_at: i64,
+    /// 商户订单引用
+    pub reference: [u8; 32],
+    /// 开具时间
+    pub timestamp: i64,
+}
+
+/// 发票支付事件
+#[event]
+pub struct InvoicePaid {
+    /// 发票账户
+    pub invoice: Pubkey,
+    /// 商户钱包
+    pub merchant: Pubkey,
+    /// 发票ID
+    pub invoice_id: u64,
+    /// 付款人
+    pub payer: Pubkey,
+    /// 商户收到的金额
+    pub amount: u64,
+    /// 付款人支付的税额
+    pub tax_amount: u64,
+    /// 税率（basis points）
+    pub tax_rate_bps: u16,
+    /// 销毁数量
+    pub burned: u64,
+    /// 商户订单引用
+    pub reference: [u8; 32],
+    /// 支付时间
+    pub timestamp: i64,
+}
+
+/// 发票关闭事件
+#[event]
+pub struct InvoiceClosed {
+    /// 发票账户
+    pub invoice: Pubkey,
+    /// 商户钱包
+    pub merchant: Pubkey,
+    /// 发票ID
+    pub invoice_id: u64,
+    /// 关闭时的状态（`InvoiceStatus as u8`，0表示未支付作废）
+    pub status: u8,
+    /// 关闭时间
+    pub timestamp: i64,
+}
@@ -0,0 +1,208 @@
+// ============================================
+// 文件: src/instructions/mint_authority.rs
+// 铸造权限管理指令（迁移到程序PDA、放弃铸造权限）
+//
+// 只有这两条指令会修改Mint的铸造权限，
+// `token_authority`的两步移交不包含铸造权限。
+// ============================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenInterface;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use spl_token_2022::instruction::{self as token_2022_instruction, AuthorityType};
+
+use crate::state::config::TotConfig;
+use crate::constants::{seeds, TOTAL_SUPPLY};
+use crate::errors::TotError;
+
+/// 迁移铸造权限账户结构
+///
+/// 用于旧版本`initialize`部署的Mint：铸造权限仍在部署者钱包，
+/// 通过此指令一次性移交给`MINT_AUTHORITY_SEED` PDA。
+#[derive(Accounts)]
+pub struct MigrateMintAuthority<'info> {
+    /// 系统管理员
+    #[account(
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// 当前持有铸造权限的地址
+    ///
+    /// Token-2022的SetAuthority要求当前权限签名，
+    /// 实际是否持有权限由Token-2022程序验证。
+    pub current_mint_authority: Signer<'info>,
+
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// TOT Mint账户
+    /// CHECK: 地址与config.mint一致，数据由Token-2022程序验证和修改
+    #[account(
+        mut,
+        address = config.mint @ TotError::InvalidMint
+    )]
+    pub mint: AccountInfo<'info>,
+
+    /// 铸造权限PDA
+    /// CHECK: 仅作为新的Mint Authority地址，地址由seeds约束
+    #[account(
+        seeds = [seeds::MINT_AUTHORITY_SEED],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// Token-2022 程序
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// 迁移铸造权限处理器
+///
+/// 把Mint的铸造权限从当前持有者移交给程序PDA。
+/// 迁移后只有`mint_to_pools`能够铸造。
+pub fn migrate_mint_authority_handler(ctx: Context<MigrateMintAuthority>) -> Result<()> {
+    require!(
+        !ctx.accounts.config.mint_authority_renounced,
+        TotError::MintAuthorityAlreadyRenounced
+    );
+
+    let mint = &ctx.accounts.mint;
+    let current_mint_authority = &ctx.accounts.current_mint_authority;
+    let mint_authority = ctx.accounts.mint_authority.key();
+
+    invoke(
+        &token_2022_instruction::set_authority(
+            &ctx.accounts.token_program.key(),
+            &mint.key(),
+            Some(&mint_authority),
+            AuthorityType::MintTokens,
+            &current_mint_authority.key(),
+            &[],
+        )?,
+        &[
+            mint.to_account_info(),
+            current_mint_authority.to_account_info(),
+        ],
+    )?;
+
+    msg!(
+        "Mint authority migrated from {} to program PDA {}",
+        current_mint_authority.key(),
+        mint_authority
+    );
+
+    emit!(MintAuthorityMigrated {
+        old_authority: current_mint_authority.key(),
+        mint_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// 放弃铸造权限账户结构
+#[derive(Accounts)]
+pub struct RenounceMintAuthority<'info> {
+    /// 系统管理员
+    #[account(
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// TOT Mint账户
+    /// CHECK: 地址与config.mint一致，数据由Token-2022程序验证和修改
+    #[account(
+        mut,
+        address = config.mint @ TotError::InvalidMint
+    )]
+    pub mint: AccountInfo<'info>,
+
+    /// 铸造权限PDA
+    /// CHECK: 仅作为当前Mint Authority签名，地址由seeds约束
+    #[account(
+        seeds = [seeds::MINT_AUTHORITY_SEED],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// Token-2022 程序
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// 放弃铸造权限处理器
+///
+/// 全部代币铸造到池子后，由程序PDA签名把Mint的铸造权限设置为None。
+/// 此操作不可逆，之后TOT总量永久固定。
+pub fn renounce_mint_authority_handler(ctx: Context<RenounceMintAuthority>) -> Result<()> {
+    require!(
+        !ctx.accounts.config.mint_authority_renounced,
+        TotError::MintAuthorityAlreadyRenounced
+    );
+    require!(
+        ctx.accounts.config.total_minted == TOTAL_SUPPLY,
+        TotError::MintingNotComplete
+    );
+
+    let mint = &ctx.accounts.mint;
+    let mint_authority = &ctx.accounts.mint_authority;
+
+    let mint_authority_seeds: &[&[u8]] = &[
+        seeds::MINT_AUTHORITY_SEED,
+        &[ctx.bumps.mint_authority],
+    ];
+
+    invoke_signed(
+        &token_2022_instruction::set_authority(
+            &ctx.accounts.token_program.key(),
+            &mint.key(),
+            None,
+            AuthorityType::MintTokens,
+            &mint_authority.key(),
+            &[],
+        )?,
+        &[
+            mint.to_account_info(),
+            mint_authority.to_account_info(),
+        ],
+        &[mint_authority_seeds],
+    )?;
+
+    let config = &mut ctx.accounts.config;
+    config.mint_authority_renounced = true;
+
+    msg!("Mint authority renounced, total supply fixed at {}", TOTAL_SUPPLY);
+
+    emit!(MintAuthorityRenounced {
+        total_supply: TOTAL_SUPPLY,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// 铸造权限迁移事件
+#[event]
+pub struct MintAuthorityMigrated {
+    pub old_authority: Pubkey,
+    /// 程序PDA地址
+    pub mint_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// 铸造权限放弃事件
+#[event]
+pub struct MintAuthorityRenounced {
+    /// 固定后的总供应量
+    pub total_supply: u64,
+    pub timestamp: i64,
+}
@@ -33,6 +33,14 @@ pub struct MintToPools<'info> {
     /// TOT Mint
     #[account(mut)]
     pub mint: InterfaceAccount<'info, Mint>,
+
+    /// 铸造权限PDA
+    /// CHECK: 仅作为Mint Authority签名，地址由seeds约束
+    #[account(
+        seeds = [seeds::MINT_AUTHORITY_SEED],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
     
     // 五大池子账户
     #[account(
@@ -99,8 +107,15 @@ pub fn handler(ctx: Context<MintToPools>) -> Result<()> {
     );
     
     let mint = &ctx.accounts.mint;
-    let authority = &ctx.accounts.authority;
+    let mint_authority = &ctx.accounts.mint_authority;
     let token_program = &ctx.accounts.token_program;
+
+    // 铸造权限由程序PDA持有，使用PDA种子签名
+    let mint_authority_seeds: &[&[u8]] = &[
+        seeds::MINT_AUTHORITY_SEED,
+        &[ctx.bumps.mint_authority],
+    ];
+    let signer_seeds = &[mint_authority_seeds];
     
     // 铸造到各池子
     let pools_and_amounts = [
@@ -113,13 +128,14 @@ pub fn handler(ctx: Context<MintToPools>) -> Result<()> {
     
     for (token_account, amount) in pools_and_amounts.iter() {
         mint_to(
-            CpiContext::new(
+            CpiContext::new_with_signer(
                 token_program.to_account_info(),
                 MintTo {
                     mint: mint.to_account_info(),
                     to: token_account.to_account_info(),
-                    authority: authority.to_account_info(),
+                    authority: mint_authority.to_account_info(),
                 },
+                signer_seeds,
             ),
             *amount,
         )?;
@@ -134,6 +150,9 @@ pub fn handler(ctx: Context<MintToPools>) -> Result<()> {
     
     // 更新配置
     config.total_minted = TOTAL_SUPPLY;
+    // 全部代币都在池子中，尚无流通供应量
+    config.locked_supply = TOTAL_SUPPLY;
+    config.locked_supply_refreshed_at = Clock::get()?.unix_timestamp;
     
     // 合并所有消息为一个，减少gas消耗
     msg!(
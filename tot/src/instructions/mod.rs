@@ -13,12 +13,28 @@
 //! - `initialize`: 系统初始化指令
 //! - `init_pool`: 池子初始化指令
 //! - `mint_to_pools`: 铸造代币到池子指令
+//! - `pool_release`: 池子释放指令、流通供应量刷新指令
 //! - `holder`: 持有者管理指令（初始化、冻结、解冻）
 //! - `tax`: 税率管理指令（初始化、更新、免税地址管理）
 //! - `transfer`: 带税转账指令（核心功能）
-//! - `admin`: 管理员指令（权限管理、系统暂停、紧急提取）
-//! - `query`: 查询指令（只读，计算税率、获取统计）
-//! - `hook`: Transfer Hook指令（initialize_transfer_hook, execute_internal, set_transfer_hook_paused）
+//! - `batch_transfer`: 批量转账/空投指令（逐个接收者执行税收规则）
+//! - `stream`: 代币流指令（创建、提取、取消，提取按transfer_with_tax的规则收税）
+//! - `offer`: P2P托管挂单指令（挂单、部分/全部成交、撤单，成交按卖出收税）
+//! - `catalog`: 价格目录管理指令（初始化、设置/删除价格、收入分成，consume_to_treasury按目录校验金额并分成）
+//! - `platform_budget`: 平台预算管理指令（周期预算、单个接收者额度、平台热钱包列表）
+//! - `voucher`: 奖励金库与签名兑换券指令（Ed25519指令内省验证平台签名，用户自行领取奖励）
+//! - `subscription`: 订阅指令（用户授权订阅代理PDA，任何人按周期调用扣款，冻结或余额不足时自动停止）
+//! - `merchant`: 商户登记与发票指令（管理员登记商户及税率上限，商户开票，顾客按transfer_with_tax的规则支付）
+//! - `partner`: 合作程序白名单与CPI入口（合作程序以签名PDA调用消费、带税转账、持有者统计上报）
+//! - `hook_stats`: 转账守卫初始化与Transfer Hook持有者统计指令（Hook为钱包、DEX转账回调主程序更新统计）
+//! - `points`: 积分指令（配置比例、领取持有积分、兑换目录商品或税率折扣；消费和拍卖夺取时发放积分）
+//! - `receipt`: 支付回执创建（transfer_with_tax、consume_to_treasury、platform_transfer共用）
+//! - `admin`: 管理员指令（权限两步移交、系统暂停、紧急提取）
+//! - `query`: 查询指令（只读，计算税率、获取持有者统计和协议统计）
+//! - `token_authority`: Token-2022 Mint权限两步移交指令
+//! - `mint_authority`: 铸造权限管理指令（迁移到程序PDA、放弃铸造权限）
+//! - `token_metadata`: Token-2022 链上元数据指令（初始化、更新字段、删除自定义字段）
+//! - `clawback`: 追回指令（通过Permanent Delegate转移或销毁冻结账户的代币）
 //! 
 //! ## 依赖关系
 //! 
@@ -35,7 +51,7 @@
 //! ## 使用示例
 //! 
 //! ```rust
-//! use crate::instructions::{Initialize, TransferWithTax, UpdateAuthority};
+//! use crate::instructions::{Initialize, TransferWithTax, ProposeAuthority};
 //! 
 //! // 在lib.rs中使用
 //! pub fn initialize(ctx: Context<Initialize>, params: InitializeParams) -> Result<()> {
@@ -51,17 +67,33 @@
 pub mod initialize;
 pub mod init_pool;
 pub mod mint_to_pools;
+pub mod pool_release;
 pub mod holder;
 pub mod tax;
 pub mod transfer;
 pub mod consume;
 pub mod platform_transfer;
+pub mod batch_transfer;
+pub mod stream;
+pub mod offer;
+pub mod catalog;
+pub mod voucher;
+pub mod platform_budget;
+pub mod subscription;
+pub mod merchant;
+pub mod partner;
+pub mod hook_stats;
+pub mod points;
+pub mod receipt;
 pub mod admin;
 pub mod query;
-pub mod hook;
 pub mod asset_mint;
 pub mod auction_create;
 pub mod auction_seize;
+pub mod token_authority;
+pub mod mint_authority;
+pub mod token_metadata;
+pub mod clawback;
 
 // 精确导出公共接口，避免通配符导出导致的模块边界不清晰
 // 只导出外部模块（如lib.rs）需要使用的账户结构体
@@ -75,6 +107,12 @@ pub use init_pool::InitPool;
 // 铸造指令公共接口
 pub use mint_to_pools::MintToPools;
 
+// 池子释放指令公共接口
+pub use pool_release::{
+    ReleasePoolTokens,
+    RefreshCirculatingSupply,
+};
+
 // 持有者管理指令公共接口
 pub use holder::{
     InitializeHolder,
@@ -96,31 +134,152 @@ pub use transfer::TransferWithTax;
 pub use consume::{
     ConsumeToTreasury,
     ConsumeType,
+    ConsumeReceiptArgs,
+    RedeemConsumeReceipt,
+    CloseConsumeReceipt,
+};
+
+// 价格目录指令公共接口
+pub use catalog::{
+    InitializePriceCatalog,
+    ManagePriceCatalog,
 };
 
 // 平台转账指令公共接口
 pub use platform_transfer::PlatformTransfer;
 
+// 平台预算指令公共接口
+pub use platform_budget::{
+    InitializePlatformBudget,
+    ManagePlatformBudget,
+    InitializePlatformVault,
+};
+
+// 奖励兑换券指令公共接口
+pub use voucher::{
+    InitializeRewardsVault,
+    RedeemVoucher,
+};
+
+// 订阅指令公共接口
+pub use subscription::{
+    CreateSubscription,
+    ChargeSubscription,
+    CancelSubscription,
+};
+
+// 商户与发票指令公共接口
+pub use merchant::{
+    RegisterMerchant,
+    UpdateMerchant,
+    CreateInvoice,
+    PayInvoice,
+    CloseInvoice,
+};
+
+// 合作程序指令公共接口
+pub use partner::{
+    RegisterPartner,
+    UpdatePartner,
+    PartnerConsume,
+    PartnerTransferWithTax,
+    PartnerRecordHolderActivity,
+    HolderActivity,
+};
+
+// Transfer Hook持有者统计指令公共接口
+pub use hook_stats::{
+    InitializeTransferGuard,
+    RecordHookTransfer,
+    HookTransferRecord,
+};
+
+// 积分指令公共接口
+pub use points::{
+    InitializePointsConfig,
+    UpdatePointsConfig,
+    InitializePointsAccount,
+    ClaimHoldingPoints,
+    RedeemPoints,
+    ConvertPointsToTaxDiscount,
+};
+
+// 批量转账指令公共接口
+pub use batch_transfer::{
+    BatchTransfer,
+    BatchTransferEntry,
+};
+
+// 代币流指令公共接口
+pub use stream::{
+    CreateStream,
+    WithdrawStream,
+    CancelStream,
+};
+
+// P2P挂单指令公共接口
+pub use offer::{
+    CreateOffer,
+    FillOffer,
+    CancelOffer,
+};
+
 // 管理员指令公共接口
 pub use admin::{
-    UpdateAuthority,
+    ProposeAuthority,
+    AcceptAuthority,
+    CancelAuthorityTransfer,
+    SetAuthorityTransferDelay,
     SetPaused,
+    SetPanicMode,
     EmergencyWithdraw,
     SetTwsTreasury,
+    SetComplianceAuthority,
+    SetReceiptAuthority,
+    SetVoucherSigner,
+    MigrateConfig,
+};
+
+// Token-2022权限移交指令公共接口
+pub use token_authority::{
+    ProposeTokenAuthority,
+    AcceptTokenAuthority,
+    CancelTokenAuthorityTransfer,
+    TokenAuthorityKind,
+};
+
+// 铸造权限管理指令公共接口
+pub use mint_authority::{
+    MigrateMintAuthority,
+    RenounceMintAuthority,
+};
+
+// 链上元数据指令公共接口
+pub use token_metadata::{
+    InitializeTokenMetadata,
+    UpdateTokenMetadata,
+    TokenMetadataField,
+};
+
+// 追回指令公共接口
+pub use clawback::{
+    Clawback,
+    MigratePermanentDelegate,
 };
 
 // 查询指令公共接口
 pub use query::{
     CalculateTax,
     GetHolderStats,
+    GetProtocolStats,
+    ProtocolStats,
+    GetPlatformBudget,
+    PlatformBudgetStatus,
+    GetPointsLeaderboard,
+    PointsLeaderboardEntry,
     DiscountTier,
-};
-
-// Transfer Hook指令公共接口
-pub use hook::{
-    InitializeTransferHook,
-    ExecuteTransferHook,
-    TransferHookAdminAction,
+    TaxCalculationResult,
+    HolderStats,
 };
 
 // 资产上链指令公共接口
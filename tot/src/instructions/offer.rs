@@ -0,0 +1,624 @@
+// ============================================
+// 文件: src/instructions/offer.rs
+// P2P托管挂单指令 - 挂单、吃单、撤单
+// ============================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::state::config::{TotConfig, PauseFlag};
+use crate::state::tax::TaxConfig;
+use crate::state::holder::HolderAccount;
+use crate::state::offer::TradeOffer;
+use crate::constants::seeds;
+use crate::errors::TotError;
+use crate::utils::tax_calculator::*;
+use crate::utils::validation::validate_transfer_amount;
+use crate::utils::token_transfer::transfer_checked_with_hook;
+
+/// 创建挂单账户结构
+#[derive(Accounts)]
+#[instruction(offer_id: u64)]
+pub struct CreateOffer<'info> {
+    /// 挂单方（签名者，支付租金）
+    #[account(mut)]
+    pub maker: Signer<'info>,
+
+    /// 挂单方TOT代币账户
+    #[account(
+        mut,
+        constraint = maker_token_account.owner == maker.key() @ TotError::InvalidOwner,
+        constraint = maker_token_account.mint == mint.key() @ TotError::InvalidMint
+    )]
+    pub maker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 挂单方持有者信息（冻结的持有者不能挂单）
+    #[account(
+        seeds = [seeds::HOLDER_SEED, maker.key().as_ref()],
+        bump = maker_holder_info.bump
+    )]
+    pub maker_holder_info: Account<'info, HolderAccount>,
+
+    /// 挂单账户
+    #[account(
+        init,
+        payer = maker,
+        space = TradeOffer::LEN,
+        seeds = [seeds::OFFER_SEED, maker.key().as_ref(), offer_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub offer: Account<'info, TradeOffer>,
+
+    /// 托管代币账户（挂单PDA的ATA）
+    #[account(
+        init,
+        payer = maker,
+        associated_token::mint = mint,
+        associated_token::authority = offer,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// TOT Mint
+    #[account(address = config.mint @ TotError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// 要价代币Mint（SOL使用Wrapped SOL）
+    pub ask_mint: InterfaceAccount<'info, Mint>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 创建挂单处理器
+///
+/// 挂单方把`tot_amount`个TOT转入托管账户，要价为`ask_amount`个`ask_mint`代币。
+///
+/// # 参数
+/// * `ctx` - 创建上下文
+/// * `offer_id` - 挂单编号（同一挂单方下唯一）
+/// * `tot_amount` - 托管的TOT数量
+/// * `ask_amount` - 全部TOT对应的要价数量
+///
+/// # 注意事项
+/// - 转入托管账户不收税，税收在吃单时按卖出收取
+/// - 挂单总量按托管账户实际收到的数量记录（扣除Transfer Fee预扣后）
+pub fn create_offer_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateOffer<'info>>,
+    offer_id: u64,
+    tot_amount: u64,
+    ask_amount: u64,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    ctx.accounts.config.require_not_paused(PauseFlag::Transfers, timestamp)?;
+    validate_transfer_amount(tot_amount)?;
+    require!(
+        ask_amount > 0 && ctx.accounts.ask_mint.key() != ctx.accounts.mint.key(),
+        TotError::InvalidOfferTerms
+    );
+    require!(!ctx.accounts.maker_holder_info.is_frozen, TotError::HolderFrozen);
+    require!(
+        ctx.accounts.maker_token_account.amount >= tot_amount,
+        TotError::InsufficientBalance
+    );
+
+    transfer_checked_with_hook(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.maker_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.escrow_token_account.to_account_info(),
+                authority: ctx.accounts.maker.to_account_info(),
+            },
+        ),
+        ctx.remaining_accounts,
+        tot_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    // 以托管账户实际收到的数量作为挂单总量
+    ctx.accounts.escrow_token_account.reload()?;
+    let escrowed = ctx.accounts.escrow_token_account.amount;
+
+    let offer = &mut ctx.accounts.offer;
+    offer.offer_id = offer_id;
+    offer.maker = ctx.accounts.maker.key();
+    offer.escrow_token_account = ctx.accounts.escrow_token_account.key();
+    offer.ask_mint = ctx.accounts.ask_mint.key();
+    offer.tot_amount = escrowed;
+    offer.tot_remaining = escrowed;
+    offer.ask_amount = ask_amount;
+    offer.is_cancelled = false;
+    offer.created_at = timestamp;
+    offer.bump = ctx.bumps.offer;
+
+    emit!(OfferCreated {
+        offer: offer.key(),
+        offer_id,
+        maker: offer.maker,
+        ask_mint: offer.ask_mint,
+        tot_amount: escrowed,
+        ask_amount,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 吃单账户结构
+#[derive(Accounts)]
+pub struct FillOffer<'info> {
+    /// 吃单方（签名者）
+    pub taker: Signer<'info>,
+
+    /// 吃单方TOT代币账户（接收TOT）
+    #[account(
+        mut,
+        constraint = taker_token_account.owner == taker.key() @ TotError::InvalidOwner,
+        constraint = taker_token_account.mint == mint.key() @ TotError::InvalidMint
+    )]
+    pub taker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 吃单方要价代币账户（支付要价）
+    #[account(
+        mut,
+        constraint = taker_ask_account.owner == taker.key() @ TotError::InvalidOwner,
+        constraint = taker_ask_account.mint == ask_mint.key() @ TotError::InvalidMint
+    )]
+    pub taker_ask_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 挂单方要价代币账户（接收要价）
+    #[account(
+        mut,
+        constraint = maker_ask_account.owner == offer.maker @ TotError::InvalidOwner,
+        constraint = maker_ask_account.mint == ask_mint.key() @ TotError::InvalidMint
+    )]
+    pub maker_ask_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 挂单方TOT代币账户（只读，计算挂单方剩余持仓）
+    #[account(
+        constraint = maker_token_account.owner == offer.maker @ TotError::InvalidOwner,
+        constraint = maker_token_account.mint == mint.key() @ TotError::InvalidMint
+    )]
+    pub maker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 挂单账户
+    #[account(
+        mut,
+        seeds = [seeds::OFFER_SEED, offer.maker.as_ref(), offer.offer_id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+        has_one = escrow_token_account @ TotError::TokenAccountMismatch,
+        has_one = ask_mint @ TotError::InvalidMint
+    )]
+    pub offer: Account<'info, TradeOffer>,
+
+    /// 托管代币账户
+    #[account(mut)]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// TOT Mint
+    #[account(
+        mut,
+        address = config.mint @ TotError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// 要价代币Mint
+    pub ask_mint: InterfaceAccount<'info, Mint>,
+
+    /// 全局配置（累计协议统计）
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 税率配置
+    #[account(
+        seeds = [seeds::TAX_CONFIG_SEED],
+        bump
+    )]
+    pub tax_config: Account<'info, TaxConfig>,
+
+    /// 挂单方持有者信息（更新卖出统计）
+    #[account(
+        mut,
+        seeds = [seeds::HOLDER_SEED, offer.maker.as_ref()],
+        bump = maker_holder_info.bump
+    )]
+    pub maker_holder_info: Account<'info, HolderAccount>,
+
+    /// 吃单方持有者信息（更新买入统计）
+    #[account(
+        mut,
+        seeds = [seeds::HOLDER_SEED, taker.key().as_ref()],
+        bump = taker_holder_info.bump
+    )]
+    pub taker_holder_info: Account<'info, HolderAccount>,
+
+    /// 税收收集账户（流动性池）
+    #[account(
+        mut,
+        constraint = tax_collector.mint == mint.key() @ TotError::InvalidMint
+    )]
+    pub tax_collector: InterfaceAccount<'info, TokenAccount>,
+
+    /// TOT的Token程序（Token-2022）
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// 要价代币的Token程序（SPL Token或Token-2022）
+    pub ask_token_program: Interface<'info, TokenInterface>,
+}
+
+/// 吃单处理器
+///
+/// 吃单方支付要价，取得挂单中的`fill_amount`个TOT，支持部分成交。
+///
+/// # 功能流程
+///
+/// 1. **验证阶段**: 检查暂停状态、挂单状态、成交数量、双方冻结状态
+/// 2. **支付要价**: 按挂单价格向上取整折算要价，从吃单方转给挂单方
+/// 3. **税收计算**: TOT离开托管按挂单方卖出计税（挂单方或吃单方免税时不收税）
+/// 4. **交付TOT**: 净额转给吃单方，税收40%销毁、其余转入税收收集账户
+/// 5. **更新统计**: 挂单方记录卖出，吃单方记录买入
+///
+/// # 注意事项
+/// - 与`transfer_with_tax`不同，冻结检查不受免税影响，任一方冻结都不能成交
+/// - 要价向上取整，多次部分成交累计支付的要价可能略高于`ask_amount`
+pub fn fill_offer_handler<'info>(ctx: Context<'_, '_, '_, 'info, FillOffer<'info>>, fill_amount: u64) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let config = &ctx.accounts.config;
+    let tax_config = &ctx.accounts.tax_config;
+    let offer = &ctx.accounts.offer;
+
+    // ========================================
+    // 验证阶段
+    // ========================================
+
+    config.require_not_paused(PauseFlag::Transfers, timestamp)?;
+    config.require_not_paused(PauseFlag::Sells, timestamp)?;
+    validate_transfer_amount(fill_amount)?;
+    require!(offer.is_open(), TotError::OfferClosed);
+
+    let maker_key = offer.maker;
+    let taker_key = ctx.accounts.taker.key();
+    require!(maker_key != taker_key, TotError::TransferToSelf);
+
+    require!(!ctx.accounts.maker_holder_info.is_frozen, TotError::HolderFrozen);
+    require!(!ctx.accounts.taker_holder_info.is_frozen, TotError::HolderFrozen);
+
+    let ask_paid = offer.ask_for_fill(fill_amount)?;
+
+    // ========================================
+    // 支付要价（吃单方 -> 挂单方）
+    // ========================================
+
+    transfer_checked_with_hook(
+        CpiContext::new(
+            ctx.accounts.ask_token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.taker_ask_account.to_account_info(),
+                mint: ctx.accounts.ask_mint.to_account_info(),
+                to: ctx.accounts.maker_ask_account.to_account_info(),
+                authority: ctx.accounts.taker.to_account_info(),
+            },
+        ),
+        ctx.remaining_accounts,
+        ask_paid,
+        ctx.accounts.ask_mint.decimals,
+    )?;
+
+    // ========================================
+    // 税率计算（按挂单方卖出）
+    // ========================================
+
+    let is_exempt = tax_config.is_exempt(&maker_key) || tax_config.is_exempt(&taker_key);
+
+    let (tax_amount, net_amount, tax_rate_bps) = if is_exempt {
+        (0, fill_amount, 0)
+    } else {
+        let calculation = TaxCalculator::calculate_tax(
+            fill_amount,
+            Some(&ctx.accounts.maker_holder_info),
+            config.circulating_supply(ctx.accounts.mint.supply),
+            timestamp,
+            false, // 对挂单方不是买入
+            true,  // TOT离开托管视为挂单方卖出
+            config.panic_mode,
+            tax_config,
+        )?;
+        (calculation.tax_amount, calculation.net_amount, calculation.final_tax_bps)
+    };
+
+    // ========================================
+    // 交付TOT（托管账户 -> 吃单方/税收）
+    // ========================================
+
+    let offer_id_bytes = offer.offer_id.to_le_bytes();
+    let offer_seeds: &[&[u8]] = &[
+        seeds::OFFER_SEED,
+        maker_key.as_ref(),
+        offer_id_bytes.as_ref(),
+        &[offer.bump],
+    ];
+    let signer_seeds = &[offer_seeds];
+    let mint_decimals = ctx.accounts.mint.decimals;
+
+    if net_amount > 0 {
+        transfer_checked_with_hook(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.escrow_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.taker_token_account.to_account_info(),
+                    authority: ctx.accounts.offer.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            ctx.remaining_accounts,
+            net_amount,
+            mint_decimals,
+        )?;
+    }
+
+    let burned = if tax_amount > 0 {
+        let tax_dist = TaxDistribution::calculate(tax_amount)?;
+
+        if tax_dist.to_burn > 0 {
+            token_interface::burn(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        from: ctx.accounts.escrow_token_account.to_account_info(),
+                        authority: ctx.accounts.offer.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                tax_dist.to_burn,
+            )?;
+        }
+
+        let remaining_tax = tax_amount
+            .checked_sub(tax_dist.to_burn)
+            .ok_or(TotError::MathUnderflow)?;
+        if remaining_tax > 0 {
+            transfer_checked_with_hook(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.escrow_token_account.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.tax_collector.to_account_info(),
+                        authority: ctx.accounts.offer.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                ctx.remaining_accounts,
+                remaining_tax,
+                mint_decimals,
+            )?;
+        }
+
+        tax_dist.to_burn
+    } else {
+        0
+    };
+
+    // 累计全局收税和销毁统计
+    if tax_amount > 0 {
+        ctx.accounts.config.record_tax(tax_amount, burned)?;
+    }
+
+    // ========================================
+    // 更新持有者统计和挂单状态
+    // ========================================
+
+    let offer = &mut ctx.accounts.offer;
+    offer.tot_remaining = offer.tot_remaining
+        .checked_sub(fill_amount)
+        .ok_or(TotError::MathUnderflow)?;
+
+    // 挂单方剩余持仓 = 钱包余额 + 本挂单未成交的托管余额
+    let maker_remaining = ctx.accounts.maker_token_account.amount
+        .checked_add(offer.tot_remaining)
+        .ok_or(TotError::MathOverflow)?;
+    ctx.accounts.maker_holder_info.record_sell(
+        fill_amount,
+        tax_amount,
+        maker_remaining,
+        timestamp,
+    )?;
+
+    let taker_holder = &mut ctx.accounts.taker_holder_info;
+    taker_holder.record_buy(net_amount, 0, timestamp)?;
+    if taker_holder.token_account == Pubkey::default() {
+        taker_holder.token_account = ctx.accounts.taker_token_account.key();
+    }
+
+    let offer = &ctx.accounts.offer;
+
+    emit!(OfferFilled {
+        offer: offer.key(),
+        maker: maker_key,
+        taker: taker_key,
+        fill_amount,
+        ask_paid,
+        tax_amount,
+        net_amount,
+        tax_rate_bps,
+        burned,
+        tot_remaining: offer.tot_remaining,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 撤单账户结构
+#[derive(Accounts)]
+pub struct CancelOffer<'info> {
+    /// 挂单方（签名者）
+    pub maker: Signer<'info>,
+
+    /// 挂单方TOT代币账户（接收退回的代币）
+    #[account(
+        mut,
+        constraint = maker_token_account.owner == maker.key() @ TotError::InvalidOwner,
+        constraint = maker_token_account.mint == mint.key() @ TotError::InvalidMint
+    )]
+    pub maker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 挂单账户
+    #[account(
+        mut,
+        seeds = [seeds::OFFER_SEED, maker.key().as_ref(), offer.offer_id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+        has_one = maker @ TotError::Unauthorized,
+        has_one = escrow_token_account @ TotError::TokenAccountMismatch
+    )]
+    pub offer: Account<'info, TradeOffer>,
+
+    /// 托管代币账户
+    #[account(mut)]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// TOT Mint
+    #[account(address = config.mint @ TotError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// 撤单处理器
+///
+/// 挂单方取回剩余未成交的TOT。退回的是挂单方自己的代币，不收税。
+pub fn cancel_offer_handler<'info>(ctx: Context<'_, '_, '_, 'info, CancelOffer<'info>>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let offer = &ctx.accounts.offer;
+
+    ctx.accounts.config.require_not_paused(PauseFlag::Transfers, timestamp)?;
+    require!(offer.is_open(), TotError::OfferClosed);
+
+    let refund = offer.tot_remaining;
+    let maker_key = offer.maker;
+    let offer_id_bytes = offer.offer_id.to_le_bytes();
+    let offer_seeds: &[&[u8]] = &[
+        seeds::OFFER_SEED,
+        maker_key.as_ref(),
+        offer_id_bytes.as_ref(),
+        &[offer.bump],
+    ];
+
+    transfer_checked_with_hook(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.maker_token_account.to_account_info(),
+                authority: ctx.accounts.offer.to_account_info(),
+            },
+            &[offer_seeds],
+        ),
+        ctx.remaining_accounts,
+        refund,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    let offer = &mut ctx.accounts.offer;
+    offer.tot_remaining = 0;
+    offer.is_cancelled = true;
+
+    emit!(OfferCancelled {
+        offer: offer.key(),
+        maker: maker_key,
+        refunded_amount: refund,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 挂单创建事件
+#[event]
+pub struct OfferCreated {
+    /// 挂单账户
+    pub offer: Pubkey,
+    /// 挂单编号
+    pub offer_id: u64,
+    /// 挂单方
+    pub maker: Pubkey,
+    /// 要价代币Mint
+    pub ask_mint: Pubkey,
+    /// 挂单TOT总量
+    pub tot_amount: u64,
+    /// 全部TOT对应的要价数量
+    pub ask_amount: u64,
+    /// 创建时间
+    pub timestamp: i64,
+}
+
+/// 吃单事件
+#[event]
+pub struct OfferFilled {
+    /// 挂单账户
+    pub offer: Pubkey,
+    /// 挂单方
+    pub maker: Pubkey,
+    /// 吃单方
+    pub taker: Pubkey,
+    /// 本次成交的TOT数量（含税）
+    pub fill_amount: u64,
+    /// 吃单方支付的要价代币数量
+    pub ask_paid: u64,
+    /// 税额
+    pub tax_amount: u64,
+    /// 吃单方实际收到的TOT数量
+    pub net_amount: u64,
+    /// 最终税率（basis points）
+    pub tax_rate_bps: u16,
+    /// 销毁的代币数量
+    pub burned: u64,
+    /// 成交后剩余的TOT数量
+    pub tot_remaining: u64,
+    /// 成交时间
+    pub timestamp: i64,
+}
+
+/// 撤单事件
+#[event]
+pub struct OfferCancelled {
+    /// 挂单账户
+    pub offer: Pubkey,
+    /// 挂单方
+    pub maker: Pubkey,
+    /// 退回挂单方的TOT数量
+    pub refunded_amount: u64,
+    /// 撤单时间
+    pub timestamp: i64,
+}
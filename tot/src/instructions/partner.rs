@@ -0,0 +1,392 @@
+// ============================================
+// 文件: src/instructions/partner.rs
+// 合作程序白名单与CPI入口指令
+// ============================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::state::config::{TotConfig, PauseFlag};
+use crate::state::holder::HolderAccount;
+use crate::state::partner::{PartnerProgram, PartnerPermission};
+// 嵌套的账户结构需要其派生宏生成的辅助模块，因此整体导入
+use crate::instructions::consume::*;
+use crate::instructions::transfer::*;
+use crate::constants::seeds;
+use crate::errors::TotError;
+
+/// 合作程序上报的持有者活动
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HolderActivity {
+    /// 持有者收到代币（如拍卖成交、预测结算派奖）
+    Buy { amount: u64, tax_paid: u64 },
+    /// 持有者转出代币
+    Sell { amount: u64, tax_paid: u64 },
+    /// 持有者消费（如预测下注进入合作程序金库）
+    Consume { amount: u64, consume_type: ConsumeType },
+}
+
+/// 登记合作程序账户结构
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey)]
+pub struct RegisterPartner<'info> {
+    /// 系统管理员（支付租金）
+    #[account(
+        mut,
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 合作程序登记PDA
+    #[account(
+        init,
+        payer = authority,
+        space = PartnerProgram::LEN,
+        seeds = [seeds::PARTNER_SEED, program_id.as_ref()],
+        bump
+    )]
+    pub partner: Account<'info, PartnerProgram>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 登记合作程序处理器
+///
+/// # 参数
+/// * `program_id` - 合作程序ID
+/// * `permissions` - 权限位图（见`PartnerPermission`）
+pub fn register_partner_handler(
+    ctx: Context<RegisterPartner>,
+    program_id: Pubkey,
+    permissions: u8,
+) -> Result<()> {
+    require!(program_id != Pubkey::default(), TotError::InvalidPartnerProgram);
+    PartnerProgram::validate_permissions(permissions)?;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let partner = &mut ctx.accounts.partner;
+    partner.program_id = program_id;
+    partner.authority = PartnerProgram::derive_authority(&program_id);
+    partner.permissions = permissions;
+    partner.is_active = true;
+    partner.registered_at = timestamp;
+    partner.bump = ctx.bumps.partner;
+
+    msg!("Registered partner program: {}", program_id);
+
+    emit!(PartnerUpdated {
+        program_id,
+        authority: partner.authority,
+        permissions,
+        is_active: true,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 更新合作程序账户结构
+#[derive(Accounts)]
+pub struct UpdatePartner<'info> {
+    /// 系统管理员
+    #[account(
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 合作程序登记PDA
+    #[account(
+        mut,
+        seeds = [seeds::PARTNER_SEED, partner.program_id.as_ref()],
+        bump = partner.bump
+    )]
+    pub partner: Account<'info, PartnerProgram>,
+}
+
+/// 更新合作程序处理器
+///
+/// 修改权限或启用状态，停用后合作程序的所有CPI调用立即被拒绝。
+pub fn update_partner_handler(
+    ctx: Context<UpdatePartner>,
+    permissions: u8,
+    is_active: bool,
+) -> Result<()> {
+    PartnerProgram::validate_permissions(permissions)?;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let partner = &mut ctx.accounts.partner;
+    partner.permissions = permissions;
+    partner.is_active = is_active;
+
+    emit!(PartnerUpdated {
+        program_id: partner.program_id,
+        authority: partner.authority,
+        permissions,
+        is_active,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 合作程序消费账户结构
+///
+/// 在`consume_to_treasury`的账户之外增加合作程序签名PDA和登记账户。
+#[derive(Accounts)]
+pub struct PartnerConsume<'info> {
+    /// 合作程序签名PDA（合作程序invoke_signed签名）
+    pub partner_authority: Signer<'info>,
+
+    /// 合作程序登记PDA
+    #[account(
+        seeds = [seeds::PARTNER_SEED, partner.program_id.as_ref()],
+        bump = partner.bump
+    )]
+    pub partner: Account<'info, PartnerProgram>,
+
+    /// 消费账户（与consume_to_treasury相同）
+    pub consume: ConsumeToTreasury<'info>,
+}
+
+/// 合作程序消费处理器
+///
+/// 校验合作程序权限后，按`consume_to_treasury`的规则执行（价格目录、收入分成、
+/// 冻结检查、消费统计、回执）。
+pub fn partner_consume_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, PartnerConsume<'info>>,
+    amount: u64,
+    consume_type: ConsumeType,
+    sku: u32,
+    reference: Option<[u8; 32]>,
+    receipt: Option<ConsumeReceiptArgs>,
+) -> Result<()> {
+    ctx.accounts.partner.require_permission(
+        &ctx.accounts.partner_authority.key(),
+        PartnerPermission::Consume,
+    )?;
+
+    process_consume(
+        &mut ctx.accounts.consume,
+        ctx.program_id,
+        ctx.remaining_accounts,
+        amount,
+        consume_type,
+        sku,
+        reference,
+        receipt,
+    )?;
+
+    emit!(PartnerInvoked {
+        program_id: ctx.accounts.partner.program_id,
+        permission: PartnerPermission::Consume as u8,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// 合作程序带税转账账户结构
+///
+/// 在`transfer_with_tax`的账户之外增加合作程序签名PDA和登记账户。
+/// 发送者可以是用户，也可以是合作程序的金库PDA（需要先初始化持有者账户）。
+#[derive(Accounts)]
+pub struct PartnerTransferWithTax<'info> {
+    /// 合作程序签名PDA（合作程序invoke_signed签名）
+    pub partner_authority: Signer<'info>,
+
+    /// 合作程序登记PDA
+    #[account(
+        seeds = [seeds::PARTNER_SEED, partner.program_id.as_ref()],
+        bump = partner.bump
+    )]
+    pub partner: Account<'info, PartnerProgram>,
+
+    /// 转账账户（与transfer_with_tax相同）
+    pub transfer: TransferWithTax<'info>,
+}
+
+/// 合作程序带税转账处理器
+///
+/// 校验合作程序权限后，按`transfer_with_tax`的规则执行（免税、冻结、动态税率、
+/// 税收分配、持有者统计、支付回执）。
+pub fn partner_transfer_with_tax_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, PartnerTransferWithTax<'info>>,
+    amount: u64,
+    is_sell: bool,
+    reference: Option<[u8; 32]>,
+) -> Result<()> {
+    ctx.accounts.partner.require_permission(
+        &ctx.accounts.partner_authority.key(),
+        PartnerPermission::TaxedTransfer,
+    )?;
+
+    process_transfer_with_tax(
+        &mut ctx.accounts.transfer,
+        ctx.program_id,
+        ctx.remaining_accounts,
+        amount,
+        is_sell,
+        false,
+        reference,
+    )?;
+
+    emit!(PartnerInvoked {
+        program_id: ctx.accounts.partner.program_id,
+        permission: PartnerPermission::TaxedTransfer as u8,
+        amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// 合作程序上报持有者活动账户结构
+#[derive(Accounts)]
+pub struct PartnerRecordHolderActivity<'info> {
+    /// 合作程序签名PDA（合作程序invoke_signed签名）
+    pub partner_authority: Signer<'info>,
+
+    /// 合作程序登记PDA
+    #[account(
+        seeds = [seeds::PARTNER_SEED, partner.program_id.as_ref()],
+        bump = partner.bump
+    )]
+    pub partner: Account<'info, PartnerProgram>,
+
+    /// 持有者信息
+    #[account(
+        mut,
+        seeds = [seeds::HOLDER_SEED, holder_info.owner.as_ref()],
+        bump = holder_info.bump
+    )]
+    pub holder_info: Account<'info, HolderAccount>,
+
+    /// 持有者TOT代币账户（卖出活动必填，用于判断是否清仓）
+    #[account(
+        constraint = holder_token_account.owner == holder_info.owner @ TotError::InvalidOwner,
+        constraint = holder_token_account.mint == config.mint @ TotError::InvalidMint
+    )]
+    pub holder_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 全局配置（消费活动累计全局消费统计）
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+}
+
+/// 合作程序上报持有者活动处理器
+///
+/// 合作程序自行转移代币后调用，更新持有者统计。
+///
+/// # 注意事项
+/// - 持有者被冻结时返回`HolderFrozen`，合作程序的整笔交易回滚，相当于冻结检查
+/// - 与对应的TOT指令共用暂停位：买入受转账暂停控制，卖出同时受卖出暂停控制，消费受消费暂停控制
+/// - 卖出必须传入持有者代币账户，未传入返回`TokenAccountMismatch`；转出后余额为0时重置持有时间
+pub fn partner_record_holder_activity_handler(
+    ctx: Context<PartnerRecordHolderActivity>,
+    activity: HolderActivity,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    ctx.accounts.partner.require_permission(
+        &ctx.accounts.partner_authority.key(),
+        PartnerPermission::HolderStats,
+    )?;
+    require!(!ctx.accounts.holder_info.is_frozen, TotError::HolderFrozen);
+
+    let config = &mut ctx.accounts.config;
+    let holder = &mut ctx.accounts.holder_info;
+    let amount = match activity {
+        HolderActivity::Buy { amount, tax_paid } => {
+            config.require_not_paused(PauseFlag::Transfers, timestamp)?;
+            holder.record_buy(amount, tax_paid, timestamp)?;
+            amount
+        }
+        HolderActivity::Sell { amount, tax_paid } => {
+            config.require_not_paused(PauseFlag::Transfers, timestamp)?;
+            config.require_not_paused(PauseFlag::Sells, timestamp)?;
+            let remaining = ctx.accounts.holder_token_account
+                .as_ref()
+                .ok_or(TotError::TokenAccountMismatch)?
+                .amount;
+            holder.record_sell(amount, tax_paid, remaining, timestamp)?;
+            amount
+        }
+        HolderActivity::Consume { amount, consume_type } => {
+            config.require_not_paused(PauseFlag::Consume, timestamp)?;
+            holder.record_consume(amount, timestamp)?;
+            config.record_consume(consume_type as u8, amount)?;
+            amount
+        }
+    };
+
+    emit!(PartnerHolderActivityRecorded {
+        program_id: ctx.accounts.partner.program_id,
+        holder: holder.owner,
+        activity,
+        amount,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 合作程序登记/更新事件
+#[event]
+pub struct PartnerUpdated {
+    /// 合作程序ID
+    pub program_id: Pubkey,
+    /// 合作程序签名PDA
+    pub authority: Pubkey,
+    /// 权限位图
+    pub permissions: u8,
+    /// 是否启用
+    pub is_active: bool,
+    /// 更新时间
+    pub timestamp: i64,
+}
+
+/// 合作程序CPI调用事件（消费、带税转账）
+#[event]
+pub struct PartnerInvoked {
+    /// 合作程序ID
+    pub program_id: Pubkey,
+    /// 使用的权限（`PartnerPermission as u8`）
+    pub permission: u8,
+    /// 金额
+    pub amount: u64,
+    /// 调用时间
+    pub timestamp: i64,
+}
+
+/// 合作程序上报持有者活动事件
+#[event]
+pub struct PartnerHolderActivityRecorded {
+    /// 合作程序ID
+    pub program_id: Pubkey,
+    /// 持有者钱包
+    pub holder: Pubkey,
+    /// 活动内容
+    pub activity: HolderActivity,
+    /// 金额
+    pub amount: u64,
+    /// 上报时间
+    pub timestamp: i64,
+}
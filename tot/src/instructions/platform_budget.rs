@@ -0,0 +1,258 @@
+// ============================================
+// 文件: src/instructions/platform_budget.rs
+// 平台预算管理指令
+// ============================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::state::config::TotConfig;
+use crate::state::platform_budget::PlatformBudget;
+use crate::constants::seeds;
+use crate::errors::TotError;
+
+/// 初始化平台预算账户结构
+#[derive(Accounts)]
+pub struct InitializePlatformBudget<'info> {
+    /// 系统管理员（支付租金）
+    #[account(
+        mut,
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 平台预算PDA
+    #[account(
+        init,
+        payer = authority,
+        space = PlatformBudget::LEN,
+        seeds = [seeds::PLATFORM_BUDGET_SEED],
+        bump
+    )]
+    pub platform_budget: Account<'info, PlatformBudget>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 初始化平台预算处理器
+///
+/// 创建平台预算账户，第一个周期从当前时间开始。
+/// `platform_transfer`需要传入该账户，部署后应先执行本指令。
+///
+/// # 参数
+/// * `period_seconds` - 周期长度（秒）
+/// * `period_cap` - 每个周期的总预算
+/// * `per_recipient_cap` - 每个周期向单个接收者转账的额度
+pub fn initialize_platform_budget_handler(
+    ctx: Context<InitializePlatformBudget>,
+    period_seconds: i64,
+    period_cap: u64,
+    per_recipient_cap: u64,
+) -> Result<()> {
+    PlatformBudget::validate_params(period_seconds, period_cap, per_recipient_cap)?;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let budget = &mut ctx.accounts.platform_budget;
+    budget.period_seconds = period_seconds;
+    budget.period_cap = period_cap;
+    budget.per_recipient_cap = per_recipient_cap;
+    budget.period_start = timestamp;
+    budget.period_spent = 0;
+    budget.signers = Vec::new();
+    budget.bump = ctx.bumps.platform_budget;
+
+    emit!(PlatformBudgetUpdated {
+        period_seconds,
+        period_cap,
+        per_recipient_cap,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 平台预算维护账户结构（更新预算、管理热钱包共用）
+#[derive(Accounts)]
+pub struct ManagePlatformBudget<'info> {
+    /// 系统管理员
+    #[account(
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 平台预算PDA
+    #[account(
+        mut,
+        seeds = [seeds::PLATFORM_BUDGET_SEED],
+        bump = platform_budget.bump
+    )]
+    pub platform_budget: Account<'info, PlatformBudget>,
+}
+
+/// 更新平台预算处理器
+///
+/// 修改周期长度和额度。当前周期的已用额度保留，新额度立即生效；
+/// 修改周期长度后，下一个周期从当前周期开始时间按新长度对齐。
+pub fn update_platform_budget_handler(
+    ctx: Context<ManagePlatformBudget>,
+    period_seconds: i64,
+    period_cap: u64,
+    per_recipient_cap: u64,
+) -> Result<()> {
+    PlatformBudget::validate_params(period_seconds, period_cap, per_recipient_cap)?;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let budget = &mut ctx.accounts.platform_budget;
+    budget.roll(timestamp);
+    budget.period_seconds = period_seconds;
+    budget.period_cap = period_cap;
+    budget.per_recipient_cap = per_recipient_cap;
+
+    emit!(PlatformBudgetUpdated {
+        period_seconds,
+        period_cap,
+        per_recipient_cap,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 登记平台热钱包处理器
+///
+/// 登记后该地址可以调用`platform_transfer`，仍受预算限制。
+pub fn add_platform_signer_handler(
+    ctx: Context<ManagePlatformBudget>,
+    signer: Pubkey,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    ctx.accounts.platform_budget.add_signer(signer)?;
+
+    msg!("Added platform signer: {}", signer);
+
+    emit!(PlatformSignerAdded {
+        signer,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 移除平台热钱包处理器
+///
+/// 热钱包私钥泄露时，管理员移除后该地址立即失去平台转账权限。
+pub fn remove_platform_signer_handler(
+    ctx: Context<ManagePlatformBudget>,
+    signer: Pubkey,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    ctx.accounts.platform_budget.remove_signer(&signer)?;
+
+    msg!("Removed platform signer: {}", signer);
+
+    emit!(PlatformSignerRemoved {
+        signer,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 初始化平台金库账户结构
+#[derive(Accounts)]
+pub struct InitializePlatformVault<'info> {
+    /// 系统管理员（支付租金）
+    #[account(
+        mut,
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        has_one = mint @ TotError::InvalidMint,
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 平台预算PDA（金库代币账户的所有者）
+    #[account(
+        seeds = [seeds::PLATFORM_BUDGET_SEED],
+        bump = platform_budget.bump
+    )]
+    pub platform_budget: Account<'info, PlatformBudget>,
+
+    /// 平台金库代币账户（平台预算PDA的ATA）
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = platform_budget,
+        associated_token::token_program = token_program,
+    )]
+    pub platform_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// TOT Mint
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 初始化平台金库处理器
+///
+/// 创建平台预算PDA持有的代币账户。`platform_transfer`只从该账户转出，
+/// 由程序以平台预算PDA签名，热钱包不持有代币，私钥泄露时也只能在预算内转账。
+/// 平台资金通过普通转账转入金库。
+pub fn initialize_platform_vault_handler(ctx: Context<InitializePlatformVault>) -> Result<()> {
+    msg!("Platform vault initialized: {}", ctx.accounts.platform_vault.key());
+    Ok(())
+}
+
+/// 平台预算更新事件
+#[event]
+pub struct PlatformBudgetUpdated {
+    /// 周期长度（秒）
+    pub period_seconds: i64,
+    /// 每个周期的总预算
+    pub period_cap: u64,
+    /// 每个周期向单个接收者转账的额度
+    pub per_recipient_cap: u64,
+    /// 更新时间
+    pub timestamp: i64,
+}
+
+/// 平台热钱包登记事件
+#[event]
+pub struct PlatformSignerAdded {
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
+
+/// 平台热钱包移除事件
+#[event]
+pub struct PlatformSignerRemoved {
+    pub signer: Pubkey,
+    pub timestamp: i64,
+}
@@ -5,32 +5,38 @@
 
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{
-    self, Mint, TokenAccount, TokenInterface, TransferChecked,
+    Mint, TokenAccount, TokenInterface, TransferChecked,
 };
 
-use crate::state::config::TotConfig;
+use crate::state::config::{TotConfig, PauseFlag};
 use crate::state::holder::HolderAccount;
+use crate::state::platform_budget::{PlatformBudget, PlatformRecipientUsage};
+use crate::state::receipt::{PaymentReceipt, PaymentKind};
+use crate::instructions::receipt::create_payment_receipt;
 use crate::constants::seeds;
 use crate::errors::TotError;
 use crate::utils::validation::validate_transfer_amount;
+use crate::utils::token_transfer::transfer_checked_with_hook;
 
 /// 平台转账账户结构
 #[derive(Accounts)]
 pub struct PlatformTransfer<'info> {
-    /// 平台钱包（签名者，需要验证权限）
+    /// 平台钱包（签名者，系统管理员或已登记的平台热钱包）
     #[account(
         mut,
-        constraint = platform.key() == config.authority @ TotError::Unauthorized
+        constraint = platform.key() == config.authority
+            || platform_budget.is_signer(&platform.key()) @ TotError::Unauthorized
     )]
     pub platform: Signer<'info>,
 
-    /// 平台代币账户
+    /// 平台金库代币账户（平台预算PDA的ATA，由程序签名转出）
     #[account(
         mut,
-        constraint = platform_token_account.owner == platform.key() @ TotError::InvalidOwner,
-        constraint = platform_token_account.mint == mint.key() @ TotError::InvalidMint
+        associated_token::mint = mint,
+        associated_token::authority = platform_budget,
+        associated_token::token_program = token_program,
     )]
-    pub platform_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub platform_vault: InterfaceAccount<'info, TokenAccount>,
 
     /// 用户代币账户
     #[account(
@@ -43,8 +49,9 @@ pub struct PlatformTransfer<'info> {
     #[account(mut)]
     pub mint: InterfaceAccount<'info, Mint>,
 
-    /// 全局配置
+    /// 全局配置（累计协议统计）
     #[account(
+        mut,
         seeds = [seeds::CONFIG_SEED],
         bump
     )]
@@ -54,8 +61,34 @@ pub struct PlatformTransfer<'info> {
     /// CHECK: 如果不存在，需要先初始化
     pub user_holder_info: Option<Account<'info, HolderAccount>>,
 
+    /// 平台预算
+    #[account(
+        mut,
+        seeds = [seeds::PLATFORM_BUDGET_SEED],
+        bump = platform_budget.bump
+    )]
+    pub platform_budget: Account<'info, PlatformBudget>,
+
+    /// 接收者额度（首次向该接收者转账时创建）
+    #[account(
+        init_if_needed,
+        payer = platform,
+        space = PlatformRecipientUsage::LEN,
+        seeds = [seeds::PLATFORM_RECIPIENT_SEED, user_token_account.owner.as_ref()],
+        bump
+    )]
+    pub recipient_usage: Account<'info, PlatformRecipientUsage>,
+
     /// Token 程序
     pub token_program: Interface<'info, TokenInterface>,
+
+    /// 支付回执PDA（提供支付引用时必须传入）
+    /// CHECK: 由create_payment_receipt校验地址并创建
+    #[account(mut)]
+    pub payment_receipt: Option<UncheckedAccount<'info>>,
+
+    /// System Program（创建接收者额度和支付回执）
+    pub system_program: Program<'info, System>,
 }
 
 /// 平台转账处理器
@@ -66,38 +99,47 @@ pub struct PlatformTransfer<'info> {
 /// # 功能流程
 /// 
 /// 1. **验证阶段**: 检查系统状态、账户状态、金额有效性
-/// 2. **权限验证**: 验证签名者是否为系统管理员（平台钱包）
-/// 3. **执行转账**: 将全额金额转给用户（无税收）
-/// 4. **更新统计**: 更新用户的买入统计信息（如果是首次接收，初始化持有时间）
-/// 5. **发出事件**: 记录转账信息到链上日志
+/// 2. **权限验证**: 验证签名者是否为系统管理员或已登记的平台热钱包
+/// 3. **预算检查**: 扣减本周期总预算和接收者额度
+/// 4. **执行转账**: 平台预算PDA签名，从平台金库将全额金额转给用户（无税收）
+/// 5. **更新统计**: 更新用户的买入统计信息（如果是首次接收，初始化持有时间）
+/// 6. **发出事件**: 记录转账信息到链上日志
 /// 
 /// # 参数
 /// * `ctx` - 平台转账上下文，包含所有必需的账户
 /// * `amount` - 转账金额（全额转账，无税收）
+/// * `reference` - 支付引用（可选），写入事件并创建回执PDA，同一引用只能使用一次
 /// 
 /// # 返回值
 /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
 /// 
 /// # 注意事项
 /// 
-/// - 只有系统管理员（config.authority）可以执行此操作
+/// - 系统管理员（config.authority）或平台预算中登记的热钱包可以执行此操作
+/// - 代币只从平台金库（平台预算PDA的ATA）转出，签名者自己的代币账户不参与，
+///   热钱包私钥泄露时无法绕过预算直接调用Token-2022转账
+/// - 所有调用都受平台预算限制，超出周期预算返回`PlatformBudgetExceeded`，
+///   超出单个接收者额度返回`RecipientBudgetExceeded`
 /// - 系统处于恐慌模式时，平台转账仍可进行（不受限制）
 /// - 转账不收取税收，全额转账给用户
 /// - 会更新用户的买入统计和首次持有时间
+/// - 后端超时重试时必须使用相同的支付引用，已成功的请求会以`DuplicatePaymentReference`失败
 /// 
 /// # 使用示例
 /// ```rust
 /// // 执行平台转账
 /// program.methods
 ///     .platformTransfer(
-///         new anchor.BN(1000000000) // 转账1000个代币
+///         new anchor.BN(1000000000), // 转账1000个代币
+///         orderReference // 订单号哈希（32字节）
 ///     )
 ///     .accounts({...})
 ///     .rpc();
 /// ```
-pub fn platform_transfer_handler(
-    ctx: Context<PlatformTransfer>,
+pub fn platform_transfer_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, PlatformTransfer<'info>>,
     amount: u64,
+    reference: Option<[u8; 32]>,
 ) -> Result<()> {
     // 获取账户和配置引用
     let config = &ctx.accounts.config;
@@ -113,17 +155,51 @@ pub fn platform_transfer_handler(
     // 验证阶段
     // ========================================
     
+    // 暂停检查: 平台转账指令族被暂停时拒绝执行
+    config.require_not_paused(PauseFlag::PlatformTransfers, timestamp)?;
+
     // 验证1: 检查转账金额是否有效（必须大于0）
     validate_transfer_amount(amount)?;
 
     // 验证2: 权限验证（已在账户结构中验证）
-    // 确保签名者是系统管理员
+    // 确保签名者是系统管理员或已登记的平台热钱包
 
     // 验证3: 检查用户账户是否被冻结（如果存在持有者账户）
     if let Some(ref user_holder) = ctx.accounts.user_holder_info {
         require!(!user_holder.is_frozen, TotError::HolderFrozen);
     }
 
+    // ========================================
+    // 预算检查
+    // ========================================
+    // 
+    // 进入新周期时先清零已用额度，再分别检查周期总预算和接收者额度。
+
+    let budget = &mut ctx.accounts.platform_budget;
+    budget.roll(timestamp);
+    let period_spent = budget.period_spent
+        .checked_add(amount)
+        .ok_or(TotError::MathOverflow)?;
+    require!(period_spent <= budget.period_cap, TotError::PlatformBudgetExceeded);
+
+    let recipient_spent = ctx.accounts.recipient_usage
+        .spent_in(budget.period_start)
+        .checked_add(amount)
+        .ok_or(TotError::MathOverflow)?;
+    require!(
+        recipient_spent <= budget.per_recipient_cap,
+        TotError::RecipientBudgetExceeded
+    );
+
+    budget.period_spent = period_spent;
+    let period_start = budget.period_start;
+
+    let usage = &mut ctx.accounts.recipient_usage;
+    usage.recipient = user_owner;
+    usage.period_start = period_start;
+    usage.spent = recipient_spent;
+    usage.bump = ctx.bumps.recipient_usage;
+
     // ========================================
     // 余额验证（在转账前验证，避免无效转账浪费gas）
     // ========================================
@@ -131,7 +207,7 @@ pub fn platform_transfer_handler(
     // 验证平台账户余额是否足够支付转账金额。
     // 平台转账是全额转账，不需要扣除税收。
     require!(
-        ctx.accounts.platform_token_account.amount >= amount,
+        ctx.accounts.platform_vault.amount >= amount,
         TotError::InsufficientBalance
     );
 
@@ -139,21 +215,25 @@ pub fn platform_transfer_handler(
     // 执行转账 - 全额金额给用户
     // ========================================
     // 
-    // 将全额金额转给用户，不收取任何税收。
+    // 将全额金额转给用户，不收取任何税收。由平台预算PDA签名。
     
-    let transfer_ctx = CpiContext::new(
+    let budget_bump = ctx.accounts.platform_budget.bump;
+    let budget_seeds: &[&[&[u8]]] = &[&[seeds::PLATFORM_BUDGET_SEED, &[budget_bump]]];
+    let transfer_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
         TransferChecked {
-            from: ctx.accounts.platform_token_account.to_account_info(),
+            from: ctx.accounts.platform_vault.to_account_info(),
             mint: ctx.accounts.mint.to_account_info(),
             to: ctx.accounts.user_token_account.to_account_info(),
-            authority: ctx.accounts.platform.to_account_info(),
+            authority: ctx.accounts.platform_budget.to_account_info(),
         },
+        budget_seeds,
     );
 
     // 执行转账（使用transfer_checked确保金额和精度正确）
-    token_interface::transfer_checked(
+    transfer_checked_with_hook(
         transfer_ctx,
+        ctx.remaining_accounts,
         amount,  // 全额转账，无税收
         mint_decimals,  // 代币精度
     )?;
@@ -176,6 +256,37 @@ pub fn platform_transfer_handler(
         }
     }
 
+    // 累计全局平台分发统计
+    let config = &mut ctx.accounts.config;
+    config.total_platform_distributed = config.total_platform_distributed
+        .checked_add(amount)
+        .ok_or(TotError::MathOverflow)?;
+
+    // ========================================
+    // 创建支付回执（提供支付引用时）
+    // ========================================
+    // 
+    // 同一引用的重试会在这里失败，整笔转账回滚，不会重复付款。
+    // 付款方记为平台预算PDA（金库所有者），换用其他热钱包重试同样会被拒绝。
+    
+    if let Some(reference) = reference {
+        create_payment_receipt(
+            ctx.program_id,
+            ctx.accounts.payment_receipt.as_ref(),
+            Some(&ctx.accounts.system_program),
+            ctx.accounts.platform.to_account_info(),
+            PaymentReceipt {
+                reference,
+                payer: ctx.accounts.platform_budget.key(),
+                recipient: user_owner,
+                kind: PaymentKind::PlatformTransfer,
+                amount,
+                created_at: timestamp,
+                bump: 0,
+            },
+        )?;
+    }
+
     // ========================================
     // 发出转账事件
     // ========================================
@@ -187,6 +298,7 @@ pub fn platform_transfer_handler(
         platform: platform_key,
         user: user_owner,
         amount,
+        reference,
         timestamp,
     });
 
@@ -208,6 +320,9 @@ pub struct PlatformTransferEvent {
     /// 转账金额（全额转账，无税收）
     pub amount: u64,
     
+    /// 支付引用（未提供时为None）
+    pub reference: Option<[u8; 32]>,
+    
     /// 交易时间戳
     pub timestamp: i64,
 }
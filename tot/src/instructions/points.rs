@@ -0,0 +1,429 @@
+// ============================================
+// 文件: src/instructions/points.rs
+// 积分配置、赚取与兑换指令
+// ============================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::state::config::{TotConfig, PauseFlag};
+use crate::state::holder::HolderAccount;
+use crate::state::catalog::PriceCatalog;
+use crate::state::points::{
+    PointsConfig, PointsConfigParams, PointsAccount, PointsSource, SECONDS_PER_DAY,
+};
+use crate::instructions::consume::ConsumeType;
+use crate::constants::{seeds, TOKEN_DECIMALS};
+use crate::errors::TotError;
+
+/// 初始化积分配置账户结构
+#[derive(Accounts)]
+pub struct InitializePointsConfig<'info> {
+    /// 系统管理员（支付租金）
+    #[account(
+        mut,
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 积分配置PDA
+    #[account(
+        init,
+        payer = authority,
+        space = PointsConfig::LEN,
+        seeds = [seeds::POINTS_CONFIG_SEED],
+        bump
+    )]
+    pub points_config: Account<'info, PointsConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 初始化积分配置处理器
+pub fn initialize_points_config_handler(
+    ctx: Context<InitializePointsConfig>,
+    params: PointsConfigParams,
+) -> Result<()> {
+    params.validate()?;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let points_config = &mut ctx.accounts.points_config;
+    points_config.apply(&params, timestamp);
+    points_config.bump = ctx.bumps.points_config;
+
+    msg!("Points config initialized");
+    Ok(())
+}
+
+/// 更新积分配置账户结构
+#[derive(Accounts)]
+pub struct UpdatePointsConfig<'info> {
+    /// 系统管理员
+    #[account(
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 积分配置PDA
+    #[account(
+        mut,
+        seeds = [seeds::POINTS_CONFIG_SEED],
+        bump = points_config.bump
+    )]
+    pub points_config: Account<'info, PointsConfig>,
+}
+
+/// 更新积分配置处理器
+///
+/// 新比例只影响之后的赚取和兑换，已发放的积分和已生效的折扣不变。
+pub fn update_points_config_handler(
+    ctx: Context<UpdatePointsConfig>,
+    params: PointsConfigParams,
+) -> Result<()> {
+    params.validate()?;
+    let timestamp = Clock::get()?.unix_timestamp;
+    ctx.accounts.points_config.apply(&params, timestamp);
+
+    msg!("Points config updated");
+    Ok(())
+}
+
+/// 初始化积分账户结构
+#[derive(Accounts)]
+pub struct InitializePointsAccount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// 积分持有者钱包
+    /// CHECK: 任何有效的钱包地址
+    pub owner: AccountInfo<'info>,
+
+    /// 积分账户PDA
+    #[account(
+        init,
+        payer = payer,
+        space = PointsAccount::LEN,
+        seeds = [seeds::POINTS_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub points_account: Account<'info, PointsAccount>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 初始化积分账户处理器
+///
+/// 持有积分从创建时开始计算，之前的持有天数不补发。
+pub fn initialize_points_account_handler(ctx: Context<InitializePointsAccount>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let points_account = &mut ctx.accounts.points_account;
+    points_account.owner = ctx.accounts.owner.key();
+    points_account.balance = 0;
+    points_account.total_earned = 0;
+    points_account.total_redeemed = 0;
+    points_account.last_holding_claim_at = timestamp;
+    points_account.tax_discount_bps = 0;
+    points_account.tax_discount_until = 0;
+    points_account.created_at = timestamp;
+    points_account.bump = ctx.bumps.points_account;
+
+    msg!("Points account initialized for: {}", points_account.owner);
+    Ok(())
+}
+
+/// 领取持有积分账户结构
+#[derive(Accounts)]
+pub struct ClaimHoldingPoints<'info> {
+    /// 积分持有者
+    pub owner: Signer<'info>,
+
+    /// 积分配置
+    #[account(
+        seeds = [seeds::POINTS_CONFIG_SEED],
+        bump = points_config.bump
+    )]
+    pub points_config: Account<'info, PointsConfig>,
+
+    /// 积分账户
+    #[account(
+        mut,
+        seeds = [seeds::POINTS_SEED, owner.key().as_ref()],
+        bump = points_account.bump
+    )]
+    pub points_account: Account<'info, PointsAccount>,
+
+    /// 持有者信息（提供持有天数）
+    #[account(
+        seeds = [seeds::HOLDER_SEED, owner.key().as_ref()],
+        bump = holder_info.bump
+    )]
+    pub holder_info: Account<'info, HolderAccount>,
+
+    /// 持有者TOT代币账户（按当前余额计算积分）
+    #[account(
+        constraint = token_account.owner == owner.key() @ TotError::InvalidOwner,
+        constraint = token_account.mint == config.mint @ TotError::InvalidMint
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 全局配置（校验代币Mint）
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+}
+
+/// 领取持有积分处理器
+///
+/// 按上次领取以来经过的整天数发放积分，天数不超过持有者当前的持有天数
+/// （清仓后持有时间重新计算，空仓期间不产生积分）。不足一天的部分留到下次领取。
+/// 每日积分按当前余额缩放：`holding_points_per_day`对应每1个完整代币。
+pub fn claim_holding_points_handler(ctx: Context<ClaimHoldingPoints>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let holder = &ctx.accounts.holder_info;
+    let rate = ctx.accounts.points_config.holding_points_per_day;
+    let balance = ctx.accounts.token_account.amount;
+    require!(!holder.is_frozen, TotError::HolderFrozen);
+    require!(balance > 0, TotError::NoPointsToClaim);
+
+    let points_account = &mut ctx.accounts.points_account;
+    let elapsed_days = timestamp
+        .saturating_sub(points_account.last_holding_claim_at)
+        .max(0) as u64
+        / SECONDS_PER_DAY as u64;
+    let days = elapsed_days.min(holder.get_holding_days(timestamp));
+    require!(days > 0 && rate > 0, TotError::NoPointsToClaim);
+
+    let points = (days as u128)
+        .checked_mul(rate as u128)
+        .and_then(|v| v.checked_mul(balance as u128))
+        .map(|v| v / 10u128.pow(TOKEN_DECIMALS as u32))
+        .ok_or(TotError::MathOverflow)?;
+    let points = u64::try_from(points).map_err(|_| TotError::MathOverflow)?;
+    require!(points > 0, TotError::NoPointsToClaim);
+    points_account.last_holding_claim_at = points_account.last_holding_claim_at
+        .checked_add(days as i64 * SECONDS_PER_DAY)
+        .ok_or(TotError::MathOverflow)?;
+
+    award_points(points_account, points, PointsSource::Holding, timestamp)
+}
+
+/// 兑换积分账户结构
+#[derive(Accounts)]
+pub struct RedeemPoints<'info> {
+    /// 积分持有者
+    pub owner: Signer<'info>,
+
+    /// 全局配置（暂停检查）
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 积分配置
+    #[account(
+        seeds = [seeds::POINTS_CONFIG_SEED],
+        bump = points_config.bump
+    )]
+    pub points_config: Account<'info, PointsConfig>,
+
+    /// 积分账户
+    #[account(
+        mut,
+        seeds = [seeds::POINTS_SEED, owner.key().as_ref()],
+        bump = points_account.bump
+    )]
+    pub points_account: Account<'info, PointsAccount>,
+
+    /// 价格目录（兑换的商品必须已登记）
+    #[account(
+        seeds = [seeds::PRICE_CATALOG_SEED],
+        bump = price_catalog.bump
+    )]
+    pub price_catalog: Account<'info, PriceCatalog>,
+}
+
+/// 兑换积分处理器
+///
+/// 按价格目录中商品的标价扣除积分，后端监听`PointsRedeemed`事件发放商品。
+///
+/// # 参数
+/// * `consume_type` - 商品的消费类型
+/// * `sku` - 商品SKU
+pub fn redeem_points_handler(
+    ctx: Context<RedeemPoints>,
+    consume_type: ConsumeType,
+    sku: u32,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    ctx.accounts.config.require_not_paused(PauseFlag::Consume, timestamp)?;
+
+    let entry = ctx.accounts.price_catalog
+        .find(consume_type as u8, sku)
+        .filter(|entry| entry.is_active && entry.price > 0)
+        .ok_or(TotError::PointsRedemptionUnavailable)?;
+    let price = entry.price;
+    let points = ctx.accounts.points_config.redeem_cost(price)?;
+
+    let points_account = &mut ctx.accounts.points_account;
+    points_account.spend(points)?;
+
+    emit!(PointsRedeemed {
+        owner: points_account.owner,
+        consume_type: consume_type as u8,
+        sku,
+        price,
+        points,
+        balance: points_account.balance,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 兑换税率折扣账户结构
+#[derive(Accounts)]
+pub struct ConvertPointsToTaxDiscount<'info> {
+    /// 积分持有者
+    pub owner: Signer<'info>,
+
+    /// 积分配置
+    #[account(
+        seeds = [seeds::POINTS_CONFIG_SEED],
+        bump = points_config.bump
+    )]
+    pub points_config: Account<'info, PointsConfig>,
+
+    /// 积分账户
+    #[account(
+        mut,
+        seeds = [seeds::POINTS_SEED, owner.key().as_ref()],
+        bump = points_account.bump
+    )]
+    pub points_account: Account<'info, PointsAccount>,
+}
+
+/// 兑换税率折扣处理器
+///
+/// 扣除`discount_cost`积分，在`discount_duration_seconds`内`transfer_with_tax`的
+/// 最终税率减少`discount_bps`（转账时需传入发送者的积分账户）。
+/// 折扣未到期时再次兑换会顺延有效期，折扣值按当前配置更新。
+pub fn convert_points_to_tax_discount_handler(
+    ctx: Context<ConvertPointsToTaxDiscount>,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let points_config = &ctx.accounts.points_config;
+    require!(points_config.discount_cost > 0, TotError::PointsRedemptionUnavailable);
+
+    let points_account = &mut ctx.accounts.points_account;
+    points_account.spend(points_config.discount_cost)?;
+    points_account.tax_discount_bps = points_config.discount_bps;
+    points_account.tax_discount_until = points_account.tax_discount_until
+        .max(timestamp)
+        .checked_add(points_config.discount_duration_seconds)
+        .ok_or(TotError::MathOverflow)?;
+
+    emit!(PointsConvertedToDiscount {
+        owner: points_account.owner,
+        points: points_config.discount_cost,
+        discount_bps: points_account.tax_discount_bps,
+        discount_until: points_account.tax_discount_until,
+        balance: points_account.balance,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 发放积分并发出事件
+///
+/// 消费、持有和拍卖夺取共用；积分为0时不做任何处理。
+pub(crate) fn award_points(
+    points_account: &mut PointsAccount,
+    points: u64,
+    source: PointsSource,
+    timestamp: i64,
+) -> Result<()> {
+    if points == 0 {
+        return Ok(());
+    }
+    points_account.earn(points)?;
+
+    emit!(PointsAwarded {
+        owner: points_account.owner,
+        source,
+        points,
+        balance: points_account.balance,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 积分发放事件
+#[event]
+pub struct PointsAwarded {
+    /// 积分持有者
+    pub owner: Pubkey,
+    /// 积分来源
+    pub source: PointsSource,
+    /// 发放的积分
+    pub points: u64,
+    /// 发放后的余额
+    pub balance: u64,
+    /// 发放时间
+    pub timestamp: i64,
+}
+
+/// 积分兑换商品事件
+#[event]
+pub struct PointsRedeemed {
+    /// 积分持有者
+    pub owner: Pubkey,
+    /// 商品的消费类型
+    pub consume_type: u8,
+    /// 商品SKU
+    pub sku: u32,
+    /// 商品标价（基础单位）
+    pub price: u64,
+    /// 扣除的积分
+    pub points: u64,
+    /// 兑换后的余额
+    pub balance: u64,
+    /// 兑换时间
+    pub timestamp: i64,
+}
+
+/// 积分兑换税率折扣事件
+#[event]
+pub struct PointsConvertedToDiscount {
+    /// 积分持有者
+    pub owner: Pubkey,
+    /// 扣除的积分
+    pub points: u64,
+    /// 税率折扣（basis points）
+    pub discount_bps: u16,
+    /// 折扣到期时间
+    pub discount_until: i64,
+    /// 兑换后的余额
+    pub balance: u64,
+    /// 兑换时间
+    pub timestamp: i64,
+}
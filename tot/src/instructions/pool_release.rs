@@ -0,0 +1,276 @@
+// ============================================
+// 文件: src/instructions/pool_release.rs
+// 池子释放与流通供应量刷新指令
+// ============================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::state::config::{TotConfig, PauseFlag};
+use crate::state::pool::*;
+use crate::constants::seeds;
+use crate::errors::TotError;
+use crate::utils::validation::validate_transfer_amount;
+use crate::utils::token_transfer::transfer_checked_with_hook;
+
+/// 池子释放账户结构
+#[derive(Accounts)]
+#[instruction(pool_type: PoolType)]
+pub struct ReleasePoolTokens<'info> {
+    /// 管理员
+    pub authority: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        has_one = authority @ TotError::InvalidAuthority,
+        has_one = mint @ TotError::InvalidMint,
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 池子状态账户
+    #[account(
+        mut,
+        seeds = [seeds::POOL_SEED, &[pool_type as u8]],
+        bump = pool_account.bump,
+    )]
+    pub pool_account: Account<'info, PoolAccount>,
+
+    /// 池子代币账户
+    #[account(mut, address = pool_account.token_account @ TotError::TokenAccountMismatch)]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 接收释放代币的账户
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == mint.key() @ TotError::InvalidMint
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// TOT Mint
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// 池子释放处理器
+///
+/// 按池子的时间锁和线性释放计划，把已解锁的代币从池子转给指定账户，
+/// 并从锁定供应量中扣除，使流通供应量随释放增加。
+///
+/// # 参数
+/// * `ctx` - 释放上下文
+/// * `pool_type` - 池子类型
+/// * `amount` - 释放数量
+///
+/// # 注意事项
+/// - 需要多签的池子（外资统战池）不能通过本指令释放
+/// - 释放数量不能超过`calculate_releasable`和池子代币账户余额
+pub fn release_pool_tokens_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ReleasePoolTokens<'info>>,
+    pool_type: PoolType,
+    amount: u64,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    ctx.accounts.config.require_not_paused(PauseFlag::PoolReleases, timestamp)?;
+    validate_transfer_amount(amount)?;
+
+    let pool_account = &ctx.accounts.pool_account;
+    require!(!pool_account.requires_multisig, TotError::PoolRequiresMultisig);
+    require!(pool_account.is_unlocked(timestamp), TotError::PoolUnlockTimeNotReached);
+    require!(
+        amount <= pool_account.calculate_releasable(timestamp)?,
+        TotError::InsufficientPoolBalance
+    );
+    require!(
+        amount <= ctx.accounts.pool_token_account.amount,
+        TotError::InsufficientPoolBalance
+    );
+
+    let bump = pool_account.bump;
+    let pool_type_seed = [pool_type as u8];
+    let seeds: &[&[u8]] = &[seeds::POOL_SEED, &pool_type_seed, &[bump]];
+
+    transfer_checked_with_hook(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.pool_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.destination_token_account.to_account_info(),
+                authority: ctx.accounts.pool_account.to_account_info(),
+            },
+            &[seeds],
+        ),
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    let pool_account = &mut ctx.accounts.pool_account;
+    pool_account.released_amount = pool_account.released_amount
+        .checked_add(amount)
+        .ok_or(TotError::MathOverflow)?;
+    let released_amount = pool_account.released_amount;
+
+    let config = &mut ctx.accounts.config;
+    config.locked_supply = config.locked_supply.saturating_sub(amount);
+    config.total_pool_released = config.total_pool_released
+        .checked_add(amount)
+        .ok_or(TotError::MathOverflow)?;
+
+    emit!(PoolReleased {
+        pool_type: pool_type as u8,
+        destination: ctx.accounts.destination_token_account.key(),
+        amount,
+        released_amount,
+        locked_supply: config.locked_supply,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 刷新流通供应量账户结构
+#[derive(Accounts)]
+pub struct RefreshCirculatingSupply<'info> {
+    /// 全局配置
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        has_one = mint @ TotError::InvalidMint,
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// TOT Mint
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // 五大池子账户
+    #[account(
+        seeds = [seeds::POOL_SEED, &[PoolType::VictoryFund as u8]],
+        bump = victory_pool.bump,
+    )]
+    pub victory_pool: Account<'info, PoolAccount>,
+
+    #[account(address = victory_pool.token_account @ TotError::TokenAccountMismatch)]
+    pub victory_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [seeds::POOL_SEED, &[PoolType::HistoryLP as u8]],
+        bump = history_pool.bump,
+    )]
+    pub history_pool: Account<'info, PoolAccount>,
+
+    #[account(address = history_pool.token_account @ TotError::TokenAccountMismatch)]
+    pub history_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [seeds::POOL_SEED, &[PoolType::CyberArmy as u8]],
+        bump = cyber_pool.bump,
+    )]
+    pub cyber_pool: Account<'info, PoolAccount>,
+
+    #[account(address = cyber_pool.token_account @ TotError::TokenAccountMismatch)]
+    pub cyber_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [seeds::POOL_SEED, &[PoolType::GlobalAlliance as u8]],
+        bump = global_pool.bump,
+    )]
+    pub global_pool: Account<'info, PoolAccount>,
+
+    #[account(address = global_pool.token_account @ TotError::TokenAccountMismatch)]
+    pub global_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        seeds = [seeds::POOL_SEED, &[PoolType::AssetAnchor as u8]],
+        bump = asset_pool.bump,
+    )]
+    pub asset_pool: Account<'info, PoolAccount>,
+
+    #[account(address = asset_pool.token_account @ TotError::TokenAccountMismatch)]
+    pub asset_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// TWS财库的关联代币账户（ATA）
+    #[account(
+        address = get_associated_token_address_with_program_id(
+            &config.tws_treasury,
+            &mint.key(),
+            &token_program.key(),
+        ) @ TotError::TokenAccountMismatch
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// 刷新流通供应量处理器
+///
+/// 按五个池子代币账户和TWS财库ATA的实际余额重新计算锁定供应量。
+/// 任何人都可以调用，用于纠正财库转出等链上无法自动跟踪的变化。
+pub fn refresh_circulating_supply_handler(ctx: Context<RefreshCirculatingSupply>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let locked_supply = [
+        ctx.accounts.victory_token_account.amount,
+        ctx.accounts.history_token_account.amount,
+        ctx.accounts.cyber_token_account.amount,
+        ctx.accounts.global_token_account.amount,
+        ctx.accounts.asset_token_account.amount,
+        ctx.accounts.treasury_token_account.amount,
+    ]
+    .iter()
+    .try_fold(0u64, |total, balance| total.checked_add(*balance))
+    .ok_or(TotError::MathOverflow)?;
+
+    let mint_supply = ctx.accounts.mint.supply;
+    let config = &mut ctx.accounts.config;
+    config.locked_supply = locked_supply;
+    config.locked_supply_refreshed_at = timestamp;
+
+    emit!(CirculatingSupplyRefreshed {
+        mint_supply,
+        locked_supply,
+        circulating_supply: config.circulating_supply(mint_supply),
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 池子释放事件
+#[event]
+pub struct PoolReleased {
+    /// 池子类型
+    pub pool_type: u8,
+    /// 接收代币账户
+    pub destination: Pubkey,
+    /// 本次释放数量
+    pub amount: u64,
+    /// 池子累计已释放数量
+    pub released_amount: u64,
+    /// 释放后的锁定供应量
+    pub locked_supply: u64,
+    /// 释放时间
+    pub timestamp: i64,
+}
+
+/// 流通供应量刷新事件
+#[event]
+pub struct CirculatingSupplyRefreshed {
+    /// Mint总供应量
+    pub mint_supply: u64,
+    /// 锁定供应量（池子和财库余额之和）
+    pub locked_supply: u64,
+    /// 流通供应量
+    pub circulating_supply: u64,
+    /// 刷新时间
+    pub timestamp: i64,
+}
@@ -7,7 +7,9 @@ use anchor_lang::prelude::*;
 use crate::state::config::TotConfig;
 use crate::state::tax::TaxConfig;
 use crate::state::holder::HolderAccount;
-use crate::constants::seeds;
+use crate::state::platform_budget::{PlatformBudget, PlatformRecipientUsage};
+use crate::state::points::{PointsConfig, PointsAccount};
+use crate::constants::{seeds, limits, TRANSFER_HOOK_PROGRAM_ID};
 use crate::utils::tax_calculator::*;
 
 /// 税率折扣等级枚举
@@ -63,10 +65,11 @@ pub fn calculate_tax_handler(
     let calculation = TaxCalculator::calculate_tax(
         amount,
         holder_ref,
-        ctx.accounts.mint.supply,
+        ctx.accounts.config.circulating_supply(ctx.accounts.mint.supply),
         clock.unix_timestamp,
         is_buy,
         is_sell,
+        ctx.accounts.config.panic_mode,
         &ctx.accounts.tax_config,
     )?;
 
@@ -142,6 +145,217 @@ pub fn get_holder_stats_handler(
     })
 }
 
+/// 获取协议统计
+#[derive(Accounts)]
+pub struct GetProtocolStats<'info> {
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        has_one = mint,
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// TOT Mint（用于获取当前总供应量）
+    pub mint: InterfaceAccount<'info, anchor_spl::token_interface::Mint>,
+
+    /// Hook程序的TransferHookConfig（可选，提供时返回Transfer Fee预扣统计）
+    /// CHECK: 地址由Hook程序的seeds约束，所有者必须是Hook程序，按固定偏移读取
+    #[account(
+        seeds = [seeds::HOOK_CONFIG_SEED],
+        bump,
+        seeds::program = TRANSFER_HOOK_PROGRAM_ID,
+        owner = TRANSFER_HOOK_PROGRAM_ID
+    )]
+    pub hook_config: Option<UncheckedAccount<'info>>,
+}
+
+/// Hook程序`TransferHookConfig.total_tax_collected`在账户数据中的偏移
+///
+/// discriminator(8) + authority(32) + tot_mint(32) + tot_config(32) + total_transfers(8)。
+/// 主程序不能依赖Hook程序crate（Hook程序依赖主程序的账户类型），因此按偏移读取。
+const HOOK_CONFIG_TAX_COLLECTED_OFFSET: usize = 112;
+
+/// 读取Hook程序记录的Transfer Fee预扣总额
+fn read_hook_tax_collected(hook_config: &AccountInfo) -> Result<u64> {
+    let data = hook_config.try_borrow_data()?;
+    let bytes: [u8; 8] = data
+        .get(HOOK_CONFIG_TAX_COLLECTED_OFFSET..HOOK_CONFIG_TAX_COLLECTED_OFFSET + 8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(ErrorCode::AccountDidNotDeserialize)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// 协议统计结果
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ProtocolStats {
+    /// Mint当前总供应量（已扣除销毁）
+    pub mint_supply: u64,
+    /// 流通供应量（总供应量 - 池子和财库锁定部分）
+    pub circulating_supply: u64,
+    /// 锁定供应量
+    pub locked_supply: u64,
+    /// 累计铸造量
+    pub total_minted: u64,
+    /// 累计收税总额（含销毁部分）
+    pub total_tax_collected: u64,
+    /// 累计销毁量（税收销毁和追回销毁）
+    pub total_burned: u64,
+    /// 按消费类型累计的消费数量（下标为`ConsumeType as u8`）
+    pub total_consumed: [u64; 8],
+    /// 累计平台分发数量
+    pub total_platform_distributed: u64,
+    /// 累计拍卖手续费
+    pub total_auction_fees: u64,
+    /// 累计池子释放数量
+    pub total_pool_released: u64,
+    /// 累计追回数量
+    pub total_clawed_back: u64,
+    /// Transfer Hook记录的Transfer Fee预扣总额（未提供hook_config时为0）
+    pub transfer_fee_withheld: u64,
+}
+
+/// 获取协议统计处理器
+pub fn get_protocol_stats_handler(ctx: Context<GetProtocolStats>) -> Result<ProtocolStats> {
+    let config = &ctx.accounts.config;
+    let mint_supply = ctx.accounts.mint.supply;
+
+    Ok(ProtocolStats {
+        mint_supply,
+        circulating_supply: config.circulating_supply(mint_supply),
+        locked_supply: config.locked_supply,
+        total_minted: config.total_minted,
+        total_tax_collected: config.total_tax_collected,
+        total_burned: config.total_burned,
+        total_consumed: config.total_consumed,
+        total_platform_distributed: config.total_platform_distributed,
+        total_auction_fees: config.total_auction_fees,
+        total_pool_released: config.total_pool_released,
+        total_clawed_back: config.total_clawed_back,
+        transfer_fee_withheld: match ctx.accounts.hook_config.as_ref() {
+            Some(hook_config) => read_hook_tax_collected(hook_config)?,
+            None => 0,
+        },
+    })
+}
+
+/// 获取平台预算剩余额度
+#[derive(Accounts)]
+pub struct GetPlatformBudget<'info> {
+    /// 平台预算
+    #[account(
+        seeds = [seeds::PLATFORM_BUDGET_SEED],
+        bump = platform_budget.bump
+    )]
+    pub platform_budget: Account<'info, PlatformBudget>,
+
+    /// 接收者额度（可选，提供时返回该接收者的剩余额度）
+    #[account(
+        seeds = [seeds::PLATFORM_RECIPIENT_SEED, recipient_usage.recipient.as_ref()],
+        bump = recipient_usage.bump
+    )]
+    pub recipient_usage: Option<Account<'info, PlatformRecipientUsage>>,
+}
+
+/// 平台预算状态
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PlatformBudgetStatus {
+    /// 当前周期开始时间
+    pub period_start: i64,
+    /// 当前周期结束（额度重置）时间
+    pub period_resets_at: i64,
+    /// 每个周期的总预算
+    pub period_cap: u64,
+    /// 当前周期已用额度
+    pub period_spent: u64,
+    /// 当前周期剩余预算
+    pub period_remaining: u64,
+    /// 每个周期向单个接收者转账的额度
+    pub per_recipient_cap: u64,
+    /// 接收者本周期剩余额度（不超过周期剩余预算；未提供接收者额度账户时按未使用计算）
+    pub recipient_remaining: u64,
+}
+
+/// 获取平台预算剩余额度处理器
+pub fn get_platform_budget_handler(ctx: Context<GetPlatformBudget>) -> Result<PlatformBudgetStatus> {
+    let budget = &ctx.accounts.platform_budget;
+    let now = Clock::get()?.unix_timestamp;
+
+    let period_start = budget.current_period_start(now);
+    let period_remaining = budget.period_remaining(now);
+    let recipient_spent = ctx.accounts.recipient_usage
+        .as_ref()
+        .map_or(0, |usage| usage.spent_in(period_start));
+
+    Ok(PlatformBudgetStatus {
+        period_start,
+        period_resets_at: period_start.saturating_add(budget.period_seconds),
+        period_cap: budget.period_cap,
+        period_spent: budget.period_cap.saturating_sub(period_remaining),
+        period_remaining,
+        per_recipient_cap: budget.per_recipient_cap,
+        recipient_remaining: budget.per_recipient_cap
+            .saturating_sub(recipient_spent)
+            .min(period_remaining),
+    })
+}
+
+/// 获取积分排行榜
+///
+/// 积分账户通过`remaining_accounts`传入（由客户端用`getProgramAccounts`收集）。
+#[derive(Accounts)]
+pub struct GetPointsLeaderboard<'info> {
+    /// 积分配置
+    #[account(
+        seeds = [seeds::POINTS_CONFIG_SEED],
+        bump = points_config.bump
+    )]
+    pub points_config: Account<'info, PointsConfig>,
+}
+
+/// 积分排行榜条目
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PointsLeaderboardEntry {
+    /// 积分持有者
+    pub owner: Pubkey,
+    /// 积分余额
+    pub balance: u64,
+    /// 累计获得的积分
+    pub total_earned: u64,
+}
+
+/// 获取积分排行榜处理器
+///
+/// 按累计获得的积分降序排列（兑换不影响排名），相同时按余额降序。
+/// 重复传入的账户只计一次，最多返回`MAX_POINTS_LEADERBOARD`条。
+pub fn get_points_leaderboard_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GetPointsLeaderboard<'info>>,
+    limit: u8,
+) -> Result<Vec<PointsLeaderboardEntry>> {
+    let mut entries: Vec<PointsLeaderboardEntry> = Vec::with_capacity(ctx.remaining_accounts.len());
+    for info in ctx.remaining_accounts.iter() {
+        // 校验账户属于本程序且类型为积分账户
+        let points = Account::<PointsAccount>::try_from(info)?;
+        if entries.iter().any(|entry| entry.owner == points.owner) {
+            continue;
+        }
+        entries.push(PointsLeaderboardEntry {
+            owner: points.owner,
+            balance: points.balance,
+            total_earned: points.total_earned,
+        });
+    }
+
+    entries.sort_by(|a, b| {
+        b.total_earned
+            .cmp(&a.total_earned)
+            .then(b.balance.cmp(&a.balance))
+    });
+    entries.truncate((limit as usize).min(limits::MAX_POINTS_LEADERBOARD));
+
+    Ok(entries)
+}
+
 /// 税率计算结果（用于返回给客户端）
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
 pub struct TaxCalculationResult {
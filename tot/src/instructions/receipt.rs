@@ -0,0 +1,215 @@
+// ============================================
+// 文件: src/instructions/receipt.rs
+// 回执创建（带支付引用的转账共用，以及消费回执）
+// ============================================
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+
+use crate::state::receipt::{PaymentReceipt, ConsumeReceipt};
+use crate::constants::seeds;
+use crate::errors::TotError;
+
+/// 创建支付回执PDA
+///
+/// 由`transfer_with_tax`、`transfer_exact_with_tax`、`consume_to_treasury`和
+/// `platform_transfer`在调用方提供支付引用时调用。
+///
+/// # 参数
+/// * `program_id` - 本程序ID
+/// * `payment_receipt` - 回执PDA `["tot_payment_receipt", receipt.payer, reference]`
+/// * `system_program` - System Program
+/// * `payer` - 支付租金的签名者
+/// * `receipt` - 回执内容，`bump`由本函数填写；`payer`为转出代币账户的所有者
+///   （带税转账和消费为签名者本人，平台转账为平台预算PDA）
+///
+/// # 返回值
+/// * `Result<()>` - 成功返回Ok(())
+///
+/// # 错误
+/// * `TotError::InvalidPaymentReceipt` - 未传入回执账户/System Program，或地址不是付款方和引用派生的PDA
+/// * `TotError::DuplicatePaymentReference` - 该付款方使用该引用的回执已存在（重放请求）
+///
+/// # 注意事项
+/// - 回执地址包含付款方，其他人提前使用同一引用不会占用该付款方的回执
+/// - 与Anchor的`init`相同，回执地址已被预先转入lamports时改用转账+allocate+assign创建，
+///   防止他人通过向回执地址转账阻止该引用被使用
+pub(crate) fn create_payment_receipt<'info>(
+    program_id: &Pubkey,
+    payment_receipt: Option<&UncheckedAccount<'info>>,
+    system_program: Option<&Program<'info, System>>,
+    payer: AccountInfo<'info>,
+    mut receipt: PaymentReceipt,
+) -> Result<()> {
+    let receipt_info = payment_receipt
+        .ok_or(error!(TotError::InvalidPaymentReceipt))?
+        .to_account_info();
+    let system_program = system_program
+        .ok_or(error!(TotError::InvalidPaymentReceipt))?
+        .to_account_info();
+
+    let payer_key = receipt.payer;
+    let (expected, bump) = Pubkey::find_program_address(
+        &[seeds::PAYMENT_RECEIPT_SEED, payer_key.as_ref(), receipt.reference.as_ref()],
+        program_id,
+    );
+    require_keys_eq!(receipt_info.key(), expected, TotError::InvalidPaymentReceipt);
+
+    // 回执已存在说明同一引用已经付过款
+    require!(
+        receipt_info.data_is_empty() && *receipt_info.owner == system_program::ID,
+        TotError::DuplicatePaymentReference
+    );
+
+    let bump_seed = [bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        seeds::PAYMENT_RECEIPT_SEED,
+        payer_key.as_ref(),
+        receipt.reference.as_ref(),
+        &bump_seed,
+    ]];
+    create_receipt_account(
+        program_id,
+        &receipt_info,
+        system_program,
+        payer,
+        signer_seeds,
+        PaymentReceipt::LEN,
+    )?;
+
+    receipt.bump = bump;
+    let mut data = receipt_info.try_borrow_mut_data()?;
+    receipt.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+/// 创建消费回执PDA
+///
+/// 由`consume_to_treasury`在调用方提供回执参数时调用。
+///
+/// # 参数
+/// * `program_id` - 本程序ID
+/// * `consume_receipt` - 回执PDA `["tot_consume_receipt", user, nonce]`
+/// * `system_program` - System Program
+/// * `payer` - 支付租金的签名者（即消费用户）
+/// * `receipt` - 回执内容，`bump`由本函数填写
+///
+/// # 错误
+/// * `TotError::InvalidConsumeReceipt` - 未传入回执账户/System Program，或地址不是派生的PDA
+/// * `TotError::DuplicateConsumeNonce` - 该用户的随机数已经使用过
+pub(crate) fn create_consume_receipt<'info>(
+    program_id: &Pubkey,
+    consume_receipt: Option<&UncheckedAccount<'info>>,
+    system_program: Option<&Program<'info, System>>,
+    payer: AccountInfo<'info>,
+    mut receipt: ConsumeReceipt,
+) -> Result<()> {
+    let receipt_info = consume_receipt
+        .ok_or(error!(TotError::InvalidConsumeReceipt))?
+        .to_account_info();
+    let system_program = system_program
+        .ok_or(error!(TotError::InvalidConsumeReceipt))?
+        .to_account_info();
+
+    let nonce_bytes = receipt.nonce.to_le_bytes();
+    let (expected, bump) = Pubkey::find_program_address(
+        &[seeds::CONSUME_RECEIPT_SEED, receipt.user.as_ref(), nonce_bytes.as_ref()],
+        program_id,
+    );
+    require_keys_eq!(receipt_info.key(), expected, TotError::InvalidConsumeReceipt);
+
+    require!(
+        receipt_info.data_is_empty() && *receipt_info.owner == system_program::ID,
+        TotError::DuplicateConsumeNonce
+    );
+
+    let bump_seed = [bump];
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        seeds::CONSUME_RECEIPT_SEED,
+        receipt.user.as_ref(),
+        nonce_bytes.as_ref(),
+        &bump_seed,
+    ]];
+    create_receipt_account(
+        program_id,
+        &receipt_info,
+        system_program,
+        payer,
+        signer_seeds,
+        ConsumeReceipt::LEN,
+    )?;
+
+    receipt.bump = bump;
+    let mut data = receipt_info.try_borrow_mut_data()?;
+    receipt.try_serialize(&mut &mut data[..])?;
+
+    Ok(())
+}
+
+/// 创建由本程序拥有的回执账户
+///
+/// 与Anchor的`init`相同，地址已被预先转入lamports时改用转账+allocate+assign创建。
+fn create_receipt_account<'info>(
+    program_id: &Pubkey,
+    receipt_info: &AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    payer: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    len: usize,
+) -> Result<()> {
+    let space = len as u64;
+    let rent = Rent::get()?.minimum_balance(len);
+    let current_lamports = receipt_info.lamports();
+
+    if current_lamports == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer,
+                    to: receipt_info.clone(),
+                },
+                signer_seeds,
+            ),
+            rent,
+            space,
+            program_id,
+        )?;
+    } else {
+        if rent > current_lamports {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: payer,
+                        to: receipt_info.clone(),
+                    },
+                ),
+                rent - current_lamports,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Allocate {
+                    account_to_allocate: receipt_info.clone(),
+                },
+                signer_seeds,
+            ),
+            space,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program,
+                Assign {
+                    account_to_assign: receipt_info.clone(),
+                },
+                signer_seeds,
+            ),
+            program_id,
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,562 @@
+// ============================================
+// 文件: src/instructions/stream.rs
+// 代币流指令 - 创建、提取、取消
+// ============================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::state::config::{TotConfig, PauseFlag};
+use crate::state::tax::TaxConfig;
+use crate::state::holder::HolderAccount;
+use crate::state::stream::StreamAccount;
+use crate::constants::seeds;
+use crate::errors::TotError;
+use crate::utils::tax_calculator::*;
+use crate::utils::validation::validate_transfer_amount;
+use crate::utils::token_transfer::transfer_checked_with_hook;
+
+/// 创建代币流账户结构
+#[derive(Accounts)]
+#[instruction(stream_id: u64)]
+pub struct CreateStream<'info> {
+    /// 发送者（签名者，支付租金）
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// 发送者代币账户
+    #[account(
+        mut,
+        constraint = sender_token_account.owner == sender.key() @ TotError::InvalidOwner,
+        constraint = sender_token_account.mint == mint.key() @ TotError::InvalidMint
+    )]
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 发送者持有者信息（冻结的持有者不能创建流）
+    #[account(
+        seeds = [seeds::HOLDER_SEED, sender.key().as_ref()],
+        bump = sender_holder_info.bump
+    )]
+    pub sender_holder_info: Account<'info, HolderAccount>,
+
+    /// 代币流账户
+    #[account(
+        init,
+        payer = sender,
+        space = StreamAccount::LEN,
+        seeds = [seeds::STREAM_SEED, sender.key().as_ref(), stream_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub stream: Account<'info, StreamAccount>,
+
+    /// 托管代币账户（代币流PDA的ATA）
+    #[account(
+        init,
+        payer = sender,
+        associated_token::mint = mint,
+        associated_token::authority = stream,
+        associated_token::token_program = token_program,
+    )]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// TOT Mint
+    #[account(address = config.mint @ TotError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 创建代币流处理器
+///
+/// 把`amount`从发送者转入托管账户，在`[start_time, end_time]`内线性释放给接收者。
+///
+/// # 参数
+/// * `ctx` - 创建上下文
+/// * `stream_id` - 流编号（同一发送者下唯一）
+/// * `recipient` - 接收者钱包地址
+/// * `amount` - 托管数量
+/// * `start_time` - 开始释放时间（可以早于当前时间）
+/// * `end_time` - 释放结束时间
+///
+/// # 注意事项
+/// - 转入托管账户不收税，税收在接收者提取时按`transfer_with_tax`的规则收取
+/// - 释放总量按托管账户实际收到的数量记录（扣除Transfer Fee预扣后）
+pub fn create_stream_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, CreateStream<'info>>,
+    stream_id: u64,
+    recipient: Pubkey,
+    amount: u64,
+    start_time: i64,
+    end_time: i64,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    ctx.accounts.config.require_not_paused(PauseFlag::Transfers, timestamp)?;
+    validate_transfer_amount(amount)?;
+    require!(
+        start_time < end_time && end_time > timestamp,
+        TotError::InvalidStreamSchedule
+    );
+    require!(recipient != Pubkey::default(), TotError::InvalidTransferDestination);
+    require!(!ctx.accounts.sender_holder_info.is_frozen, TotError::HolderFrozen);
+    require!(
+        ctx.accounts.sender_token_account.amount >= amount,
+        TotError::InsufficientBalance
+    );
+
+    transfer_checked_with_hook(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.sender_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.escrow_token_account.to_account_info(),
+                authority: ctx.accounts.sender.to_account_info(),
+            },
+        ),
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    // 以托管账户实际收到的数量作为释放总量
+    ctx.accounts.escrow_token_account.reload()?;
+    let total_amount = ctx.accounts.escrow_token_account.amount;
+
+    let stream = &mut ctx.accounts.stream;
+    stream.stream_id = stream_id;
+    stream.sender = ctx.accounts.sender.key();
+    stream.recipient = recipient;
+    stream.escrow_token_account = ctx.accounts.escrow_token_account.key();
+    stream.total_amount = total_amount;
+    stream.withdrawn_amount = 0;
+    stream.start_time = start_time;
+    stream.end_time = end_time;
+    stream.is_cancelled = false;
+    stream.created_at = timestamp;
+    stream.bump = ctx.bumps.stream;
+
+    emit!(StreamCreated {
+        stream: stream.key(),
+        stream_id,
+        sender: stream.sender,
+        recipient,
+        total_amount,
+        start_time,
+        end_time,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 提取代币流账户结构
+#[derive(Accounts)]
+pub struct WithdrawStream<'info> {
+    /// 接收者（签名者）
+    pub recipient: Signer<'info>,
+
+    /// 接收者代币账户
+    #[account(
+        mut,
+        constraint = recipient_token_account.owner == recipient.key() @ TotError::InvalidOwner,
+        constraint = recipient_token_account.mint == mint.key() @ TotError::InvalidMint
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 代币流账户
+    #[account(
+        mut,
+        seeds = [seeds::STREAM_SEED, stream.sender.as_ref(), stream.stream_id.to_le_bytes().as_ref()],
+        bump = stream.bump,
+        has_one = recipient @ TotError::Unauthorized,
+        has_one = escrow_token_account @ TotError::TokenAccountMismatch
+    )]
+    pub stream: Account<'info, StreamAccount>,
+
+    /// 托管代币账户
+    #[account(mut)]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// TOT Mint
+    #[account(
+        mut,
+        address = config.mint @ TotError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// 全局配置（累计协议统计）
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 税率配置
+    #[account(
+        seeds = [seeds::TAX_CONFIG_SEED],
+        bump
+    )]
+    pub tax_config: Account<'info, TaxConfig>,
+
+    /// 发送者持有者信息（用于冻结检查和持有时间折扣）
+    #[account(
+        seeds = [seeds::HOLDER_SEED, stream.sender.as_ref()],
+        bump = sender_holder_info.bump
+    )]
+    pub sender_holder_info: Account<'info, HolderAccount>,
+
+    /// 接收者持有者信息（可选，存在时更新买入统计）
+    #[account(
+        mut,
+        seeds = [seeds::HOLDER_SEED, recipient.key().as_ref()],
+        bump = recipient_holder_info.bump
+    )]
+    pub recipient_holder_info: Option<Account<'info, HolderAccount>>,
+
+    /// 税收收集账户（流动性池）
+    #[account(
+        mut,
+        constraint = tax_collector.mint == mint.key() @ TotError::InvalidMint
+    )]
+    pub tax_collector: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// 提取代币流处理器
+///
+/// 接收者提取截至当前已释放、尚未提取的全部代币。
+///
+/// # 税收规则
+///
+/// 提取视为发送者向接收者的一次普通转账（非卖出），与`transfer_with_tax`一致：
+/// - 发送者或接收者免税时全额到账，跳过冻结检查
+/// - 否则发送者和接收者都不能处于冻结状态
+/// - 税率按发送者的持有时间计算，税收40%销毁、其余转入税收收集账户
+/// - 接收者持有者账户存在时记录买入（`record_buy`）
+///
+/// 因此把转账改为代币流不能绕开税收。
+pub fn withdraw_stream_handler<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawStream<'info>>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let config = &ctx.accounts.config;
+    let tax_config = &ctx.accounts.tax_config;
+    let stream = &ctx.accounts.stream;
+
+    config.require_not_paused(PauseFlag::Transfers, timestamp)?;
+
+    let amount = stream.withdrawable_amount(timestamp)?;
+    require!(amount > 0, TotError::StreamNothingToWithdraw);
+
+    let sender_key = stream.sender;
+    let recipient_key = stream.recipient;
+    let is_exempt = tax_config.is_exempt(&sender_key) || tax_config.is_exempt(&recipient_key);
+
+    let (tax_amount, net_amount, tax_rate_bps) = if is_exempt {
+        (0, amount, 0)
+    } else {
+        let sender_holder = &ctx.accounts.sender_holder_info;
+        require!(!sender_holder.is_frozen, TotError::HolderFrozen);
+        if let Some(ref recipient_holder) = ctx.accounts.recipient_holder_info {
+            require!(!recipient_holder.is_frozen, TotError::HolderFrozen);
+        }
+
+        let calculation = TaxCalculator::calculate_tax(
+            amount,
+            Some(sender_holder),
+            config.circulating_supply(ctx.accounts.mint.supply),
+            timestamp,
+            false, // 代币流提取不是买入
+            false, // 代币流提取不是卖出
+            config.panic_mode,
+            tax_config,
+        )?;
+        (calculation.tax_amount, calculation.net_amount, calculation.final_tax_bps)
+    };
+
+    let stream_id_bytes = stream.stream_id.to_le_bytes();
+    let stream_seeds: &[&[u8]] = &[
+        seeds::STREAM_SEED,
+        sender_key.as_ref(),
+        stream_id_bytes.as_ref(),
+        &[stream.bump],
+    ];
+    let signer_seeds = &[stream_seeds];
+    let mint_decimals = ctx.accounts.mint.decimals;
+
+    if net_amount > 0 {
+        transfer_checked_with_hook(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.escrow_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.recipient_token_account.to_account_info(),
+                    authority: ctx.accounts.stream.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            ctx.remaining_accounts,
+            net_amount,
+            mint_decimals,
+        )?;
+    }
+
+    let burned = if tax_amount > 0 {
+        let tax_dist = TaxDistribution::calculate(tax_amount)?;
+
+        if tax_dist.to_burn > 0 {
+            token_interface::burn(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Burn {
+                        mint: ctx.accounts.mint.to_account_info(),
+                        from: ctx.accounts.escrow_token_account.to_account_info(),
+                        authority: ctx.accounts.stream.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                tax_dist.to_burn,
+            )?;
+        }
+
+        let remaining_tax = tax_amount
+            .checked_sub(tax_dist.to_burn)
+            .ok_or(TotError::MathUnderflow)?;
+        if remaining_tax > 0 {
+            transfer_checked_with_hook(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: ctx.accounts.escrow_token_account.to_account_info(),
+                        mint: ctx.accounts.mint.to_account_info(),
+                        to: ctx.accounts.tax_collector.to_account_info(),
+                        authority: ctx.accounts.stream.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                ctx.remaining_accounts,
+                remaining_tax,
+                mint_decimals,
+            )?;
+        }
+
+        tax_dist.to_burn
+    } else {
+        0
+    };
+
+    // 累计全局收税和销毁统计
+    if tax_amount > 0 {
+        ctx.accounts.config.record_tax(tax_amount, burned)?;
+    }
+
+    // 更新接收者统计（接收代币相当于买入操作）
+    if let Some(ref mut recipient_holder) = ctx.accounts.recipient_holder_info {
+        recipient_holder.record_buy(net_amount, 0, timestamp)?;
+        if recipient_holder.token_account == Pubkey::default() {
+            recipient_holder.token_account = ctx.accounts.recipient_token_account.key();
+        }
+    }
+
+    let stream = &mut ctx.accounts.stream;
+    stream.withdrawn_amount = stream.withdrawn_amount
+        .checked_add(amount)
+        .ok_or(TotError::MathOverflow)?;
+
+    emit!(StreamWithdrawn {
+        stream: stream.key(),
+        recipient: recipient_key,
+        amount,
+        tax_amount,
+        net_amount,
+        tax_rate_bps,
+        burned,
+        withdrawn_amount: stream.withdrawn_amount,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 取消代币流账户结构
+#[derive(Accounts)]
+pub struct CancelStream<'info> {
+    /// 发送者（签名者）
+    pub sender: Signer<'info>,
+
+    /// 发送者代币账户（接收退回的代币）
+    #[account(
+        mut,
+        constraint = sender_token_account.owner == sender.key() @ TotError::InvalidOwner,
+        constraint = sender_token_account.mint == mint.key() @ TotError::InvalidMint
+    )]
+    pub sender_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 代币流账户
+    #[account(
+        mut,
+        seeds = [seeds::STREAM_SEED, sender.key().as_ref(), stream.stream_id.to_le_bytes().as_ref()],
+        bump = stream.bump,
+        has_one = sender @ TotError::Unauthorized,
+        has_one = escrow_token_account @ TotError::TokenAccountMismatch
+    )]
+    pub stream: Account<'info, StreamAccount>,
+
+    /// 托管代币账户
+    #[account(mut)]
+    pub escrow_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// TOT Mint
+    #[account(address = config.mint @ TotError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// 取消代币流处理器
+///
+/// 发送者取回尚未释放的部分。已释放但未提取的部分留在托管账户，
+/// 接收者之后仍可通过`withdraw_stream`提取。
+///
+/// # 注意事项
+/// - 退回的是发送者自己的代币，不收税
+/// - 取消后`total_amount`截断为已释放数量，`end_time`截断为取消时刻（不早于`start_time`）
+pub fn cancel_stream_handler<'info>(ctx: Context<'_, '_, '_, 'info, CancelStream<'info>>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let stream = &ctx.accounts.stream;
+
+    ctx.accounts.config.require_not_paused(PauseFlag::Transfers, timestamp)?;
+    require!(!stream.is_cancelled, TotError::StreamAlreadyCancelled);
+
+    let vested = stream.vested_amount(timestamp)?;
+    let refund = stream.total_amount
+        .checked_sub(vested)
+        .ok_or(TotError::MathUnderflow)?;
+
+    if refund > 0 {
+        let sender_key = stream.sender;
+        let stream_id_bytes = stream.stream_id.to_le_bytes();
+        let stream_seeds: &[&[u8]] = &[
+            seeds::STREAM_SEED,
+            sender_key.as_ref(),
+            stream_id_bytes.as_ref(),
+            &[stream.bump],
+        ];
+
+        transfer_checked_with_hook(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.escrow_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.sender_token_account.to_account_info(),
+                    authority: ctx.accounts.stream.to_account_info(),
+                },
+                &[stream_seeds],
+            ),
+            ctx.remaining_accounts,
+            refund,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    let stream = &mut ctx.accounts.stream;
+    stream.total_amount = vested;
+    stream.end_time = std::cmp::max(timestamp, stream.start_time);
+    stream.is_cancelled = true;
+
+    emit!(StreamCancelled {
+        stream: stream.key(),
+        sender: stream.sender,
+        recipient: stream.recipient,
+        vested_amount: vested,
+        refunded_amount: refund,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 代币流创建事件
+#[event]
+pub struct StreamCreated {
+    /// 代币流账户
+    pub stream: Pubkey,
+    /// 流编号
+    pub stream_id: u64,
+    /// 发送者
+    pub sender: Pubkey,
+    /// 接收者
+    pub recipient: Pubkey,
+    /// 释放总量
+    pub total_amount: u64,
+    /// 开始时间
+    pub start_time: i64,
+    /// 结束时间
+    pub end_time: i64,
+    /// 创建时间
+    pub timestamp: i64,
+}
+
+/// 代币流提取事件
+#[event]
+pub struct StreamWithdrawn {
+    /// 代币流账户
+    pub stream: Pubkey,
+    /// 接收者
+    pub recipient: Pubkey,
+    /// 本次提取数量（含税）
+    pub amount: u64,
+    /// 税额
+    pub tax_amount: u64,
+    /// 接收者实际收到的数量
+    pub net_amount: u64,
+    /// 最终税率（basis points）
+    pub tax_rate_bps: u16,
+    /// 销毁的代币数量
+    pub burned: u64,
+    /// 累计已提取数量
+    pub withdrawn_amount: u64,
+    /// 提取时间
+    pub timestamp: i64,
+}
+
+/// 代币流取消事件
+#[event]
+pub struct StreamCancelled {
+    /// 代币流账户
+    pub stream: Pubkey,
+    /// 发送者
+    pub sender: Pubkey,
+    /// 接收者
+    pub recipient: Pubkey,
+    /// 取消时已释放的数量（接收者仍可提取未提取的部分）
+    pub vested_amount: u64,
+    /// 退回发送者的数量
+    pub refunded_amount: u64,
+    /// 取消时间
+    pub timestamp: i64,
+}
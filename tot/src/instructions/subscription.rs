@@ -0,0 +1,619 @@
+// ============================================
+// 文件: src/instructions/subscription.rs
+// 订阅指令 - 创建、扣款、取消
+// ============================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, Approve, Burn, Mint, Revoke, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::state::config::{TotConfig, PauseFlag};
+use crate::state::holder::HolderAccount;
+use crate::state::catalog::PriceCatalog;
+use crate::state::subscription::{Subscription, SubscriptionStatus};
+use crate::instructions::consume::{ConsumeType, resolve_revenue_distribution};
+use crate::constants::seeds;
+use crate::errors::TotError;
+use crate::utils::token_transfer::transfer_checked_with_hook;
+
+/// 创建订阅账户结构
+#[derive(Accounts)]
+#[instruction(subscription_id: u64)]
+pub struct CreateSubscription<'info> {
+    /// 用户（签名者，支付租金）
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// 用户代币账户（授权给订阅代理PDA）
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user.key() @ TotError::InvalidOwner,
+        constraint = user_token_account.mint == mint.key() @ TotError::InvalidMint
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 用户持有者信息（冻结的持有者不能订阅）
+    #[account(
+        seeds = [seeds::HOLDER_SEED, user.key().as_ref()],
+        bump = user_holder_info.bump
+    )]
+    pub user_holder_info: Account<'info, HolderAccount>,
+
+    /// 订阅账户
+    #[account(
+        init,
+        payer = user,
+        space = Subscription::LEN,
+        seeds = [seeds::SUBSCRIPTION_SEED, user.key().as_ref(), subscription_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    /// 订阅代理PDA（扣款授权对象）
+    /// CHECK: 仅作为Approve的delegate地址，通过种子约束校验
+    #[account(
+        seeds = [seeds::SUBSCRIPTION_DELEGATE_SEED],
+        bump
+    )]
+    pub subscription_delegate: UncheckedAccount<'info>,
+
+    /// 价格目录（校验每期金额）
+    #[account(
+        seeds = [seeds::PRICE_CATALOG_SEED],
+        bump = price_catalog.bump
+    )]
+    pub price_catalog: Account<'info, PriceCatalog>,
+
+    /// TOT Mint
+    #[account(address = config.mint @ TotError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 创建订阅处理器
+///
+/// 记录订阅计划，并把`amount × max_periods`的扣款额度授权给订阅代理PDA。
+/// 第一期在创建后即可扣款。
+///
+/// # 参数
+/// * `ctx` - 创建上下文
+/// * `subscription_id` - 订阅编号（同一用户下唯一）
+/// * `consume_type` - 消费类型
+/// * `sku` - 商品SKU（0表示该消费类型的默认价格）
+/// * `amount` - 每期金额
+/// * `period_seconds` - 周期长度（秒）
+/// * `max_periods` - 最大期数
+///
+/// # 注意事项
+/// - 代币账户只能有一个delegate，授权给订阅代理PDA会覆盖之前授权给其他地址的额度
+/// - 已授权给订阅代理PDA时（用户有多个订阅），新额度在原额度上累加
+/// - 价格目录为该商品登记了价格时，每期金额必须等于目录价格
+pub fn create_subscription_handler(
+    ctx: Context<CreateSubscription>,
+    subscription_id: u64,
+    consume_type: ConsumeType,
+    sku: u32,
+    amount: u64,
+    period_seconds: i64,
+    max_periods: u32,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    // ========================================
+    // 验证阶段
+    // ========================================
+
+    ctx.accounts.config.require_not_paused(PauseFlag::Consume, timestamp)?;
+    require!(
+        amount > 0 && period_seconds > 0 && max_periods > 0,
+        TotError::InvalidSubscriptionPlan
+    );
+    let total_allowance = amount
+        .checked_mul(max_periods as u64)
+        .ok_or(TotError::InvalidSubscriptionPlan)?;
+    require!(!ctx.accounts.user_holder_info.is_frozen, TotError::HolderFrozen);
+
+    let holding_days = ctx.accounts.user_holder_info.get_holding_days(timestamp);
+    if let Some(expected) = ctx.accounts.price_catalog
+        .expected_price(consume_type as u8, sku, Some(holding_days))?
+    {
+        require!(amount == expected, TotError::PriceMismatch);
+    }
+
+    // ========================================
+    // 授权订阅代理PDA
+    // ========================================
+
+    let delegate_key = ctx.accounts.subscription_delegate.key();
+    let user_token_account = &ctx.accounts.user_token_account;
+    let allowance = if user_token_account.delegate == Some(delegate_key).into() {
+        user_token_account.delegated_amount
+            .checked_add(total_allowance)
+            .ok_or(TotError::MathOverflow)?
+    } else {
+        total_allowance
+    };
+
+    token_interface::approve(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Approve {
+                to: ctx.accounts.user_token_account.to_account_info(),
+                delegate: ctx.accounts.subscription_delegate.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        allowance,
+    )?;
+
+    // ========================================
+    // 记录订阅
+    // ========================================
+
+    let subscription = &mut ctx.accounts.subscription;
+    subscription.user = ctx.accounts.user.key();
+    subscription.subscription_id = subscription_id;
+    subscription.token_account = ctx.accounts.user_token_account.key();
+    subscription.consume_type = consume_type as u8;
+    subscription.sku = sku;
+    subscription.amount = amount;
+    subscription.period_seconds = period_seconds;
+    subscription.max_periods = max_periods;
+    subscription.periods_charged = 0;
+    subscription.next_charge_at = timestamp;
+    subscription.status = SubscriptionStatus::Active;
+    subscription.created_at = timestamp;
+    subscription.bump = ctx.bumps.subscription;
+
+    emit!(SubscriptionCreated {
+        subscription: subscription.key(),
+        user: subscription.user,
+        consume_type: subscription.consume_type,
+        sku,
+        amount,
+        period_seconds,
+        max_periods,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 订阅扣款账户结构（无需签名，任何人都可以调用）
+#[derive(Accounts)]
+pub struct ChargeSubscription<'info> {
+    /// 订阅账户
+    #[account(
+        mut,
+        seeds = [
+            seeds::SUBSCRIPTION_SEED,
+            subscription.user.as_ref(),
+            subscription.subscription_id.to_le_bytes().as_ref()
+        ],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    /// 用户代币账户
+    #[account(
+        mut,
+        address = subscription.token_account @ TotError::TokenAccountMismatch
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 用户持有者信息
+    #[account(
+        mut,
+        seeds = [seeds::HOLDER_SEED, subscription.user.as_ref()],
+        bump = user_holder_info.bump
+    )]
+    pub user_holder_info: Account<'info, HolderAccount>,
+
+    /// 订阅代理PDA（以delegate身份签名扣款）
+    /// CHECK: 通过种子约束校验，无账户数据
+    #[account(
+        seeds = [seeds::SUBSCRIPTION_DELEGATE_SEED],
+        bump
+    )]
+    pub subscription_delegate: UncheckedAccount<'info>,
+
+    /// TWS财库代币账户
+    #[account(
+        mut,
+        constraint = treasury_token_account.mint == mint.key() @ TotError::InvalidMint,
+        constraint = treasury_token_account.owner == config.tws_treasury @ TotError::InvalidOwner
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 受益人代币账户（商品登记了受益人且分成包含受益人份额时必须传入）
+    #[account(
+        mut,
+        constraint = beneficiary_token_account.mint == mint.key() @ TotError::InvalidMint
+    )]
+    pub beneficiary_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// 价格目录（提供收入分成配置）
+    #[account(
+        seeds = [seeds::PRICE_CATALOG_SEED],
+        bump = price_catalog.bump
+    )]
+    pub price_catalog: Account<'info, PriceCatalog>,
+
+    /// TOT Mint
+    #[account(
+        mut,
+        address = config.mint @ TotError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// 全局配置（累计消费统计、锁定供应量）
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// 订阅扣款处理器
+///
+/// 到期时由订阅代理PDA从用户代币账户扣取一期费用，按收入分成销毁、转入TWS财库、
+/// 转给受益人，与`consume_to_treasury`记录相同的消费统计。
+///
+/// # 注意事项
+/// - 无需签名，后端或任何人都可以作为crank调用
+/// - 每次调用只扣一期；逾期多期时可以连续调用补扣
+/// - 用户被冻结、余额不足或授权额度不足（用户撤销了授权）时不报错，
+///   而是停止订阅并发出`SubscriptionStopped`事件，之后不再扣款
+/// - 受`PauseFlag::Consume`暂停位控制
+pub fn charge_subscription_handler<'info>(ctx: Context<'_, '_, '_, 'info, ChargeSubscription<'info>>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let config = &ctx.accounts.config;
+    let subscription = &ctx.accounts.subscription;
+    let amount = subscription.amount;
+    let consume_type = subscription.consume_type;
+    let sku = subscription.sku;
+
+    // ========================================
+    // 验证阶段
+    // ========================================
+
+    config.require_not_paused(PauseFlag::Consume, timestamp)?;
+    require!(
+        subscription.status == SubscriptionStatus::Active,
+        TotError::SubscriptionNotActive
+    );
+    require!(timestamp >= subscription.next_charge_at, TotError::SubscriptionNotDue);
+    require!(
+        config.tws_treasury != config.mint,
+        TotError::InvalidInitParams
+    );
+
+    // ========================================
+    // 自动停止条件
+    // ========================================
+
+    let delegate_key = ctx.accounts.subscription_delegate.key();
+    let user_token_account = &ctx.accounts.user_token_account;
+    let stop_status = if ctx.accounts.user_holder_info.is_frozen || user_token_account.is_frozen() {
+        Some(SubscriptionStatus::Frozen)
+    } else if user_token_account.amount < amount
+        || user_token_account.delegate != Some(delegate_key).into()
+        || user_token_account.delegated_amount < amount
+    {
+        Some(SubscriptionStatus::InsufficientFunds)
+    } else {
+        None
+    };
+
+    if let Some(status) = stop_status {
+        let subscription = &mut ctx.accounts.subscription;
+        subscription.status = status;
+
+        msg!("Subscription stopped: {:?}", status);
+
+        emit!(SubscriptionStopped {
+            subscription: subscription.key(),
+            user: subscription.user,
+            status: status as u8,
+            periods_charged: subscription.periods_charged,
+            timestamp,
+        });
+
+        return Ok(());
+    }
+
+    // ========================================
+    // 解析收入分成和受益人（与consume_to_treasury相同）
+    // ========================================
+
+    let (distribution, beneficiary) = resolve_revenue_distribution(
+        &ctx.accounts.price_catalog,
+        consume_type,
+        sku,
+        amount,
+        ctx.accounts.beneficiary_token_account.as_ref(),
+    )?;
+
+    // ========================================
+    // 执行扣款（订阅代理PDA签名）
+    // ========================================
+
+    let delegate_bump = ctx.bumps.subscription_delegate;
+    let delegate_seeds: &[&[u8]] = &[seeds::SUBSCRIPTION_DELEGATE_SEED, &[delegate_bump]];
+    let mint_decimals = ctx.accounts.mint.decimals;
+
+    if distribution.to_burn > 0 {
+        token_interface::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.subscription_delegate.to_account_info(),
+                },
+                &[delegate_seeds],
+            ),
+            distribution.to_burn,
+        )?;
+    }
+
+    if distribution.to_treasury > 0 {
+        transfer_checked_with_hook(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.treasury_token_account.to_account_info(),
+                    authority: ctx.accounts.subscription_delegate.to_account_info(),
+                },
+                &[delegate_seeds],
+            ),
+            ctx.remaining_accounts,
+            distribution.to_treasury,
+            mint_decimals,
+        )?;
+    }
+
+    if distribution.to_beneficiary > 0 {
+        let beneficiary_account = ctx.accounts.beneficiary_token_account
+            .as_ref()
+            .ok_or(TotError::BeneficiaryAccountMissing)?;
+        transfer_checked_with_hook(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_token_account.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: beneficiary_account.to_account_info(),
+                    authority: ctx.accounts.subscription_delegate.to_account_info(),
+                },
+                &[delegate_seeds],
+            ),
+            ctx.remaining_accounts,
+            distribution.to_beneficiary,
+            mint_decimals,
+        )?;
+    }
+
+    // ========================================
+    // 更新订阅与统计
+    // ========================================
+
+    ctx.accounts.subscription.record_charge()?;
+    ctx.accounts.user_holder_info.record_consume(amount, timestamp)?;
+
+    // 进入财库的代币不再流通（按财库实际收到的数量，扣除Transfer Fee预扣后）
+    let treasury_balance_before = ctx.accounts.treasury_token_account.amount;
+    ctx.accounts.treasury_token_account.reload()?;
+    let received = ctx.accounts.treasury_token_account.amount
+        .saturating_sub(treasury_balance_before);
+    let config = &mut ctx.accounts.config;
+    config.locked_supply = config.locked_supply.saturating_add(received);
+    config.record_consume(consume_type, amount)?;
+    config.record_burn(distribution.to_burn)?;
+
+    let subscription = &ctx.accounts.subscription;
+    emit!(SubscriptionCharged {
+        subscription: subscription.key(),
+        user: subscription.user,
+        amount,
+        consume_type,
+        sku,
+        burned: distribution.to_burn,
+        treasury_amount: distribution.to_treasury,
+        beneficiary,
+        beneficiary_amount: distribution.to_beneficiary,
+        period: subscription.periods_charged,
+        next_charge_at: subscription.next_charge_at,
+        completed: subscription.status == SubscriptionStatus::Completed,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 取消订阅账户结构
+#[derive(Accounts)]
+pub struct CancelSubscription<'info> {
+    /// 用户（签名者，收回租金）
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// 订阅账户（取消后关闭）
+    #[account(
+        mut,
+        close = user,
+        has_one = user @ TotError::Unauthorized,
+        seeds = [
+            seeds::SUBSCRIPTION_SEED,
+            user.key().as_ref(),
+            subscription.subscription_id.to_le_bytes().as_ref()
+        ],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+
+    /// 用户代币账户（收回剩余授权额度）
+    #[account(
+        mut,
+        address = subscription.token_account @ TotError::TokenAccountMismatch
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 订阅代理PDA
+    /// CHECK: 通过种子约束校验，无账户数据
+    #[account(
+        seeds = [seeds::SUBSCRIPTION_DELEGATE_SEED],
+        bump
+    )]
+    pub subscription_delegate: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// 取消订阅处理器
+///
+/// 关闭订阅账户并退还租金，同时从订阅代理PDA的授权额度中扣除本订阅尚未扣款的部分，
+/// 额度归零时直接撤销授权。任何状态的订阅都可以取消。
+///
+/// # 注意事项
+/// - 代币账户的delegate已不是订阅代理PDA时（用户授权给了其他地址），不修改授权
+pub fn cancel_subscription_handler(ctx: Context<CancelSubscription>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let subscription = &ctx.accounts.subscription;
+    let remaining = subscription.remaining_amount()?;
+
+    let delegate_key = ctx.accounts.subscription_delegate.key();
+    let user_token_account = &ctx.accounts.user_token_account;
+    if remaining > 0 && user_token_account.delegate == Some(delegate_key).into() {
+        let allowance = user_token_account.delegated_amount.saturating_sub(remaining);
+        if allowance > 0 {
+            token_interface::approve(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Approve {
+                        to: ctx.accounts.user_token_account.to_account_info(),
+                        delegate: ctx.accounts.subscription_delegate.to_account_info(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    },
+                ),
+                allowance,
+            )?;
+        } else {
+            token_interface::revoke(CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Revoke {
+                    source: ctx.accounts.user_token_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ))?;
+        }
+    }
+
+    emit!(SubscriptionCancelled {
+        subscription: subscription.key(),
+        user: subscription.user,
+        periods_charged: subscription.periods_charged,
+        released_allowance: remaining,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 订阅创建事件
+#[event]
+pub struct SubscriptionCreated {
+    /// 订阅账户
+    pub subscription: Pubkey,
+    /// 用户
+    pub user: Pubkey,
+    /// 消费类型
+    pub consume_type: u8,
+    /// 商品SKU
+    pub sku: u32,
+    /// 每期金额
+    pub amount: u64,
+    /// 周期长度（秒）
+    pub period_seconds: i64,
+    /// 最大期数
+    pub max_periods: u32,
+    /// 创建时间
+    pub timestamp: i64,
+}
+
+/// 订阅扣款事件
+#[event]
+pub struct SubscriptionCharged {
+    /// 订阅账户
+    pub subscription: Pubkey,
+    /// 用户
+    pub user: Pubkey,
+    /// 本期金额
+    pub amount: u64,
+    /// 消费类型
+    pub consume_type: u8,
+    /// 商品SKU
+    pub sku: u32,
+    /// 销毁数量
+    pub burned: u64,
+    /// 转入财库数量
+    pub treasury_amount: u64,
+    /// 受益人
+    pub beneficiary: Option<Pubkey>,
+    /// 转给受益人数量
+    pub beneficiary_amount: u64,
+    /// 本次是第几期
+    pub period: u32,
+    /// 下一期扣款时间
+    pub next_charge_at: i64,
+    /// 是否已扣满最大期数
+    pub completed: bool,
+    /// 扣款时间
+    pub timestamp: i64,
+}
+
+/// 订阅停止事件（冻结、余额或授权额度不足）
+#[event]
+pub struct SubscriptionStopped {
+    /// 订阅账户
+    pub subscription: Pubkey,
+    /// 用户
+    pub user: Pubkey,
+    /// 停止后的状态（`SubscriptionStatus as u8`）
+    pub status: u8,
+    /// 已扣款期数
+    pub periods_charged: u32,
+    /// 停止时间
+    pub timestamp: i64,
+}
+
+/// 订阅取消事件
+#[event]
+pub struct SubscriptionCancelled {
+    /// 订阅账户
+    pub subscription: Pubkey,
+    /// 用户
+    pub user: Pubkey,
+    /// 已扣款期数
+    pub periods_charged: u32,
+    /// 释放的授权额度
+    pub released_allowance: u64,
+    /// 取消时间
+    pub timestamp: i64,
+}
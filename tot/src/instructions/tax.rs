@@ -14,6 +14,7 @@ use crate::utils::validation::validate_tax_rate;
 #[derive(Accounts)]
 pub struct InitializeTaxConfig<'info> {
     #[account(
+        mut,
         constraint = authority.key() == config.authority @ TotError::Unauthorized
     )]
     pub authority: Signer<'info>,
@@ -50,6 +51,8 @@ pub fn initialize_tax_config_handler(ctx: Context<InitializeTaxConfig>) -> Resul
     tax_config.panic_tax_bps = crate::constants::tax::PANIC_TAX_BPS;
     tax_config.enabled = true;
     tax_config.exempt_addresses = vec![];
+    tax_config.buy_tax_bps = crate::constants::tax::BUY_TAX_BPS;
+    tax_config.venue_addresses = vec![];
     tax_config.last_updated = clock.unix_timestamp;
     tax_config.bump = ctx.bumps.tax_config;
 
@@ -88,6 +91,7 @@ pub fn update_tax_config_handler(
     gamma_bps: Option<u16>,
     panic_threshold_bps: Option<u16>,
     panic_tax_bps: Option<u16>,
+    buy_tax_bps: Option<u16>,
 ) -> Result<()> {
     let tax_config = &mut ctx.accounts.tax_config;
     let clock = Clock::get()?;
@@ -121,6 +125,11 @@ pub fn update_tax_config_handler(
         tax_config.panic_tax_bps = panic_tax;
     }
 
+    if let Some(buy_tax) = buy_tax_bps {
+        validate_tax_rate(buy_tax)?;
+        tax_config.buy_tax_bps = buy_tax;
+    }
+
     tax_config.last_updated = timestamp;
 
     emit!(TaxConfigUpdated {
@@ -128,6 +137,7 @@ pub fn update_tax_config_handler(
         alpha: tax_config.alpha,
         beta: tax_config.beta,
         gamma_bps: tax_config.gamma_bps,
+        buy_tax_bps: tax_config.buy_tax_bps,
         timestamp,
     });
 
@@ -198,6 +208,50 @@ pub fn remove_tax_exempt_handler(
     Ok(())
 }
 
+/// 登记交易场所处理器
+///
+/// 登记后，从该地址转出的代币视为接收者买入，按买入税率计税。
+pub fn add_trading_venue_handler(
+    ctx: Context<ManageTaxExempt>,
+    address: Pubkey,
+) -> Result<()> {
+    let tax_config = &mut ctx.accounts.tax_config;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    tax_config.add_venue(address)?;
+    tax_config.last_updated = timestamp;
+
+    msg!("Added trading venue: {}", address);
+
+    emit!(TradingVenueAdded {
+        address,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 移除交易场所处理器
+pub fn remove_trading_venue_handler(
+    ctx: Context<ManageTaxExempt>,
+    address: Pubkey,
+) -> Result<()> {
+    let tax_config = &mut ctx.accounts.tax_config;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    tax_config.remove_venue(&address)?;
+    tax_config.last_updated = timestamp;
+
+    msg!("Removed trading venue: {}", address);
+
+    emit!(TradingVenueRemoved {
+        address,
+        timestamp,
+    });
+
+    Ok(())
+}
+
 use crate::utils::validation::validate_bps;
 
 /// 税率配置更新事件
@@ -207,6 +261,7 @@ pub struct TaxConfigUpdated {
     pub alpha: u64,
     pub beta: u64,
     pub gamma_bps: u16,
+    pub buy_tax_bps: u16,
     pub timestamp: i64,
 }
 
@@ -223,3 +278,17 @@ pub struct TaxExemptRemoved {
     pub address: Pubkey,
     pub timestamp: i64,
 }
+
+/// 交易场所登记事件
+#[event]
+pub struct TradingVenueAdded {
+    pub address: Pubkey,
+    pub timestamp: i64,
+}
+
+/// 交易场所移除事件
+#[event]
+pub struct TradingVenueRemoved {
+    pub address: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,302 @@
+// ============================================
+// 文件: src/instructions/token_authority.rs
+// Token-2022 权限两步移交指令
+// ============================================
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenInterface;
+use anchor_lang::solana_program::program::invoke;
+use spl_token_2022::instruction::{self as token_2022_instruction, AuthorityType};
+
+use crate::state::config::TotConfig;
+use crate::constants::seeds;
+use crate::errors::TotError;
+
+/// Token-2022 权限类型
+///
+/// 对应`initialize`时分配给部署者的Mint权限。
+/// 每种权限在`TotConfig.pending_token_authority_kinds`中占用一位。
+/// 铸造权限由程序PDA持有（见`mint_authority`模块），不在此列。
+///
+/// `PermanentDelegate`只适用于永久代理仍由钱包持有的旧版Mint；
+/// 执行`migrate_permanent_delegate`后永久代理由程序PDA持有，无法再通过此流程移交。
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TokenAuthorityKind {
+    /// 冻结权限
+    FreezeAccount = 0,
+    /// 转账费配置权限
+    TransferFeeConfig = 1,
+    /// 提取累积转账费权限
+    WithheldWithdraw = 2,
+    /// 元数据指针权限
+    MetadataPointer = 3,
+    /// Transfer Hook程序ID权限
+    TransferHook = 4,
+    /// 永久代理
+    PermanentDelegate = 5,
+}
+
+impl TokenAuthorityKind {
+    /// 所有权限类型（按位顺序）
+    pub const ALL: [TokenAuthorityKind; 6] = [
+        TokenAuthorityKind::FreezeAccount,
+        TokenAuthorityKind::TransferFeeConfig,
+        TokenAuthorityKind::WithheldWithdraw,
+        TokenAuthorityKind::MetadataPointer,
+        TokenAuthorityKind::TransferHook,
+        TokenAuthorityKind::PermanentDelegate,
+    ];
+
+    /// 在位图中的掩码
+    pub fn bit(self) -> u8 {
+        1 << (self as u8)
+    }
+
+    /// 对应的Token-2022权限类型
+    pub fn authority_type(self) -> AuthorityType {
+        match self {
+            TokenAuthorityKind::FreezeAccount => AuthorityType::FreezeAccount,
+            TokenAuthorityKind::TransferFeeConfig => AuthorityType::TransferFeeConfig,
+            TokenAuthorityKind::WithheldWithdraw => AuthorityType::WithheldWithdraw,
+            TokenAuthorityKind::MetadataPointer => AuthorityType::MetadataPointer,
+            TokenAuthorityKind::TransferHook => AuthorityType::TransferHookProgramId,
+            TokenAuthorityKind::PermanentDelegate => AuthorityType::PermanentDelegate,
+        }
+    }
+}
+
+/// 提议移交Token-2022权限
+#[derive(Accounts)]
+pub struct ProposeTokenAuthority<'info> {
+    #[account(
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+}
+
+/// 提议移交Token-2022权限处理器
+///
+/// 由系统管理员发起，记录新地址、待移交的权限类型和生效时间。
+/// 一次可以同时移交多种权限，重复提议会覆盖之前的提议。
+pub fn propose_token_authority_handler(
+    ctx: Context<ProposeTokenAuthority>,
+    kinds: Vec<TokenAuthorityKind>,
+    new_authority: Pubkey,
+) -> Result<()> {
+    require!(!kinds.is_empty(), TotError::InvalidParameter);
+    require!(
+        new_authority != Pubkey::default(),
+        TotError::InvalidNewAuthority
+    );
+
+    let config = &mut ctx.accounts.config;
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    let kinds_mask = kinds.iter().fold(0u8, |mask, kind| mask | kind.bit());
+    let eta = timestamp
+        .checked_add(config.authority_transfer_delay_at(timestamp))
+        .ok_or(TotError::MathOverflow)?;
+
+    config.pending_token_authority = new_authority;
+    config.pending_token_authority_kinds = kinds_mask;
+    config.token_authority_transfer_eta = eta;
+
+    msg!(
+        "Token authority transfer proposed: kinds {:#08b} -> {}, eta {}",
+        kinds_mask,
+        new_authority,
+        eta
+    );
+
+    emit!(TokenAuthorityTransferProposed {
+        kinds: kinds_mask,
+        pending_authority: new_authority,
+        eta,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 接受Token-2022权限
+#[derive(Accounts)]
+pub struct AcceptTokenAuthority<'info> {
+    /// 新权限地址（必须是提议中的地址）
+    #[account(
+        constraint = new_authority.key() == config.pending_token_authority @ TotError::PendingAuthorityMismatch
+    )]
+    pub new_authority: Signer<'info>,
+
+    /// 当前持有Mint权限的地址
+    ///
+    /// Token-2022的SetAuthority要求当前权限签名，
+    /// 实际是否持有权限由Token-2022程序验证。
+    pub current_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// TOT Mint账户
+    /// CHECK: 地址与config.mint一致，数据由Token-2022程序验证和修改
+    #[account(
+        mut,
+        address = config.mint @ TotError::InvalidMint
+    )]
+    pub mint: AccountInfo<'info>,
+
+    /// Token-2022 程序
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// 接受Token-2022权限处理器
+///
+/// 时间锁到期后，对位图中的每种权限调用Token-2022的SetAuthority，
+/// 完成后清空待移交状态。
+pub fn accept_token_authority_handler(ctx: Context<AcceptTokenAuthority>) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let kinds_mask = ctx.accounts.config.pending_token_authority_kinds;
+
+    require!(
+        kinds_mask != 0
+            && ctx.accounts.config.pending_token_authority != Pubkey::default(),
+        TotError::NoPendingAuthority
+    );
+    require!(
+        timestamp >= ctx.accounts.config.token_authority_transfer_eta,
+        TotError::AuthorityTransferTimelocked
+    );
+
+    let new_authority = ctx.accounts.new_authority.key();
+    let current_authority = &ctx.accounts.current_authority;
+    let mint = &ctx.accounts.mint;
+
+    for kind in TokenAuthorityKind::ALL
+        .iter()
+        .filter(|kind| kinds_mask & kind.bit() != 0)
+    {
+        invoke(
+            &token_2022_instruction::set_authority(
+                &ctx.accounts.token_program.key(),
+                &mint.key(),
+                Some(&new_authority),
+                kind.authority_type(),
+                &current_authority.key(),
+                &[],
+            )?,
+            &[
+                mint.to_account_info(),
+                current_authority.to_account_info(),
+            ],
+        )?;
+    }
+
+    let config = &mut ctx.accounts.config;
+    config.pending_token_authority = Pubkey::default();
+    config.pending_token_authority_kinds = 0;
+    config.token_authority_transfer_eta = 0;
+
+    msg!(
+        "Token authorities {:#08b} transferred from {} to {}",
+        kinds_mask,
+        current_authority.key(),
+        new_authority
+    );
+
+    emit!(TokenAuthorityTransferred {
+        kinds: kinds_mask,
+        old_authority: current_authority.key(),
+        new_authority,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 取消Token-2022权限移交
+#[derive(Accounts)]
+pub struct CancelTokenAuthorityTransfer<'info> {
+    #[account(
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+}
+
+/// 取消Token-2022权限移交处理器
+pub fn cancel_token_authority_transfer_handler(
+    ctx: Context<CancelTokenAuthorityTransfer>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    require!(
+        config.pending_token_authority_kinds != 0,
+        TotError::NoPendingAuthority
+    );
+
+    let cancelled_authority = config.pending_token_authority;
+    let kinds_mask = config.pending_token_authority_kinds;
+
+    msg!(
+        "Token authority transfer {:#08b} to {} cancelled",
+        kinds_mask,
+        cancelled_authority
+    );
+
+    config.pending_token_authority = Pubkey::default();
+    config.pending_token_authority_kinds = 0;
+    config.token_authority_transfer_eta = 0;
+
+    emit!(TokenAuthorityTransferCancelled {
+        kinds: kinds_mask,
+        cancelled_authority,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Token-2022权限移交提议事件
+#[event]
+pub struct TokenAuthorityTransferProposed {
+    /// 权限类型位图
+    pub kinds: u8,
+    pub pending_authority: Pubkey,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+/// Token-2022权限移交完成事件
+#[event]
+pub struct TokenAuthorityTransferred {
+    /// 权限类型位图
+    pub kinds: u8,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Token-2022权限移交取消事件
+#[event]
+pub struct TokenAuthorityTransferCancelled {
+    /// 权限类型位图
+    pub kinds: u8,
+    pub cancelled_authority: Pubkey,
+    pub timestamp: i64,
+}
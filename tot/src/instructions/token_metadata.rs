@@ -0,0 +1,399 @@
+// ============================================
+// 文件: src/instructions/token_metadata.rs
+// Token-2022 链上元数据指令（初始化、更新字段、删除自定义字段）
+// ============================================
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::TokenInterface;
+use anchor_lang::solana_program::program::invoke_signed;
+use spl_token_2022::{
+    extension::{
+        metadata_pointer::MetadataPointer, BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::Mint,
+};
+use spl_token_metadata_interface::{
+    instruction as token_metadata_instruction,
+    state::{Field, TokenMetadata},
+};
+
+use crate::state::config::TotConfig;
+use crate::constants::{limits, seeds, TOKEN_NAME, TOKEN_SYMBOL};
+use crate::errors::TotError;
+
+/// 元数据字段
+///
+/// 对应Token-2022 TokenMetadata的字段。
+/// `Key`用于添加或更新自定义键值对（如税收政策链接）。
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum TokenMetadataField {
+    /// 代币名称
+    Name,
+    /// 代币符号
+    Symbol,
+    /// 元数据URI
+    Uri,
+    /// 自定义字段
+    Key(String),
+}
+
+impl From<TokenMetadataField> for Field {
+    fn from(field: TokenMetadataField) -> Self {
+        match field {
+            TokenMetadataField::Name => Field::Name,
+            TokenMetadataField::Symbol => Field::Symbol,
+            TokenMetadataField::Uri => Field::Uri,
+            TokenMetadataField::Key(key) => Field::Key(key),
+        }
+    }
+}
+
+/// 初始化元数据账户结构
+#[derive(Accounts)]
+pub struct InitializeTokenMetadata<'info> {
+    /// 元数据权限（Metadata Pointer权限持有者）
+    ///
+    /// 同时支付Mint账户扩容所需的租金。
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// TOT Mint账户（元数据存储在Mint账户本身）
+    /// CHECK: 地址与config.mint一致，数据由Token-2022程序验证和修改
+    #[account(
+        mut,
+        address = config.mint @ TotError::InvalidMint
+    )]
+    pub mint: AccountInfo<'info>,
+
+    /// 铸造权限PDA（Token-2022要求初始化元数据时由铸造权限签名）
+    /// CHECK: 仅作为签名者，地址由seeds约束
+    #[account(
+        seeds = [seeds::MINT_AUTHORITY_SEED],
+        bump
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// 元数据更新权限PDA
+    /// CHECK: 仅作为TokenMetadata的update_authority，地址由seeds约束
+    #[account(
+        seeds = [seeds::METADATA_AUTHORITY_SEED],
+        bump
+    )]
+    pub metadata_authority: UncheckedAccount<'info>,
+
+    /// Token-2022 程序
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 初始化元数据处理器
+///
+/// 使用`TOKEN_NAME`和`TOKEN_SYMBOL`写入Token-2022 TokenMetadata扩展。
+/// 元数据的update_authority设置为程序PDA，后续更新由本程序校验
+/// Metadata Pointer权限后代为签名，因此移交Metadata Pointer权限即可移交元数据管理权。
+pub fn initialize_token_metadata_handler(
+    ctx: Context<InitializeTokenMetadata>,
+    uri: String,
+) -> Result<()> {
+    require!(
+        uri.len() <= limits::MAX_METADATA_VALUE_LEN,
+        TotError::MetadataFieldTooLong
+    );
+    // 初始化元数据需要铸造权限签名，放弃铸造权限后无法再初始化
+    require!(
+        !ctx.accounts.config.mint_authority_renounced,
+        TotError::MintAuthorityAlreadyRenounced
+    );
+
+    let mint = &ctx.accounts.mint;
+    let mint_authority = &ctx.accounts.mint_authority;
+    let metadata_authority = &ctx.accounts.metadata_authority;
+
+    require_metadata_authority(mint, &ctx.accounts.authority.key())?;
+
+    let metadata = TokenMetadata {
+        update_authority: Some(metadata_authority.key()).try_into()?,
+        mint: mint.key(),
+        name: TOKEN_NAME.to_string(),
+        symbol: TOKEN_SYMBOL.to_string(),
+        uri: uri.clone(),
+        additional_metadata: vec![],
+    };
+    top_up_mint_rent(
+        &ctx.accounts.authority,
+        mint,
+        &ctx.accounts.system_program,
+        &metadata,
+    )?;
+
+    let mint_authority_seeds: &[&[u8]] = &[
+        seeds::MINT_AUTHORITY_SEED,
+        &[ctx.bumps.mint_authority],
+    ];
+
+    invoke_signed(
+        &token_metadata_instruction::initialize(
+            &ctx.accounts.token_program.key(),
+            &mint.key(),
+            &metadata_authority.key(),
+            &mint.key(),
+            &mint_authority.key(),
+            metadata.name,
+            metadata.symbol,
+            metadata.uri,
+        ),
+        &[
+            mint.to_account_info(),
+            metadata_authority.to_account_info(),
+            mint_authority.to_account_info(),
+        ],
+        &[mint_authority_seeds],
+    )?;
+
+    msg!("Token metadata initialized: {} ({}), uri {}", TOKEN_NAME, TOKEN_SYMBOL, uri);
+
+    emit!(TokenMetadataInitialized {
+        name: TOKEN_NAME.to_string(),
+        symbol: TOKEN_SYMBOL.to_string(),
+        uri,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// 更新元数据账户结构
+///
+/// `update_token_metadata_field`和`remove_token_metadata_key`共用。
+#[derive(Accounts)]
+pub struct UpdateTokenMetadata<'info> {
+    /// 元数据权限（Metadata Pointer权限持有者）
+    ///
+    /// 同时支付Mint账户扩容所需的租金。
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// TOT Mint账户（元数据存储在Mint账户本身）
+    /// CHECK: 地址与config.mint一致，数据由Token-2022程序验证和修改
+    #[account(
+        mut,
+        address = config.mint @ TotError::InvalidMint
+    )]
+    pub mint: AccountInfo<'info>,
+
+    /// 元数据更新权限PDA
+    /// CHECK: 仅作为签名者，地址由seeds约束
+    #[account(
+        seeds = [seeds::METADATA_AUTHORITY_SEED],
+        bump
+    )]
+    pub metadata_authority: UncheckedAccount<'info>,
+
+    /// Token-2022 程序
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 更新元数据字段处理器
+///
+/// 更新名称、符号、URI，或添加/覆盖自定义键值对。
+/// 新内容比原内容长时，先向Mint账户补足租金，再由Token-2022扩容。
+pub fn update_token_metadata_field_handler(
+    ctx: Context<UpdateTokenMetadata>,
+    field: TokenMetadataField,
+    value: String,
+) -> Result<()> {
+    if let TokenMetadataField::Key(key) = &field {
+        require!(
+            !key.is_empty() && key.len() <= limits::MAX_METADATA_KEY_LEN,
+            TotError::MetadataFieldTooLong
+        );
+    }
+    require!(
+        value.len() <= limits::MAX_METADATA_VALUE_LEN,
+        TotError::MetadataFieldTooLong
+    );
+
+    let mint = &ctx.accounts.mint;
+    let metadata_authority = &ctx.accounts.metadata_authority;
+
+    require_metadata_authority(mint, &ctx.accounts.authority.key())?;
+
+    // 按更新后的内容计算Mint账户的新大小
+    let mut metadata = {
+        let data = mint.try_borrow_data()?;
+        let state = StateWithExtensions::<Mint>::unpack(&data)?;
+        state.get_variable_len_extension::<TokenMetadata>()?
+    };
+    metadata.update(field.clone().into(), value.clone());
+    top_up_mint_rent(
+        &ctx.accounts.authority,
+        mint,
+        &ctx.accounts.system_program,
+        &metadata,
+    )?;
+
+    let metadata_authority_seeds: &[&[u8]] = &[
+        seeds::METADATA_AUTHORITY_SEED,
+        &[ctx.bumps.metadata_authority],
+    ];
+
+    invoke_signed(
+        &token_metadata_instruction::update_field(
+            &ctx.accounts.token_program.key(),
+            &mint.key(),
+            &metadata_authority.key(),
+            field.clone().into(),
+            value.clone(),
+        ),
+        &[
+            mint.to_account_info(),
+            metadata_authority.to_account_info(),
+        ],
+        &[metadata_authority_seeds],
+    )?;
+
+    msg!("Token metadata field {:?} updated", field);
+
+    emit!(TokenMetadataFieldUpdated {
+        field,
+        value,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// 删除自定义元数据字段处理器
+///
+/// 只能删除自定义键值对，名称、符号和URI不能删除。
+/// 字段不存在时返回错误。
+pub fn remove_token_metadata_key_handler(
+    ctx: Context<UpdateTokenMetadata>,
+    key: String,
+) -> Result<()> {
+    let mint = &ctx.accounts.mint;
+    let metadata_authority = &ctx.accounts.metadata_authority;
+
+    require_metadata_authority(mint, &ctx.accounts.authority.key())?;
+
+    let metadata_authority_seeds: &[&[u8]] = &[
+        seeds::METADATA_AUTHORITY_SEED,
+        &[ctx.bumps.metadata_authority],
+    ];
+
+    invoke_signed(
+        &token_metadata_instruction::remove_key(
+            &ctx.accounts.token_program.key(),
+            &mint.key(),
+            &metadata_authority.key(),
+            key.clone(),
+            false, // idempotent: 字段不存在时报错
+        ),
+        &[
+            mint.to_account_info(),
+            metadata_authority.to_account_info(),
+        ],
+        &[metadata_authority_seeds],
+    )?;
+
+    msg!("Token metadata key {} removed", key);
+
+    emit!(TokenMetadataKeyRemoved {
+        key,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// 校验签名者是否为Metadata Pointer权限
+///
+/// 元数据管理权跟随Mint的Metadata Pointer权限，
+/// 该权限可以通过`propose_token_authority`/`accept_token_authority`移交。
+fn require_metadata_authority(mint: &AccountInfo, signer: &Pubkey) -> Result<()> {
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<Mint>::unpack(&data)?;
+    let pointer = state.get_extension::<MetadataPointer>()?;
+
+    require!(
+        Option::<Pubkey>::from(pointer.authority) == Some(*signer),
+        TotError::InvalidMetadataAuthority
+    );
+
+    Ok(())
+}
+
+/// 向Mint账户补足元数据扩容所需的租金
+///
+/// Token-2022在写入元数据时会自行调整Mint账户大小，
+/// 但不会补充租金，需要调用方预先转入差额。
+fn top_up_mint_rent<'info>(
+    payer: &Signer<'info>,
+    mint: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    new_metadata: &TokenMetadata,
+) -> Result<()> {
+    let new_len = {
+        let data = mint.try_borrow_data()?;
+        let state = StateWithExtensions::<Mint>::unpack(&data)?;
+        state.try_get_new_account_len(new_metadata)?
+    };
+
+    let required = Rent::get()?.minimum_balance(new_len);
+    let current = mint.lamports();
+
+    if required > current {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: mint.to_account_info(),
+                },
+            ),
+            required - current,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// 元数据初始化事件
+#[event]
+pub struct TokenMetadataInitialized {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub timestamp: i64,
+}
+
+/// 元数据字段更新事件
+#[event]
+pub struct TokenMetadataFieldUpdated {
+    pub field: TokenMetadataField,
+    pub value: String,
+    pub timestamp: i64,
+}
+
+/// 自定义元数据字段删除事件
+#[event]
+pub struct TokenMetadataKeyRemoved {
+    pub key: String,
+    pub timestamp: i64,
+}
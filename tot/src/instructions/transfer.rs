@@ -8,13 +8,17 @@ use anchor_spl::token_interface::{
     self, Mint, TokenAccount, TokenInterface, TransferChecked, Burn,
 };
 
-use crate::state::config::TotConfig;
+use crate::state::config::{TotConfig, PauseFlag};
 use crate::state::tax::TaxConfig;
 use crate::state::holder::HolderAccount;
+use crate::state::receipt::{PaymentReceipt, PaymentKind};
+use crate::state::points::PointsAccount;
+use crate::instructions::receipt::create_payment_receipt;
 use crate::constants::seeds;
 use crate::errors::TotError;
 use crate::utils::tax_calculator::*;
 use crate::utils::validation::validate_transfer_amount;
+use crate::utils::token_transfer::transfer_checked_with_hook;
 
 /// 带税转账账户结构
 #[derive(Accounts)]
@@ -42,8 +46,9 @@ pub struct TransferWithTax<'info> {
     #[account(mut)]
     pub mint: InterfaceAccount<'info, Mint>,
 
-    /// 全局配置
+    /// 全局配置（累计协议统计）
     #[account(
+        mut,
         seeds = [seeds::CONFIG_SEED],
         bump
     )]
@@ -64,9 +69,14 @@ pub struct TransferWithTax<'info> {
     )]
     pub sender_holder_info: Account<'info, HolderAccount>,
 
-    /// 接收者持有者信息（可能需要初始化）
-    /// CHECK: 如果不存在，需要先初始化
-    pub receiver_holder_info: Option<Account<'info, HolderAccount>>,
+    /// 接收者持有者信息 `["tot_holder", receiver_token_account.owner]`
+    /// CHECK: 地址由种子约束；尚未初始化时跳过冻结检查和统计更新
+    #[account(
+        mut,
+        seeds = [seeds::HOLDER_SEED, receiver_token_account.owner.as_ref()],
+        bump
+    )]
+    pub receiver_holder_info: UncheckedAccount<'info>,
 
     /// 税收收集账户（流动性池）
     #[account(mut)]
@@ -74,6 +84,21 @@ pub struct TransferWithTax<'info> {
 
     /// Token 程序
     pub token_program: Interface<'info, TokenInterface>,
+
+    /// 支付回执PDA（提供支付引用时必须传入）
+    /// CHECK: 由create_payment_receipt校验地址并创建
+    #[account(mut)]
+    pub payment_receipt: Option<UncheckedAccount<'info>>,
+
+    /// System Program（提供支付引用时必须传入）
+    pub system_program: Option<Program<'info, System>>,
+
+    /// 发送者积分账户（可选，用于积分兑换的税率折扣）
+    #[account(
+        seeds = [seeds::POINTS_SEED, sender.key().as_ref()],
+        bump = sender_points.bump
+    )]
+    pub sender_points: Option<Account<'info, PointsAccount>>,
 }
 
 /// 带税转账处理器
@@ -97,6 +122,7 @@ pub struct TransferWithTax<'info> {
 /// * `is_sell` - 是否为卖出操作
 ///   - `true`: 卖出操作，会计算大额交易惩罚
 ///   - `false`: 普通转账，不计算大额交易惩罚
+/// * `reference` - 支付引用（可选），写入事件并创建回执PDA，同一引用只能使用一次
 /// 
 /// # 返回值
 /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
@@ -115,6 +141,8 @@ pub struct TransferWithTax<'info> {
 /// - 系统处于恐慌模式时，卖出操作会被拒绝
 /// - 免税地址的转账不收取税收
 /// - 持有时间越长，税率折扣越大
+/// - 发送者是已登记的交易场所且不是卖出时按买入计税（买入税率，折扣按接收者持有时间），
+///   税额记入接收者的已缴税额
 /// 
 /// # 使用示例
 /// ```rust
@@ -122,27 +150,93 @@ pub struct TransferWithTax<'info> {
 /// program.methods
 ///     .transferWithTax(
 ///         new anchor.BN(1000000), // 转账100万代币
-///         true // 是卖出操作
+///         true, // 是卖出操作
+///         null // 不使用支付引用
 ///     )
 ///     .accounts({...})
 ///     .rpc();
 /// ```
-pub fn transfer_with_tax_handler(
-    ctx: Context<TransferWithTax>,
+pub fn transfer_with_tax_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, TransferWithTax<'info>>,
+    amount: u64,
+    is_sell: bool,
+    reference: Option<[u8; 32]>,
+) -> Result<()> {
+    process_transfer_with_tax(
+        ctx.accounts,
+        ctx.program_id,
+        ctx.remaining_accounts,
+        amount,
+        is_sell,
+        false,
+        reference,
+    )
+}
+
+/// 不含税转账处理器（接收者收到精确金额）
+/// 
+/// 用于按标价付款的场景，例如购买标价100 TOT的应用内物品。
+/// 调用方指定接收者实际收到的净额，程序反推含税总额，
+/// 从发送者账户扣除"净额 + 税额"。
+/// 
+/// # 参数
+/// * `ctx` - 转账上下文，与`transfer_with_tax`相同
+/// * `net_amount` - 接收者实际收到的金额
+/// * `is_sell` - 是否为卖出操作
+/// * `reference` - 支付引用（可选），同`transfer_with_tax`
+/// 
+/// # 返回值
+/// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+/// 
+/// # 注意事项
+/// 
+/// - 免税、冻结、暂停规则与`transfer_with_tax`完全一致
+/// - 含税总额由`TaxCalculator::calculate_tax_exclusive`计算，税率不超过`MAX_TAX_BPS`
+/// - 发送者余额必须覆盖含税总额
+/// - 事件中的`amount`为含税总额，`net_amount`等于传入的净额
+pub fn transfer_exact_with_tax_handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, TransferWithTax<'info>>,
+    net_amount: u64,
+    is_sell: bool,
+    reference: Option<[u8; 32]>,
+) -> Result<()> {
+    process_transfer_with_tax(
+        ctx.accounts,
+        ctx.program_id,
+        ctx.remaining_accounts,
+        net_amount,
+        is_sell,
+        true,
+        reference,
+    )
+}
+
+/// 带税转账的共享实现
+/// 
+/// `transfer_with_tax`、`transfer_exact_with_tax`和合作程序CPI入口`partner_transfer_with_tax`共用。
+/// `tax_exclusive`为`false`时`amount`是含税总额（税从中扣除），
+/// 为`true`时`amount`是接收者应收到的净额（税额另外从发送者扣除）。
+/// `hook_accounts`为Transfer Hook需要的账户（指令的`remaining_accounts`）。
+pub(crate) fn process_transfer_with_tax<'info>(
+    accounts: &mut TransferWithTax<'info>,
+    program_id: &Pubkey,
+    hook_accounts: &[AccountInfo<'info>],
     amount: u64,
     is_sell: bool,
+    tax_exclusive: bool,
+    reference: Option<[u8; 32]>,
 ) -> Result<()> {
     // 获取账户和配置引用
-    let config = &ctx.accounts.config;
-    let tax_config = &ctx.accounts.tax_config;
-    let sender_holder = &mut ctx.accounts.sender_holder_info;
+    let config = &accounts.config;
+    let tax_config = &accounts.tax_config;
+    let sender_holder = &mut accounts.sender_holder_info;
     let clock = Clock::get()?;
     let timestamp = clock.unix_timestamp;
 
     // 缓存常用值以减少重复访问
-    let sender_key = ctx.accounts.sender.key();
-    let receiver_owner = ctx.accounts.receiver_token_account.owner;
-    let mint_decimals = ctx.accounts.mint.decimals;
+    let sender_key = accounts.sender.key();
+    let receiver_owner = accounts.receiver_token_account.owner;
+    let mint_decimals = accounts.mint.decimals;
 
     // ========================================
     // 免税检查（提前检查以节省gas）
@@ -164,26 +258,51 @@ pub fn transfer_with_tax_handler(
     // 这是所有转账都需要的基本验证
     validate_transfer_amount(amount)?;
 
+    // 验证2: 检查转账/卖出是否被暂停（免税转账同样受暂停约束）
+    config.require_not_paused(PauseFlag::Transfers, timestamp)?;
+    if is_sell {
+        config.require_not_paused(PauseFlag::Sells, timestamp)?;
+    }
+
     // 如果免税，只进行基本验证，然后直接转账
     if is_exempt {
         // 免税转账路径：只进行基本验证，跳过税收计算
         // 直接执行全额转账
         let transfer_ctx = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
+            accounts.token_program.to_account_info(),
             TransferChecked {
-                from: ctx.accounts.sender_token_account.to_account_info(),
-                mint: ctx.accounts.mint.to_account_info(),
-                to: ctx.accounts.receiver_token_account.to_account_info(),
-                authority: ctx.accounts.sender.to_account_info(),
+                from: accounts.sender_token_account.to_account_info(),
+                mint: accounts.mint.to_account_info(),
+                to: accounts.receiver_token_account.to_account_info(),
+                authority: accounts.sender.to_account_info(),
             },
         );
 
-        token_interface::transfer_checked(
+        transfer_checked_with_hook(
             transfer_ctx,
+            hook_accounts,
             amount,  // 全额转账，无税收
             mint_decimals,
         )?;
 
+        if let Some(reference) = reference {
+            create_payment_receipt(
+                program_id,
+                accounts.payment_receipt.as_ref(),
+                accounts.system_program.as_ref(),
+                accounts.sender.to_account_info(),
+                PaymentReceipt {
+                    reference,
+                    payer: sender_key,
+                    recipient: receiver_owner,
+                    kind: PaymentKind::TransferWithTax,
+                    amount,
+                    created_at: timestamp,
+                    bump: 0,
+                },
+            )?;
+        }
+
         // 发出免税转账事件
         emit!(TransferWithTaxEvent {
             from: sender_key,
@@ -193,6 +312,7 @@ pub fn transfer_with_tax_handler(
             net_amount: amount,
             tax_rate_bps: 0,
             burned: 0,
+            reference,
             timestamp,
         });
 
@@ -200,14 +320,11 @@ pub fn transfer_with_tax_handler(
     }
 
     // 非免税转账路径：进行完整验证和税收计算
+    // 从已登记的交易场所（DEX池、销售合约等）转出视为接收者买入
+    let is_buy = !is_sell && tax_config.is_venue(&sender_key);
+
     // 缓存常用字段值以减少重复访问
-    let panic_mode = config.panic_mode;
     let sender_frozen = sender_holder.is_frozen;
-    
-    // 验证2: 检查系统是否暂停
-    // 如果系统处于恐慌模式且是卖出操作，拒绝执行
-    // 这是保护机制，防止在市场异常时的大规模抛售
-    require!(!panic_mode || !is_sell, TotError::SystemPaused);
 
     // 验证3: 检查发送者账户是否被冻结
     // 被冻结的账户无法进行转账操作
@@ -215,7 +332,13 @@ pub fn transfer_with_tax_handler(
 
     // 验证4: 检查接收者账户是否被冻结（如果存在）
     // 如果接收者持有者账户存在，需要检查是否被冻结
-    if let Some(ref receiver_holder) = ctx.accounts.receiver_holder_info {
+    // 转给自己时与发送者持有者是同一账户，只由sender_holder_info写回，避免互相覆盖
+    let mut receiver_holder = if receiver_owner == sender_key {
+        None
+    } else {
+        HolderAccount::load_optional(&accounts.receiver_holder_info, program_id)?
+    };
+    if let Some(ref receiver_holder) = receiver_holder {
         require!(!receiver_holder.is_frozen, TotError::HolderFrozen);
     }
 
@@ -229,20 +352,56 @@ pub fn transfer_with_tax_handler(
     // 参数说明:
     // - amount: 转账金额
     // - sender_holder: 发送者持有者信息（用于计算持有时间折扣）
-    // - mint.supply: 总供应量（用于计算大额交易惩罚）
+    // - circulating_supply: 流通供应量（用于计算大额交易惩罚，不含池子和财库锁定部分）
     // - timestamp: 当前时间（用于计算持有天数）
-    // - false: 不是买入操作
+    // - is_buy: 是否为买入操作（使用买入税率，持有时间折扣按接收者计算）
     // - is_sell: 是否为卖出操作（影响大额交易惩罚的计算）
+    // - panic_mode: 恐慌税率模式（卖出时可能提升至恐慌税率）
     // - tax_config: 税率配置（包含所有税率参数）
-    let tax_calculation = TaxCalculator::calculate_tax(
-        amount,
-        Some(sender_holder),
-        ctx.accounts.mint.supply,
-        timestamp,
-        false, // 普通转账不是买入
-        is_sell,
-        tax_config,
-    )?;
+    // 
+    // 不含税模式下，amount是接收者应收到的净额，由计算器反推含税总额
+    let discount_holder = if is_buy {
+        receiver_holder.as_ref()
+    } else {
+        Some(&**sender_holder)
+    };
+    let circulating_supply = config.circulating_supply(accounts.mint.supply);
+    let tax_calculation = if tax_exclusive {
+        TaxCalculator::calculate_tax_exclusive(
+            amount,
+            discount_holder,
+            circulating_supply,
+            timestamp,
+            is_buy,
+            is_sell,
+            config.panic_mode,
+            tax_config,
+        )?
+    } else {
+        TaxCalculator::calculate_tax(
+            amount,
+            discount_holder,
+            circulating_supply,
+            timestamp,
+            is_buy,
+            is_sell,
+            config.panic_mode,
+            tax_config,
+        )?
+    };
+
+    // 积分兑换的税率折扣：发送者持有未到期的折扣时，从最终税率中扣除
+    let points_discount_bps = accounts.sender_points
+        .as_ref()
+        .map_or(0, |points| points.active_tax_discount_bps(timestamp));
+    let tax_calculation = if points_discount_bps > 0 {
+        TaxCalculator::apply_rate_reduction(&tax_calculation, points_discount_bps, tax_exclusive)?
+    } else {
+        tax_calculation
+    };
+
+    // 发送者被扣除的总额（含税模式下等于传入的amount）
+    let amount = tax_calculation.gross_amount()?;
 
     // 税率计算信息将在事件中记录，这里不输出msg!以节省gas
 
@@ -261,9 +420,11 @@ pub fn transfer_with_tax_handler(
     // 
     // 注意：这个验证在税收计算之后进行，确保tax_amount已经计算完成
     require!(
-        ctx.accounts.sender_token_account.amount >= amount,
+        accounts.sender_token_account.amount >= amount,
         TotError::InsufficientBalance
     );
+    // 转账后发送者的剩余余额（卖空时重置持有时间）
+    let sender_remaining = accounts.sender_token_account.amount - amount;
 
     // ========================================
     // 执行转账 - 净金额给接收者
@@ -274,18 +435,19 @@ pub fn transfer_with_tax_handler(
     
     if tax_calculation.net_amount > 0 {
         let transfer_ctx = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
+            accounts.token_program.to_account_info(),
             TransferChecked {
-                from: ctx.accounts.sender_token_account.to_account_info(),
-                mint: ctx.accounts.mint.to_account_info(),
-                to: ctx.accounts.receiver_token_account.to_account_info(),
-                authority: ctx.accounts.sender.to_account_info(),
+                from: accounts.sender_token_account.to_account_info(),
+                mint: accounts.mint.to_account_info(),
+                to: accounts.receiver_token_account.to_account_info(),
+                authority: accounts.sender.to_account_info(),
             },
         );
 
         // 执行转账（使用transfer_checked确保金额和精度正确）
-        token_interface::transfer_checked(
+        transfer_checked_with_hook(
             transfer_ctx,
+            hook_accounts,
             tax_calculation.net_amount,  // 转账净金额（扣除税收后）
             mint_decimals,  // 代币精度
         )?;
@@ -324,11 +486,11 @@ pub fn transfer_with_tax_handler(
         // 销毁代币会减少总供应量，实现通缩机制
         if tax_dist.to_burn > 0 {
             let burn_ctx = CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
+                accounts.token_program.to_account_info(),
                 Burn {
-                    mint: ctx.accounts.mint.to_account_info(),
-                    from: ctx.accounts.sender_token_account.to_account_info(),
-                    authority: ctx.accounts.sender.to_account_info(),
+                    mint: accounts.mint.to_account_info(),
+                    from: accounts.sender_token_account.to_account_info(),
+                    authority: accounts.sender.to_account_info(),
                 },
             );
 
@@ -345,19 +507,20 @@ pub fn transfer_with_tax_handler(
 
         if remaining_tax > 0 {
             let tax_transfer_ctx = CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
+                accounts.token_program.to_account_info(),
                 TransferChecked {
-                    from: ctx.accounts.sender_token_account.to_account_info(),
-                    mint: ctx.accounts.mint.to_account_info(),
-                    to: ctx.accounts.tax_collector.to_account_info(),
-                    authority: ctx.accounts.sender.to_account_info(),
+                    from: accounts.sender_token_account.to_account_info(),
+                    mint: accounts.mint.to_account_info(),
+                    to: accounts.tax_collector.to_account_info(),
+                    authority: accounts.sender.to_account_info(),
                 },
             );
 
             // 将剩余税收转入收集账户
             // 后续会按照比例分配到流动性池、社区、营销等
-            token_interface::transfer_checked(
+            transfer_checked_with_hook(
                 tax_transfer_ctx,
+                hook_accounts,
                 remaining_tax,
                 mint_decimals,
             )?;
@@ -370,6 +533,9 @@ pub fn transfer_with_tax_handler(
             remaining_tax,
             tax_calculation.tax_amount
         );
+
+        // 累计全局收税和销毁统计
+        accounts.config.record_tax(tax_calculation.tax_amount, tax_dist.to_burn)?;
     }
 
     // ========================================
@@ -382,25 +548,55 @@ pub fn transfer_with_tax_handler(
     
     // 更新发送者统计（如果是卖出操作）
     if is_sell {
-        sender_holder.record_sell(amount, tax_calculation.tax_amount, timestamp)?;
+        sender_holder.record_sell(
+            amount,
+            tax_calculation.tax_amount,
+            sender_remaining,
+            timestamp,
+        )?;
     }
     
     // 更新接收者统计（接收代币相当于买入操作）
     // 注意：接收者持有者账户可能不存在（新用户），需要先初始化
     // 如果存在，更新其买入统计和首次持有时间
-    if let Some(ref mut receiver_holder) = ctx.accounts.receiver_holder_info {
+    if let Some(ref mut receiver_holder) = receiver_holder {
         // 接收代币相当于买入操作，使用净金额（实际收到的代币数量）
-        // 接收者不支付税收（税收由发送者支付），所以tax_paid为0
+        // 从交易场所买入时税收计入接收者的已缴税额，其他转账的税收由发送者支付
         receiver_holder.record_buy(
             tax_calculation.net_amount,  // 接收者实际收到的代币数量
-            0,                           // 接收者不支付税收
+            if is_buy { tax_calculation.tax_amount } else { 0 },
             timestamp
         )?;
         
         // 如果接收者的代币账户地址未设置，更新为当前接收者代币账户
         if receiver_holder.token_account == Pubkey::default() {
-            receiver_holder.token_account = ctx.accounts.receiver_token_account.key();
+            receiver_holder.token_account = accounts.receiver_token_account.key();
         }
+        receiver_holder.store(&accounts.receiver_holder_info)?;
+    }
+
+    // ========================================
+    // 创建支付回执（提供支付引用时）
+    // ========================================
+    // 
+    // 回执PDA按引用派生，同一引用重复提交会在这里失败，整笔转账回滚。
+    
+    if let Some(reference) = reference {
+        create_payment_receipt(
+            program_id,
+            accounts.payment_receipt.as_ref(),
+            accounts.system_program.as_ref(),
+            accounts.sender.to_account_info(),
+            PaymentReceipt {
+                reference,
+                payer: sender_key,
+                recipient: receiver_owner,
+                kind: PaymentKind::TransferWithTax,
+                amount: tax_calculation.net_amount,
+                created_at: timestamp,
+                bump: 0,
+            },
+        )?;
     }
 
     // ========================================
@@ -418,6 +614,7 @@ pub fn transfer_with_tax_handler(
         net_amount: tax_calculation.net_amount,
         tax_rate_bps: tax_calculation.final_tax_bps,
         burned: tax_distribution.as_ref().map(|d| d.to_burn).unwrap_or(0),
+        reference,
         timestamp,
     });
 
@@ -451,6 +648,9 @@ pub struct TransferWithTaxEvent {
     /// 销毁的代币数量
     pub burned: u64,
     
+    /// 支付引用（未提供时为None）
+    pub reference: Option<[u8; 32]>,
+    
     /// 交易时间戳
     pub timestamp: i64,
 }
@@ -0,0 +1,291 @@
+// ============================================
+// 文件: src/instructions/voucher.rs
+// 奖励金库与签名兑换券指令
+// ============================================
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::state::config::{TotConfig, PauseFlag};
+use crate::state::holder::HolderAccount;
+use crate::state::voucher::{Voucher, RewardsVault, VoucherRedemption};
+use crate::constants::seeds;
+use crate::errors::TotError;
+use crate::utils::ed25519::verify_ed25519_instruction;
+use crate::utils::validation::validate_transfer_amount;
+use crate::utils::token_transfer::transfer_checked_with_hook;
+
+/// 初始化奖励金库账户结构
+#[derive(Accounts)]
+pub struct InitializeRewardsVault<'info> {
+    /// 系统管理员（支付租金）
+    #[account(
+        mut,
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// 全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        has_one = mint @ TotError::InvalidMint,
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 奖励金库PDA
+    #[account(
+        init,
+        payer = authority,
+        space = RewardsVault::LEN,
+        seeds = [seeds::REWARDS_VAULT_SEED],
+        bump
+    )]
+    pub rewards_vault: Account<'info, RewardsVault>,
+
+    /// 金库代币账户（奖励金库PDA的ATA）
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = rewards_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// TOT Mint
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 初始化奖励金库处理器
+///
+/// 创建奖励金库PDA及其代币账户。之后平台向金库代币账户转入奖励预算，
+/// 用户凭兑换券从金库领取。
+pub fn initialize_rewards_vault_handler(ctx: Context<InitializeRewardsVault>) -> Result<()> {
+    let vault = &mut ctx.accounts.rewards_vault;
+    vault.token_account = ctx.accounts.vault_token_account.key();
+    vault.total_redeemed = 0;
+    vault.redemption_count = 0;
+    vault.bump = ctx.bumps.rewards_vault;
+
+    msg!("Rewards vault initialized: {}", vault.token_account);
+    Ok(())
+}
+
+/// 兑换奖励兑换券账户结构
+#[derive(Accounts)]
+#[instruction(voucher: Voucher)]
+pub struct RedeemVoucher<'info> {
+    /// 接收者（签名者，支付核销记录租金）
+    #[account(
+        mut,
+        constraint = recipient.key() == voucher.recipient @ TotError::VoucherRecipientMismatch
+    )]
+    pub recipient: Signer<'info>,
+
+    /// 接收者代币账户
+    #[account(
+        mut,
+        constraint = recipient_token_account.owner == voucher.recipient @ TotError::VoucherRecipientMismatch,
+        constraint = recipient_token_account.mint == mint.key() @ TotError::InvalidMint
+    )]
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 接收者持有者信息（可选）
+    #[account(
+        mut,
+        seeds = [seeds::HOLDER_SEED, recipient.key().as_ref()],
+        bump = recipient_holder_info.bump
+    )]
+    pub recipient_holder_info: Option<Account<'info, HolderAccount>>,
+
+    /// 全局配置（累计平台分发统计）
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        has_one = mint @ TotError::InvalidMint,
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 奖励金库PDA
+    #[account(
+        mut,
+        seeds = [seeds::REWARDS_VAULT_SEED],
+        bump = rewards_vault.bump,
+    )]
+    pub rewards_vault: Account<'info, RewardsVault>,
+
+    /// 金库代币账户
+    #[account(
+        mut,
+        address = rewards_vault.token_account @ TotError::TokenAccountMismatch
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 核销记录PDA（已存在说明随机数已被使用，创建失败）
+    #[account(
+        init,
+        payer = recipient,
+        space = VoucherRedemption::LEN,
+        seeds = [seeds::VOUCHER_SEED, voucher.nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub voucher_redemption: Account<'info, VoucherRedemption>,
+
+    /// TOT Mint
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// 指令sysvar（读取Ed25519签名验证指令）
+    /// CHECK: 通过地址约束确认是指令sysvar
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 兑换奖励兑换券处理器
+///
+/// 校验紧邻的前一条Ed25519签名验证指令确实由`config.voucher_signer`签署了该兑换券，
+/// 然后由奖励金库PDA签名把奖励转给接收者（免税），并创建核销记录防止重放。
+///
+/// # 参数
+/// * `ctx` - 兑换上下文
+/// * `voucher` - 平台签发的兑换券
+///
+/// # 注意事项
+/// - 交易中本指令的前一条必须是Ed25519程序指令，签名、公钥、消息都内联在该指令中
+/// - 签名消息由`Voucher::message`构造，包含程序ID
+/// - 受`PauseFlag::PlatformTransfers`暂停位控制
+pub fn redeem_voucher_handler<'info>(ctx: Context<'_, '_, '_, 'info, RedeemVoucher<'info>>, voucher: Voucher) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let config = &ctx.accounts.config;
+
+    // ========================================
+    // 验证阶段
+    // ========================================
+
+    config.require_not_paused(PauseFlag::PlatformTransfers, timestamp)?;
+    validate_transfer_amount(voucher.amount)?;
+    require!(
+        config.voucher_signer != Pubkey::default(),
+        TotError::VoucherSignerNotSet
+    );
+    require!(timestamp <= voucher.expires_at, TotError::VoucherExpired);
+
+    if let Some(ref holder) = ctx.accounts.recipient_holder_info {
+        require!(!holder.is_frozen, TotError::HolderFrozen);
+    }
+
+    // 读取前一条指令，确认是voucher_signer对本兑换券的Ed25519签名验证
+    let instructions = ctx.accounts.instructions.to_account_info();
+    let current_index = load_current_index_checked(&instructions)?;
+    require!(current_index > 0, TotError::InvalidVoucherSignature);
+    let ed25519_ix = load_instruction_at_checked((current_index - 1) as usize, &instructions)?;
+    verify_ed25519_instruction(
+        &ed25519_ix,
+        &config.voucher_signer,
+        &voucher.message(ctx.program_id),
+    )?;
+
+    require!(
+        ctx.accounts.vault_token_account.amount >= voucher.amount,
+        TotError::InsufficientBalance
+    );
+
+    // ========================================
+    // 从奖励金库转账（金库PDA签名）
+    // ========================================
+
+    let vault_bump = ctx.accounts.rewards_vault.bump;
+    let vault_seeds: &[&[u8]] = &[seeds::REWARDS_VAULT_SEED, &[vault_bump]];
+
+    transfer_checked_with_hook(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.vault_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.recipient_token_account.to_account_info(),
+                authority: ctx.accounts.rewards_vault.to_account_info(),
+            },
+            &[vault_seeds],
+        ),
+        ctx.remaining_accounts,
+        voucher.amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    // ========================================
+    // 记录核销与统计
+    // ========================================
+
+    let voucher_signer = config.voucher_signer;
+    let redemption = &mut ctx.accounts.voucher_redemption;
+    redemption.nonce = voucher.nonce;
+    redemption.recipient = voucher.recipient;
+    redemption.amount = voucher.amount;
+    redemption.signer = voucher_signer;
+    redemption.redeemed_at = timestamp;
+    redemption.bump = ctx.bumps.voucher_redemption;
+
+    let vault = &mut ctx.accounts.rewards_vault;
+    vault.total_redeemed = vault.total_redeemed
+        .checked_add(voucher.amount)
+        .ok_or(TotError::MathOverflow)?;
+    vault.redemption_count = vault.redemption_count
+        .checked_add(1)
+        .ok_or(TotError::MathOverflow)?;
+
+    if let Some(ref mut holder) = ctx.accounts.recipient_holder_info {
+        // 兑换奖励相当于用户买入，接收者不支付税收
+        holder.record_buy(voucher.amount, 0, timestamp)?;
+        if holder.token_account == Pubkey::default() {
+            holder.token_account = ctx.accounts.recipient_token_account.key();
+        }
+    }
+
+    let config = &mut ctx.accounts.config;
+    config.total_platform_distributed = config.total_platform_distributed
+        .checked_add(voucher.amount)
+        .ok_or(TotError::MathOverflow)?;
+
+    emit!(VoucherRedeemed {
+        recipient: voucher.recipient,
+        amount: voucher.amount,
+        nonce: voucher.nonce,
+        signer: voucher_signer,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+/// 兑换券兑换事件
+#[event]
+pub struct VoucherRedeemed {
+    /// 接收者
+    pub recipient: Pubkey,
+    /// 兑换数量
+    pub amount: u64,
+    /// 兑换券随机数
+    pub nonce: u64,
+    /// 签发兑换券的签名公钥
+    pub signer: Pubkey,
+    /// 兑换时间
+    pub timestamp: i64,
+}
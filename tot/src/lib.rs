@@ -24,9 +24,10 @@
 // TOT Token 主程序入口
 // ============================================
 
+// Anchor指令参数与IDL一一对应，部分指令和税率计算函数的参数超过clippy默认上限
+#![allow(clippy::too_many_arguments)]
+
 use anchor_lang::prelude::*;
-use solana_program::program_error::ProgramError;
-use spl_transfer_hook_interface::instruction::TransferHookInstruction;
 
 // 模块声明
 pub mod constants;  // 常量定义模块
@@ -42,6 +43,8 @@ use instructions::{
     // 池子相关
     InitPool,
     MintToPools,
+    ReleasePoolTokens,
+    RefreshCirculatingSupply,
     // 持有者相关
     InitializeHolder,
     FreezeHolder,
@@ -55,37 +58,150 @@ use instructions::{
     // 消费相关
     ConsumeToTreasury,
     ConsumeType,
+    ConsumeReceiptArgs,
+    RedeemConsumeReceipt,
+    CloseConsumeReceipt,
+    // 价格目录相关
+    InitializePriceCatalog,
+    ManagePriceCatalog,
     // 平台转账相关
     PlatformTransfer,
+    // 平台预算相关
+    InitializePlatformBudget,
+    ManagePlatformBudget,
+    InitializePlatformVault,
+    // 奖励兑换券相关
+    InitializeRewardsVault,
+    RedeemVoucher,
+    // 订阅相关
+    CreateSubscription,
+    ChargeSubscription,
+    CancelSubscription,
+    // 商户与发票相关
+    RegisterMerchant,
+    UpdateMerchant,
+    CreateInvoice,
+    PayInvoice,
+    CloseInvoice,
+    // 合作程序相关
+    RegisterPartner,
+    UpdatePartner,
+    PartnerConsume,
+    PartnerTransferWithTax,
+    PartnerRecordHolderActivity,
+    HolderActivity,
+    // 积分相关
+    InitializePointsConfig,
+    UpdatePointsConfig,
+    InitializePointsAccount,
+    ClaimHoldingPoints,
+    RedeemPoints,
+    ConvertPointsToTaxDiscount,
+    // 批量转账相关
+    BatchTransfer,
+    BatchTransferEntry,
+    // 代币流相关
+    CreateStream,
+    WithdrawStream,
+    CancelStream,
+    // P2P挂单相关
+    CreateOffer,
+    FillOffer,
+    CancelOffer,
     // 管理员相关
-    UpdateAuthority,
+    ProposeAuthority,
+    AcceptAuthority,
+    CancelAuthorityTransfer,
+    SetAuthorityTransferDelay,
     SetPaused,
+    SetPanicMode,
     EmergencyWithdraw,
     SetTwsTreasury,
+    SetComplianceAuthority,
+    SetReceiptAuthority,
+    SetVoucherSigner,
     // 查询相关
     CalculateTax,
     GetHolderStats,
-    DiscountTier,
-    // Transfer Hook相关
-    InitializeTransferHook,
-    ExecuteTransferHook,
-    TransferHookAdminAction,
+    GetProtocolStats,
+    ProtocolStats,
+    GetPlatformBudget,
+    PlatformBudgetStatus,
+    GetPointsLeaderboard,
+    PointsLeaderboardEntry,
+    TaxCalculationResult,
+    HolderStats,
+    // Token-2022权限移交相关
+    ProposeTokenAuthority,
+    AcceptTokenAuthority,
+    CancelTokenAuthorityTransfer,
+    TokenAuthorityKind,
+    // 铸造权限相关
+    MigrateMintAuthority,
+    RenounceMintAuthority,
+    // 链上元数据相关
+    InitializeTokenMetadata,
+    UpdateTokenMetadata,
+    TokenMetadataField,
+    // 追回相关
+    Clawback,
+    MigratePermanentDelegate,
     // 资产上链相关
     MintAsset,
     // 拍卖相关
     CreateAuction,
     SeizeAuction,
 };
+// Anchor的#[program]宏在crate根路径下查找各账户结构生成的客户端模块
+// （`__client_accounts_*`、`__cpi_client_accounts_*`），因此在根部通配导出各指令模块
+use instructions::initialize::*;
+use instructions::init_pool::*;
+use instructions::mint_to_pools::*;
+use instructions::pool_release::*;
+use instructions::holder::*;
+use instructions::tax::*;
+use instructions::transfer::*;
+use instructions::consume::*;
+use instructions::platform_transfer::*;
+use instructions::batch_transfer::*;
+use instructions::stream::*;
+use instructions::offer::*;
+use instructions::catalog::*;
+use instructions::voucher::*;
+use instructions::platform_budget::*;
+use instructions::subscription::*;
+use instructions::merchant::*;
+use instructions::partner::*;
+use instructions::hook_stats::*;
+use instructions::points::*;
+use instructions::admin::*;
+use instructions::query::*;
+use instructions::asset_mint::*;
+use instructions::auction_create::*;
+use instructions::auction_seize::*;
+use instructions::token_authority::*;
+use instructions::mint_authority::*;
+use instructions::token_metadata::*;
+use instructions::clawback::*;
+
 use state::{
     // 初始化参数在state模块中定义
     InitializeParams,
     // 池子类型在state模块中定义，lib.rs中直接使用
     PoolType,
+    // 可暂停的指令族
+    PauseFlag,
+    // 追回方式
+    ClawbackAction,
+    // 奖励兑换券
+    Voucher,
+    // 积分配置参数
+    PointsConfigParams,
 };
 
-/// 程序ID声明
-/// 这是TOT代币程序的唯一标识符，部署后不可更改
-/// 注意：实际部署时需要替换为真实的程序ID
+// 程序ID声明
+// 这是TOT代币程序的唯一标识符，部署后不可更改
+// 注意：实际部署时需要替换为真实的程序ID
 declare_id!("ToT1111111111111111111111111111111111111111");
 
 /// TOT Token 主程序
@@ -112,9 +228,9 @@ pub mod tot_token {
     /// 
     /// 1. 创建Token-2022 Mint账户（包含所有扩展所需空间）
     /// 2. 初始化Transfer Fee扩展（交易税功能）
-    /// 3. 初始化Permanent Delegate扩展（永久代理权，用于强制转移/销毁）
+    /// 3. 初始化Permanent Delegate扩展（永久代理权由程序PDA持有，只能通过clawback使用）
     /// 4. 初始化Metadata Pointer扩展（可更新的元数据）
-    /// 5. 初始化Transfer Hook扩展（自定义转账逻辑，可选）
+    /// 5. 初始化Transfer Hook扩展（指向独立部署的Hook程序`TRANSFER_HOOK_PROGRAM_ID`）
     /// 6. 初始化全局配置账户（存储系统状态）
     /// 
     /// # 参数
@@ -216,7 +332,7 @@ pub mod tot_token {
     /// # 注意事项
     /// * 此指令只能执行一次，重复调用会失败
     /// * 必须在所有池子初始化完成后才能调用
-    /// * 需要Mint Authority权限
+    /// * 铸造权限由程序PDA（MINT_AUTHORITY_SEED）持有，由程序签名
     /// 
     /// # 使用示例
     /// ```rust
@@ -227,6 +343,7 @@ pub mod tot_token {
     ///         authority: deployer,
     ///         config: configPda,
     ///         mint: mintKeypair.publicKey,
+    ///         mintAuthority: mintAuthorityPda,
     ///         // ... 所有池子账户
     ///     })
     ///     .rpc();
@@ -235,6 +352,173 @@ pub mod tot_token {
         instructions::mint_to_pools::handler(ctx)
     }
 
+    /// 从池子释放代币
+    /// 
+    /// 按池子的时间锁和线性释放计划，把已解锁的代币转给指定账户。
+    /// 
+    /// # 功能说明
+    /// 
+    /// 1. 检查池子释放未被暂停、池子不需要多签、时间锁已解除
+    /// 2. 由池子PDA签名转出代币，累加池子的`released_amount`
+    /// 3. 从`TotConfig.locked_supply`中扣除释放数量，流通供应量随之增加
+    /// 
+    /// # 参数
+    /// * `ctx` - 释放上下文
+    /// * `pool_type` - 池子类型
+    /// * `amount` - 释放数量（不能超过`calculate_releasable`和池子余额）
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有系统管理员可以调用
+    /// * 外资统战池需要多签，本指令返回`PoolRequiresMultisig`
+    /// 
+    /// # 使用示例
+    /// ```rust
+    /// program.methods
+    ///     .releasePoolTokens({ historyLp: {} }, new anchor.BN(1000000000000))
+    ///     .accounts({...})
+    ///     .rpc();
+    /// ```
+    pub fn release_pool_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReleasePoolTokens<'info>>,
+        pool_type: PoolType,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::pool_release::release_pool_tokens_handler(ctx, pool_type, amount)
+    }
+
+    /// 刷新流通供应量
+    /// 
+    /// 按五个池子代币账户和TWS财库ATA的实际余额重新计算锁定供应量。
+    /// 大额交易附加税和恐慌税率阈值按`Mint总供应量 - 锁定供应量`计算。
+    /// 
+    /// # 参数
+    /// * `ctx` - 刷新上下文，包含五个池子及其代币账户和财库ATA
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 任何人都可以调用
+    /// * 池子释放、消费到财库时锁定供应量会自动更新，销毁直接反映在Mint总供应量中；
+    ///   财库转出等变化需要调用本指令同步
+    pub fn refresh_circulating_supply(ctx: Context<RefreshCirculatingSupply>) -> Result<()> {
+        instructions::pool_release::refresh_circulating_supply_handler(ctx)
+    }
+
+    /// 迁移铸造权限到程序PDA
+    /// 
+    /// 旧版本`initialize`把铸造权限留在部署者钱包。
+    /// 此指令一次性把铸造权限移交给`MINT_AUTHORITY_SEED` PDA，
+    /// 之后只有`mint_to_pools`能够铸造。
+    /// 
+    /// # 参数
+    /// * `ctx` - 迁移上下文，需要系统管理员和当前铸造权限持有者签名
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 新部署的Mint在`initialize`时已经使用PDA作为铸造权限，无需迁移
+    pub fn migrate_mint_authority(ctx: Context<MigrateMintAuthority>) -> Result<()> {
+        instructions::mint_authority::migrate_mint_authority_handler(ctx)
+    }
+
+    /// 放弃铸造权限
+    /// 
+    /// 全部代币铸造完成后（`total_minted == TOTAL_SUPPLY`），
+    /// 由程序PDA签名把Mint的铸造权限设置为None，TOT总量永久固定。
+    /// 
+    /// # 参数
+    /// * `ctx` - 放弃权限上下文
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，铸造未完成时返回MintingNotComplete
+    /// 
+    /// # 注意事项
+    /// * 此操作不可逆
+    pub fn renounce_mint_authority(ctx: Context<RenounceMintAuthority>) -> Result<()> {
+        instructions::mint_authority::renounce_mint_authority_handler(ctx)
+    }
+
+    /// 初始化链上元数据
+    /// 
+    /// 在Mint账户上写入Token-2022 TokenMetadata扩展，
+    /// 名称和符号使用`TOKEN_NAME`和`TOKEN_SYMBOL`，钱包和浏览器据此显示TOT。
+    /// 
+    /// # 参数
+    /// * `ctx` - 初始化元数据上下文，签名者必须是Metadata Pointer权限
+    /// * `uri` - 指向链下JSON元数据（图标、描述等）的URI
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 签名者支付Mint账户扩容所需的租金
+    /// * 需要铸造权限PDA签名，必须在`renounce_mint_authority`之前调用
+    /// * 只能初始化一次，之后使用`update_token_metadata_field`修改
+    /// 
+    /// # 使用示例
+    /// ```rust
+    /// program.methods
+    ///     .initializeTokenMetadata("https://example.com/tot.json")
+    ///     .accounts({
+    ///         authority: deployer,
+    ///         config: configPda,
+    ///         mint: mintPublicKey,
+    ///         mintAuthority: mintAuthorityPda,
+    ///         metadataAuthority: metadataAuthorityPda,
+    ///         tokenProgram: TOKEN_2022_PROGRAM_ID,
+    ///         systemProgram: SystemProgram.programId,
+    ///     })
+    ///     .rpc();
+    /// ```
+    pub fn initialize_token_metadata(
+        ctx: Context<InitializeTokenMetadata>,
+        uri: String,
+    ) -> Result<()> {
+        instructions::token_metadata::initialize_token_metadata_handler(ctx, uri)
+    }
+
+    /// 更新链上元数据字段
+    /// 
+    /// 更新名称、符号、URI，或添加/覆盖自定义键值对（如税收政策链接）。
+    /// 
+    /// # 参数
+    /// * `ctx` - 更新元数据上下文，签名者必须是Metadata Pointer权限
+    /// * `field` - 要更新的字段
+    /// * `value` - 新的字段值
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 内容变长时签名者需要支付Mint账户扩容的租金
+    pub fn update_token_metadata_field(
+        ctx: Context<UpdateTokenMetadata>,
+        field: TokenMetadataField,
+        value: String,
+    ) -> Result<()> {
+        instructions::token_metadata::update_token_metadata_field_handler(ctx, field, value)
+    }
+
+    /// 删除自定义元数据字段
+    /// 
+    /// # 参数
+    /// * `ctx` - 更新元数据上下文，签名者必须是Metadata Pointer权限
+    /// * `key` - 自定义字段名
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，字段不存在时返回错误
+    pub fn remove_token_metadata_key(
+        ctx: Context<UpdateTokenMetadata>,
+        key: String,
+    ) -> Result<()> {
+        instructions::token_metadata::remove_token_metadata_key_handler(ctx, key)
+    }
+
     // ============================================
     // 持有者管理指令
     // ============================================
@@ -383,6 +667,7 @@ pub mod tot_token {
     ///   - 忠诚度权重γ: 20% (用于持有时间奖励)
     ///   - 恐慌阈值: 0.5% (触发恐慌模式的池子深度比例)
     ///   - 恐慌税率: 30% (恐慌模式下的税率)
+    ///   - 买入税率: 5% (从已登记交易场所买入的基础税率)
     /// 
     /// # 参数
     /// * `ctx` - 初始化税率配置上下文
@@ -425,6 +710,7 @@ pub mod tot_token {
     /// - `gamma_bps`: 忠诚度权重（basis points）
     /// - `panic_threshold_bps`: 恐慌模式触发阈值（basis points）
     /// - `panic_tax_bps`: 恐慌模式税率（basis points）
+    /// - `buy_tax_bps`: 买入税率（basis points，从已登记交易场所买入时使用）
     /// 
     /// # 参数
     /// * `ctx` - 更新税率配置上下文
@@ -434,6 +720,7 @@ pub mod tot_token {
     /// * `gamma_bps` - 可选，新的忠诚度权重（basis points）
     /// * `panic_threshold_bps` - 可选，新的恐慌阈值（basis points）
     /// * `panic_tax_bps` - 可选，新的恐慌税率（basis points）
+    /// * `buy_tax_bps` - 可选，新的买入税率（basis points，最大9900 = 99%）
     /// 
     /// # 返回值
     /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
@@ -450,7 +737,7 @@ pub mod tot_token {
     /// program.methods
     ///     .updateTaxConfig(
     ///         Some(300), // 基础税率改为3%
-    ///         None, None, None, None, None
+    ///         None, None, None, None, None, None
     ///     )
     ///     .accounts({...})
     ///     .rpc();
@@ -463,6 +750,7 @@ pub mod tot_token {
         gamma_bps: Option<u16>,
         panic_threshold_bps: Option<u16>,
         panic_tax_bps: Option<u16>,
+        buy_tax_bps: Option<u16>,
     ) -> Result<()> {
         instructions::tax::update_tax_config_handler(
             ctx,
@@ -472,6 +760,7 @@ pub mod tot_token {
             gamma_bps,
             panic_threshold_bps,
             panic_tax_bps,
+            buy_tax_bps,
         )
     }
 
@@ -552,6 +841,69 @@ pub mod tot_token {
         instructions::tax::remove_tax_exempt_handler(ctx, address)
     }
 
+    /// 登记交易场所
+    /// 
+    /// 将DEX池、代币销售合约等交易场所登记到税率配置中。
+    /// 从交易场所转出的代币视为接收者买入，按买入税率计税。
+    /// 
+    /// # 功能说明
+    /// 
+    /// 登记后：
+    /// - `transfer_with_tax`、`batch_transfer`的发送者是交易场所时，按买入计税
+    ///   （基础税率为`buy_tax_bps`，持有时间折扣按接收者计算，大额交易附加税同样适用）
+    /// - 接收者的持有者统计记录买入时支付的税额
+    /// 
+    /// # 参数
+    /// * `ctx` - 管理税率配置上下文（与免税地址管理共用）
+    /// * `address` - 交易场所地址（代币账户的所有者）
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有管理员可以执行此操作
+    /// * 最多登记20个交易场所
+    /// * 同时在免税列表中的交易场所仍然免税
+    /// 
+    /// # 使用示例
+    /// ```rust
+    /// program.methods
+    ///     .addTradingVenue(dexPoolAuthority)
+    ///     .accounts({...})
+    ///     .rpc();
+    /// ```
+    pub fn add_trading_venue(
+        ctx: Context<ManageTaxExempt>,
+        address: Pubkey,
+    ) -> Result<()> {
+        instructions::tax::add_trading_venue_handler(ctx, address)
+    }
+
+    /// 移除交易场所
+    /// 
+    /// 从交易场所列表中移除指定地址，之后从该地址转出的代币按普通转账计税。
+    /// 
+    /// # 参数
+    /// * `ctx` - 管理税率配置上下文（与免税地址管理共用）
+    /// * `address` - 要移除的交易场所地址
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，地址未登记时返回`VenueNotRegistered`
+    /// 
+    /// # 使用示例
+    /// ```rust
+    /// program.methods
+    ///     .removeTradingVenue(dexPoolAuthority)
+    ///     .accounts({...})
+    ///     .rpc();
+    /// ```
+    pub fn remove_trading_venue(
+        ctx: Context<ManageTaxExempt>,
+        address: Pubkey,
+    ) -> Result<()> {
+        instructions::tax::remove_trading_venue_handler(ctx, address)
+    }
+
     // ============================================
     // 转账相关（带税收）
     // ============================================
@@ -588,12 +940,14 @@ pub mod tot_token {
     /// * `is_sell` - 是否为卖出操作
     ///   - `true`: 卖出操作，会计算大额交易惩罚
     ///   - `false`: 普通转账，不计算大额交易惩罚
+    /// * `reference` - 支付引用（可选，32字节），写入事件并创建回执PDA `["tot_payment_receipt", payer, reference]`
     /// 
     /// # 返回值
     /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
     /// 
     /// # 注意事项
     /// * 发送者账户不能处于冻结状态
+    /// * 提供支付引用时需要传入payment_receipt和system_program，同一引用只能使用一次
     /// * 系统处于恐慌模式时，卖出操作会被拒绝
     /// * 税收会立即分配，不会累积
     /// * 免税地址（如流动性池）的转账不收取税收
@@ -605,7 +959,8 @@ pub mod tot_token {
     /// program.methods
     ///     .transferWithTax(
     ///         new anchor.BN(1000000), // 转账100万代币
-    ///         true // 是卖出操作
+    ///         true, // 是卖出操作
+    ///         null // 不使用支付引用
     ///     )
     ///     .accounts({
     ///         sender: user,
@@ -615,12 +970,55 @@ pub mod tot_token {
     ///     })
     ///     .rpc();
     /// ```
-    pub fn transfer_with_tax(
-        ctx: Context<TransferWithTax>,
+    pub fn transfer_with_tax<'info>(
+        ctx: Context<'_, '_, '_, 'info, TransferWithTax<'info>>,
         amount: u64,
         is_sell: bool,
+        reference: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::transfer::transfer_with_tax_handler(ctx, amount, is_sell, reference)
+    }
+
+    /// 不含税转账（接收者收到精确金额）
+    /// 
+    /// 与`transfer_with_tax`相反，税额不从转账金额中扣除，而是另外由发送者支付。
+    /// 适用于按标价付款，例如购买标价100 TOT的应用内物品。
+    /// 
+    /// # 功能说明
+    /// 
+    /// 1. 按`net_amount`反推含税总额（`TaxCalculator::calculate_tax_exclusive`）
+    /// 2. 向接收者转账`net_amount`
+    /// 3. 税额按与`transfer_with_tax`相同的比例销毁和分配
+    /// 
+    /// # 参数
+    /// * `ctx` - 转账上下文，账户与`transfer_with_tax`相同
+    /// * `net_amount` - 接收者实际收到的金额
+    /// * `is_sell` - 是否为卖出操作
+    /// * `reference` - 支付引用（可选，32字节），写入事件并创建回执PDA `["tot_payment_receipt", payer, reference]`
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 发送者余额必须覆盖"净额 + 税额"
+    /// * 含税总额跨过大额交易档位时，按更高档位的税率计算
+    /// * 免税地址的转账直接转出`net_amount`
+    /// 
+    /// # 使用示例
+    /// ```rust
+    /// // 接收者恰好收到100 TOT
+    /// program.methods
+    ///     .transferExactWithTax(new anchor.BN(100_000_000), false, orderReference)
+    ///     .accounts({...})
+    ///     .rpc();
+    /// ```
+    pub fn transfer_exact_with_tax<'info>(
+        ctx: Context<'_, '_, '_, 'info, TransferWithTax<'info>>,
+        net_amount: u64,
+        is_sell: bool,
+        reference: Option<[u8; 32]>,
     ) -> Result<()> {
-        instructions::transfer::transfer_with_tax_handler(ctx, amount, is_sell)
+        instructions::transfer::transfer_exact_with_tax_handler(ctx, net_amount, is_sell, reference)
     }
 
     /// 用户向TWS财库消费（免税）
@@ -632,9 +1030,11 @@ pub mod tot_token {
     /// 
     /// 1. 验证转账合法性（系统状态、账户冻结状态、金额等）
     /// 2. 验证接收者是否为配置的TWS财库地址
-    /// 3. 执行全额转账（无税收）
-    /// 4. 更新用户消费统计
-    /// 5. 发出消费事件
+    /// 3. 按价格目录校验消费金额
+    /// 4. 按收入分成销毁、转入财库、转给受益人（无税收）
+    /// 5. 更新用户消费统计
+    /// 6. 提供回执参数时创建消费回执PDA
+    /// 7. 发出消费事件
     /// 
     /// # 参数
     /// * `ctx` - 消费转账上下文，包含用户、财库、配置等账户
@@ -643,6 +1043,10 @@ pub mod tot_token {
     ///   - `MapAction`: 地图功能操作（修缮妈祖庙、放飞孔明灯、祭拜祖先等）
     ///   - `AncestorMarking`: 祖籍标记
     ///   - `Other`: 其他消费
+    /// * `sku` - 商品SKU（0表示该消费类型的默认价格）
+    /// * `reference` - 支付引用（可选，32字节），写入事件并创建回执PDA `["tot_payment_receipt", payer, reference]`
+    /// * `receipt` - 消费回执参数（可选），创建回执PDA `["tot_consume_receipt", user, nonce]`，
+    ///   记录金额、消费类型、Slot、时间和商品引用，供后端核销
     /// 
     /// # 返回值
     /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
@@ -652,6 +1056,10 @@ pub mod tot_token {
     /// * TWS财库地址必须已配置（不能是占位符）
     /// * 消费不收取税收，全额转账给TWS财库
     /// * 消费会更新用户的消费统计
+    /// * 价格目录为该消费类型登记了价格时，金额不等于目录价格返回`PriceMismatch`，
+    ///   SKU未登记返回`PriceNotListed`，条目已下架返回`PriceInactive`
+    /// * 配置了收入分成时，受益人份额只会转给价格目录为该商品登记的受益人，
+    ///   需要传入其代币账户`beneficiaryTokenAccount`；未登记受益人时并入财库
     /// 
     /// # 使用示例
     /// ```rust
@@ -659,175 +1067,1470 @@ pub mod tot_token {
     /// program.methods
     ///     .consumeToTreasury(
     ///         new anchor.BN(100000000), // 消费100个代币
-    ///         { mapAction: {} } // 地图操作
+    ///         { mapAction: {} }, // 地图操作
+    ///         0,    // 默认SKU
+    ///         null, // 不使用支付引用
+    ///         null  // 不创建消费回执
     ///     )
     ///     .accounts({...})
     ///     .rpc();
     /// ```
-    pub fn consume_to_treasury(
-        ctx: Context<ConsumeToTreasury>,
+    pub fn consume_to_treasury<'info>(
+        ctx: Context<'_, '_, '_, 'info, ConsumeToTreasury<'info>>,
         amount: u64,
         consume_type: ConsumeType,
+        sku: u32,
+        reference: Option<[u8; 32]>,
+        receipt: Option<ConsumeReceiptArgs>,
     ) -> Result<()> {
-        instructions::consume::consume_to_treasury_handler(ctx, amount, consume_type)
+        instructions::consume::consume_to_treasury_handler(ctx, amount, consume_type, sku, reference, receipt)
     }
 
-    /// 平台向用户转账（免税）
-    /// 
-    /// 平台钱包向用户转账，不收取任何税收。用于TOT购买订单完成后的转账等场景。
+    /// 核销消费回执
     /// 
-    /// # 功能说明
-    /// 
-    /// 1. 验证权限（只有系统管理员可以执行）
-    /// 2. 验证转账合法性（账户冻结状态、金额等）
-    /// 3. 执行全额转账（无税收）
-    /// 4. 更新用户买入统计（如果是首次接收，初始化持有时间）
-    /// 5. 发出转账事件
+    /// 后端确认已为某笔消费提供服务后，把对应的消费回执标记为已核销。
     /// 
     /// # 参数
-    /// * `ctx` - 平台转账上下文，包含平台、用户、配置等账户
-    /// * `amount` - 转账金额（全额转账，无税收）
+    /// * `ctx` - 核销上下文，包含核销者、全局配置和消费回执
     /// 
     /// # 返回值
     /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
     /// 
     /// # 注意事项
-    /// * 只有系统管理员（config.authority）可以执行此操作
-    /// * 用户账户不能处于冻结状态
-    /// * 转账不收取税收，全额转账给用户
-    /// * 会更新用户的买入统计和首次持有时间
+    /// * 只有系统管理员或回执核销权限（`set_receipt_authority`）可以核销
+    /// * 每张回执只能核销一次，重复核销返回`ConsumeReceiptAlreadyRedeemed`
     /// 
     /// # 使用示例
     /// ```rust
-    /// // 执行平台转账
     /// program.methods
-    ///     .platformTransfer(
-    ///         new anchor.BN(1000000000) // 转账1000个代币
-    ///     )
-    ///     .accounts({...})
+    ///     .redeemConsumeReceipt()
+    ///     .accounts({ redeemer: backend, config: configPda, consumeReceipt: receiptPda })
+    ///     .signers([backendKeypair])
     ///     .rpc();
     /// ```
-    pub fn platform_transfer(
-        ctx: Context<PlatformTransfer>,
-        amount: u64,
-    ) -> Result<()> {
-        instructions::platform_transfer::platform_transfer_handler(ctx, amount)
+    pub fn redeem_consume_receipt(ctx: Context<RedeemConsumeReceipt>) -> Result<()> {
+        instructions::consume::redeem_consume_receipt_handler(ctx)
     }
 
-    // ============================================
-    // 管理员功能
-    // ============================================
+    /// 关闭消费回执
+    /// 
+    /// 关闭已核销的消费回执，租金退还给消费用户。
+    /// 
+    /// # 参数
+    /// * `ctx` - 关闭上下文
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 消费用户本人、系统管理员或回执核销权限都可以关闭
+    /// * 未核销的回执返回`ConsumeReceiptNotRedeemed`
+    pub fn close_consume_receipt(ctx: Context<CloseConsumeReceipt>) -> Result<()> {
+        instructions::consume::close_consume_receipt_handler(ctx)
+    }
 
-    /// 更新管理员
+    /// 初始化价格目录
     /// 
-    /// 将系统管理员权限转移给新的地址。这是重要的安全操作，用于多签钱包
-    /// 升级或权限移交。
+    /// 创建全局价格目录PDA `["tot_price_catalog"]`，初始为空。
     /// 
-    /// # 功能说明
+    /// # 参数
+    /// * `ctx` - 初始化上下文
     /// 
-    /// 1. 验证当前调用者是否为管理员
-    /// 2. 验证新管理员地址的有效性（不能为空地址）
-    /// 3. 更新全局配置中的管理员地址
-    /// 4. 发出权限更新事件
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有系统管理员可以执行此操作
+    /// * `consume_to_treasury`需要传入价格目录账户，部署后应先执行本指令
+    pub fn initialize_price_catalog(ctx: Context<InitializePriceCatalog>) -> Result<()> {
+        instructions::catalog::initialize_price_catalog_handler(ctx)
+    }
+
+    /// 设置目录价格
+    /// 
+    /// 新增或更新（消费类型, SKU）的价格条目，发出`CatalogPriceUpdated`事件。
     /// 
     /// # 参数
-    /// * `ctx` - 更新权限上下文
-    /// * `new_authority` - 新的管理员地址（Pubkey）
+    /// * `ctx` - 目录维护上下文
+    /// * `consume_type` - 消费类型
+    /// * `sku` - 商品SKU（0表示该消费类型的默认价格）
+    /// * `price` - 价格（基础单位，0表示不限金额，此时必须登记受益人）
+    /// * `discount_bps` - 持有者折扣（basis points，最大10000）
+    /// * `min_holding_days` - 享受折扣所需的最少持有天数
+    /// * `is_active` - 是否上架
+    /// * `beneficiary` - 收入分成受益人（如功能运营方，Pubkey::default()表示不登记）
     /// 
     /// # 返回值
     /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
     /// 
     /// # 注意事项
-    /// * 只有当前管理员可以执行此操作
-    /// * 新管理员地址不能为空地址（Pubkey::default()）
-    /// * 权限转移后，旧管理员将失去所有管理权限
-    /// * 建议转移到多签钱包以提高安全性
-    /// * 此操作会发出链上事件，便于审计
+    /// * 只有系统管理员可以执行此操作
+    /// * 目录最多`MAX_CATALOG_ENTRIES`（64）个条目，已满时返回`CatalogFull`
+    /// * 某个消费类型登记任一价格后，该类型的所有消费都按目录校验
     /// 
     /// # 使用示例
     /// ```rust
-    /// // 将管理员权限转移到多签钱包
+    /// // 地图操作默认价格100 TOT，持有满180天打9折
     /// program.methods
-    ///     .updateAuthority(multisigWallet)
-    ///     .accounts({
-    ///         authority: currentAdmin,
-    ///         config: configPda,
-    ///     })
+    ///     .setCatalogPrice({ mapAction: {} }, 0, new anchor.BN(100_000_000), 1000, 180, true, PublicKey.default)
+    ///     .accounts({ authority, config, priceCatalog })
     ///     .rpc();
     /// ```
-    pub fn update_authority(
-        ctx: Context<UpdateAuthority>,
-        new_authority: Pubkey,
+    pub fn set_catalog_price(
+        ctx: Context<ManagePriceCatalog>,
+        consume_type: ConsumeType,
+        sku: u32,
+        price: u64,
+        discount_bps: u16,
+        min_holding_days: u16,
+        is_active: bool,
+        beneficiary: Pubkey,
     ) -> Result<()> {
-        instructions::admin::update_authority_handler(ctx, new_authority)
+        instructions::catalog::set_catalog_price_handler(
+            ctx,
+            consume_type,
+            sku,
+            price,
+            discount_bps,
+            min_holding_days,
+            is_active,
+            beneficiary,
+        )
     }
 
-    /// 暂停/恢复系统
+    /// 删除目录价格
     /// 
-    /// 管理员可以暂停或恢复整个系统。暂停后，所有卖出操作将被拒绝，
-    /// 但买入和普通转账仍可进行（取决于具体实现）。
+    /// 删除（消费类型, SKU）的价格条目，发出`CatalogPriceRemoved`事件。
     /// 
-    /// # 功能说明
+    /// # 参数
+    /// * `ctx` - 目录维护上下文
+    /// * `consume_type` - 消费类型
+    /// * `sku` - 商品SKU
     /// 
-    /// 1. 验证调用者是否为管理员
-    /// 2. 更新系统的暂停状态（panic_mode字段）
-    /// 3. 发出系统状态变更事件
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有系统管理员可以执行此操作
+    /// * 条目不存在返回`PriceNotListed`
+    /// * 消费类型的条目全部删除后，该类型不再限制消费金额
+    pub fn remove_catalog_price(
+        ctx: Context<ManagePriceCatalog>,
+        consume_type: ConsumeType,
+        sku: u32,
+    ) -> Result<()> {
+        instructions::catalog::remove_catalog_price_handler(ctx, consume_type, sku)
+    }
+
+    /// 设置收入分成
+    /// 
+    /// 设置消费类型的收入在销毁、TWS财库和受益人之间的分配比例，
+    /// 发出`RevenueSplitUpdated`事件。
     /// 
     /// # 参数
-    /// * `ctx` - 设置暂停状态上下文
-    /// * `paused` - 暂停状态
-    ///   - `true`: 暂停系统（启用恐慌模式）
-    ///   - `false`: 恢复系统（关闭恐慌模式）
+    /// * `ctx` - 目录维护上下文
+    /// * `consume_type` - 消费类型
+    /// * `burn_bps` - 销毁比例（basis points）
+    /// * `treasury_bps` - 财库比例（basis points）
+    /// * `beneficiary_bps` - 受益人比例（basis points）
     /// 
     /// # 返回值
     /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
     /// 
     /// # 注意事项
-    /// * 只有管理员可以执行此操作
-    /// * 暂停状态下，卖出操作会被拒绝
-    /// * 暂停状态用于应对市场异常或安全威胁
-    /// * 建议在暂停前通知社区
+    /// * 只有系统管理员可以执行此操作
+    /// * 三项之和必须为10000，否则返回`InvalidRevenueSplit`；三项全部为0表示取消分成
+    /// * 受益人由价格目录条目登记，商品未登记受益人时受益人份额并入财库
     /// 
     /// # 使用示例
     /// ```rust
-    /// // 暂停系统（启用恐慌模式）
+    /// // 预测下注：20%销毁，50%财库，30%给市场创建者
     /// program.methods
-    ///     .setPaused(true)
-    ///     .accounts({
-    ///         authority: admin,
-    ///         config: configPda,
-    ///     })
+    ///     .setRevenueSplit({ predictionBet: {} }, 2000, 5000, 3000)
+    ///     .accounts({ authority, config, priceCatalog })
+    ///     .rpc();
+    /// // 按市场登记创建者（价格0表示下注金额不限）
+    /// program.methods
+    ///     .setCatalogPrice({ predictionBet: {} }, marketSku, new anchor.BN(0), 0, 0, true, marketCreator)
+    ///     .accounts({ authority, config, priceCatalog })
     ///     .rpc();
     /// ```
-    pub fn set_paused(
-        ctx: Context<SetPaused>,
-        paused: bool,
+    pub fn set_revenue_split(
+        ctx: Context<ManagePriceCatalog>,
+        consume_type: ConsumeType,
+        burn_bps: u16,
+        treasury_bps: u16,
+        beneficiary_bps: u16,
     ) -> Result<()> {
-        instructions::admin::set_paused_handler(ctx, paused)
+        instructions::catalog::set_revenue_split_handler(
+            ctx,
+            consume_type,
+            burn_bps,
+            treasury_bps,
+            beneficiary_bps,
+        )
     }
 
-    /// 紧急提取（仅限紧急情况）
+    /// 平台向用户转账（免税）
     /// 
-    /// 在系统处于暂停状态时，管理员可以从指定池子提取代币。
-    /// 这是最后的安全措施，仅在极端情况下使用。
+    /// 平台钱包向用户转账，不收取任何税收。用于TOT购买订单完成后的转账等场景。
     /// 
     /// # 功能说明
     /// 
-    /// 1. 验证系统处于暂停状态
-    /// 2. 验证调用者是否为管理员
-    /// 3. 从源账户转移指定数量的代币到目标账户
-    /// 4. 发出紧急提取事件
+    /// 1. 验证权限（系统管理员或已登记的平台热钱包）
+    /// 2. 验证转账合法性（账户冻结状态、金额等）
+    /// 3. 扣减平台预算（周期总预算和接收者额度）
+    /// 4. 从平台金库执行全额转账（平台预算PDA签名，无税收）
+    /// 5. 更新用户买入统计（如果是首次接收，初始化持有时间）
+    /// 6. 发出转账事件
     /// 
     /// # 参数
-    /// * `ctx` - 紧急提取上下文
-    /// * `amount` - 提取的代币数量
+    /// * `ctx` - 平台转账上下文，包含平台、平台金库、用户、配置等账户
+    /// * `amount` - 转账金额（全额转账，无税收）
+    /// * `reference` - 支付引用（可选，32字节），写入事件并创建回执PDA `["tot_payment_receipt", payer, reference]`
     /// 
     /// # 返回值
     /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
     /// 
     /// # 注意事项
-    /// * 只有管理员可以执行此操作
-    /// * 系统必须处于暂停状态才能执行
+    /// * 系统管理员（config.authority）或平台预算中登记的热钱包可以执行此操作
+    /// * 代币从平台金库（平台预算PDA的ATA，见`initialize_platform_vault`）转出，签名者不持有代币
+    /// * 支付回执的付款方为平台预算PDA，换用其他热钱包重试同一引用同样会被拒绝
+    /// * 超出周期预算返回`PlatformBudgetExceeded`，超出单个接收者额度返回`RecipientBudgetExceeded`
+    /// * 首次向某个接收者转账时创建接收者额度PDA `["tot_platform_recipient", recipient]`，租金由平台签名者支付
+    /// * 后端重试时使用同一支付引用，已成功的请求会以`DuplicatePaymentReference`失败，不会重复付款
+    /// * 用户账户不能处于冻结状态
+    /// * 转账不收取税收，全额转账给用户
+    /// * 会更新用户的买入统计和首次持有时间
+    /// 
+    /// # 使用示例
+    /// ```rust
+    /// // 执行平台转账
+    /// program.methods
+    ///     .platformTransfer(
+    ///         new anchor.BN(1000000000), // 转账1000个代币
+    ///         orderReference // 订单号哈希（32字节）
+    ///     )
+    ///     .accounts({...})
+    ///     .rpc();
+    /// ```
+    pub fn platform_transfer<'info>(
+        ctx: Context<'_, '_, '_, 'info, PlatformTransfer<'info>>,
+        amount: u64,
+        reference: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::platform_transfer::platform_transfer_handler(ctx, amount, reference)
+    }
+
+    /// 初始化平台预算
+    /// 
+    /// 创建平台预算PDA `["tot_platform_budget"]`，第一个周期从当前时间开始。
+    /// 
+    /// # 参数
+    /// * `ctx` - 初始化上下文
+    /// * `period_seconds` - 周期长度（秒，如86400表示按天）
+    /// * `period_cap` - 每个周期的平台转账总预算
+    /// * `per_recipient_cap` - 每个周期向单个接收者转账的额度
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有系统管理员可以执行此操作
+    /// * 参数为0或单个接收者额度大于周期预算时返回`InvalidPlatformBudget`
+    /// * `platform_transfer`需要传入平台预算账户，部署后应先执行本指令
+    pub fn initialize_platform_budget(
+        ctx: Context<InitializePlatformBudget>,
+        period_seconds: i64,
+        period_cap: u64,
+        per_recipient_cap: u64,
+    ) -> Result<()> {
+        instructions::platform_budget::initialize_platform_budget_handler(
+            ctx,
+            period_seconds,
+            period_cap,
+            per_recipient_cap,
+        )
+    }
+
+    /// 更新平台预算
+    /// 
+    /// # 参数
+    /// * `ctx` - 预算维护上下文
+    /// * `period_seconds` - 周期长度（秒）
+    /// * `period_cap` - 每个周期的平台转账总预算
+    /// * `per_recipient_cap` - 每个周期向单个接收者转账的额度
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有系统管理员可以执行此操作
+    /// * 当前周期已用额度保留，新额度立即生效
+    pub fn update_platform_budget(
+        ctx: Context<ManagePlatformBudget>,
+        period_seconds: i64,
+        period_cap: u64,
+        per_recipient_cap: u64,
+    ) -> Result<()> {
+        instructions::platform_budget::update_platform_budget_handler(
+            ctx,
+            period_seconds,
+            period_cap,
+            per_recipient_cap,
+        )
+    }
+
+    /// 登记平台热钱包
+    /// 
+    /// 登记后该地址可以调用`platform_transfer`（仍受预算限制），
+    /// 服务器不再需要持有系统管理员私钥。
+    /// 
+    /// # 参数
+    /// * `ctx` - 预算维护上下文
+    /// * `signer` - 热钱包地址
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有系统管理员可以执行此操作
+    /// * 最多登记`MAX_PLATFORM_SIGNERS`（10）个热钱包
+    pub fn add_platform_signer(
+        ctx: Context<ManagePlatformBudget>,
+        signer: Pubkey,
+    ) -> Result<()> {
+        instructions::platform_budget::add_platform_signer_handler(ctx, signer)
+    }
+
+    /// 移除平台热钱包
+    /// 
+    /// # 参数
+    /// * `ctx` - 预算维护上下文
+    /// * `signer` - 热钱包地址
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有系统管理员可以执行此操作
+    /// * 热钱包私钥泄露时应立即移除
+    pub fn remove_platform_signer(
+        ctx: Context<ManagePlatformBudget>,
+        signer: Pubkey,
+    ) -> Result<()> {
+        instructions::platform_budget::remove_platform_signer_handler(ctx, signer)
+    }
+
+    /// 初始化平台金库
+    /// 
+    /// 创建平台预算PDA `["tot_platform_budget"]`持有的代币账户（平台预算PDA的ATA）。
+    /// 
+    /// # 参数
+    /// * `ctx` - 初始化上下文
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有系统管理员可以执行此操作，需要先初始化平台预算
+    /// * `platform_transfer`只从平台金库转出，平台资金通过普通转账转入金库
+    pub fn initialize_platform_vault(ctx: Context<InitializePlatformVault>) -> Result<()> {
+        instructions::platform_budget::initialize_platform_vault_handler(ctx)
+    }
+
+    /// 初始化奖励金库
+    /// 
+    /// 创建奖励金库PDA `["tot_rewards_vault"]`及其代币账户（金库PDA的ATA）。
+    /// 
+    /// # 参数
+    /// * `ctx` - 初始化上下文
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有系统管理员可以执行此操作
+    /// * 奖励预算通过普通转账转入金库代币账户
+    pub fn initialize_rewards_vault(ctx: Context<InitializeRewardsVault>) -> Result<()> {
+        instructions::voucher::initialize_rewards_vault_handler(ctx)
+    }
+
+    /// 兑换奖励兑换券
+    /// 
+    /// 用户提交平台离线签名的兑换券，从奖励金库领取奖励（免税）。
+    /// 平台签名公钥（`config.voucher_signer`）与系统管理员相互独立，
+    /// 发放奖励不再需要管理员私钥在线签名。
+    /// 
+    /// # 功能说明
+    /// 
+    /// 1. 通过指令sysvar读取前一条Ed25519签名验证指令
+    /// 2. 校验签名公钥为`config.voucher_signer`、签名消息为该兑换券
+    /// 3. 校验兑换券未过期、提交者为接收者
+    /// 4. 由金库PDA签名转账给接收者
+    /// 5. 创建核销记录PDA `["tot_voucher", nonce]`，同一随机数不能再次兑换
+    /// 
+    /// # 参数
+    /// * `ctx` - 兑换上下文
+    /// * `voucher` - 兑换券（recipient, amount, expires_at, nonce）
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 签名消息 = "TOT_VOUCHER_V1" || 程序ID || recipient || amount(LE) || expires_at(LE) || nonce(LE)
+    /// * Ed25519指令必须紧邻在本指令之前，且签名、公钥、消息内联在该指令中
+    /// * 受平台转账暂停位控制
+    /// 
+    /// # 使用示例
+    /// ```rust
+    /// const message = buildVoucherMessage(programId, voucher);
+    /// const signature = nacl.sign.detached(message, voucherSigner.secretKey);
+    /// await program.methods
+    ///     .redeemVoucher(voucher)
+    ///     .accounts({...})
+    ///     .preInstructions([
+    ///         Ed25519Program.createInstructionWithPublicKey({
+    ///             publicKey: voucherSigner.publicKey.toBytes(),
+    ///             message,
+    ///             signature,
+    ///         }),
+    ///     ])
+    ///     .rpc();
+    /// ```
+    pub fn redeem_voucher<'info>(ctx: Context<'_, '_, '_, 'info, RedeemVoucher<'info>>, voucher: Voucher) -> Result<()> {
+        instructions::voucher::redeem_voucher_handler(ctx, voucher)
+    }
+
+    /// 创建订阅
+    /// 
+    /// 用户批准一个按周期扣款的订阅计划，并把扣款额度授权给订阅代理PDA
+    /// `["tot_sub_delegate"]`，之后每期无需用户签名。
+    /// 
+    /// # 功能说明
+    /// 
+    /// 1. 校验计划参数和价格目录价格
+    /// 2. 向订阅代理PDA授权`amount × max_periods`（已授权给该PDA时累加）
+    /// 3. 创建订阅账户PDA `["tot_subscription", user, subscription_id]`，第一期立即到期
+    /// 
+    /// # 参数
+    /// * `ctx` - 创建上下文
+    /// * `subscription_id` - 订阅编号（同一用户下唯一）
+    /// * `consume_type` - 消费类型
+    /// * `sku` - 商品SKU
+    /// * `amount` - 每期金额
+    /// * `period_seconds` - 周期长度（秒）
+    /// * `max_periods` - 最大期数
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 用户必须已初始化持有者账户且未被冻结
+    /// * 代币账户只能有一个delegate，会覆盖授权给其他地址的额度
+    /// 
+    /// # 使用示例
+    /// ```rust
+    /// program.methods
+    ///     .createSubscription(
+    ///         new anchor.BN(1), // 订阅编号
+    ///         { mapAction: {} },
+    ///         7, // SKU
+    ///         new anchor.BN(10_000_000), // 每期10 TOT
+    ///         new anchor.BN(30 * 86400), // 每30天
+    ///         12 // 最多12期
+    ///     )
+    ///     .accounts({...})
+    ///     .rpc();
+    /// ```
+    pub fn create_subscription(
+        ctx: Context<CreateSubscription>,
+        subscription_id: u64,
+        consume_type: ConsumeType,
+        sku: u32,
+        amount: u64,
+        period_seconds: i64,
+        max_periods: u32,
+    ) -> Result<()> {
+        instructions::subscription::create_subscription_handler(
+            ctx,
+            subscription_id,
+            consume_type,
+            sku,
+            amount,
+            period_seconds,
+            max_periods,
+        )
+    }
+
+    /// 订阅扣款
+    /// 
+    /// 到期时扣取一期订阅费用，按收入分成处理并记录消费统计。
+    /// 
+    /// # 功能说明
+    /// 
+    /// 1. 校验订阅生效且已到期
+    /// 2. 用户被冻结、余额或授权额度不足时停止订阅（不报错）
+    /// 3. 由订阅代理PDA签名销毁、转入TWS财库、转给受益人
+    /// 4. 推进下一期扣款时间，扣满最大期数时订阅完成
+    /// 
+    /// # 参数
+    /// * `ctx` - 扣款上下文
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 无需签名，任何人都可以调用（后端crank）
+    /// * 受消费暂停位控制
+    pub fn charge_subscription<'info>(ctx: Context<'_, '_, '_, 'info, ChargeSubscription<'info>>) -> Result<()> {
+        instructions::subscription::charge_subscription_handler(ctx)
+    }
+
+    /// 取消订阅
+    /// 
+    /// 关闭订阅账户退还租金，并收回本订阅尚未扣款的授权额度。
+    /// 
+    /// # 参数
+    /// * `ctx` - 取消上下文
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有订阅用户可以取消，任何状态都可以取消
+    pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+        instructions::subscription::cancel_subscription_handler(ctx)
+    }
+
+    /// 登记商户
+    /// 
+    /// 系统管理员登记接受TOT付款的合作商户，创建商户账户PDA `["tot_merchant", merchant]`。
+    /// 
+    /// # 参数
+    /// * `ctx` - 登记上下文
+    /// * `merchant` - 商户钱包地址
+    /// * `tax_cap_bps` - 税率上限（`None`标准税率，`Some(0)`免税，`Some(n)`最终税率不超过n）
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有系统管理员可以执行此操作
+    /// * 税率上限不能超过MAX_TAX_BPS
+    pub fn register_merchant(
+        ctx: Context<RegisterMerchant>,
+        merchant: Pubkey,
+        tax_cap_bps: Option<u16>,
+    ) -> Result<()> {
+        instructions::merchant::register_merchant_handler(ctx, merchant, tax_cap_bps)
+    }
+
+    /// 更新商户
+    /// 
+    /// 修改商户的税率上限或启用状态。
+    /// 
+    /// # 参数
+    /// * `ctx` - 更新上下文
+    /// * `tax_cap_bps` - 新的税率上限
+    /// * `is_active` - 是否启用（停用后不能开票，未支付的发票也不能支付）
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有系统管理员可以执行此操作
+    pub fn update_merchant(
+        ctx: Context<UpdateMerchant>,
+        tax_cap_bps: Option<u16>,
+        is_active: bool,
+    ) -> Result<()> {
+        instructions::merchant::update_merchant_handler(ctx, tax_cap_bps, is_active)
+    }
+
+    /// 开具发票
+    /// 
+    /// 已登记的商户创建发票PDA `["tot_invoice", merchant, invoice_id]`。
+    /// 
+    /// # 参数
+    /// * `ctx` - 开票上下文
+    /// * `invoice_id` - 发票ID（同一商户下唯一）
+    /// * `amount` - 发票金额（商户应收到的净额）
+    /// * `due_at` - 到期时间（之后不能支付）
+    /// * `reference` - 商户订单引用（如订单号的哈希）
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 使用示例
+    /// ```rust
+    /// program.methods
+    ///     .createInvoice(
+    ///         new anchor.BN(1001), // 发票ID
+    ///         new anchor.BN(100_000_000), // 100 TOT
+    ///         new anchor.BN(now + 3600), // 1小时内有效
+    ///         orderHash
+    ///     )
+    ///     .accounts({...})
+    ///     .rpc();
+    /// ```
+    pub fn create_invoice(
+        ctx: Context<CreateInvoice>,
+        invoice_id: u64,
+        amount: u64,
+        due_at: i64,
+        reference: [u8; 32],
+    ) -> Result<()> {
+        instructions::merchant::create_invoice_handler(ctx, invoice_id, amount, due_at, reference)
+    }
+
+    /// 支付发票
+    /// 
+    /// 顾客支付发票，商户收到发票金额，税额另外从顾客扣除。
+    /// 
+    /// # 功能说明
+    /// 
+    /// 1. 校验发票待支付、未过期，商户已启用
+    /// 2. 按`transfer_exact_with_tax`的规则计算税额（免税列表、冻结、持有时间折扣）
+    /// 3. 商户税率上限为0时免税，计算出的税率高于上限时按上限收税
+    /// 4. 转账给商户，税收40%销毁、其余转入税收收集账户
+    /// 5. 发票标记为已支付，发出`InvoicePaid`事件
+    /// 
+    /// # 参数
+    /// * `ctx` - 支付上下文
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 受转账暂停位控制
+    /// * 同一发票只能支付一次
+    pub fn pay_invoice<'info>(ctx: Context<'_, '_, '_, 'info, PayInvoice<'info>>) -> Result<()> {
+        instructions::merchant::pay_invoice_handler(ctx)
+    }
+
+    /// 关闭发票
+    /// 
+    /// 商户关闭发票并收回租金：已支付的归档，未支付的作废。
+    /// 
+    /// # 参数
+    /// * `ctx` - 关闭上下文
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有开票商户可以关闭
+    pub fn close_invoice(ctx: Context<CloseInvoice>) -> Result<()> {
+        instructions::merchant::close_invoice_handler(ctx)
+    }
+
+    /// 登记合作程序
+    /// 
+    /// 把合作程序（如`tws-asset`、`prediction_market`）加入白名单，
+    /// 创建登记PDA `["tot_partner", program_id]`，并保存其签名PDA地址。
+    /// 
+    /// # 参数
+    /// * `ctx` - 登记上下文
+    /// * `program_id` - 合作程序ID
+    /// * `permissions` - 权限位图（bit0消费，bit1带税转账，bit2持有者统计）
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有系统管理员可以执行此操作
+    /// * 合作程序的签名PDA = `["tot_partner_authority"]`在合作程序ID下派生
+    pub fn register_partner(
+        ctx: Context<RegisterPartner>,
+        program_id: Pubkey,
+        permissions: u8,
+    ) -> Result<()> {
+        instructions::partner::register_partner_handler(ctx, program_id, permissions)
+    }
+
+    /// 更新合作程序
+    /// 
+    /// 修改合作程序的权限位图或启用状态。
+    /// 
+    /// # 参数
+    /// * `ctx` - 更新上下文
+    /// * `permissions` - 新的权限位图
+    /// * `is_active` - 是否启用
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有系统管理员可以执行此操作
+    pub fn update_partner(
+        ctx: Context<UpdatePartner>,
+        permissions: u8,
+        is_active: bool,
+    ) -> Result<()> {
+        instructions::partner::update_partner_handler(ctx, permissions, is_active)
+    }
+
+    /// 合作程序消费（CPI入口）
+    /// 
+    /// 合作程序通过CPI调用，按`consume_to_treasury`的规则消费。
+    /// 
+    /// # 功能说明
+    /// 
+    /// 1. 校验签名者是已登记、已启用且有消费权限的合作程序的签名PDA
+    /// 2. 执行与`consume_to_treasury`相同的逻辑
+    /// 
+    /// # 参数
+    /// * `ctx` - 消费上下文（`consume`为consume_to_treasury的账户）
+    /// * 其余参数同`consume_to_treasury`
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 使用示例
+    /// ```rust
+    /// // 合作程序中（依赖tot-token并启用cpi feature）
+    /// let signer_seeds: &[&[&[u8]]] = &[&[b"tot_partner_authority", &[authority_bump]]];
+    /// tot_token::cpi::partner_consume(
+    ///     CpiContext::new_with_signer(tot_program, accounts, signer_seeds),
+    ///     amount,
+    ///     ConsumeType::PredictionBet,
+    ///     market_sku,
+    ///     None,
+    ///     None,
+    /// )?;
+    /// ```
+    pub fn partner_consume<'info>(
+        ctx: Context<'_, '_, '_, 'info, PartnerConsume<'info>>,
+        amount: u64,
+        consume_type: ConsumeType,
+        sku: u32,
+        reference: Option<[u8; 32]>,
+        receipt: Option<ConsumeReceiptArgs>,
+    ) -> Result<()> {
+        instructions::partner::partner_consume_handler(ctx, amount, consume_type, sku, reference, receipt)
+    }
+
+    /// 合作程序带税转账（CPI入口）
+    /// 
+    /// 合作程序通过CPI调用，按`transfer_with_tax`的规则转账。
+    /// 发送者可以是用户，也可以是合作程序的金库PDA（合作程序同时为其签名）。
+    /// 
+    /// # 参数
+    /// * `ctx` - 转账上下文（`transfer`为transfer_with_tax的账户）
+    /// * 其余参数同`transfer_with_tax`
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 需要带税转账权限
+    /// * 发送者需要有持有者账户（金库PDA也需要先初始化持有者账户）
+    pub fn partner_transfer_with_tax<'info>(
+        ctx: Context<'_, '_, '_, 'info, PartnerTransferWithTax<'info>>,
+        amount: u64,
+        is_sell: bool,
+        reference: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::partner::partner_transfer_with_tax_handler(ctx, amount, is_sell, reference)
+    }
+
+    /// 合作程序上报持有者活动（CPI入口）
+    /// 
+    /// 合作程序自行转移代币后，通过CPI更新持有者的买入、卖出或消费统计。
+    /// 
+    /// # 参数
+    /// * `ctx` - 上报上下文
+    /// * `activity` - 持有者活动（买入、卖出、消费）
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 需要持有者统计权限
+    /// * 持有者被冻结时返回错误，合作程序的整笔交易回滚
+    /// * 卖出需传入持有者代币账户，清仓后持有时间重新计算
+    pub fn partner_record_holder_activity(
+        ctx: Context<PartnerRecordHolderActivity>,
+        activity: HolderActivity,
+    ) -> Result<()> {
+        instructions::partner::partner_record_holder_activity_handler(ctx, activity)
+    }
+
+    /// 初始化转账守卫
+    /// 
+    /// 创建转账守卫PDA `["tot_transfer_guard"]`。主程序发起转账时在其中置位，
+    /// Transfer Hook据此区分转账来源。
+    /// 
+    /// # 参数
+    /// * `ctx` - 初始化上下文
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有系统管理员可以执行此操作
+    /// * 先由Hook管理员执行`update_extra_account_meta_list`登记守卫账户，再执行本指令
+    /// * 初始化之前Hook不更新钱包、DEX转账的持有者统计
+    pub fn initialize_transfer_guard(ctx: Context<InitializeTransferGuard>) -> Result<()> {
+        instructions::hook_stats::initialize_transfer_guard_handler(ctx)
+    }
+
+    /// Transfer Hook上报转账（CPI入口）
+    /// 
+    /// 钱包和DEX发起的TOT转账不经过主程序指令，Transfer Hook在执行中
+    /// 以Hook配置PDA签名回调本指令，更新双方的持有者统计。
+    /// 
+    /// # 参数
+    /// * `ctx` - 上报上下文
+    /// * `record` - 转账双方、金额、预扣税额及买卖方向
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只接受Hook配置PDA的签名
+    /// * 主程序发起的转账由各指令自行更新统计，Hook不会回调
+    pub fn record_hook_transfer(
+        ctx: Context<RecordHookTransfer>,
+        record: HookTransferRecord,
+    ) -> Result<()> {
+        instructions::hook_stats::record_hook_transfer_handler(ctx, record)
+    }
+
+    /// 初始化积分配置
+    /// 
+    /// 创建全局积分配置PDA `["tot_points_config"]`，设置各来源的积分比例和兑换规则。
+    /// 
+    /// # 参数
+    /// * `ctx` - 初始化上下文
+    /// * `params` - 积分配置参数
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有系统管理员可以执行此操作
+    /// * 消费积分比例按`ConsumeType`下标配置，单位为每1 TOT获得的积分
+    /// * `redeem_points_per_tot`或`discount_cost`为0时关闭对应的兑换方式
+    pub fn initialize_points_config(
+        ctx: Context<InitializePointsConfig>,
+        params: PointsConfigParams,
+    ) -> Result<()> {
+        instructions::points::initialize_points_config_handler(ctx, params)
+    }
+
+    /// 更新积分配置
+    /// 
+    /// # 参数
+    /// * `ctx` - 更新上下文
+    /// * `params` - 新的积分配置参数
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有系统管理员可以执行此操作
+    /// * 已发放的积分和已生效的税率折扣不受影响
+    pub fn update_points_config(
+        ctx: Context<UpdatePointsConfig>,
+        params: PointsConfigParams,
+    ) -> Result<()> {
+        instructions::points::update_points_config_handler(ctx, params)
+    }
+
+    /// 初始化积分账户
+    /// 
+    /// 为钱包创建不可转让的积分账户PDA `["tot_points", owner]`。
+    /// 
+    /// # 参数
+    /// * `ctx` - 初始化上下文
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 任何人都可以为任意钱包创建（付款人支付租金）
+    /// * 持有积分从创建时开始计算
+    pub fn initialize_points_account(ctx: Context<InitializePointsAccount>) -> Result<()> {
+        instructions::points::initialize_points_account_handler(ctx)
+    }
+
+    /// 领取持有积分
+    /// 
+    /// 按上次领取以来经过的整天数发放积分，天数不超过当前持有天数，
+    /// 积分按当前余额缩放（每日积分对应每1个完整代币）。
+    /// 
+    /// # 参数
+    /// * `ctx` - 领取上下文
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 不足一天或余额为0时返回`NoPointsToClaim`
+    /// * 持有者被冻结时不能领取
+    pub fn claim_holding_points(ctx: Context<ClaimHoldingPoints>) -> Result<()> {
+        instructions::points::claim_holding_points_handler(ctx)
+    }
+
+    /// 积分兑换商品
+    /// 
+    /// 按价格目录中商品的标价扣除积分，后端监听`PointsRedeemed`事件发放商品。
+    /// 
+    /// # 参数
+    /// * `ctx` - 兑换上下文
+    /// * `consume_type` - 商品的消费类型
+    /// * `sku` - 商品SKU
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 所需积分 = 标价（TOT）× `redeem_points_per_tot`，向上取整
+    /// * 商品未登记、已停用或未开放积分兑换时返回`PointsRedemptionUnavailable`
+    /// * 消费被暂停时不能兑换
+    pub fn redeem_points(
+        ctx: Context<RedeemPoints>,
+        consume_type: ConsumeType,
+        sku: u32,
+    ) -> Result<()> {
+        instructions::points::redeem_points_handler(ctx, consume_type, sku)
+    }
+
+    /// 积分兑换税率折扣
+    /// 
+    /// 扣除`discount_cost`积分，在有效期内`transfer_with_tax`的最终税率减少`discount_bps`。
+    /// 
+    /// # 参数
+    /// * `ctx` - 兑换上下文
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 转账时需要传入发送者的积分账户（`senderPoints`）折扣才会生效
+    /// * 折扣未到期时再次兑换会顺延有效期
+    pub fn convert_points_to_tax_discount(ctx: Context<ConvertPointsToTaxDiscount>) -> Result<()> {
+        instructions::points::convert_points_to_tax_discount_handler(ctx)
+    }
+
+    /// 批量转账/空投
+    /// 
+    /// 一笔交易内向多个接收者转账，用于营销、社区奖励发放和空投。
+    /// 
+    /// # 功能说明
+    /// 
+    /// 对每个接收者执行与`transfer_with_tax`相同的免税、冻结和税率规则，
+    /// 更新接收者持有者统计，并为每个接收者发出`TransferWithTaxEvent`，
+    /// 最后发出一条`BatchTransferEvent`汇总。所有接收者的税收合并为一次销毁和一次转入税收收集账户。
+    /// 
+    /// # 参数
+    /// * `ctx` - 批量转账上下文
+    /// * `recipients` - 接收者和金额列表
+    /// 
+    /// # Remaining Accounts
+    /// 每个接收者按顺序传入两个可写账户：
+    /// 1. 接收者代币账户
+    /// 2. 接收者持有者PDA `["tot_holder", recipient]`（可以尚未初始化）
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，任一接收者失败则整笔交易回滚
+    /// 
+    /// # 注意事项
+    /// * 每笔最多8个接收者（`MAX_BATCH_TRANSFER_RECIPIENTS`）
+    /// * 8个接收者约需600,000 CU，需要在交易前加入ComputeBudget指令
+    /// * 接收者较多时建议使用地址查找表控制交易大小
+    /// 
+    /// # 使用示例
+    /// ```rust
+    /// program.methods
+    ///     .batchTransfer([
+    ///         { recipient: alice, amount: new anchor.BN(1000000000) },
+    ///         { recipient: bob, amount: new anchor.BN(2000000000) },
+    ///     ])
+    ///     .accounts({...})
+    ///     .remainingAccounts([aliceAta, aliceHolder, bobAta, bobHolder])
+    ///     .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 600_000 })])
+    ///     .rpc();
+    /// ```
+    pub fn batch_transfer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchTransfer<'info>>,
+        recipients: Vec<BatchTransferEntry>,
+    ) -> Result<()> {
+        instructions::batch_transfer::batch_transfer_handler(ctx, recipients)
+    }
+
+    /// 创建代币流
+    ///
+    /// 把TOT托管在代币流PDA中，在开始和结束时间之间线性释放给接收者，用于工资、订阅等持续付款。
+    ///
+    /// # 功能说明
+    ///
+    /// 从发送者账户转入托管账户（代币流PDA的ATA），记录释放计划。
+    /// 转入托管不收税，税收在接收者提取时收取。
+    ///
+    /// # 参数
+    /// * `ctx` - 创建上下文
+    /// * `stream_id` - 流编号（同一发送者下唯一，参与PDA派生）
+    /// * `recipient` - 接收者钱包地址
+    /// * `amount` - 托管数量
+    /// * `start_time` - 开始释放时间（Unix时间戳，可以早于当前时间）
+    /// * `end_time` - 释放结束时间（必须晚于开始时间和当前时间）
+    ///
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())
+    ///
+    /// # 注意事项
+    /// * 发送者的持有者账户必须已初始化且未冻结
+    /// * 释放总量为托管账户实际收到的数量（扣除Transfer Fee预扣后）
+    ///
+    /// # 使用示例
+    /// ```rust
+    /// program.methods
+    ///     .createStream(
+    ///         new anchor.BN(1),           // 流编号
+    ///         recipient,                  // 接收者
+    ///         new anchor.BN(30000000000), // 托管30000个代币
+    ///         new anchor.BN(start),
+    ///         new anchor.BN(start + 30 * 86400) // 30天内线性释放
+    ///     )
+    ///     .accounts({...})
+    ///     .rpc();
+    /// ```
+    pub fn create_stream<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateStream<'info>>,
+        stream_id: u64,
+        recipient: Pubkey,
+        amount: u64,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<()> {
+        instructions::stream::create_stream_handler(ctx, stream_id, recipient, amount, start_time, end_time)
+    }
+
+    /// 提取代币流
+    ///
+    /// 接收者提取截至当前已释放、尚未提取的全部代币。
+    ///
+    /// # 功能说明
+    ///
+    /// 提取按发送者向接收者的普通转账处理，与`transfer_with_tax`规则一致：
+    /// - 发送者或接收者免税时全额到账
+    /// - 否则检查双方冻结状态，按发送者持有时间计算税率，税收40%销毁、其余转入税收收集账户
+    /// - 接收者持有者账户存在时更新买入统计
+    ///
+    /// # 参数
+    /// * `ctx` - 提取上下文（接收者签名）
+    ///
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())
+    ///
+    /// # 注意事项
+    /// * 没有可提取的数量时返回`StreamNothingToWithdraw`
+    /// * 代币流被取消后，接收者仍可提取取消前已释放的部分
+    ///
+    /// # 使用示例
+    /// ```rust
+    /// program.methods
+    ///     .withdrawStream()
+    ///     .accounts({...})
+    ///     .rpc();
+    /// ```
+    pub fn withdraw_stream<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawStream<'info>>) -> Result<()> {
+        instructions::stream::withdraw_stream_handler(ctx)
+    }
+
+    /// 取消代币流
+    ///
+    /// 发送者停止代币流并取回尚未释放的部分。
+    ///
+    /// # 功能说明
+    ///
+    /// 按当前时间计算已释放数量，其余部分退回发送者，
+    /// 释放总量和结束时间截断到取消时刻。
+    ///
+    /// # 参数
+    /// * `ctx` - 取消上下文（发送者签名）
+    ///
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())
+    ///
+    /// # 注意事项
+    /// * 已释放但未提取的部分留在托管账户，接收者仍可提取
+    /// * 每个代币流只能取消一次
+    ///
+    /// # 使用示例
+    /// ```rust
+    /// program.methods
+    ///     .cancelStream()
+    ///     .accounts({...})
+    ///     .rpc();
+    /// ```
+    pub fn cancel_stream<'info>(ctx: Context<'_, '_, '_, 'info, CancelStream<'info>>) -> Result<()> {
+        instructions::stream::cancel_stream_handler(ctx)
+    }
+
+    /// 创建P2P挂单
+    ///
+    /// 挂单方把TOT托管在挂单PDA中，标明想换取的代币和数量，吃单方可以原子地成交。
+    ///
+    /// # 功能说明
+    ///
+    /// 从挂单方账户转入托管账户（挂单PDA的ATA），记录要价。
+    /// 转入托管不收税，税收在吃单时按卖出收取。
+    ///
+    /// # 参数
+    /// * `ctx` - 创建上下文
+    /// * `offer_id` - 挂单编号（同一挂单方下唯一，参与PDA派生）
+    /// * `tot_amount` - 托管的TOT数量
+    /// * `ask_amount` - 全部TOT对应的要价数量（要价代币基础单位）
+    ///
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())
+    ///
+    /// # 注意事项
+    /// * 要价代币可以是任意SPL Token或Token-2022代币，SOL使用Wrapped SOL
+    /// * 挂单方的持有者账户必须已初始化且未冻结
+    ///
+    /// # 使用示例
+    /// ```rust
+    /// program.methods
+    ///     .createOffer(
+    ///         new anchor.BN(1),             // 挂单编号
+    ///         new anchor.BN(1000000000000), // 出售1000个TOT
+    ///         new anchor.BN(5000000000)     // 换取5 SOL
+    ///     )
+    ///     .accounts({ askMint: NATIVE_MINT, ... })
+    ///     .rpc();
+    /// ```
+    pub fn create_offer<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateOffer<'info>>,
+        offer_id: u64,
+        tot_amount: u64,
+        ask_amount: u64,
+    ) -> Result<()> {
+        instructions::offer::create_offer_handler(ctx, offer_id, tot_amount, ask_amount)
+    }
+
+    /// 吃单（P2P成交）
+    ///
+    /// 吃单方支付要价并取得挂单中的TOT，同一笔交易内完成，支持部分成交。
+    ///
+    /// # 功能说明
+    ///
+    /// - 要价按挂单价格折算并向上取整，从吃单方直接转给挂单方
+    /// - TOT离开托管按挂单方卖出计税（挂单方或吃单方免税时不收税）
+    /// - 挂单方记录卖出，吃单方记录买入
+    ///
+    /// # 参数
+    /// * `ctx` - 吃单上下文（吃单方签名）
+    /// * `fill_amount` - 本次成交的TOT数量（含税，不超过挂单剩余数量）
+    ///
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())
+    ///
+    /// # 注意事项
+    /// * 受转账暂停和卖出暂停约束
+    /// * 挂单方或吃单方被冻结时不能成交
+    /// * 吃单方的持有者账户必须已初始化
+    ///
+    /// # 使用示例
+    /// ```rust
+    /// program.methods
+    ///     .fillOffer(new anchor.BN(500000000000)) // 成交500个TOT
+    ///     .accounts({...})
+    ///     .rpc();
+    /// ```
+    pub fn fill_offer<'info>(ctx: Context<'_, '_, '_, 'info, FillOffer<'info>>, fill_amount: u64) -> Result<()> {
+        instructions::offer::fill_offer_handler(ctx, fill_amount)
+    }
+
+    /// 撤销P2P挂单
+    ///
+    /// 挂单方取回剩余未成交的TOT，挂单关闭后不能再成交。
+    ///
+    /// # 参数
+    /// * `ctx` - 撤单上下文（挂单方签名）
+    ///
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())
+    ///
+    /// # 注意事项
+    /// * 退回挂单方自己的代币，不收税
+    /// * 已取消或已全部成交的挂单返回`OfferClosed`
+    ///
+    /// # 使用示例
+    /// ```rust
+    /// program.methods
+    ///     .cancelOffer()
+    ///     .accounts({...})
+    ///     .rpc();
+    /// ```
+    pub fn cancel_offer<'info>(ctx: Context<'_, '_, '_, 'info, CancelOffer<'info>>) -> Result<()> {
+        instructions::offer::cancel_offer_handler(ctx)
+    }
+
+    // ============================================
+    // 管理员功能
+    // ============================================
+
+    /// 提议新管理员（权限移交第一步）
+    /// 
+    /// 记录待接受的新管理员地址和最早生效时间。当前管理员在新地址
+    /// 签名接受之前仍然保有全部权限，输错地址不会导致管理功能失效。
+    /// 
+    /// # 功能说明
+    /// 
+    /// 1. 验证当前调用者是否为管理员
+    /// 2. 验证新管理员地址的有效性（不能为空地址或当前管理员）
+    /// 3. 记录pending_authority，生效时间 = 当前时间 + 当前生效的权限移交延迟
+    /// 4. 发出移交提议事件
+    /// 
+    /// # 参数
+    /// * `ctx` - 提议上下文
+    /// * `new_authority` - 新的管理员地址（Pubkey）
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有当前管理员可以执行此操作
+    /// * 重复提议会覆盖之前的提议，并重新计算生效时间
+    /// * 新地址必须调用`accept_authority`才能获得权限
+    /// * 提议有误时可以通过`cancel_authority_transfer`取消
+    /// 
+    /// # 使用示例
+    /// ```rust
+    /// // 将管理员权限移交到多签钱包
+    /// program.methods
+    ///     .proposeAuthority(multisigWallet)
+    ///     .accounts({
+    ///         authority: currentAdmin,
+    ///         config: configPda,
+    ///     })
+    ///     .rpc();
+    /// ```
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::admin::propose_authority_handler(ctx, new_authority)
+    }
+
+    /// 接受管理员权限（权限移交第二步）
+    /// 
+    /// 由提议中的新地址签名，时间锁到期后正式成为系统管理员。
+    /// 
+    /// # 功能说明
+    /// 
+    /// 1. 验证签名者是pending_authority
+    /// 2. 验证时间锁已到期
+    /// 3. 更新管理员地址并清空待移交状态
+    /// 4. 发出权限更新事件
+    /// 
+    /// # 参数
+    /// * `ctx` - 接受权限上下文
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 签名者不是提议地址时返回PendingAuthorityMismatch
+    /// * 时间锁未到期时返回AuthorityTransferTimelocked
+    /// * 权限转移后，旧管理员将失去所有管理权限
+    /// 
+    /// # 使用示例
+    /// ```rust
+    /// program.methods
+    ///     .acceptAuthority()
+    ///     .accounts({
+    ///         newAuthority: multisigWallet,
+    ///         config: configPda,
+    ///     })
+    ///     .rpc();
+    /// ```
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::admin::accept_authority_handler(ctx)
+    }
+
+    /// 取消管理员移交
+    /// 
+    /// 当前管理员可以在新地址接受之前取消进行中的移交。
+    /// 
+    /// # 参数
+    /// * `ctx` - 取消移交上下文
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，没有进行中的移交时返回NoPendingAuthority
+    pub fn cancel_authority_transfer(ctx: Context<CancelAuthorityTransfer>) -> Result<()> {
+        instructions::admin::cancel_authority_transfer_handler(ctx)
+    }
+
+    /// 设置权限移交最小延迟
+    /// 
+    /// 设置之后所有权限移交提议（管理员、Transfer Hook管理员、Token-2022权限）
+    /// 必须等待的最短时间。
+    /// 
+    /// # 参数
+    /// * `ctx` - 管理员操作上下文
+    /// * `delay_seconds` - 延迟秒数（0 ~ 30天）
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，超出范围返回InvalidAuthorityTransferDelay
+    /// 
+    /// # 注意事项
+    /// * 只影响之后的提议，已提议的移交保持原有的生效时间
+    /// * 延长立即生效；缩短在当前延迟过去之后才生效，防止先清零延迟再立即完成移交
+    pub fn set_authority_transfer_delay(
+        ctx: Context<SetAuthorityTransferDelay>,
+        delay_seconds: i64,
+    ) -> Result<()> {
+        instructions::admin::set_authority_transfer_delay_handler(ctx, delay_seconds)
+    }
+
+    /// 提议移交Token-2022 Mint权限
+    /// 
+    /// `initialize`将冻结、转账费、提取费用、元数据指针和Transfer Hook权限分配给部署者。
+    /// 此指令由系统管理员提议把其中一种或多种权限移交给新地址。
+    /// 铸造权限由程序PDA持有，不能通过此指令移交；
+    /// 永久代理只有在旧版Mint尚未迁移到程序PDA时才能移交。
+    /// 
+    /// # 参数
+    /// * `ctx` - 提议上下文
+    /// * `kinds` - 待移交的权限类型列表
+    /// * `new_authority` - 新的权限地址
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 同一时间只有一个进行中的Token-2022权限移交，重复提议会覆盖
+    /// * 接受时需要新地址和当前权限持有者同时签名
+    pub fn propose_token_authority(
+        ctx: Context<ProposeTokenAuthority>,
+        kinds: Vec<TokenAuthorityKind>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::token_authority::propose_token_authority_handler(ctx, kinds, new_authority)
+    }
+
+    /// 接受Token-2022 Mint权限
+    /// 
+    /// 时间锁到期后，由新地址和当前权限持有者共同签名，
+    /// 通过CPI调用Token-2022的SetAuthority完成移交。
+    /// 
+    /// # 参数
+    /// * `ctx` - 接受权限上下文
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    pub fn accept_token_authority(ctx: Context<AcceptTokenAuthority>) -> Result<()> {
+        instructions::token_authority::accept_token_authority_handler(ctx)
+    }
+
+    /// 取消Token-2022 Mint权限移交
+    /// 
+    /// # 参数
+    /// * `ctx` - 取消移交上下文
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，没有进行中的移交时返回NoPendingAuthority
+    pub fn cancel_token_authority_transfer(ctx: Context<CancelTokenAuthorityTransfer>) -> Result<()> {
+        instructions::token_authority::cancel_token_authority_transfer_handler(ctx)
+    }
+
+    /// 暂停/恢复指令族
+    /// 
+    /// 管理员可以按指令族（转账、卖出、消费、平台转账、拍卖、资产上链、池子释放）
+    /// 分别暂停或恢复系统功能。各指令族互相独立，被暂停的指令返回OperationPaused。
+    /// 
+    /// # 功能说明
+    /// 
+    /// 1. 验证调用者是否为管理员
+    /// 2. 更新暂停位图（paused_operations字段）中对应的位
+    /// 3. 记录或清除各指令族的自动恢复时间
+    /// 4. 发出系统状态变更事件
+    /// 
+    /// # 参数
+    /// * `ctx` - 设置暂停状态上下文
+    /// * `operations` - 本次变更的指令族列表（不能为空）
+    /// * `paused` - 暂停状态
+    ///   - `true`: 暂停指定的指令族
+    ///   - `false`: 恢复指定的指令族
+    /// * `resume_at` - 可选的自动恢复时间（Unix时间戳）
+    ///   - 仅在暂停时有效，必须晚于当前时间
+    ///   - 为None时需要管理员手动恢复
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有管理员可以执行此操作
+    /// * 暂停不影响税率；恐慌税率请使用`set_panic_mode`
+    /// * 紧急提取要求转账指令族处于暂停状态
+    /// * 建议在暂停前通知社区
+    /// 
+    /// # 使用示例
+    /// ```rust
+    /// // 暂停卖出和拍卖，24小时后自动恢复
+    /// program.methods
+    ///     .setPaused([{ sells: {} }, { auctions: {} }], true, new anchor.BN(now + 86400))
+    ///     .accounts({
+    ///         authority: admin,
+    ///         config: configPda,
+    ///     })
+    ///     .rpc();
+    /// ```
+    pub fn set_paused(
+        ctx: Context<SetPaused>,
+        operations: Vec<PauseFlag>,
+        paused: bool,
+        resume_at: Option<i64>,
+    ) -> Result<()> {
+        instructions::admin::set_paused_handler(ctx, operations, paused, resume_at)
+    }
+
+    /// 开启/关闭恐慌税率模式
+    /// 
+    /// 恐慌模式下，卖出金额占供应量比例达到TaxConfig.panic_threshold_bps的交易
+    /// 按panic_tax_bps征税。恐慌模式与暂停相互独立，不会拒绝任何操作。
+    /// 
+    /// # 参数
+    /// * `ctx` - 管理员操作上下文
+    /// * `enabled` - 是否开启恐慌税率模式
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())
+    pub fn set_panic_mode(
+        ctx: Context<SetPanicMode>,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::admin::set_panic_mode_handler(ctx, enabled)
+    }
+
+    /// 紧急提取（仅限紧急情况）
+    /// 
+    /// 在转账指令族处于暂停状态时，管理员可以从指定池子提取代币。
+    /// 这是最后的安全措施，仅在极端情况下使用。
+    /// 
+    /// # 功能说明
+    /// 
+    /// 1. 验证转账指令族处于暂停状态（PauseFlag::Transfers）
+    /// 2. 验证调用者是否为管理员
+    /// 3. 从源账户转移指定数量的代币到目标账户
+    /// 4. 发出紧急提取事件
+    /// 
+    /// # 参数
+    /// * `ctx` - 紧急提取上下文
+    /// * `amount` - 提取的代币数量
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有管理员可以执行此操作
+    /// * 转账指令族必须处于暂停状态才能执行
     /// * 此操作会发出链上事件，便于审计
     /// * 建议仅在真正的紧急情况下使用
     /// * 提取的代币应妥善保管，后续可能需要归还
@@ -846,8 +2549,8 @@ pub mod tot_token {
     ///     })
     ///     .rpc();
     /// ```
-    pub fn emergency_withdraw(
-        ctx: Context<EmergencyWithdraw>,
+    pub fn emergency_withdraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, EmergencyWithdraw<'info>>,
         amount: u64,
     ) -> Result<()> {
         instructions::admin::emergency_withdraw_handler(ctx, amount)
@@ -893,6 +2596,155 @@ pub mod tot_token {
         instructions::admin::set_tws_treasury_handler(ctx, tws_treasury)
     }
 
+    /// 设置合规权限
+    /// 
+    /// 合规权限可以与系统管理员（多签）一起执行`clawback`。
+    /// 
+    /// # 参数
+    /// * `ctx` - 管理员操作上下文
+    /// * `compliance_authority` - 合规权限地址，Pubkey::default()表示撤销
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有系统管理员可以执行此操作
+    pub fn set_compliance_authority(
+        ctx: Context<SetComplianceAuthority>,
+        compliance_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::admin::set_compliance_authority_handler(ctx, compliance_authority)
+    }
+
+    /// 设置消费回执核销权限
+    /// 
+    /// 回执核销权限（通常是后端服务的钱包）可以与系统管理员一起核销和关闭消费回执。
+    /// 
+    /// # 参数
+    /// * `ctx` - 管理员操作上下文
+    /// * `receipt_authority` - 回执核销权限地址，Pubkey::default()表示撤销
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有系统管理员可以执行此操作
+    pub fn set_receipt_authority(
+        ctx: Context<SetReceiptAuthority>,
+        receipt_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::admin::set_receipt_authority_handler(ctx, receipt_authority)
+    }
+
+    /// 设置奖励兑换券签名公钥
+    /// 
+    /// 平台用该公钥对应的私钥离线签发奖励兑换券。
+    /// 
+    /// # 参数
+    /// * `ctx` - 管理员操作上下文
+    /// * `voucher_signer` - 兑换券签名公钥，Pubkey::default()表示关闭兑换券功能
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有系统管理员可以执行此操作
+    /// * 签名公钥应与系统管理员不同，轮换后旧公钥签发的兑换券全部失效
+    pub fn set_voucher_signer(
+        ctx: Context<SetVoucherSigner>,
+        voucher_signer: Pubkey,
+    ) -> Result<()> {
+        instructions::admin::set_voucher_signer_handler(ctx, voucher_signer)
+    }
+
+    /// 迁移配置账户布局
+    /// 
+    /// 把旧版本（版本1，只有128字节预留空间）的配置账户扩容到当前大小，
+    /// 保留原有字段，新增字段取初始值，并由管理员补足租金。
+    /// 
+    /// # 参数
+    /// * `ctx` - 迁移上下文
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * 只有配置中的系统管理员可以执行此操作
+    /// * 升级程序后、执行其他指令前执行一次；新部署的配置账户无需迁移
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        instructions::admin::migrate_config_handler(ctx)
+    }
+
+    /// 追回冻结账户的代币
+    /// 
+    /// 使用Token-2022 Permanent Delegate（程序PDA）把冻结持有者的代币
+    /// 转移到指定账户或直接销毁，并留下链上追回记录。
+    /// 
+    /// # 功能说明
+    /// 
+    /// 1. 验证执行者为系统管理员或合规权限
+    /// 2. 验证持有者已冻结满MIN_CLAWBACK_FROZEN_PERIOD（30天）
+    /// 3. 由永久代理PDA签名转移或销毁代币
+    /// 4. 创建ClawbackRecord记录案件编号、原因哈希等信息
+    /// 5. 更新全局追回总量和次数
+    /// 
+    /// # 参数
+    /// * `ctx` - 追回上下文
+    /// * `case_id` - 案件编号（每个编号只能使用一次）
+    /// * `action` - 追回方式（Transfer/Burn）
+    /// * `amount` - 追回数量
+    /// * `reason_hash` - 链下追回理由文档的哈希
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    /// 
+    /// # 注意事项
+    /// * Transfer方式必须提供destination_token_account
+    /// * Burn方式会同时增加config.total_burned
+    /// 
+    /// # 使用示例
+    /// ```rust
+    /// program.methods
+    ///     .clawback(new BN(1), { burn: {} }, amount, reasonHash)
+    ///     .accounts({
+    ///         operator: multisig,
+    ///         config: configPda,
+    ///         holderInfo: holderPda,
+    ///         sourceTokenAccount: holderAta,
+    ///         destinationTokenAccount: null,
+    ///         mint: mintPublicKey,
+    ///         permanentDelegate: permanentDelegatePda,
+    ///         clawbackRecord: clawbackRecordPda,
+    ///         tokenProgram: TOKEN_2022_PROGRAM_ID,
+    ///         systemProgram: SystemProgram.programId,
+    ///     })
+    ///     .rpc();
+    /// ```
+    pub fn clawback<'info>(
+        ctx: Context<'_, '_, '_, 'info, Clawback<'info>>,
+        case_id: u64,
+        action: ClawbackAction,
+        amount: u64,
+        reason_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::clawback::clawback_handler(ctx, case_id, action, amount, reason_hash)
+    }
+
+    /// 迁移永久代理到程序PDA
+    /// 
+    /// 旧版本`initialize`把Permanent Delegate设置为部署者钱包。
+    /// 此指令一次性把永久代理移交给`PERMANENT_DELEGATE_SEED` PDA，
+    /// 之后只能通过`clawback`使用永久代理权限。
+    /// 
+    /// # 参数
+    /// * `ctx` - 迁移上下文，需要系统管理员和当前永久代理签名
+    /// 
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    pub fn migrate_permanent_delegate(ctx: Context<MigratePermanentDelegate>) -> Result<()> {
+        instructions::clawback::migrate_permanent_delegate_handler(ctx)
+    }
+
     /// 资产上链
     /// 
     /// 资产审核通过后，将资产信息上链到Solana。
@@ -1025,8 +2877,8 @@ pub mod tot_token {
     /// * 5%给财库（免税，因为是向TWS官方消费）
     /// * 95%给上一任房主（免税，因为是平台资产转移）
     /// * 会更新新所有者的消费统计
-    pub fn seize_auction(
-        ctx: Context<SeizeAuction>,
+    pub fn seize_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, SeizeAuction<'info>>,
         bid_message: String,
     ) -> Result<()> {
         instructions::auction_seize::seize_auction_handler(ctx, bid_message)
@@ -1043,15 +2895,15 @@ pub mod tot_token {
     /// 
     /// # 功能说明
     /// 
-    /// 1. 根据转账金额和类型计算基础税率
+    /// 1. 根据转账类型确定基础税率（买入使用买入税率）
     /// 2. 根据持有者信息计算持有时间折扣
-    /// 3. 如果是卖出操作，计算大额交易附加税
+    /// 3. 如果是买入或卖出操作，计算大额交易附加税
     /// 4. 返回完整的税率计算结果
     /// 
     /// # 参数
     /// * `ctx` - 查询上下文，包含配置、税率配置、Mint等账户
     /// * `amount` - 查询的转账金额
-    /// * `is_buy` - 是否为买入操作（当前版本买入不收取税收）
+    /// * `is_buy` - 是否为买入操作（从已登记交易场所买入，使用买入税率；此时`holder_info`应为买入方）
     /// * `is_sell` - 是否为卖出操作（会计算大额交易惩罚）
     /// 
     /// # 返回值
@@ -1142,272 +2994,98 @@ pub mod tot_token {
         instructions::query::get_holder_stats_handler(ctx)
     }
 
-    // ============================================
-    // Transfer Hook 指令
-    // ============================================
-    
-    /// 初始化Transfer Hook配置
-    /// 
-    /// 创建Transfer Hook配置账户，设置TOT Mint地址和配置地址。
-    /// 这是部署Transfer Hook程序后的第一步。
-    /// 
-    /// # 功能说明
-    /// 
-    /// 1. 创建Transfer Hook配置PDA账户
-    /// 2. 设置管理员权限
-    /// 3. 关联TOT Mint和配置账户
-    /// 4. 初始化统计信息
-    /// 
-    /// # 参数
-    /// * `ctx` - 初始化上下文
-    /// * `tot_mint` - TOT代币的Mint地址
-    /// * `tot_config` - TOT全局配置账户地址
-    /// 
-    /// # 返回值
-    /// * `Result<()>` - 成功返回Ok(())
-    pub fn initialize_transfer_hook(
-        ctx: Context<InitializeTransferHook>,
-        tot_mint: Pubkey,
-        tot_config: Pubkey,
-    ) -> Result<()> {
-        instructions::hook::initialize_transfer_hook(ctx, tot_mint, tot_config)
-    }
-
-    /// Transfer Hook执行入口
+    /// 获取协议统计（只读）
     /// 
-    /// 这是Token-2022在每次转账时调用的核心函数。
-    /// 必须符合spl-transfer-hook-interface规范。
+    /// 返回`TotConfig`中累计的全局统计，供透明度页面直接读取，无需扫描日志。
     /// 
     /// # 功能说明
     /// 
-    /// 1. 检查Hook是否暂停
-    /// 2. 验证Mint地址（确保是TOT代币）
-    /// 3. 更新统计信息
-    /// 4. 执行其他自定义逻辑（可扩展）
+    /// 返回以下数据：
+    ///   - 供应量: Mint总供应量、流通供应量、锁定供应量、累计铸造量
+    ///   - 税收: 累计收税总额、累计销毁量
+    ///   - 消费: 按`ConsumeType`累计的消费数量
+    ///   - 分发: 平台分发、拍卖手续费、池子释放、追回
+    ///   - Transfer Fee: Hook程序记录的预扣总额（需要传入Hook程序的`hookConfig`）
     /// 
     /// # 参数
-    /// * `ctx` - Transfer Hook执行上下文（由Token-2022程序自动传递）
-    /// * `amount` - 转账金额
+    /// * `ctx` - 查询上下文，包含全局配置、Mint和可选的Hook配置
     /// 
     /// # 返回值
-    /// * `Result<()>` - 成功返回Ok(())，失败会导致转账回滚
+    /// * `Result<ProtocolStats>` - 协议统计
     /// 
     /// # 注意事项
-    /// - 此函数由Token-2022程序调用，不是用户直接调用
-    /// - 执行失败会导致整个转账失败
-    /// - 应该尽量轻量，避免高gas消耗
-    /// - 账户由Token-2022程序自动传递和验证
-    /// Transfer Hook执行入口（内部实现）
-    /// 
-    /// 这是execute函数的内部实现，由execute函数调用。
-    /// execute_internal包含实际的执行逻辑，execute函数是符合spl-transfer-hook-interface规范的入口。
-    pub fn execute_internal(ctx: Context<ExecuteTransferHook>, amount: u64) -> Result<()> {
-        instructions::hook::execute_internal(ctx, amount)
-    }
-    
-    /// Transfer Hook执行入口（符合spl-transfer-hook-interface规范）
+    /// * 这是一个只读查询，不会修改链上状态
+    /// * 统计从本版本部署后开始累计，之前的历史数据不包含在内
     /// 
-    /// 这是Token-2022在每次转账时调用的核心函数。
-    /// 函数名必须为`execute`以符合spl-transfer-hook-interface规范。
+    /// # 使用示例
+    /// ```rust
+    /// const stats = await program.methods
+    ///     .getProtocolStats()
+    ///     .accounts({ config: configPda, mint, hookConfig: hookConfigPda })
+    ///     .view();
     /// 
-    /// # 功能说明
+    /// console.log(`累计销毁: ${stats.totalBurned}`);
+    /// ```
+    pub fn get_protocol_stats(ctx: Context<GetProtocolStats>) -> Result<ProtocolStats> {
+        instructions::query::get_protocol_stats_handler(ctx)
+    }
+
+    /// 获取平台预算剩余额度（只读）
     /// 
-    /// 1. 检查Hook是否暂停
-    /// 2. 验证Mint地址（确保是TOT代币）
-    /// 3. 更新统计信息
-    /// 4. 执行其他自定义逻辑（可扩展）
+    /// 返回平台转账当前周期的总预算、已用额度、剩余额度和重置时间，
+    /// 传入接收者额度账户时同时返回该接收者的剩余额度。
     /// 
     /// # 参数
-    /// * `ctx` - Transfer Hook执行上下文（由Token-2022程序自动传递）
-    /// * `amount` - 转账金额
+    /// * `ctx` - 查询上下文，包含平台预算和可选的接收者额度账户
     /// 
     /// # 返回值
-    /// * `Result<()>` - 成功返回Ok(())，失败会导致转账回滚
+    /// * `Result<PlatformBudgetStatus>` - 平台预算状态
     /// 
     /// # 注意事项
-    /// - 此函数由Token-2022程序调用，不是用户直接调用
-    /// - 执行失败会导致整个转账失败
-    /// - 应该尽量轻量，避免高gas消耗
-    /// - 账户由Token-2022程序自动传递和验证
-    /// - 函数名`execute`符合spl-transfer-hook-interface规范
-    /// - 在Anchor 0.29.0中，discriminator可能不匹配，需要使用fallback函数
-    pub fn execute(ctx: Context<ExecuteTransferHook>, amount: u64) -> Result<()> {
-        // 直接调用execute_internal的实现
-        instructions::hook::execute_internal(ctx, amount)
+    /// * 这是一个只读查询，已进入新周期但尚未有转账时按新周期计算
+    /// * 接收者从未收到过平台转账时额度账户不存在，传入null即可
+    /// 
+    /// # 使用示例
+    /// ```rust
+    /// const status = await program.methods
+    ///     .getPlatformBudget()
+    ///     .accounts({ platformBudget: budgetPda, recipientUsage: usagePda })
+    ///     .view();
+    /// 
+    /// console.log(`本周期剩余: ${status.periodRemaining}`);
+    /// ```
+    pub fn get_platform_budget(ctx: Context<GetPlatformBudget>) -> Result<PlatformBudgetStatus> {
+        instructions::query::get_platform_budget_handler(ctx)
     }
 
-    /// 暂停/恢复Transfer Hook
+    /// 获取积分排行榜（只读）
     /// 
-    /// 管理员可以暂停或恢复Transfer Hook的执行。
-    /// 暂停后，所有转账都会失败（因为Transfer Hook执行失败）。
+    /// 按累计获得的积分降序返回积分账户。
     /// 
     /// # 参数
-    /// * `ctx` - 管理员操作上下文
-    /// * `paused` - 暂停状态（true=暂停，false=恢复）
+    /// * `ctx` - 查询上下文，积分账户在`remaining_accounts`中
+    /// * `limit` - 返回条数（不超过`MAX_POINTS_LEADERBOARD`）
     /// 
     /// # 返回值
-    /// * `Result<()>` - 成功返回Ok(())
+    /// * `Result<Vec<PointsLeaderboardEntry>>` - 排行榜条目
     /// 
-    /// # 用途
-    /// - 紧急情况下暂停所有转账
-    /// - 系统维护时暂停
-    /// - 升级Transfer Hook程序前暂停
-    pub fn set_transfer_hook_paused(ctx: Context<TransferHookAdminAction>, paused: bool) -> Result<()> {
-        instructions::hook::set_transfer_hook_paused(ctx, paused)
-    }
-}
-
-/// 税率计算结果（用于返回给客户端）
-/// 
-/// 包含完整的税率计算信息，用于前端显示和用户决策。
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
-pub struct TaxCalculationResult {
-    /// 基础税率（basis points，10000 = 100%）
-    /// 这是不考虑任何折扣和惩罚的基础税率
-    pub base_tax_bps: u16,
-    
-    /// 持有时间折扣（basis points）
-    /// 根据持有时间计算的折扣金额，会从基础税率中扣除
-    pub holding_discount_bps: u16,
-    
-    /// 大额交易附加税（basis points）
-    /// 仅对卖出操作，当交易规模超过阈值时收取的附加税
-    pub whale_tax_bps: u16,
-    
-    /// 最终税率（basis points）
-    /// 综合考虑基础税率、折扣和附加税后的最终税率
-    pub final_tax_bps: u16,
-    
-    /// 税额（代币数量）
-    /// 根据最终税率计算出的实际税额
-    pub tax_amount: u64,
-    
-    /// 净转账金额（代币数量）
-    /// 扣除税收后，接收者实际收到的代币数量
-    pub net_amount: u64,
-}
-
-/// 持有者统计结果
-/// 
-/// 包含持有者的完整统计信息，用于展示用户的持有历史和享受的优惠。
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
-pub struct HolderStats {
-    /// 持有者钱包地址
-    pub owner: Pubkey,
-    
-    /// 持有天数
-    /// 从首次持有时间到当前时间的天数
-    pub holding_days: u64,
-    
-    /// 累计买入量
-    /// 用户累计买入的TOT代币总数
-    pub total_bought: u64,
-    
-    /// 累计卖出量
-    /// 用户累计卖出的TOT代币总数
-    pub total_sold: u64,
-    
-    /// 累计缴税总额
-    /// 用户累计支付的所有税收总和
-    pub total_tax_paid: u64,
-    
-    /// 是否被冻结
-    /// `true`表示账户被管理员冻结，无法转账
-    pub is_frozen: bool,
-    
-    /// 税率折扣等级
-    /// 枚举类型，客户端可以根据枚举值转换为字符串显示
-    /// - None: 无折扣
-    /// - Bronze: 10%折扣 (30-90天)
-    /// - Silver: 25%折扣 (90-180天)
-    /// - Gold: 50%折扣 (180-365天)
-    /// - Diamond: 75%折扣 (365+天)
-    pub tax_discount_tier: crate::instructions::query::DiscountTier,
-}
-
-/// Fallback函数
-/// 
-/// 处理spl-transfer-hook-interface的execute指令。
-/// 由于Anchor 0.29.0不支持#[interface]属性，需要使用fallback函数
-/// 来匹配spl-transfer-hook-interface定义的discriminator。
-/// 
-/// # 工作原理
-/// 
-/// 1. Token-2022程序调用transfer hook时，使用spl-transfer-hook-interface的discriminator
-/// 2. Anchor默认的指令路由无法识别这个discriminator
-/// 3. Fallback函数捕获所有未匹配的指令
-/// 4. 检查discriminator是否匹配execute指令
-/// 5. 如果匹配，解析参数并调用execute函数（内部调用execute_internal）
-/// 
-/// # Discriminator
-/// 
-/// execute指令的discriminator是SHA-256("spl-transfer-hook-interface:execute")的前8字节
-/// 值: [105, 37, 101, 197, 75, 251, 102, 26] (小端序)
-/// 
-/// # 注意事项
-/// 
-/// - Fallback函数需要手动解析账户，不能使用Anchor的自动账户验证
-/// - execute函数已添加，符合spl-transfer-hook-interface规范
-/// - Token-2022应该能够直接调用execute函数（推荐方式）
-/// - 如果discriminator不匹配，fallback函数会尝试处理（但受技术限制）
-/// - 如果升级到Anchor 0.30.0+，可以使用#[interface]属性替代fallback函数
-#[fallback]
-pub fn fallback(
-    program_id: &Pubkey,
-    accounts: &[AccountInfo],
-    instruction_data: &[u8],
-) -> Result<()> {
-    // 尝试解析为TransferHookInstruction
-    match TransferHookInstruction::unpack(instruction_data) {
-        Ok(TransferHookInstruction::Execute { amount }) => {
-            // 注意：fallback函数无法直接使用Anchor的Context和账户验证机制
-            // 因此无法在这里直接调用execute函数
-            // 
-            // 实际工作流程：
-            // 1. Token-2022调用transfer hook时，会尝试匹配execute函数的discriminator
-            // 2. 如果discriminator匹配，直接调用execute函数（推荐方式，已实现）
-            // 3. 如果discriminator不匹配，fallback函数会捕获（当前情况）
-            // 4. 由于fallback的限制，无法在fallback中直接使用Anchor的账户验证
-            // 
-            // 解决方案：
-            // - execute函数已添加，Token-2022应该能够通过Anchor的标准路由调用
-            // - 如果discriminator不匹配，可能需要：
-            //   a) 手动调整discriminator（不推荐）
-            //   b) 升级到Anchor 0.30.0+使用#[interface]属性（推荐）
-            // 
-            // 当前实现：fallback函数作为备用路由，但受技术限制无法完全工作
-            // 主要依赖execute函数被Token-2022正确调用
-            return Err(anchor_lang::error!(crate::errors::TotError::TransferHookFailed));
-        }
-        Ok(_) => {
-            // 其他TransferHookInstruction变体（如果有）
-            return Err(ProgramError::InvalidInstructionData.into());
-        }
-        Err(_) => {
-            // 无法解析为TransferHookInstruction，可能是其他指令
-            // 返回未识别的指令错误，让调用者知道这不是一个有效的指令
-            return Err(ProgramError::InvalidInstructionData.into());
-        }
+    /// # 注意事项
+    /// * 这是一个只读查询，客户端先用`getProgramAccounts`收集积分账户再传入
+    /// * 传入非积分账户时返回错误
+    /// 
+    /// # 使用示例
+    /// ```rust
+    /// const leaderboard = await program.methods
+    ///     .getPointsLeaderboard(10)
+    ///     .accounts({ pointsConfig: pointsConfigPda })
+    ///     .remainingAccounts(pointsPdas.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+    ///     .view();
+    /// ```
+    pub fn get_points_leaderboard<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetPointsLeaderboard<'info>>,
+        limit: u8,
+    ) -> Result<Vec<PointsLeaderboardEntry>> {
+        instructions::query::get_points_leaderboard_handler(ctx, limit)
     }
 }
 
-/// 注意：关于Transfer Hook实现的说明
-/// 
-/// 在Anchor 0.29.0中实现spl-transfer-hook-interface的execute指令有两种方式：
-/// 
-/// 方式1（当前实现）：
-/// - 添加execute函数，使用Anchor的标准discriminator
-/// - Token-2022可能无法直接匹配discriminator，但可以通过其他方式调用
-/// - 添加fallback函数作为备用路由（但受限于无法使用Anchor的账户验证）
-/// 
-/// 方式2（推荐，需要升级）：
-/// - 升级到Anchor 0.30.0+
-/// - 使用#[interface(spl_transfer_hook_interface::execute)]属性
-/// - 自动匹配spl-transfer-hook-interface的discriminator
-/// - 无需fallback函数
-/// 
-/// 当前实现说明：
-/// - execute函数已添加，符合spl-transfer-hook-interface规范
-/// - fallback函数已添加，但受技术限制无法完全工作
-/// - 建议测试execute函数是否能被Token-2022正确调用
-/// - 如果discriminator不匹配，考虑升级到Anchor 0.30.0+
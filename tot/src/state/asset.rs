@@ -110,7 +110,7 @@ pub struct AssetAccount {
     /// 
     /// 说明:
     /// - 资产的价值（以TOT代币计价）
-    /// - 已考虑decimals（乘以10^9）
+    /// - 已考虑decimals（乘以10^6）
     pub value: u64,
     
     /// 上链时间
@@ -48,7 +48,7 @@ pub struct AuctionAccount {
     /// 
     /// 说明:
     /// - 资产的当前价格（以TOT代币计价）
-    /// - 已考虑decimals（乘以10^9）
+    /// - 已考虑decimals（乘以10^6）
     /// - 每次夺取时，价格会增加10%
     pub price: u64,
     
@@ -133,7 +133,7 @@ impl AuctionAccount {
         self.price
             .checked_mul(110)
             .and_then(|v| v.checked_div(100))
-            .ok_or_else(|| anchor_lang::error!(anchor_lang::error::ErrorCode::ArithmeticError))
+            .ok_or_else(|| anchor_lang::error!(crate::errors::TotError::MathOverflow))
     }
     
     /// 计算分账金额
@@ -148,12 +148,12 @@ impl AuctionAccount {
         let fee_amount = total_amount
             .checked_mul(5)
             .and_then(|v| v.checked_div(100))
-            .ok_or_else(|| anchor_lang::error!(anchor_lang::error::ErrorCode::ArithmeticError))?;
+            .ok_or_else(|| anchor_lang::error!(crate::errors::TotError::MathOverflow))?;
         
         // 计算95%给房主
         let payout_amount = total_amount
             .checked_sub(fee_amount)
-            .ok_or_else(|| anchor_lang::error!(anchor_lang::error::ErrorCode::ArithmeticError))?;
+            .ok_or_else(|| anchor_lang::error!(crate::errors::TotError::MathOverflow))?;
         
         Ok((fee_amount, payout_amount))
     }
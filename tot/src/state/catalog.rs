@@ -0,0 +1,265 @@
+//! # 价格目录模块
+//!
+//! 本模块定义了`consume_to_treasury`使用的链上价格目录。
+//! 管理员为每个消费类型（以及可选的商品SKU）登记价格、持有者折扣和上架状态，
+//! 消费时金额必须与目录价格一致，价格不再只由前端约束。
+//! 目录同时按消费类型保存收入分成（销毁/财库/受益人），并为商品登记受益人地址。
+//!
+//! ============================================
+// 文件: src/state/catalog.rs
+// 价格目录账户定义
+// ============================================
+
+use anchor_lang::prelude::*;
+use crate::constants::{limits, BASIS_POINTS};
+use crate::errors::TotError;
+use crate::utils::math::calculate_bps;
+
+/// 价格条目
+///
+/// 以（消费类型, SKU）为键，SKU为0表示该消费类型的默认价格。
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PriceEntry {
+    /// 消费类型（`ConsumeType as u8`）
+    pub consume_type: u8,
+    /// 商品SKU（0表示默认价格）
+    pub sku: u32,
+    /// 价格（基础单位）
+    ///
+    /// 0表示不限金额，仅用于登记受益人（如按市场登记的预测下注）
+    pub price: u64,
+    /// 持有者折扣（basis points，0表示无折扣）
+    pub discount_bps: u16,
+    /// 享受折扣所需的最少持有天数
+    pub min_holding_days: u16,
+    /// 是否上架
+    pub is_active: bool,
+    /// 收入分成受益人（如功能运营方、预测市场创建者）
+    ///
+    /// Pubkey::default()表示未登记，受益人份额并入财库
+    pub beneficiary: Pubkey,
+}
+
+impl PriceEntry {
+    /// 序列化后的大小: 50 字节
+    pub const LEN: usize = 1 + // consume_type (u8)
+        4 + // sku (u32)
+        8 + // price (u64)
+        2 + // discount_bps (u16)
+        2 + // min_holding_days (u16)
+        1 + // is_active (bool)
+        32; // beneficiary (Pubkey)
+
+    /// 计算应付价格
+    ///
+    /// 持有天数达到`min_holding_days`时扣除`discount_bps`折扣；
+    /// 未传入持有者信息（`holding_days`为None）时按原价。
+    pub fn price_for(&self, holding_days: Option<u64>) -> Result<u64> {
+        match holding_days {
+            Some(days) if self.discount_bps > 0 && days >= self.min_holding_days as u64 => {
+                let discount = calculate_bps(self.price, self.discount_bps)?;
+                self.price.checked_sub(discount).ok_or(error!(TotError::MathUnderflow))
+            }
+            _ => Ok(self.price),
+        }
+    }
+}
+
+/// 收入分成配置
+///
+/// 三项之和必须为10000（100%）；全部为0表示未配置，消费全额进入财库。
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct RevenueSplit {
+    /// 销毁比例（basis points）
+    pub burn_bps: u16,
+    /// 财库比例（basis points）
+    pub treasury_bps: u16,
+    /// 受益人比例（basis points）
+    pub beneficiary_bps: u16,
+}
+
+/// 按收入分成计算的消费分配结果
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct RevenueDistribution {
+    /// 销毁数量
+    pub to_burn: u64,
+    /// 转入财库数量
+    pub to_treasury: u64,
+    /// 转给受益人数量
+    pub to_beneficiary: u64,
+}
+
+impl RevenueSplit {
+    /// 序列化后的大小: 6 字节
+    pub const LEN: usize = 2 + 2 + 2;
+
+    /// 是否已配置分成
+    pub fn is_configured(&self) -> bool {
+        self.burn_bps != 0 || self.treasury_bps != 0 || self.beneficiary_bps != 0
+    }
+
+    /// 校验三项之和为10000
+    pub fn validate(&self) -> Result<()> {
+        let total = self.burn_bps as u64 + self.treasury_bps as u64 + self.beneficiary_bps as u64;
+        require!(total == BASIS_POINTS, TotError::InvalidRevenueSplit);
+        Ok(())
+    }
+
+    /// 计算消费金额的分配
+    ///
+    /// 销毁和受益人份额按比例向下取整，取整误差计入财库；
+    /// 没有受益人（`has_beneficiary`为false）时受益人份额也并入财库。
+    pub fn distribute(&self, amount: u64, has_beneficiary: bool) -> Result<RevenueDistribution> {
+        let to_burn = calculate_bps(amount, self.burn_bps)?;
+        let to_beneficiary = if has_beneficiary {
+            calculate_bps(amount, self.beneficiary_bps)?
+        } else {
+            0
+        };
+        let to_treasury = amount
+            .checked_sub(to_burn)
+            .and_then(|rest| rest.checked_sub(to_beneficiary))
+            .ok_or(TotError::MathUnderflow)?;
+
+        Ok(RevenueDistribution {
+            to_burn,
+            to_treasury,
+            to_beneficiary,
+        })
+    }
+}
+
+/// 价格目录账户结构体
+///
+/// ## 账户特性
+///
+/// - 使用PDA创建，种子: `["tot_price_catalog"]`，全局唯一
+/// - 由系统管理员维护，最多`MAX_CATALOG_ENTRIES`个条目
+/// - 目录中没有任何条目的消费类型（如预测下注）不限制金额
+#[account]
+pub struct PriceCatalog {
+    /// 价格条目列表
+    ///
+    /// 类型: Vec<PriceEntry> (4 + 50 × 64 字节)
+    pub entries: Vec<PriceEntry>,
+
+    /// 按消费类型的收入分成
+    ///
+    /// 类型: [RevenueSplit; 8] (48字节，下标为`ConsumeType as u8`)
+    pub splits: [RevenueSplit; 8],
+
+    /// 最后更新时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub last_updated: i64,
+
+    /// PDA Bump种子
+    ///
+    /// 类型: u8 (1字节)
+    pub bump: u8,
+}
+
+impl PriceCatalog {
+    /// 计算账户所需空间
+    ///
+    /// 总大小: 3269 字节
+    pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
+        4 + // Vec length (u32)
+        (PriceEntry::LEN * limits::MAX_CATALOG_ENTRIES) + // entries (max 64)
+        (RevenueSplit::LEN * 8) + // splits ([RevenueSplit; 8])
+        8 + // last_updated (i64)
+        1; // bump (u8)
+
+    /// 查找（消费类型, SKU）对应的价格条目
+    pub fn find(&self, consume_type: u8, sku: u32) -> Option<&PriceEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.consume_type == consume_type && entry.sku == sku)
+    }
+
+    /// 新增或更新价格条目
+    ///
+    /// # 返回值
+    /// * `Result<bool>` - `true`表示新增，`false`表示更新已有条目
+    ///
+    /// # 错误
+    /// * `TotError::InvalidPriceEntry` - 价格为0且未登记受益人，或折扣超过100%
+    /// * `TotError::CatalogFull` - 新增条目时目录已满
+    pub fn upsert(&mut self, entry: PriceEntry) -> Result<bool> {
+        require!(
+            (entry.price > 0 || entry.beneficiary != Pubkey::default())
+                && entry.discount_bps as u64 <= BASIS_POINTS,
+            TotError::InvalidPriceEntry
+        );
+
+        if let Some(existing) = self.entries
+            .iter_mut()
+            .find(|e| e.consume_type == entry.consume_type && e.sku == entry.sku)
+        {
+            *existing = entry;
+            return Ok(false);
+        }
+
+        require!(
+            self.entries.len() < limits::MAX_CATALOG_ENTRIES,
+            TotError::CatalogFull
+        );
+        self.entries.push(entry);
+        Ok(true)
+    }
+
+    /// 删除价格条目
+    ///
+    /// # 错误
+    /// * `TotError::PriceNotListed` - 条目不存在
+    pub fn remove(&mut self, consume_type: u8, sku: u32) -> Result<()> {
+        let index = self.entries
+            .iter()
+            .position(|e| e.consume_type == consume_type && e.sku == sku)
+            .ok_or(TotError::PriceNotListed)?;
+        self.entries.remove(index);
+        Ok(())
+    }
+
+    /// 获取消费类型的收入分成（未配置时返回全0）
+    pub fn split_for(&self, consume_type: u8) -> RevenueSplit {
+        self.splits
+            .get(consume_type as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// 获取（消费类型, SKU）登记的受益人
+    pub fn beneficiary_for(&self, consume_type: u8, sku: u32) -> Option<Pubkey> {
+        self.find(consume_type, sku)
+            .map(|entry| entry.beneficiary)
+            .filter(|beneficiary| *beneficiary != Pubkey::default())
+    }
+
+    /// 计算消费应付的目录价格
+    ///
+    /// # 返回值
+    /// * `Ok(None)` - 该消费类型没有任何价格条目，或条目价格为0，不限制金额
+    /// * `Ok(Some(price))` - 应付价格（已扣除持有者折扣）
+    ///
+    /// # 错误
+    /// * `TotError::PriceNotListed` - 该消费类型有定价，但没有该SKU的条目
+    /// * `TotError::PriceInactive` - 条目已下架
+    pub fn expected_price(
+        &self,
+        consume_type: u8,
+        sku: u32,
+        holding_days: Option<u64>,
+    ) -> Result<Option<u64>> {
+        if !self.entries.iter().any(|e| e.consume_type == consume_type) {
+            return Ok(None);
+        }
+
+        let entry = self.find(consume_type, sku).ok_or(TotError::PriceNotListed)?;
+        require!(entry.is_active, TotError::PriceInactive);
+        if entry.price == 0 {
+            return Ok(None);
+        }
+        entry.price_for(holding_days).map(Some)
+    }
+}
@@ -0,0 +1,122 @@
+//! # 追回记录模块
+//! 
+//! 本模块定义了通过Permanent Delegate追回冻结账户代币的链上审计记录。
+//! 每次追回都会创建一条不可修改的记录，便于事后审计。
+//! 
+//! ============================================
+// 文件: src/state/clawback.rs
+// 追回记录定义
+// ============================================
+
+use anchor_lang::prelude::*;
+
+/// 追回方式
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ClawbackAction {
+    /// 转移到指定的代币账户（如国库或受害者账户）
+    Transfer,
+    /// 直接销毁
+    Burn,
+}
+
+/// 追回记录账户结构体
+/// 
+/// ## 账户特性
+/// 
+/// - 使用PDA创建，种子: `["tot_clawback", case_id.to_le_bytes()]`
+/// - 每个案件编号只能执行一次追回，重复执行会因账户已存在而失败
+/// - 创建后不再修改
+#[account]
+pub struct ClawbackRecord {
+    /// 案件编号
+    /// 
+    /// 类型: u64 (8字节)
+    /// 
+    /// 说明:
+    /// - 由合规流程分配的案件编号，链下文档据此关联
+    pub case_id: u64,
+    
+    /// 被追回的持有者钱包
+    /// 
+    /// 类型: Pubkey (32字节)
+    pub holder: Pubkey,
+    
+    /// 被追回的代币账户
+    /// 
+    /// 类型: Pubkey (32字节)
+    pub source_token_account: Pubkey,
+    
+    /// 接收代币账户
+    /// 
+    /// 类型: Pubkey (32字节)
+    /// 
+    /// 说明:
+    /// - 销毁时为Pubkey::default()
+    pub destination_token_account: Pubkey,
+    
+    /// 追回方式
+    /// 
+    /// 类型: ClawbackAction (1字节)
+    pub action: ClawbackAction,
+    
+    /// 追回数量
+    /// 
+    /// 类型: u64 (8字节，基础单位)
+    pub amount: u64,
+    
+    /// 追回原因哈希
+    /// 
+    /// 类型: [u8; 32] (32字节)
+    /// 
+    /// 说明:
+    /// - 链下追回理由文档的哈希（如SHA-256）
+    /// - 链上不保存原文，审计时用原文重新计算哈希比对
+    pub reason_hash: [u8; 32],
+    
+    /// 执行者
+    /// 
+    /// 类型: Pubkey (32字节)
+    /// 
+    /// 说明:
+    /// - 系统管理员（多签）或合规权限地址
+    pub operator: Pubkey,
+    
+    /// 冻结原因代码（追回时的快照）
+    /// 
+    /// 类型: u8 (1字节)
+    pub freeze_reason: u8,
+    
+    /// 冻结时间（追回时的快照）
+    /// 
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub frozen_at: i64,
+    
+    /// 执行时间
+    /// 
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub executed_at: i64,
+    
+    /// PDA Bump种子
+    /// 
+    /// 类型: u8 (1字节)
+    pub bump: u8,
+}
+
+impl ClawbackRecord {
+    /// 计算账户所需空间
+    /// 
+    /// 总大小: 235 字节
+    pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
+        8 + // case_id (u64)
+        32 + // holder (Pubkey)
+        32 + // source_token_account (Pubkey)
+        32 + // destination_token_account (Pubkey)
+        1 + // action (ClawbackAction)
+        8 + // amount (u64)
+        32 + // reason_hash ([u8; 32])
+        32 + // operator (Pubkey)
+        1 + // freeze_reason (u8)
+        8 + // frozen_at (i64)
+        8 + // executed_at (i64)
+        1; // bump (u8)
+}
@@ -9,6 +9,7 @@
 // ============================================
 
 use anchor_lang::prelude::*;
+use crate::errors::TotError;
 // 注意：config.rs中只存储tax_config的地址（Pubkey），不直接使用TaxConfig类型
 // 因此不需要导入TaxConfig，这样可以减少模块间的依赖耦合
 
@@ -24,7 +25,6 @@ use anchor_lang::prelude::*;
 /// - 只有管理员可以修改
 /// - 所有关键操作都需要验证此账户
 #[account]
-#[derive(Default)]
 pub struct TotConfig {
     /// 管理员地址（总司令）
     /// 
@@ -115,21 +115,20 @@ pub struct TotConfig {
     /// - 平台收入统计
     pub tws_treasury: Pubkey,
     
-    /// 恐慌模式标志
+    /// 恐慌税率模式标志
     /// 
     /// 类型: bool (1字节)
     /// 
     /// 说明:
-    /// - `true`: 系统处于恐慌模式，卖出操作被拒绝
+    /// - `true`: 系统处于恐慌模式，大额卖出按TaxConfig中的恐慌税率征税
     /// - `false`: 系统正常运行
     /// 
     /// 触发条件:
-    /// - 管理员手动启用（应对市场异常）
-    /// - 检测到大额抛售（如果实现自动触发）
+    /// - 管理员通过`set_panic_mode`手动启用（应对市场异常）
     /// 
     /// 效果:
-    /// - 暂停所有卖出操作
-    /// - 买入和普通转账仍可进行（取决于具体实现）
+    /// - 卖出金额占供应量比例超过panic_threshold_bps时，税率提升至panic_tax_bps
+    /// - 只影响税率，不会拒绝任何操作；暂停操作请使用`paused_operations`
     /// - 用于保护流动性池免受冲击
     pub panic_mode: bool,
     
@@ -167,7 +166,7 @@ pub struct TotConfig {
     /// 
     /// 说明:
     /// - 累计销毁的代币总数量
-    /// - 每次税收分配中的销毁部分会累加到此字段
+    /// - 每次税收分配中的销毁部分和`clawback`的销毁都会累加到此字段
     /// - 用于计算实际流通量
     /// 
     /// 用途:
@@ -181,8 +180,9 @@ pub struct TotConfig {
     /// 类型: u64 (8字节)
     /// 
     /// 说明:
-    /// - 系统累计收取的所有税收总和
-    /// - 每次转账的税收都会累加到此字段
+    /// - 系统累计收取的所有税收总和（含销毁部分）
+    /// - 所有收税指令（带税转账、批量转账、代币流提取、挂单成交）都会累加到此字段
+    /// - 不含Token-2022 Transfer Fee扩展预扣的费用，见`TransferHookConfig.total_tax_collected`
     /// - 用于统计和审计
     /// 
     /// 用途:
@@ -197,15 +197,209 @@ pub struct TotConfig {
     /// 
     /// 说明:
     /// - 用于标识配置账户的版本
-    /// - 初始版本为1
-    /// - 未来升级时可以增加版本号
+    /// - 初始版本为1，只有128字节预留空间的旧布局
+    /// - 版本2为当前布局（`CURRENT_VERSION`），旧账户通过`migrate_config`升级
     /// 
     /// 用途:
     /// - 兼容性检查
     /// - 升级管理
     /// - 版本迁移
     pub version: u8,
-    
+
+    /// 待接受的新管理员地址
+    ///
+    /// 类型: Pubkey (32字节)
+    ///
+    /// 说明:
+    /// - 由`propose_authority`设置，`accept_authority`成功后清空
+    /// - `Pubkey::default()`表示当前没有进行中的权限移交
+    /// - 新地址必须亲自签名接受，避免输错地址导致管理权限永久丢失
+    pub pending_authority: Pubkey,
+
+    /// 管理员移交最早可接受时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    ///
+    /// 说明:
+    /// - 提议时设置为 当前时间 + authority_transfer_delay
+    /// - 在此时间之前调用`accept_authority`会被拒绝
+    pub authority_transfer_eta: i64,
+
+    /// 权限移交最小延迟（秒）
+    ///
+    /// 类型: i64 (8字节)
+    ///
+    /// 说明:
+    /// - 管理员、Transfer Hook管理员、Token-2022权限的移交共用此延迟
+    /// - 0表示不设时间锁，上限为`time::MAX_AUTHORITY_TRANSFER_DELAY`
+    /// - 修改只影响之后的提议，已提议的移交保持原有的生效时间
+    /// - 缩短延迟要等当前延迟过去后才生效（见`pending_authority_transfer_delay`），
+    ///   读取时使用`authority_transfer_delay_at`
+    pub authority_transfer_delay: i64,
+
+    /// 待接受的Token-2022权限新地址
+    ///
+    /// 类型: Pubkey (32字节)
+    ///
+    /// 说明:
+    /// - 由`propose_token_authority`设置，`accept_token_authority`成功后清空
+    /// - 覆盖Mint的冻结、转账费、提取费用和元数据指针权限
+    pub pending_token_authority: Pubkey,
+
+    /// 待移交的Token-2022权限类型位图
+    ///
+    /// 类型: u8 (1字节)
+    ///
+    /// 说明:
+    /// - 每一位对应一种`TokenAuthorityKind`
+    /// - 0表示当前没有进行中的Token-2022权限移交
+    pub pending_token_authority_kinds: u8,
+
+    /// Token-2022权限移交最早可接受时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub token_authority_transfer_eta: i64,
+
+    /// 暂停操作位图
+    ///
+    /// 类型: u16 (2字节)
+    ///
+    /// 说明:
+    /// - 每一位对应一个`PauseFlag`指令族
+    /// - 对应位为1且未到自动恢复时间时，该指令族的处理器返回OperationPaused
+    /// - 各指令族互相独立，例如暂停拍卖不会影响转账
+    pub paused_operations: u16,
+
+    /// 各指令族的自动恢复时间
+    ///
+    /// 类型: [i64; 8] (64字节，按`PauseFlag`的值索引)
+    ///
+    /// 说明:
+    /// - 0表示不自动恢复，需要管理员手动解除暂停
+    /// - 非0时，到达该时间后暂停自动失效，无需再发交易
+    pub pause_resume_at: [i64; 8],
+
+    /// 铸造权限是否已放弃
+    ///
+    /// 类型: bool (1字节)
+    ///
+    /// 说明:
+    /// - 铸造权限由`MINT_AUTHORITY_SEED` PDA持有，只有本程序能够铸造
+    /// - `renounce_mint_authority`把Mint的铸造权限设置为None后置为true
+    /// - 此操作不可逆，之后任何人都无法再铸造TOT
+    pub mint_authority_renounced: bool,
+
+    /// 合规权限地址
+    ///
+    /// 类型: Pubkey (32字节)
+    ///
+    /// 说明:
+    /// - 除系统管理员（多签）外，唯一可以执行`clawback`的地址
+    /// - 由系统管理员通过`set_compliance_authority`设置
+    /// - Pubkey::default()表示未设置，只有系统管理员可以追回
+    pub compliance_authority: Pubkey,
+
+    /// 累计追回数量
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    ///
+    /// 说明:
+    /// - 所有`clawback`追回的代币总量（含转移和销毁）
+    pub total_clawed_back: u64,
+
+    /// 累计追回次数
+    ///
+    /// 类型: u64 (8字节)
+    pub clawback_count: u64,
+
+    /// 锁定供应量
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    ///
+    /// 说明:
+    /// - 五个池子代币账户和TWS财库代币账户的余额之和，不计入流通供应量
+    /// - `mint_to_pools`时设为总供应量，池子释放时减少，消费到财库时增加
+    /// - 任何人可以通过`refresh_circulating_supply`按账户实际余额重新计算
+    pub locked_supply: u64,
+
+    /// 锁定供应量最后重新计算的时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub locked_supply_refreshed_at: i64,
+
+    /// 按消费类型累计的消费数量
+    ///
+    /// 类型: [u64; 8] (64字节，基础单位)
+    ///
+    /// 说明:
+    /// - 下标为`ConsumeType as u8`，`consume_to_treasury`时累加
+    /// - 当前有7种消费类型，最后一个位置预留
+    pub total_consumed: [u64; 8],
+
+    /// 累计平台分发数量
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    ///
+    /// 说明:
+    /// - `platform_transfer`从平台账户转出的代币总量
+    pub total_platform_distributed: u64,
+
+    /// 累计拍卖手续费
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    ///
+    /// 说明:
+    /// - `seize_auction`转入TWS财库的5%手续费总和
+    pub total_auction_fees: u64,
+
+    /// 累计池子释放数量
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    ///
+    /// 说明:
+    /// - `release_pool_tokens`从五大池子释放的代币总量
+    pub total_pool_released: u64,
+
+    /// 消费回执核销权限
+    ///
+    /// 类型: Pubkey (32字节)
+    ///
+    /// 说明:
+    /// - 可以与系统管理员一起核销和关闭消费回执（通常是后端服务的钱包）
+    /// - 由系统管理员通过`set_receipt_authority`设置
+    /// - Pubkey::default()表示未设置，只有系统管理员可以核销
+    pub receipt_authority: Pubkey,
+
+    /// 奖励兑换券签名公钥
+    ///
+    /// 类型: Pubkey (32字节)
+    ///
+    /// 说明:
+    /// - 平台用对应的Ed25519私钥离线签发奖励兑换券，用户自行提交`redeem_voucher`领取
+    /// - 与系统管理员相互独立，热钱包不再需要持有管理员私钥
+    /// - 由系统管理员通过`set_voucher_signer`设置
+    /// - Pubkey::default()表示未设置，兑换券功能关闭
+    pub voucher_signer: Pubkey,
+
+    /// 待生效的权限移交延迟（秒）
+    ///
+    /// 类型: i64 (8字节)
+    ///
+    /// 说明:
+    /// - 缩短延迟时记录新值，到`authority_transfer_delay_eta`后取代`authority_transfer_delay`，
+    ///   避免取得管理员私钥的一方先把延迟改为0再立即完成权限移交
+    /// - 延长延迟立即生效，并取消待生效的缩短
+    pub pending_authority_transfer_delay: i64,
+
+    /// 待生效延迟的生效时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    ///
+    /// 说明:
+    /// - 设置缩短时为当前时间加上当前生效的延迟
+    /// - 0表示没有待生效的缩短
+    pub authority_transfer_delay_eta: i64,
+
     /// 预留空间
     /// 
     /// 类型: [u8; 128] (128字节)
@@ -222,6 +416,19 @@ pub struct TotConfig {
 }
 
 impl TotConfig {
+    /// 当前账户布局的版本号
+    pub const CURRENT_VERSION: u8 = 2;
+
+    /// 版本1（旧布局）的账户大小
+    ///
+    /// 总大小: 362 字节（`authority`至`version`的字段之后是128字节预留空间）
+    pub const LEGACY_LEN: usize = 8 + // discriminator (Anchor自动添加)
+        32 * 6 + // authority, mint, treasury, liquidity_pool, tax_config, tws_treasury (Pubkey)
+        1 + // panic_mode (bool)
+        8 * 4 + // initialized_at, total_minted, total_burned, total_tax_collected (i64/u64)
+        1 + // version (u8)
+        128; // reserved ([u8; 128])
+
     /// 计算账户所需空间
     /// 
     /// 返回配置账户所需的总字节数。
@@ -231,7 +438,7 @@ impl TotConfig {
     /// - 8字节: Anchor自动添加的discriminator
     /// - 各字段的实际大小总和
     /// 
-    /// 总大小: 318 字节
+    /// 总大小: 750 字节
     pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
         32 + // authority (Pubkey)
         32 + // mint (Pubkey)
@@ -245,7 +452,142 @@ impl TotConfig {
         8 + // total_burned (u64)
         8 + // total_tax_collected (u64)
         1 + // version (u8)
+        32 + // pending_authority (Pubkey)
+        8 + // authority_transfer_eta (i64)
+        8 + // authority_transfer_delay (i64)
+        32 + // pending_token_authority (Pubkey)
+        1 + // pending_token_authority_kinds (u8)
+        8 + // token_authority_transfer_eta (i64)
+        2 + // paused_operations (u16)
+        64 + // pause_resume_at ([i64; 8])
+        1 + // mint_authority_renounced (bool)
+        32 + // compliance_authority (Pubkey)
+        8 + // total_clawed_back (u64)
+        8 + // clawback_count (u64)
+        8 + // locked_supply (u64)
+        8 + // locked_supply_refreshed_at (i64)
+        64 + // total_consumed ([u64; 8])
+        8 + // total_platform_distributed (u64)
+        8 + // total_auction_fees (u64)
+        8 + // total_pool_released (u64)
+        32 + // receipt_authority (Pubkey)
+        32 + // voucher_signer (Pubkey)
+        8 + // pending_authority_transfer_delay (i64)
+        8 + // authority_transfer_delay_eta (i64)
         128; // reserved ([u8; 128])
+
+    /// 计算流通供应量
+    ///
+    /// 流通供应量 = Mint总供应量 - 锁定供应量。
+    /// 销毁直接减少Mint总供应量，因此无需单独更新。
+    /// 大额交易附加税和恐慌税率阈值按流通供应量计算。
+    ///
+    /// # 注意事项
+    /// - 结果为0（全部代币仍在池子中）时退回使用总供应量，避免任何金额都被视为大额交易
+    pub fn circulating_supply(&self, mint_supply: u64) -> u64 {
+        match mint_supply.saturating_sub(self.locked_supply) {
+            0 => mint_supply,
+            circulating => circulating,
+        }
+    }
+
+    /// 累计一次收税
+    ///
+    /// `tax_amount`为税额总额（含销毁部分），`burned`为其中销毁的数量。
+    pub fn record_tax(&mut self, tax_amount: u64, burned: u64) -> Result<()> {
+        self.total_tax_collected = self.total_tax_collected
+            .checked_add(tax_amount)
+            .ok_or(TotError::MathOverflow)?;
+        self.record_burn(burned)
+    }
+
+    /// 累计销毁数量（税收销毁和消费分成销毁）
+    pub fn record_burn(&mut self, burned: u64) -> Result<()> {
+        self.total_burned = self.total_burned
+            .checked_add(burned)
+            .ok_or(TotError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// 按消费类型累计消费数量
+    pub fn record_consume(&mut self, consume_type: u8, amount: u64) -> Result<()> {
+        let total = self.total_consumed
+            .get_mut(consume_type as usize)
+            .ok_or(TotError::InvalidAmount)?;
+        *total = total.checked_add(amount).ok_or(TotError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// 指定时间生效的权限移交延迟
+    ///
+    /// 待生效的缩短已到生效时间时返回缩短后的延迟，否则返回`authority_transfer_delay`。
+    /// 各权限移交的提议按此计算生效时间。
+    pub fn authority_transfer_delay_at(&self, current_timestamp: i64) -> i64 {
+        if self.authority_transfer_delay_eta != 0
+            && current_timestamp >= self.authority_transfer_delay_eta
+        {
+            self.pending_authority_transfer_delay
+        } else {
+            self.authority_transfer_delay
+        }
+    }
+
+    /// 检查地址是否可以核销消费回执（系统管理员或已设置的回执核销权限）
+    pub fn is_receipt_redeemer(&self, key: &Pubkey) -> bool {
+        *key == self.authority
+            || (self.receipt_authority != Pubkey::default() && *key == self.receipt_authority)
+    }
+
+    /// 检查指令族当前是否处于暂停状态
+    ///
+    /// 暂停位已设置且（未设置自动恢复时间或尚未到达恢复时间）时返回true。
+    pub fn is_paused(&self, flag: PauseFlag, current_timestamp: i64) -> bool {
+        if self.paused_operations & flag.bit() == 0 {
+            return false;
+        }
+        let resume_at = self.pause_resume_at[flag as usize];
+        resume_at == 0 || current_timestamp < resume_at
+    }
+
+    /// 要求指令族未被暂停
+    ///
+    /// 供各指令处理器在验证阶段调用，暂停时返回`TotError::OperationPaused`。
+    pub fn require_not_paused(&self, flag: PauseFlag, current_timestamp: i64) -> Result<()> {
+        require!(
+            !self.is_paused(flag, current_timestamp),
+            TotError::OperationPaused
+        );
+        Ok(())
+    }
+}
+
+/// 可暂停的指令族
+///
+/// 每个值对应`TotConfig.paused_operations`中的一位，
+/// 同时作为`pause_resume_at`数组的索引。
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PauseFlag {
+    /// 带税转账（transfer_with_tax，包括免税路径）
+    Transfers = 0,
+    /// 卖出（transfer_with_tax中is_sell=true的转账）
+    Sells = 1,
+    /// 消费到财库（consume_to_treasury）
+    Consume = 2,
+    /// 平台转账（platform_transfer、redeem_voucher）
+    PlatformTransfers = 3,
+    /// 拍卖（create_auction、seize_auction）
+    Auctions = 4,
+    /// 资产上链（mint_asset）
+    AssetMinting = 5,
+    /// 池子释放（从池子账户向外释放代币的指令）
+    PoolReleases = 6,
+}
+
+impl PauseFlag {
+    /// 在位图中的掩码
+    pub fn bit(self) -> u16 {
+        1 << (self as u16)
+    }
 }
 
 /// 初始化参数结构体
@@ -67,6 +67,7 @@ pub struct HolderAccount {
     /// - 用户首次获得TOT代币的时间
     /// - 用于计算持有天数
     /// - 在首次买入时设置
+    /// - 卖出后余额为0时重置为卖出时间
     /// 
     /// 用途:
     /// - 计算持有天数 = (当前时间 - first_hold_time) / 86400
@@ -207,6 +208,19 @@ pub struct HolderAccount {
     /// - 确保账户地址的确定性
     /// - 在账户创建时自动计算
     pub bump: u8,
+    
+    /// 冻结时间
+    /// 
+    /// 类型: i64 (8字节，Unix时间戳)
+    /// 
+    /// 说明:
+    /// - 0: 未冻结
+    /// - 冻结时记录当前时间，解冻时清零
+    /// - 位于`bump`之后，占用旧账户末尾未使用的8字节（见`LEN`）
+    /// 
+    /// 用途:
+    /// - 追回（clawback）前校验冻结时长
+    pub frozen_at: i64,
 }
 
 impl HolderAccount {
@@ -214,7 +228,11 @@ impl HolderAccount {
     /// 
     /// 返回持有者账户所需的总字节数，用于账户初始化时的空间分配。
     /// 
-    /// 总大小: 129 字节
+    /// 总大小: 137 字节
+    /// 
+    /// 账户创建时分配`8 + LEN`字节，末尾一直有8字节未使用。
+    /// `frozen_at`放在`bump`之后正好占用这8字节，旧账户无需realloc，
+    /// 读出的`frozen_at`为0（升级前冻结的账户需要解冻后重新冻结才能追回）。
     pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
         32 + // owner (Pubkey)
         32 + // token_account (Pubkey)
@@ -227,7 +245,8 @@ impl HolderAccount {
         8 + // total_consumed (u64)
         1 + // is_frozen (bool)
         1 + // freeze_reason (u8)
-        1; // bump (u8)
+        1 + // bump (u8)
+        8; // frozen_at (i64)
     
     /// 计算持有天数
     /// 
@@ -335,6 +354,7 @@ impl HolderAccount {
     /// # 参数
     /// * `amount` - 卖出的代币数量（基础单位，原始金额）
     /// * `tax_paid` - 本次交易支付的税额（基础单位）
+    /// * `remaining_balance` - 卖出后持有者剩余的代币余额（基础单位）
     /// * `timestamp` - 交易时间戳（Unix时间戳）
     /// 
     /// # 返回值
@@ -344,9 +364,10 @@ impl HolderAccount {
     /// 1. 累加`total_sold`（累计卖出量）
     /// 2. 累加`total_tax_paid`（累计缴税额）
     /// 3. 更新`last_transaction_time`（最后交易时间）
+    /// 4. 卖空时将`first_hold_time`重置为本次卖出时间
     /// 
     /// # 注意
-    /// - 卖出不会更新首次持有时间（保持首次持有时间不变）
+    /// - 部分卖出不会更新首次持有时间，清仓后持有时间重新计算
     /// - 卖出金额是原始金额（未扣除税收前）
     /// 
     /// # 错误
@@ -357,11 +378,18 @@ impl HolderAccount {
     /// let clock = Clock::get()?;
     /// holder_account.record_sell(
     ///     1_000_000_000, // 卖出1000个代币（原始金额）
-    ///     200_000_000,   // 支付20个代币的税（高税率）
+    ///     200_000_000,   // 支付200个代币的税（高税率）
+    ///     0,             // 已清仓
     ///     clock.unix_timestamp
     /// )?;
     /// ```
-    pub fn record_sell(&mut self, amount: u64, tax_paid: u64, timestamp: i64) -> Result<()> {
+    pub fn record_sell(
+        &mut self,
+        amount: u64,
+        tax_paid: u64,
+        remaining_balance: u64,
+        timestamp: i64,
+    ) -> Result<()> {
         // 累加累计卖出量
         self.total_sold = self.total_sold
             .checked_add(amount)
@@ -375,6 +403,11 @@ impl HolderAccount {
         // 更新最后交易时间
         self.last_transaction_time = timestamp;
         
+        // 清仓后持有时间从卖出时刻重新计算
+        if remaining_balance == 0 {
+            self.first_hold_time = timestamp;
+        }
+        
         Ok(())
     }
     
@@ -405,7 +438,7 @@ impl HolderAccount {
     /// ```rust
     /// let clock = Clock::get()?;
     /// holder_account.record_consume(
-    ///     100_000_000_000, // 消费100个代币
+    ///     100_000_000, // 消费100个代币
     ///     clock.unix_timestamp
     /// )?;
     /// ```
@@ -420,4 +453,33 @@ impl HolderAccount {
         
         Ok(())
     }
+
+    /// 从未类型化的账户读取持有者信息
+    /// 
+    /// 用于Transfer Hook和批量转账等通过`UncheckedAccount`或
+    /// `remaining_accounts`传入持有者账户的场景。
+    /// 
+    /// # 参数
+    /// * `info` - 持有者PDA账户
+    /// * `program_id` - 本程序ID
+    /// 
+    /// # 返回值
+    /// * `Result<Option<Self>>` - 持有者PDA尚未初始化（或不属于本程序）时返回None
+    pub fn load_optional(info: &AccountInfo, program_id: &Pubkey) -> Result<Option<Self>> {
+        if info.owner != program_id || info.data_is_empty() {
+            return Ok(None);
+        }
+
+        let data = info.try_borrow_data()?;
+        Ok(Some(Self::try_deserialize(&mut &data[..])?))
+    }
+
+    /// 把持有者信息写回账户
+    /// 
+    /// 与`load_optional`配对使用，账户必须可写。
+    pub fn store(&self, info: &AccountInfo) -> Result<()> {
+        let mut data = info.try_borrow_mut_data()?;
+        self.try_serialize(&mut &mut data[..])?;
+        Ok(())
+    }
 }
@@ -0,0 +1,187 @@
+//! # 商户与发票模块
+//!
+//! 合作商户经系统管理员登记后，可以开具TOT发票（Invoice PDA），
+//! 顾客通过`pay_invoice`按`transfer_with_tax`的规则支付，商户收到发票金额。
+//!
+//! 每个商户可以单独设置税率上限：免税，或按不超过上限的优惠税率收税。
+//!
+//! ============================================
+// 文件: src/state/merchant.rs
+// 商户与发票账户定义
+// ============================================
+
+use anchor_lang::prelude::*;
+use crate::constants::tax;
+use crate::errors::TotError;
+
+/// 商户账户结构体
+///
+/// ## 账户特性
+///
+/// - 使用PDA创建，种子: `["tot_merchant", merchant]`
+/// - 由系统管理员通过`register_merchant`登记，租金由管理员支付
+/// - 停用后不能开具新发票，已开具的发票也不能支付
+#[account]
+pub struct MerchantAccount {
+    /// 商户钱包
+    ///
+    /// 类型: Pubkey (32字节)
+    pub merchant: Pubkey,
+
+    /// 税率上限（basis points）
+    ///
+    /// 类型: Option<u16> (3字节)
+    ///
+    /// 说明:
+    /// - `None`: 按标准动态税率收税
+    /// - `Some(0)`: 免税
+    /// - `Some(n)`: 最终税率不超过n
+    pub tax_cap_bps: Option<u16>,
+
+    /// 是否启用
+    ///
+    /// 类型: bool (1字节)
+    pub is_active: bool,
+
+    /// 累计开具发票数
+    ///
+    /// 类型: u64 (8字节)
+    pub invoice_count: u64,
+
+    /// 累计收款金额（商户实际收到的净额）
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    pub total_received: u64,
+
+    /// 登记时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub registered_at: i64,
+
+    /// PDA Bump种子
+    ///
+    /// 类型: u8 (1字节)
+    pub bump: u8,
+}
+
+impl MerchantAccount {
+    /// 计算账户所需空间
+    ///
+    /// 总大小: 69 字节
+    pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
+        32 + // merchant (Pubkey)
+        3 + // tax_cap_bps (Option<u16>)
+        1 + // is_active (bool)
+        8 + // invoice_count (u64)
+        8 + // total_received (u64)
+        8 + // registered_at (i64)
+        1; // bump (u8)
+
+    /// 校验税率上限
+    ///
+    /// # 错误
+    /// * `TotError::InvalidMerchantTaxRate` - 税率上限超过`MAX_TAX_BPS`
+    pub fn validate_tax_cap(tax_cap_bps: Option<u16>) -> Result<()> {
+        if let Some(cap) = tax_cap_bps {
+            require!(cap <= tax::MAX_TAX_BPS, TotError::InvalidMerchantTaxRate);
+        }
+        Ok(())
+    }
+
+    /// 商户是否免税
+    pub fn is_tax_exempt(&self) -> bool {
+        self.tax_cap_bps == Some(0)
+    }
+}
+
+/// 发票状态
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InvoiceStatus {
+    /// 待支付
+    Open = 0,
+    /// 已支付
+    Paid = 1,
+}
+
+/// 发票账户结构体
+///
+/// ## 账户特性
+///
+/// - 使用PDA创建，种子: `["tot_invoice", merchant, invoice_id.to_le_bytes()]`
+/// - 由商户通过`create_invoice`创建，租金由商户支付
+/// - 商户通过`close_invoice`关闭（已支付的归档，未支付的作废），退还租金
+#[account]
+pub struct Invoice {
+    /// 商户钱包
+    ///
+    /// 类型: Pubkey (32字节)
+    pub merchant: Pubkey,
+
+    /// 发票ID（同一商户下唯一，参与PDA派生）
+    ///
+    /// 类型: u64 (8字节)
+    pub invoice_id: u64,
+
+    /// 发票金额（商户应收到的净额）
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    pub amount: u64,
+
+    /// 到期时间（之后不能支付）
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub due_at: i64,
+
+    /// 商户订单引用（如订单号的哈希）
+    ///
+    /// 类型: [u8; 32] (32字节)
+    pub reference: [u8; 32],
+
+    /// 发票状态
+    ///
+    /// 类型: InvoiceStatus (1字节)
+    pub status: InvoiceStatus,
+
+    /// 付款人（未支付时为默认值）
+    ///
+    /// 类型: Pubkey (32字节)
+    pub payer: Pubkey,
+
+    /// 付款人支付的税额
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    pub tax_amount: u64,
+
+    /// 创建时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub created_at: i64,
+
+    /// 支付时间（未支付时为0）
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub paid_at: i64,
+
+    /// PDA Bump种子
+    ///
+    /// 类型: u8 (1字节)
+    pub bump: u8,
+}
+
+impl Invoice {
+    /// 计算账户所需空间
+    ///
+    /// 总大小: 154 字节
+    pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
+        32 + // merchant (Pubkey)
+        8 + // invoice_id (u64)
+        8 + // amount (u64)
+        8 + // due_at (i64)
+        32 + // reference ([u8; 32])
+        1 + // status (InvoiceStatus)
+        32 + // payer (Pubkey)
+        8 + // tax_amount (u64)
+        8 + // created_at (i64)
+        8 + // paid_at (i64)
+        1; // bump (u8)
+}
@@ -14,7 +14,6 @@
 //! - `pool`: 池子账户（PoolAccount, PoolType）
 //! - `holder`: 持有者账户（HolderAccount）
 //! - `tax`: 税收配置账户（TaxConfig）
-//! - `hook`: Transfer Hook配置账户（TransferHookConfig）
 //! 
 //! ## 依赖关系
 //! 
@@ -52,15 +51,26 @@ pub mod config;
 pub mod pool;
 pub mod holder;
 pub mod tax;
-pub mod hook;
 pub mod asset;
 pub mod auction;
+pub mod clawback;
+pub mod receipt;
+pub mod stream;
+pub mod offer;
+pub mod catalog;
+pub mod voucher;
+pub mod platform_budget;
+pub mod subscription;
+pub mod merchant;
+pub mod partner;
+pub mod points;
+pub mod transfer_guard;
 
 // 精确导出公共API，避免通配符导出导致的模块边界不清晰
 // 只导出外部模块需要使用的类型和常量
 
 // 配置模块公共API
-pub use config::{TotConfig, InitializeParams};
+pub use config::{TotConfig, InitializeParams, PauseFlag};
 
 // 池子模块公共API
 pub use pool::{PoolAccount, PoolType};
@@ -71,11 +81,43 @@ pub use holder::HolderAccount;
 // 税收配置模块公共API
 pub use tax::TaxConfig;
 
-// Transfer Hook配置模块公共API
-pub use hook::TransferHookConfig;
-
 // 资产模块公共API
 pub use asset::{AssetAccount, AssetLocation};
 
 // 拍卖模块公共API
 pub use auction::AuctionAccount;
+
+pub use clawback::{ClawbackRecord, ClawbackAction};
+
+// 支付回执模块公共API
+pub use receipt::{PaymentReceipt, PaymentKind, ConsumeReceipt};
+
+// 代币流模块公共API
+pub use stream::StreamAccount;
+
+// P2P挂单模块公共API
+pub use offer::TradeOffer;
+
+// 价格目录模块公共API
+pub use catalog::{PriceCatalog, PriceEntry, RevenueSplit};
+
+// 奖励兑换券模块公共API
+pub use voucher::{Voucher, RewardsVault, VoucherRedemption};
+
+// 平台预算模块公共API
+pub use platform_budget::{PlatformBudget, PlatformRecipientUsage};
+
+// 订阅模块公共API
+pub use subscription::{Subscription, SubscriptionStatus};
+
+// 商户与发票模块公共API
+pub use merchant::{MerchantAccount, Invoice, InvoiceStatus};
+
+// 合作程序模块公共API
+pub use partner::{PartnerProgram, PartnerPermission};
+
+// 积分模块公共API
+pub use points::{PointsConfig, PointsConfigParams, PointsAccount, PointsSource};
+
+// 转账守卫模块公共API
+pub use transfer_guard::TransferGuard;
@@ -0,0 +1,110 @@
+//! # P2P挂单模块
+//!
+//! 本模块定义了TOT的P2P托管挂单账户。
+//! 挂单方把TOT托管在PDA中并标明想换取的代币和数量，
+//! 吃单方在同一笔交易内支付要价并取得TOT，双方都不需要信任对方。
+//!
+//! ============================================
+// 文件: src/state/offer.rs
+// P2P挂单账户定义
+// ============================================
+
+use anchor_lang::prelude::*;
+use crate::errors::TotError;
+use crate::utils::math::mul_div_ceil;
+
+/// P2P挂单账户结构体
+///
+/// ## 账户特性
+///
+/// - 使用PDA创建，种子: `["tot_offer", maker, offer_id.to_le_bytes()]`
+/// - 托管TOT存放在以本账户为authority的关联代币账户（ATA）中
+/// - 价格固定为 `ask_amount / tot_amount`，部分成交按比例折算
+#[account]
+pub struct TradeOffer {
+    /// 挂单编号
+    ///
+    /// 类型: u64 (8字节)
+    ///
+    /// 说明:
+    /// - 由挂单方选择，同一挂单方下唯一
+    pub offer_id: u64,
+
+    /// 挂单方钱包
+    ///
+    /// 类型: Pubkey (32字节)
+    pub maker: Pubkey,
+
+    /// 托管代币账户
+    ///
+    /// 类型: Pubkey (32字节)
+    pub escrow_token_account: Pubkey,
+
+    /// 要价代币Mint（SOL使用Wrapped SOL）
+    ///
+    /// 类型: Pubkey (32字节)
+    pub ask_mint: Pubkey,
+
+    /// 挂单TOT总量
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    ///
+    /// 说明:
+    /// - 托管账户实际收到的数量（扣除Transfer Fee预扣后）
+    pub tot_amount: u64,
+
+    /// 剩余未成交的TOT数量
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    pub tot_remaining: u64,
+
+    /// 全部TOT对应的要价数量
+    ///
+    /// 类型: u64 (8字节，要价代币基础单位)
+    pub ask_amount: u64,
+
+    /// 是否已取消
+    ///
+    /// 类型: bool (1字节)
+    pub is_cancelled: bool,
+
+    /// 创建时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub created_at: i64,
+
+    /// PDA Bump种子
+    ///
+    /// 类型: u8 (1字节)
+    pub bump: u8,
+}
+
+impl TradeOffer {
+    /// 计算账户所需空间
+    ///
+    /// 总大小: 146 字节
+    pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
+        8 + // offer_id (u64)
+        32 + // maker (Pubkey)
+        32 + // escrow_token_account (Pubkey)
+        32 + // ask_mint (Pubkey)
+        8 + // tot_amount (u64)
+        8 + // tot_remaining (u64)
+        8 + // ask_amount (u64)
+        1 + // is_cancelled (bool)
+        8 + // created_at (i64)
+        1; // bump (u8)
+
+    /// 挂单是否仍可成交
+    pub fn is_open(&self) -> bool {
+        !self.is_cancelled && self.tot_remaining > 0
+    }
+
+    /// 成交`fill_amount`个TOT时吃单方应支付的要价代币数量
+    ///
+    /// 向上取整，保证挂单方收到的不少于挂单价格
+    pub fn ask_for_fill(&self, fill_amount: u64) -> Result<u64> {
+        require!(fill_amount <= self.tot_remaining, TotError::OfferFillExceedsRemaining);
+        mul_div_ceil(fill_amount, self.ask_amount, self.tot_amount)
+    }
+}
@@ -0,0 +1,124 @@
+//! # 合作程序模块
+//!
+//! `tws-asset`、`prediction_market`等合作程序自行转移TOT时，会绕过持有者统计、
+//! 冻结检查和消费统计。本模块定义合作程序白名单，白名单内的程序可以通过CPI调用
+//! TOT的消费、带税转账和持有者统计入口，复用TOT的规则。
+//!
+//! 合作程序用`["tot_partner_authority"]`在自己的程序ID下派生签名PDA，
+//! CPI时以`invoke_signed`签名；只有该程序能为这个PDA签名，TOT据此确认调用方。
+//!
+//! ============================================
+// 文件: src/state/partner.rs
+// 合作程序白名单账户定义
+// ============================================
+
+use anchor_lang::prelude::*;
+use crate::constants::seeds;
+use crate::errors::TotError;
+
+/// 合作程序权限
+///
+/// 每个值对应`PartnerProgram.permissions`中的一位。
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PartnerPermission {
+    /// 消费到财库（partner_consume）
+    Consume = 0,
+    /// 带税转账（partner_transfer_with_tax）
+    TaxedTransfer = 1,
+    /// 更新持有者统计（partner_record_holder_activity）
+    HolderStats = 2,
+}
+
+impl PartnerPermission {
+    /// 所有已定义权限的掩码
+    pub const ALL: u8 = 0b111;
+
+    /// 在位图中的掩码
+    pub fn bit(self) -> u8 {
+        1 << (self as u8)
+    }
+}
+
+/// 合作程序登记账户结构体
+///
+/// ## 账户特性
+///
+/// - 使用PDA创建，种子: `["tot_partner", program_id]`
+/// - 由系统管理员通过`register_partner`登记，租金由管理员支付
+/// - `authority`为合作程序的签名PDA，登记时计算并保存
+#[account]
+pub struct PartnerProgram {
+    /// 合作程序ID
+    ///
+    /// 类型: Pubkey (32字节)
+    pub program_id: Pubkey,
+
+    /// 合作程序签名PDA（`["tot_partner_authority"]`在合作程序ID下派生）
+    ///
+    /// 类型: Pubkey (32字节)
+    pub authority: Pubkey,
+
+    /// 权限位图（见`PartnerPermission`）
+    ///
+    /// 类型: u8 (1字节)
+    pub permissions: u8,
+
+    /// 是否启用
+    ///
+    /// 类型: bool (1字节)
+    pub is_active: bool,
+
+    /// 登记时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub registered_at: i64,
+
+    /// PDA Bump种子
+    ///
+    /// 类型: u8 (1字节)
+    pub bump: u8,
+}
+
+impl PartnerProgram {
+    /// 计算账户所需空间
+    ///
+    /// 总大小: 83 字节
+    pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
+        32 + // program_id (Pubkey)
+        32 + // authority (Pubkey)
+        1 + // permissions (u8)
+        1 + // is_active (bool)
+        8 + // registered_at (i64)
+        1; // bump (u8)
+
+    /// 计算合作程序的签名PDA
+    pub fn derive_authority(program_id: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[seeds::PARTNER_AUTHORITY_SEED], program_id).0
+    }
+
+    /// 校验权限位图
+    ///
+    /// # 错误
+    /// * `TotError::InvalidPartnerProgram` - 包含未定义的权限位
+    pub fn validate_permissions(permissions: u8) -> Result<()> {
+        require!(
+            permissions & !PartnerPermission::ALL == 0,
+            TotError::InvalidPartnerProgram
+        );
+        Ok(())
+    }
+
+    /// 要求合作程序已启用且拥有指定权限，且签名者是其签名PDA
+    ///
+    /// # 错误
+    /// * `TotError::PartnerNotAuthorized` - 已停用、缺少权限或签名者不匹配
+    pub fn require_permission(&self, signer: &Pubkey, permission: PartnerPermission) -> Result<()> {
+        require!(
+            self.is_active
+                && self.permissions & permission.bit() != 0
+                && self.authority == *signer,
+            TotError::PartnerNotAuthorized
+        );
+        Ok(())
+    }
+}
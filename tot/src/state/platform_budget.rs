@@ -0,0 +1,199 @@
+//! # 平台预算模块
+//!
+//! 本模块定义了`platform_transfer`的预算和限额账户。
+//! 平台转账按周期累计额度，每个周期有总预算和单个接收者额度，
+//! 服务器上的热钱包只需登记为平台签名者，不再持有管理员私钥。
+//! 平台资金存放在平台预算PDA持有的金库代币账户中，只能通过`platform_transfer`转出。
+//!
+//! ============================================
+// 文件: src/state/platform_budget.rs
+// 平台预算与接收者额度账户定义
+// ============================================
+
+use anchor_lang::prelude::*;
+use crate::constants::limits;
+use crate::errors::TotError;
+
+/// 平台预算账户结构体
+///
+/// ## 账户特性
+///
+/// - 使用PDA创建，种子: `["tot_platform_budget"]`，全局唯一
+/// - 由系统管理员维护预算参数和热钱包列表
+/// - 周期按`period_seconds`对齐滚动，进入新周期时已用额度清零
+/// - 同时是平台金库代币账户（本PDA的ATA）的所有者，`platform_transfer`以本PDA签名转出
+#[account]
+pub struct PlatformBudget {
+    /// 周期长度（秒，如86400表示按天）
+    ///
+    /// 类型: i64 (8字节)
+    pub period_seconds: i64,
+
+    /// 每个周期的平台转账总预算
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    pub period_cap: u64,
+
+    /// 每个周期向单个接收者转账的额度
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    pub per_recipient_cap: u64,
+
+    /// 当前周期开始时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub period_start: i64,
+
+    /// 当前周期已用额度
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    pub period_spent: u64,
+
+    /// 允许调用`platform_transfer`的热钱包（系统管理员始终允许）
+    ///
+    /// 类型: Vec<Pubkey> (4 + 32 × 10 字节)
+    pub signers: Vec<Pubkey>,
+
+    /// PDA Bump种子
+    ///
+    /// 类型: u8 (1字节)
+    pub bump: u8,
+}
+
+impl PlatformBudget {
+    /// 计算账户所需空间
+    ///
+    /// 总大小: 373 字节
+    pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
+        8 + // period_seconds (i64)
+        8 + // period_cap (u64)
+        8 + // per_recipient_cap (u64)
+        8 + // period_start (i64)
+        8 + // period_spent (u64)
+        4 + (32 * limits::MAX_PLATFORM_SIGNERS) + // signers (max 10)
+        1; // bump (u8)
+
+    /// 校验预算参数
+    ///
+    /// # 错误
+    /// * `TotError::InvalidPlatformBudget` - 任一参数为0，或单个接收者额度大于周期预算
+    pub fn validate_params(period_seconds: i64, period_cap: u64, per_recipient_cap: u64) -> Result<()> {
+        require!(
+            period_seconds > 0
+                && period_cap > 0
+                && per_recipient_cap > 0
+                && per_recipient_cap <= period_cap,
+            TotError::InvalidPlatformBudget
+        );
+        Ok(())
+    }
+
+    /// 计算`now`所在周期的开始时间（从`period_start`起按周期长度对齐）
+    pub fn current_period_start(&self, now: i64) -> i64 {
+        if now < self.period_start.saturating_add(self.period_seconds) {
+            return self.period_start;
+        }
+        let elapsed_periods = (now - self.period_start) / self.period_seconds;
+        self.period_start + elapsed_periods * self.period_seconds
+    }
+
+    /// 进入新周期时清零已用额度
+    pub fn roll(&mut self, now: i64) {
+        let current = self.current_period_start(now);
+        if current != self.period_start {
+            self.period_start = current;
+            self.period_spent = 0;
+        }
+    }
+
+    /// 本周期剩余预算（不修改账户状态）
+    pub fn period_remaining(&self, now: i64) -> u64 {
+        if self.current_period_start(now) != self.period_start {
+            return self.period_cap;
+        }
+        self.period_cap.saturating_sub(self.period_spent)
+    }
+
+    /// 检查地址是否是已登记的平台热钱包
+    pub fn is_signer(&self, key: &Pubkey) -> bool {
+        self.signers.contains(key)
+    }
+
+    /// 登记平台热钱包
+    ///
+    /// # 错误
+    /// * `TotError::PlatformSignerAlreadyRegistered` - 已登记
+    /// * `TotError::TooManyPlatformSigners` - 列表已满
+    pub fn add_signer(&mut self, signer: Pubkey) -> Result<()> {
+        require!(!self.is_signer(&signer), TotError::PlatformSignerAlreadyRegistered);
+        require!(
+            self.signers.len() < limits::MAX_PLATFORM_SIGNERS,
+            TotError::TooManyPlatformSigners
+        );
+        self.signers.push(signer);
+        Ok(())
+    }
+
+    /// 移除平台热钱包
+    ///
+    /// # 错误
+    /// * `TotError::PlatformSignerNotRegistered` - 未登记
+    pub fn remove_signer(&mut self, signer: &Pubkey) -> Result<()> {
+        let index = self.signers
+            .iter()
+            .position(|s| s == signer)
+            .ok_or(TotError::PlatformSignerNotRegistered)?;
+        self.signers.remove(index);
+        Ok(())
+    }
+}
+
+/// 平台转账接收者额度账户结构体
+///
+/// ## 账户特性
+///
+/// - 使用PDA创建，种子: `["tot_platform_recipient", recipient]`
+/// - 首次向该接收者平台转账时创建，租金由平台签名者支付
+/// - `period_start`与平台预算的当前周期不同时，视为本周期尚未使用
+#[account]
+pub struct PlatformRecipientUsage {
+    /// 接收者钱包
+    ///
+    /// 类型: Pubkey (32字节)
+    pub recipient: Pubkey,
+
+    /// 记录所在周期的开始时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub period_start: i64,
+
+    /// 该周期已向接收者转账的数量
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    pub spent: u64,
+
+    /// PDA Bump种子
+    ///
+    /// 类型: u8 (1字节)
+    pub bump: u8,
+}
+
+impl PlatformRecipientUsage {
+    /// 计算账户所需空间
+    ///
+    /// 总大小: 57 字节
+    pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
+        32 + // recipient (Pubkey)
+        8 + // period_start (i64)
+        8 + // spent (u64)
+        1; // bump (u8)
+
+    /// 指定周期内已使用的额度
+    pub fn spent_in(&self, period_start: i64) -> u64 {
+        if self.period_start == period_start {
+            self.spent
+        } else {
+            0
+        }
+    }
+}
@@ -0,0 +1,280 @@
+//! # 积分模块
+//!
+//! 每个持有者一个不可转让的积分账户。积分通过消费（按消费类型配置比例）、
+//! 持有（按天）和拍卖夺取获得，可以兑换价格目录中的商品，或兑换限时税率折扣。
+//!
+//! 积分只记录在`PointsAccount`中，没有转账指令，因此不可转让。
+//!
+//! ============================================
+// 文件: src/state/points.rs
+// 积分配置与积分账户定义
+// ============================================
+
+use anchor_lang::prelude::*;
+use crate::constants::{tax, TOKEN_DECIMALS};
+use crate::errors::TotError;
+
+/// 一天的秒数（持有积分按天计算）
+pub const SECONDS_PER_DAY: i64 = 86400;
+
+/// 积分来源
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PointsSource {
+    /// 消费到财库
+    Consume = 0,
+    /// 持有天数
+    Holding = 1,
+    /// 拍卖夺取
+    Seize = 2,
+}
+
+/// 积分配置参数
+///
+/// `initialize_points_config`和`update_points_config`共用。
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct PointsConfigParams {
+    /// 每消费1 TOT获得的积分（下标为`ConsumeType as u8`）
+    pub consume_rates: [u32; 8],
+    /// 每持有1 TOT一天获得的积分
+    pub holding_points_per_day: u64,
+    /// 每次拍卖夺取获得的积分
+    pub seize_points: u64,
+    /// 兑换目录商品时每1 TOT价格所需的积分（0表示不开放商品兑换）
+    pub redeem_points_per_tot: u64,
+    /// 兑换的税率折扣（basis points，从最终税率中扣除）
+    pub discount_bps: u16,
+    /// 兑换一次税率折扣所需的积分（0表示不开放折扣兑换）
+    pub discount_cost: u64,
+    /// 一次税率折扣的有效时长（秒）
+    pub discount_duration_seconds: i64,
+}
+
+impl PointsConfigParams {
+    /// 校验参数
+    ///
+    /// # 错误
+    /// * `TotError::InvalidPointsConfig` - 折扣超过`MAX_TAX_BPS`，或开放折扣兑换但折扣/时长为0
+    pub fn validate(&self) -> Result<()> {
+        require!(self.discount_bps <= tax::MAX_TAX_BPS, TotError::InvalidPointsConfig);
+        if self.discount_cost > 0 {
+            require!(
+                self.discount_bps > 0 && self.discount_duration_seconds > 0,
+                TotError::InvalidPointsConfig
+            );
+        }
+        Ok(())
+    }
+}
+
+/// 积分配置账户结构体
+///
+/// ## 账户特性
+///
+/// - 使用PDA创建，种子: `["tot_points_config"]`，全局唯一
+/// - 由系统管理员维护积分比例
+#[account]
+pub struct PointsConfig {
+    /// 每消费1 TOT获得的积分
+    ///
+    /// 类型: [u32; 8] (32字节，下标为`ConsumeType as u8`)
+    pub consume_rates: [u32; 8],
+
+    /// 每持有1 TOT一天获得的积分
+    ///
+    /// 类型: u64 (8字节)
+    pub holding_points_per_day: u64,
+
+    /// 每次拍卖夺取获得的积分
+    ///
+    /// 类型: u64 (8字节)
+    pub seize_points: u64,
+
+    /// 兑换目录商品时每1 TOT价格所需的积分
+    ///
+    /// 类型: u64 (8字节，0表示不开放)
+    pub redeem_points_per_tot: u64,
+
+    /// 兑换的税率折扣
+    ///
+    /// 类型: u16 (2字节，basis points)
+    pub discount_bps: u16,
+
+    /// 兑换一次税率折扣所需的积分
+    ///
+    /// 类型: u64 (8字节，0表示不开放)
+    pub discount_cost: u64,
+
+    /// 一次税率折扣的有效时长
+    ///
+    /// 类型: i64 (8字节，秒)
+    pub discount_duration_seconds: i64,
+
+    /// 最后更新时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub last_updated: i64,
+
+    /// PDA Bump种子
+    ///
+    /// 类型: u8 (1字节)
+    pub bump: u8,
+}
+
+impl PointsConfig {
+    /// 计算账户所需空间
+    ///
+    /// 总大小: 91 字节
+    pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
+        32 + // consume_rates ([u32; 8])
+        8 + // holding_points_per_day (u64)
+        8 + // seize_points (u64)
+        8 + // redeem_points_per_tot (u64)
+        2 + // discount_bps (u16)
+        8 + // discount_cost (u64)
+        8 + // discount_duration_seconds (i64)
+        8 + // last_updated (i64)
+        1; // bump (u8)
+
+    /// 写入配置参数
+    pub fn apply(&mut self, params: &PointsConfigParams, timestamp: i64) {
+        self.consume_rates = params.consume_rates;
+        self.holding_points_per_day = params.holding_points_per_day;
+        self.seize_points = params.seize_points;
+        self.redeem_points_per_tot = params.redeem_points_per_tot;
+        self.discount_bps = params.discount_bps;
+        self.discount_cost = params.discount_cost;
+        self.discount_duration_seconds = params.discount_duration_seconds;
+        self.last_updated = timestamp;
+    }
+
+    /// 消费获得的积分（按整TOT计算，向下取整）
+    pub fn consume_points(&self, consume_type: u8, amount: u64) -> Result<u64> {
+        let rate = self.consume_rates
+            .get(consume_type as usize)
+            .copied()
+            .unwrap_or(0);
+        let points = (amount as u128)
+            .checked_mul(rate as u128)
+            .ok_or(error!(TotError::MathOverflow))?
+            / 10u128.pow(TOKEN_DECIMALS as u32);
+        u64::try_from(points).map_err(|_| error!(TotError::MathOverflow))
+    }
+
+    /// 兑换目录商品所需的积分（按价格折算，向上取整）
+    ///
+    /// # 错误
+    /// * `TotError::PointsRedemptionUnavailable` - 未开放商品兑换
+    pub fn redeem_cost(&self, price: u64) -> Result<u64> {
+        require!(self.redeem_points_per_tot > 0, TotError::PointsRedemptionUnavailable);
+        let unit = 10u128.pow(TOKEN_DECIMALS as u32);
+        let cost = (price as u128)
+            .checked_mul(self.redeem_points_per_tot as u128)
+            .ok_or(error!(TotError::MathOverflow))?
+            .div_ceil(unit);
+        u64::try_from(cost).map_err(|_| error!(TotError::MathOverflow))
+    }
+}
+
+/// 积分账户结构体
+///
+/// ## 账户特性
+///
+/// - 使用PDA创建，种子: `["tot_points", owner]`
+/// - 由持有者通过`initialize_points_account`创建，租金由付款人支付
+/// - 不可转让：只能通过赚取和兑换改变余额
+#[account]
+pub struct PointsAccount {
+    /// 持有者钱包
+    ///
+    /// 类型: Pubkey (32字节)
+    pub owner: Pubkey,
+
+    /// 积分余额
+    ///
+    /// 类型: u64 (8字节)
+    pub balance: u64,
+
+    /// 累计获得的积分（排行榜按此排序）
+    ///
+    /// 类型: u64 (8字节)
+    pub total_earned: u64,
+
+    /// 累计兑换的积分
+    ///
+    /// 类型: u64 (8字节)
+    pub total_redeemed: u64,
+
+    /// 持有积分已领取到的时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub last_holding_claim_at: i64,
+
+    /// 当前生效的税率折扣
+    ///
+    /// 类型: u16 (2字节，basis points)
+    pub tax_discount_bps: u16,
+
+    /// 税率折扣到期时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub tax_discount_until: i64,
+
+    /// 创建时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub created_at: i64,
+
+    /// PDA Bump种子
+    ///
+    /// 类型: u8 (1字节)
+    pub bump: u8,
+}
+
+impl PointsAccount {
+    /// 计算账户所需空间
+    ///
+    /// 总大小: 91 字节
+    pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
+        32 + // owner (Pubkey)
+        8 + // balance (u64)
+        8 + // total_earned (u64)
+        8 + // total_redeemed (u64)
+        8 + // last_holding_claim_at (i64)
+        2 + // tax_discount_bps (u16)
+        8 + // tax_discount_until (i64)
+        8 + // created_at (i64)
+        1; // bump (u8)
+
+    /// 增加积分
+    pub fn earn(&mut self, points: u64) -> Result<()> {
+        self.balance = self.balance
+            .checked_add(points)
+            .ok_or(TotError::MathOverflow)?;
+        self.total_earned = self.total_earned
+            .checked_add(points)
+            .ok_or(TotError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// 扣除积分
+    ///
+    /// # 错误
+    /// * `TotError::InsufficientPoints` - 余额不足
+    pub fn spend(&mut self, points: u64) -> Result<()> {
+        require!(self.balance >= points, TotError::InsufficientPoints);
+        self.balance -= points;
+        self.total_redeemed = self.total_redeemed
+            .checked_add(points)
+            .ok_or(TotError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// 当前生效的税率折扣（已过期返回0）
+    pub fn active_tax_discount_bps(&self, now: i64) -> u16 {
+        if now < self.tax_discount_until {
+            self.tax_discount_bps
+        } else {
+            0
+        }
+    }
+}
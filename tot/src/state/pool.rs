@@ -212,7 +212,7 @@ pub struct PoolAccount {
     /// 类型: u64 (8字节)
     /// 
     /// 说明:
-    /// - 该池子初始分配的代币数量（基础单位，已乘以10^9）
+    /// - 该池子初始分配的代币数量（基础单位，已乘以10^6）
     /// - 在`mint_to_pools`时设置
     /// - 用于计算可释放量
     /// 
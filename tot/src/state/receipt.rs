@@ -0,0 +1,175 @@
+//! # 支付回执模块
+//!
+//! 本模块定义了带支付引用（reference）的转账在链上留下的回执。
+//! 后端用32字节引用（如订单号哈希）匹配链上付款，
+//! 同一付款方的每个引用只能创建一次回执，重放的请求会失败而不会重复付款。
+//!
+//! 消费回执（ConsumeReceipt）由`consume_to_treasury`按（用户, 客户端随机数）创建，
+//! 后端确认消费后核销，核销后可以关闭并把租金退还给用户。
+//!
+//! ============================================
+// 文件: src/state/receipt.rs
+// 支付回执定义
+// ============================================
+
+use anchor_lang::prelude::*;
+
+/// 付款类型
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PaymentKind {
+    /// 带税转账（transfer_with_tax / transfer_exact_with_tax）
+    TransferWithTax,
+    /// 向TWS财库消费（consume_to_treasury）
+    Consume,
+    /// 平台向用户转账（platform_transfer）
+    PlatformTransfer,
+}
+
+/// 支付回执账户结构体
+///
+/// ## 账户特性
+///
+/// - 使用PDA创建，种子: `["tot_payment_receipt", payer, reference]`
+/// - 同一付款方的每个引用只能创建一次，重复使用同一引用的转账会失败
+/// - 种子包含付款方，他人无法抢先使用同一引用阻止付款
+/// - 创建后不再修改，租金由付款方支付
+#[account]
+pub struct PaymentReceipt {
+    /// 支付引用
+    ///
+    /// 类型: [u8; 32] (32字节)
+    ///
+    /// 说明:
+    /// - 由调用方生成（如订单号的SHA-256），链上不解释其内容
+    pub reference: [u8; 32],
+
+    /// 付款方钱包
+    ///
+    /// 类型: Pubkey (32字节)
+    pub payer: Pubkey,
+
+    /// 收款方钱包（代币账户的所有者）
+    ///
+    /// 类型: Pubkey (32字节)
+    pub recipient: Pubkey,
+
+    /// 付款类型
+    ///
+    /// 类型: PaymentKind (1字节)
+    pub kind: PaymentKind,
+
+    /// 收款方实际收到的数量
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    pub amount: u64,
+
+    /// 创建时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub created_at: i64,
+
+    /// PDA Bump种子
+    ///
+    /// 类型: u8 (1字节)
+    pub bump: u8,
+}
+
+impl PaymentReceipt {
+    /// 计算账户所需空间
+    ///
+    /// 总大小: 122 字节
+    pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
+        32 + // reference ([u8; 32])
+        32 + // payer (Pubkey)
+        32 + // recipient (Pubkey)
+        1 + // kind (PaymentKind)
+        8 + // amount (u64)
+        8 + // created_at (i64)
+        1; // bump (u8)
+}
+
+/// 消费回执账户结构体
+///
+/// ## 账户特性
+///
+/// - 使用PDA创建，种子: `["tot_consume_receipt", user, nonce.to_le_bytes()]`
+/// - 由`consume_to_treasury`在调用方提供回执参数时创建，租金由用户支付
+/// - 由回执核销权限核销（`redeem_consume_receipt`），核销后才能关闭
+#[account]
+pub struct ConsumeReceipt {
+    /// 消费用户钱包
+    ///
+    /// 类型: Pubkey (32字节)
+    pub user: Pubkey,
+
+    /// 客户端随机数
+    ///
+    /// 类型: u64 (8字节)
+    ///
+    /// 说明:
+    /// - 由客户端生成，同一用户下唯一，参与PDA派生
+    pub nonce: u64,
+
+    /// 消费数量
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    pub amount: u64,
+
+    /// 消费类型
+    ///
+    /// 类型: u8 (1字节)
+    ///
+    /// 说明:
+    /// - `ConsumeType as u8`
+    pub consume_type: u8,
+
+    /// 商品引用
+    ///
+    /// 类型: [u8; 32] (32字节)
+    ///
+    /// 说明:
+    /// - 由调用方生成（如地图操作或拍卖资产ID的哈希），链上不解释其内容
+    pub item_ref: [u8; 32],
+
+    /// 创建时的Slot
+    ///
+    /// 类型: u64 (8字节)
+    pub slot: u64,
+
+    /// 创建时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub created_at: i64,
+
+    /// 是否已核销
+    ///
+    /// 类型: bool (1字节)
+    pub is_redeemed: bool,
+
+    /// 核销时间（未核销时为0）
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub redeemed_at: i64,
+
+    /// PDA Bump种子
+    ///
+    /// 类型: u8 (1字节)
+    pub bump: u8,
+}
+
+impl ConsumeReceipt {
+    /// 计算账户所需空间
+    ///
+    /// 总大小: 115 字节
+    pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
+        32 + // user (Pubkey)
+        8 + // nonce (u64)
+        8 + // amount (u64)
+        1 + // consume_type (u8)
+        32 + // item_ref ([u8; 32])
+        8 + // slot (u64)
+        8 + // created_at (i64)
+        1 + // is_redeemed (bool)
+        8 + // redeemed_at (i64)
+        1; // bump (u8)
+}
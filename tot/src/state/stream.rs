@@ -0,0 +1,116 @@
+//! # 代币流模块
+//!
+//! 本模块定义了代币流（stream）账户，用于工资、订阅等持续付款场景。
+//! 发送者把TOT托管在PDA中，代币在开始和结束时间之间线性释放，
+//! 接收者可以随时提取已释放的部分，发送者可以取消并取回尚未释放的部分。
+//!
+//! ============================================
+// 文件: src/state/stream.rs
+// 代币流账户定义
+// ============================================
+
+use anchor_lang::prelude::*;
+use crate::utils::math::calculate_linear_vested;
+
+/// 代币流账户结构体
+///
+/// ## 账户特性
+///
+/// - 使用PDA创建，种子: `["tot_stream", sender, stream_id.to_le_bytes()]`
+/// - 托管代币存放在以本账户为authority的关联代币账户（ATA）中
+/// - 取消后`total_amount`和`end_time`截断到取消时刻，接收者仍可提取已释放部分
+#[account]
+pub struct StreamAccount {
+    /// 流编号
+    ///
+    /// 类型: u64 (8字节)
+    ///
+    /// 说明:
+    /// - 由发送者选择，同一发送者下唯一
+    pub stream_id: u64,
+
+    /// 发送者钱包
+    ///
+    /// 类型: Pubkey (32字节)
+    pub sender: Pubkey,
+
+    /// 接收者钱包
+    ///
+    /// 类型: Pubkey (32字节)
+    pub recipient: Pubkey,
+
+    /// 托管代币账户
+    ///
+    /// 类型: Pubkey (32字节)
+    pub escrow_token_account: Pubkey,
+
+    /// 释放总量
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    ///
+    /// 说明:
+    /// - 创建时为托管账户实际收到的数量（扣除Transfer Fee预扣后）
+    /// - 取消时截断为取消时刻已释放的数量
+    pub total_amount: u64,
+
+    /// 已提取数量
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    ///
+    /// 说明:
+    /// - 包含提取时收取的税额
+    pub withdrawn_amount: u64,
+
+    /// 开始时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub start_time: i64,
+
+    /// 结束时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub end_time: i64,
+
+    /// 是否已取消
+    ///
+    /// 类型: bool (1字节)
+    pub is_cancelled: bool,
+
+    /// 创建时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub created_at: i64,
+
+    /// PDA Bump种子
+    ///
+    /// 类型: u8 (1字节)
+    pub bump: u8,
+}
+
+impl StreamAccount {
+    /// 计算账户所需空间
+    ///
+    /// 总大小: 155 字节
+    pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
+        8 + // stream_id (u64)
+        32 + // sender (Pubkey)
+        32 + // recipient (Pubkey)
+        32 + // escrow_token_account (Pubkey)
+        8 + // total_amount (u64)
+        8 + // withdrawn_amount (u64)
+        8 + // start_time (i64)
+        8 + // end_time (i64)
+        1 + // is_cancelled (bool)
+        8 + // created_at (i64)
+        1; // bump (u8)
+
+    /// 截至指定时间已释放的数量
+    pub fn vested_amount(&self, current_time: i64) -> Result<u64> {
+        calculate_linear_vested(self.total_amount, self.start_time, self.end_time, current_time)
+    }
+
+    /// 截至指定时间可提取的数量（已释放 - 已提取）
+    pub fn withdrawable_amount(&self, current_time: i64) -> Result<u64> {
+        Ok(self.vested_amount(current_time)?.saturating_sub(self.withdrawn_amount))
+    }
+}
@@ -0,0 +1,145 @@
+//! # 订阅模块
+//!
+//! 用户批准一个订阅计划（每期金额、周期、最大期数），并把代币账户的扣款额度
+//! 授权（Approve）给程序的订阅代理PDA。之后任何人都可以调用`charge_subscription`
+//! 在到期时扣取一期费用，用户无需每期签名。
+//!
+//! ============================================
+// 文件: src/state/subscription.rs
+// 订阅账户定义
+// ============================================
+
+use anchor_lang::prelude::*;
+use crate::errors::TotError;
+
+/// 订阅状态
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SubscriptionStatus {
+    /// 生效中
+    Active = 0,
+    /// 已扣满最大期数
+    Completed = 1,
+    /// 余额或授权额度不足，已停止
+    InsufficientFunds = 2,
+    /// 用户被冻结，已停止
+    Frozen = 3,
+}
+
+/// 订阅账户结构体
+///
+/// ## 账户特性
+///
+/// - 使用PDA创建，种子: `["tot_subscription", user, subscription_id.to_le_bytes()]`
+/// - 由用户通过`create_subscription`创建，租金由用户支付
+/// - 用户通过`cancel_subscription`取消，账户关闭并退还租金
+#[account]
+pub struct Subscription {
+    /// 订阅用户钱包
+    ///
+    /// 类型: Pubkey (32字节)
+    pub user: Pubkey,
+
+    /// 订阅ID（同一用户下唯一，参与PDA派生）
+    ///
+    /// 类型: u64 (8字节)
+    pub subscription_id: u64,
+
+    /// 扣款的用户代币账户
+    ///
+    /// 类型: Pubkey (32字节)
+    pub token_account: Pubkey,
+
+    /// 消费类型（`ConsumeType as u8`）
+    ///
+    /// 类型: u8 (1字节)
+    pub consume_type: u8,
+
+    /// 商品SKU
+    ///
+    /// 类型: u32 (4字节)
+    pub sku: u32,
+
+    /// 每期金额
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    pub amount: u64,
+
+    /// 周期长度（秒）
+    ///
+    /// 类型: i64 (8字节)
+    pub period_seconds: i64,
+
+    /// 最大期数
+    ///
+    /// 类型: u32 (4字节)
+    pub max_periods: u32,
+
+    /// 已扣款期数
+    ///
+    /// 类型: u32 (4字节)
+    pub periods_charged: u32,
+
+    /// 下一期扣款时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub next_charge_at: i64,
+
+    /// 订阅状态
+    ///
+    /// 类型: SubscriptionStatus (1字节)
+    pub status: SubscriptionStatus,
+
+    /// 创建时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub created_at: i64,
+
+    /// PDA Bump种子
+    ///
+    /// 类型: u8 (1字节)
+    pub bump: u8,
+}
+
+impl Subscription {
+    /// 计算账户所需空间
+    ///
+    /// 总大小: 131 字节
+    pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
+        32 + // user (Pubkey)
+        8 + // subscription_id (u64)
+        32 + // token_account (Pubkey)
+        1 + // consume_type (u8)
+        4 + // sku (u32)
+        8 + // amount (u64)
+        8 + // period_seconds (i64)
+        4 + // max_periods (u32)
+        4 + // periods_charged (u32)
+        8 + // next_charge_at (i64)
+        1 + // status (SubscriptionStatus)
+        8 + // created_at (i64)
+        1; // bump (u8)
+
+    /// 尚未扣款的剩余金额（用于调整授权额度）
+    pub fn remaining_amount(&self) -> Result<u64> {
+        let remaining_periods = self.max_periods.saturating_sub(self.periods_charged) as u64;
+        self.amount
+            .checked_mul(remaining_periods)
+            .ok_or(error!(TotError::MathOverflow))
+    }
+
+    /// 记录一期扣款，推进下一期扣款时间
+    ///
+    /// 扣满最大期数时状态变为`Completed`。
+    pub fn record_charge(&mut self) -> Result<()> {
+        self.periods_charged = self.periods_charged
+            .checked_add(1)
+            .ok_or(TotError::MathOverflow)?;
+        self.next_charge_at = self.next_charge_at
+            .checked_add(self.period_seconds)
+            .ok_or(TotError::MathOverflow)?;
+        if self.periods_charged >= self.max_periods {
+            self.status = SubscriptionStatus::Completed;
+        }
+        Ok(())
+    }
+}
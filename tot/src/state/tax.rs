@@ -171,9 +171,29 @@ pub struct TaxConfig {
     /// - 系统地址免税（避免税收循环）
     /// - 特殊合约地址免税
     /// - 管理员可以动态添加/移除
-    #[max_len(50)]
     pub exempt_addresses: Vec<Pubkey>,
     
+    /// 买入税率
+    /// 
+    /// 类型: u16 (2字节，basis points)
+    /// 
+    /// 默认值: 500 (5%)
+    /// 
+    /// 说明:
+    /// - 从`venue_addresses`中的地址转入代币时，作为买入的基础税率（代替base_tax_bps）
+    /// - 持有时间折扣按买入方的持有时间计算，大额交易附加税同样适用
+    pub buy_tax_bps: u16,
+    
+    /// 交易场所地址列表
+    /// 
+    /// 类型: Vec<Pubkey> (动态数组，最多20个地址)
+    /// 
+    /// 说明:
+    /// - 已登记的DEX池、销售合约等交易场所（代币账户的所有者）
+    /// - 从这些地址转出的代币视为接收者买入，按buy_tax_bps计税并记入买入方的已缴税额
+    /// - 同时在免税列表中的地址仍然免税
+    pub venue_addresses: Vec<Pubkey>,
+    
     /// 最后更新时间戳
     /// 
     /// 类型: i64 (8字节，Unix时间戳)
@@ -203,9 +223,9 @@ impl TaxConfig {
     /// 
     /// 返回税率配置账户所需的总字节数，用于账户初始化时的空间分配。
     /// 
-    /// 总大小: 1655 字节
+    /// 总大小: 2301 字节
     /// 
-    /// 注意: 由于Vec<Pubkey>需要预留最大空间（50个地址），账户大小较大。
+    /// 注意: 由于Vec<Pubkey>需要预留最大空间（50个免税地址、20个交易场所），账户大小较大。
     pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
         2 + // base_tax_bps (u16)
         8 + // alpha (u64)
@@ -216,6 +236,9 @@ impl TaxConfig {
         1 + // enabled (bool)
         4 + // Vec length (u32)
         (32 * 50) + // exempt_addresses (Vec<Pubkey>, max 50 addresses)
+        2 + // buy_tax_bps (u16)
+        4 + // Vec length (u32)
+        (32 * 20) + // venue_addresses (Vec<Pubkey>, max 20 addresses)
         8 + // last_updated (i64)
         1; // bump (u8)
     
@@ -321,4 +344,44 @@ impl TaxConfig {
         self.exempt_addresses.remove(index);
         Ok(())
     }
+    
+    /// 检查地址是否为已登记的交易场所
+    /// 
+    /// 从交易场所转出代币视为接收者买入，按`buy_tax_bps`计税。
+    pub fn is_venue(&self, address: &Pubkey) -> bool {
+        self.venue_addresses.contains(address)
+    }
+    
+    /// 登记交易场所
+    /// 
+    /// # 错误
+    /// * 如果地址已登记，返回`TotError::VenueAlreadyRegistered`
+    /// * 如果列表已满，返回`TotError::TooManyVenues`
+    pub fn add_venue(&mut self, address: Pubkey) -> Result<()> {
+        require!(
+            !self.venue_addresses.contains(&address),
+            crate::errors::TotError::VenueAlreadyRegistered
+        );
+        require!(
+            self.venue_addresses.len() < crate::constants::limits::MAX_TRADING_VENUES,
+            crate::errors::TotError::TooManyVenues
+        );
+
+        self.venue_addresses.push(address);
+        Ok(())
+    }
+    
+    /// 移除交易场所
+    /// 
+    /// # 错误
+    /// * 如果地址未登记，返回`TotError::VenueNotRegistered`
+    pub fn remove_venue(&mut self, address: &Pubkey) -> Result<()> {
+        let index = self.venue_addresses
+            .iter()
+            .position(|&x| x == *address)
+            .ok_or(anchor_lang::error!(crate::errors::TotError::VenueNotRegistered))?;
+
+        self.venue_addresses.remove(index);
+        Ok(())
+    }
 }
@@ -0,0 +1,72 @@
+//! # 转账守卫模块
+//!
+//! 本模块定义了主程序发起转账时设置的守卫标记。
+//! Transfer Hook读取标记区分转账来源：主程序指令自己维护持有者统计，
+//! 钱包和DEX发起的转账由Hook回调主程序`record_hook_transfer`更新统计。
+//!
+//! ============================================
+// 文件: src/state/transfer_guard.rs
+// 转账守卫账户定义
+// ============================================
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+/// 转账守卫账户结构体
+///
+/// ## 账户特性
+///
+/// - 使用PDA创建，种子: `["tot_transfer_guard"]`，全局唯一
+/// - 登记在Transfer Hook的额外账户列表中（可写），每次TOT转账都会传入
+/// - `utils::token_transfer::transfer_checked_with_hook`在转账CPI前置位、返回后清除，
+///   交易原子执行，标记不会残留
+/// - 账户未初始化时Hook不回调主程序，钱包转账不更新持有者统计
+#[account]
+pub struct TransferGuard {
+    /// 是否有主程序发起的转账正在执行
+    ///
+    /// 类型: bool (1字节)
+    pub in_progress: bool,
+
+    /// PDA Bump种子
+    ///
+    /// 类型: u8 (1字节)
+    pub bump: u8,
+}
+
+impl TransferGuard {
+    /// 计算账户所需空间
+    ///
+    /// 总大小: 10 字节
+    pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
+        1 + // in_progress (bool)
+        1; // bump (u8)
+
+    /// 读取守卫账户
+    ///
+    /// 账户不属于`program_id`或尚未初始化时返回`None`。
+    pub fn load_optional(info: &AccountInfo, program_id: &Pubkey) -> Result<Option<Self>> {
+        if info.owner != program_id || !Self::is_initialized(info) {
+            return Ok(None);
+        }
+
+        let data = info.try_borrow_data()?;
+        Ok(Some(Self::try_deserialize(&mut &data[..])?))
+    }
+
+    /// 设置转账进行中标记
+    ///
+    /// 账户必须由主程序持有且可写，只改写`in_progress`一个字节。
+    pub fn set_in_progress(info: &AccountInfo, in_progress: bool) -> Result<()> {
+        let mut data = info.try_borrow_mut_data()?;
+        data[Self::DISCRIMINATOR.len()] = in_progress as u8;
+        Ok(())
+    }
+
+    /// 账户数据是否以TransferGuard的discriminator开头
+    fn is_initialized(info: &AccountInfo) -> bool {
+        info.try_borrow_data()
+            .map(|data| data.starts_with(&Self::DISCRIMINATOR))
+            .unwrap_or(false)
+    }
+}
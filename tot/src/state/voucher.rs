@@ -0,0 +1,142 @@
+//! # 奖励兑换券模块
+//!
+//! 平台用独立的Ed25519签名公钥离线签发奖励兑换券（Voucher），
+//! 用户自行提交`redeem_voucher`，从程序控制的奖励金库领取代币。
+//!
+//! 兑换券随机数在兑换时创建`VoucherRedemption` PDA，同一随机数只能兑换一次。
+//!
+//! ============================================
+// 文件: src/state/voucher.rs
+// 奖励兑换券与奖励金库账户定义
+// ============================================
+
+use anchor_lang::prelude::*;
+
+/// 奖励兑换券
+///
+/// 由平台离线签名，作为`redeem_voucher`的参数提交。
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Voucher {
+    /// 接收者钱包
+    pub recipient: Pubkey,
+    /// 奖励数量（基础单位）
+    pub amount: u64,
+    /// 过期时间（Unix时间戳）
+    pub expires_at: i64,
+    /// 随机数（全局唯一，参与核销记录PDA派生）
+    pub nonce: u64,
+}
+
+impl Voucher {
+    /// 签名消息的域分隔前缀
+    pub const DOMAIN: &'static [u8] = b"TOT_VOUCHER_V1";
+
+    /// 构造平台需要签名的消息
+    ///
+    /// 消息 = 域分隔前缀 || 程序ID || recipient || amount(LE) || expires_at(LE) || nonce(LE)，
+    /// 包含程序ID，避免兑换券在其他部署上重放。
+    pub fn message(&self, program_id: &Pubkey) -> Vec<u8> {
+        let mut message = Vec::with_capacity(Self::DOMAIN.len() + 32 + 32 + 8 + 8 + 8);
+        message.extend_from_slice(Self::DOMAIN);
+        message.extend_from_slice(program_id.as_ref());
+        message.extend_from_slice(self.recipient.as_ref());
+        message.extend_from_slice(&self.amount.to_le_bytes());
+        message.extend_from_slice(&self.expires_at.to_le_bytes());
+        message.extend_from_slice(&self.nonce.to_le_bytes());
+        message
+    }
+}
+
+/// 奖励金库账户结构体
+///
+/// ## 账户特性
+///
+/// - 使用PDA创建，种子: `["tot_rewards_vault"]`，全局唯一
+/// - 是金库代币账户（ATA）的所有者，兑换时由程序签名转出
+/// - 任何人都可以向金库代币账户转入代币补充奖励
+#[account]
+pub struct RewardsVault {
+    /// 金库代币账户
+    ///
+    /// 类型: Pubkey (32字节)
+    pub token_account: Pubkey,
+
+    /// 累计兑换数量
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    pub total_redeemed: u64,
+
+    /// 累计兑换次数
+    ///
+    /// 类型: u64 (8字节)
+    pub redemption_count: u64,
+
+    /// PDA Bump种子
+    ///
+    /// 类型: u8 (1字节)
+    pub bump: u8,
+}
+
+impl RewardsVault {
+    /// 计算账户所需空间
+    ///
+    /// 总大小: 57 字节
+    pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
+        32 + // token_account (Pubkey)
+        8 + // total_redeemed (u64)
+        8 + // redemption_count (u64)
+        1; // bump (u8)
+}
+
+/// 兑换券核销记录账户结构体
+///
+/// ## 账户特性
+///
+/// - 使用PDA创建，种子: `["tot_voucher", nonce.to_le_bytes()]`
+/// - 由`redeem_voucher`创建，租金由接收者支付
+/// - 账户存在即表示该随机数已兑换，防止重放
+#[account]
+pub struct VoucherRedemption {
+    /// 兑换券随机数
+    ///
+    /// 类型: u64 (8字节)
+    pub nonce: u64,
+
+    /// 接收者钱包
+    ///
+    /// 类型: Pubkey (32字节)
+    pub recipient: Pubkey,
+
+    /// 兑换数量
+    ///
+    /// 类型: u64 (8字节，基础单位)
+    pub amount: u64,
+
+    /// 签发兑换券的签名公钥
+    ///
+    /// 类型: Pubkey (32字节)
+    pub signer: Pubkey,
+
+    /// 兑换时间
+    ///
+    /// 类型: i64 (8字节，Unix时间戳)
+    pub redeemed_at: i64,
+
+    /// PDA Bump种子
+    ///
+    /// 类型: u8 (1字节)
+    pub bump: u8,
+}
+
+impl VoucherRedemption {
+    /// 计算账户所需空间
+    ///
+    /// 总大小: 97 字节
+    pub const LEN: usize = 8 + // discriminator (Anchor自动添加)
+        8 + // nonce (u64)
+        32 + // recipient (Pubkey)
+        8 + // amount (u64)
+        32 + // signer (Pubkey)
+        8 + // redeemed_at (i64)
+        1; // bump (u8)
+}
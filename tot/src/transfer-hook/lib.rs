@@ -0,0 +1,1085 @@
+//! # TOT Transfer Hook 程序
+//!
+//! Token-2022在每次TOT转账时通过CPI调用本程序的`Execute`指令，
+//! 对钱包和DEX发起的普通转账执行TOT规则（暂停、冻结、恐慌卖出限制）。
+//!
+//! ## 为什么独立部署
+//!
+//! 主程序的`transfer_with_tax`、`consume`等指令本身通过CPI调用Token-2022转账。
+//! 如果Hook就是主程序，调用链为 主程序 → Token-2022 → 主程序，
+//! Solana运行时禁止这种间接重入，所有由主程序发起的转账都会失败。
+//!
+//! ## 与主程序的关系
+//!
+//! - 读取主程序的`TotConfig`、`TaxConfig`和`HolderAccount`执行转账规则
+//! - 主程序发起转账时把Hook需要的账户放在`remaining_accounts`中，
+//!   由`utils::token_transfer::transfer_checked_with_hook`转交给Token-2022，
+//!   并在转账期间置位主程序的`TransferGuard`
+//! - 持有者统计：主程序发起的转账由其指令自行维护；其余转账（钱包、DEX）
+//!   由Hook以Hook配置PDA签名回调主程序`record_hook_transfer`更新。
+//!   主程序发起的转账中主程序已在调用栈上，回调会被运行时作为重入拒绝，
+//!   因此必须由`TransferGuard`区分
+//!
+//! ============================================
+// 文件: src/transfer-hook/lib.rs
+// TOT Transfer Hook 程序入口
+// ============================================
+
+use std::collections::BTreeSet;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+use spl_token_2022::extension::{
+    transfer_fee::TransferFeeConfig, transfer_hook::TransferHookAccount,
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use tot_token::cpi::accounts::RecordHookTransfer;
+use tot_token::instructions::HookTransferRecord;
+use tot_token::state::config::{TotConfig, PauseFlag};
+use tot_token::state::tax::TaxConfig;
+use tot_token::state::holder::HolderAccount;
+use tot_token::state::transfer_guard::TransferGuard;
+use tot_token::utils::tax_calculator::TaxCalculator;
+use tot_token::utils::hook_interface::parse_execute_amount;
+use tot_token::constants::seeds;
+use tot_token::errors::TotError;
+
+// 程序ID声明
+// 必须与主程序`constants::TRANSFER_HOOK_PROGRAM_ID`一致，
+// 主程序`initialize`把该地址写入Mint的Transfer Hook扩展
+declare_id!("ToTHook111111111111111111111111111111111111");
+
+/// 额外账户中主程序ID的位置
+///
+/// Execute指令的账户依次为source(0)、mint(1)、destination(2)、authority(3)、
+/// extra_account_meta_list(4)，额外账户从5开始，第一个额外账户是主程序ID，
+/// 之后的主程序PDA都以它作为派生程序。
+const TOT_PROGRAM_INDEX: u8 = 5;
+
+#[program]
+pub mod tot_transfer_hook {
+    use super::*;
+
+    /// 初始化Transfer Hook配置
+    ///
+    /// 创建Transfer Hook配置账户，设置TOT Mint地址和配置地址。
+    /// 这是部署Transfer Hook程序后的第一步。
+    ///
+    /// # 参数
+    /// * `ctx` - 初始化上下文，签名者必须是TOT系统管理员
+    /// * `tot_mint` - TOT代币的Mint地址
+    /// * `tot_config` - TOT全局配置账户地址
+    ///
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())
+    pub fn initialize_transfer_hook(
+        ctx: Context<InitializeTransferHook>,
+        tot_mint: Pubkey,
+        tot_config: Pubkey,
+    ) -> Result<()> {
+        let hook_config = &mut ctx.accounts.hook_config;
+
+        // 设置管理员
+        hook_config.authority = ctx.accounts.authority.key();
+
+        // 关联TOT Mint和配置
+        hook_config.tot_mint = tot_mint;
+        hook_config.tot_config = tot_config;
+
+        // 初始化统计信息
+        hook_config.total_transfers = 0;
+        hook_config.total_tax_collected = 0;
+        hook_config.total_burned = 0;
+        hook_config.is_paused = false;
+        hook_config.pending_authority = Pubkey::default();
+        hook_config.authority_transfer_eta = 0;
+        hook_config.bump = ctx.bumps.hook_config;
+
+        msg!("Transfer Hook initialized");
+        msg!("TOT Mint: {}", tot_mint);
+        msg!("TOT Config: {}", tot_config);
+        Ok(())
+    }
+
+    /// Transfer Hook执行入口
+    ///
+    /// 此入口使用Anchor discriminator，供客户端直接调用；
+    /// Token-2022的CPI使用接口discriminator，由`fallback`路由到相同逻辑。
+    ///
+    /// # 参数
+    /// * `ctx` - Transfer Hook执行上下文
+    /// * `amount` - 转账金额
+    ///
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败会导致转账回滚
+    pub fn execute(ctx: Context<ExecuteTransferHook>, amount: u64) -> Result<()> {
+        execute_internal(ctx, amount)
+    }
+
+    /// 初始化Transfer Hook的Extra Account Meta List
+    ///
+    /// 创建`["extra-account-metas", mint]` PDA，登记Hook需要的额外账户：
+    /// 主程序ID、Hook配置，以及主程序的全局配置、税率配置、
+    /// 由代币账户所有者派生的发送者、接收者持有者PDA和转账守卫。
+    /// 钱包、DEX和主程序据此解析转账所需账户。
+    ///
+    /// # 参数
+    /// * `ctx` - 初始化上下文，签名者必须是Hook管理员
+    ///
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+    ) -> Result<()> {
+        let metas = extra_account_metas()?;
+        let mut data = ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?;
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &metas)?;
+
+        msg!("Extra account meta list initialized with {} accounts", metas.len());
+        Ok(())
+    }
+
+    /// 更新Transfer Hook的Extra Account Meta List
+    ///
+    /// 按当前程序版本的`extra_account_metas()`重写额外账户列表。
+    /// 升级程序改变Hook所需账户后调用，账户大小不足时由管理员补足租金并扩容。
+    ///
+    /// # 参数
+    /// * `ctx` - 更新上下文，签名者必须是Hook管理员
+    ///
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())，失败返回相应错误
+    pub fn update_extra_account_meta_list(
+        ctx: Context<UpdateExtraAccountMetaList>,
+    ) -> Result<()> {
+        let metas = extra_account_metas()?;
+        let meta_list = &ctx.accounts.extra_account_meta_list;
+        let new_len = ExtraAccountMetaList::size_of(metas.len())?;
+
+        // 补足租金
+        let required = Rent::get()?.minimum_balance(new_len);
+        let current = meta_list.lamports();
+        if required > current {
+            system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: meta_list.to_account_info(),
+                    },
+                ),
+                required - current,
+            )?;
+        }
+
+        // 调整大小并清空旧列表后重新写入
+        meta_list.realloc(new_len, false)?;
+        let mut data = meta_list.try_borrow_mut_data()?;
+        data.fill(0);
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &metas)?;
+
+        msg!("Extra account meta list updated with {} accounts", metas.len());
+        Ok(())
+    }
+
+    /// 暂停/恢复Transfer Hook
+    ///
+    /// 暂停后，所有转账都会失败（因为Transfer Hook执行失败）。
+    ///
+    /// # 参数
+    /// * `ctx` - 管理员操作上下文
+    /// * `paused` - 暂停状态（true=暂停，false=恢复）
+    ///
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())
+    pub fn set_transfer_hook_paused(ctx: Context<TransferHookAdminAction>, paused: bool) -> Result<()> {
+        let hook_config = &mut ctx.accounts.hook_config;
+        hook_config.is_paused = paused;
+
+        msg!("Hook paused status: {}", paused);
+        Ok(())
+    }
+
+    /// 提议新的Transfer Hook管理员
+    ///
+    /// 与主程序`propose_authority`相同的两步流程，
+    /// 时间锁使用TotConfig中当前生效的权限移交延迟（`authority_transfer_delay_at`）。
+    ///
+    /// # 参数
+    /// * `ctx` - 提议上下文
+    /// * `new_authority` - 新的Hook管理员地址
+    ///
+    /// # 返回值
+    /// * `Result<()>` - 成功返回Ok(())
+    pub fn propose_hook_authority(
+        ctx: Context<ProposeHookAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        let hook_config = &mut ctx.accounts.hook_config;
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        require!(
+            new_authority != Pubkey::default() && new_authority != hook_config.authority,
+            TotError::InvalidNewAuthority
+        );
+
+        let eta = timestamp
+            .checked_add(ctx.accounts.config.authority_transfer_delay_at(timestamp))
+            .ok_or(TotError::MathOverflow)?;
+
+        hook_config.pending_authority = new_authority;
+        hook_config.authority_transfer_eta = eta;
+
+        msg!("Hook authority transfer proposed: {}, eta {}", new_authority, eta);
+
+        emit!(HookAuthorityTransferProposed {
+            current_authority: hook_config.authority,
+            pending_authority: new_authority,
+            eta,
+            timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// 接受Transfer Hook管理员权限
+    ///
+    /// 必须由提议中的新地址签名，且时间锁已到期。
+    pub fn accept_hook_authority(ctx: Context<AcceptHookAuthority>) -> Result<()> {
+        let hook_config = &mut ctx.accounts.hook_config;
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        require!(
+            hook_config.pending_authority != Pubkey::default(),
+            TotError::NoPendingAuthority
+        );
+        require!(
+            timestamp >= hook_config.authority_transfer_eta,
+            TotError::AuthorityTransferTimelocked
+        );
+
+        let old_authority = hook_config.authority;
+        hook_config.authority = hook_config.pending_authority;
+        hook_config.pending_authority = Pubkey::default();
+        hook_config.authority_transfer_eta = 0;
+
+        msg!("Hook authority updated from {} to {}", old_authority, hook_config.authority);
+
+        emit!(HookAuthorityUpdated {
+            old_authority,
+            new_authority: hook_config.authority,
+            timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// 取消Transfer Hook管理员移交
+    pub fn cancel_hook_authority_transfer(ctx: Context<TransferHookAdminAction>) -> Result<()> {
+        let hook_config = &mut ctx.accounts.hook_config;
+
+        require!(
+            hook_config.pending_authority != Pubkey::default(),
+            TotError::NoPendingAuthority
+        );
+
+        msg!("Hook authority transfer to {} cancelled", hook_config.pending_authority);
+
+        hook_config.pending_authority = Pubkey::default();
+        hook_config.authority_transfer_eta = 0;
+        Ok(())
+    }
+
+    /// Fallback函数（spl-transfer-hook-interface路由）
+    ///
+    /// Token-2022在转账时使用spl-transfer-hook-interface的discriminator
+    /// （SHA-256("spl-transfer-hook-interface:execute")的前8字节）CPI调用Hook，
+    /// 与Anchor的`global:execute` discriminator不同，只能落到这里。
+    ///
+    /// 1. 解析接口指令，识别`Execute { amount }`
+    /// 2. 按`ExecuteTransferHook`的约束手动校验账户并构造Context
+    /// 3. 执行与`execute`相同的Hook逻辑，并写回可变账户
+    ///
+    /// # 注意事项
+    /// - Anchor 0.29.0把`#[program]`模块中第一个参数不是`Context`的函数识别为fallback
+    /// - 非Execute指令返回`InstructionFallbackNotFound`
+    /// - 升级到Anchor 0.30.0+后可改用`#[interface(spl_transfer_hook_interface::execute)]`
+    pub fn fallback<'info>(
+        program_id: &Pubkey,
+        accounts: &'info [AccountInfo<'info>],
+        data: &[u8],
+    ) -> Result<()> {
+        let amount = parse_execute_amount(data)?;
+
+        let mut bumps = ExecuteTransferHookBumps::default();
+        let mut remaining_accounts = accounts;
+        let mut hook_accounts = ExecuteTransferHook::try_accounts(
+            program_id,
+            &mut remaining_accounts,
+            &data[8..],
+            &mut bumps,
+            &mut BTreeSet::new(),
+        )?;
+
+        execute_internal(
+            Context::new(program_id, &mut hook_accounts, remaining_accounts, bumps),
+            amount,
+        )?;
+
+        hook_accounts.exit(program_id)
+    }
+}
+
+/// Transfer Hook执行逻辑
+///
+/// DEX和钱包发起的普通转账不经过`transfer_with_tax`，
+/// Hook是对这类转账执行TOT规则的唯一位置。
+///
+/// # 执行流程
+/// 1. 检查Hook是否暂停、Mint地址是否为TOT
+/// 2. 检查转账/卖出指令族是否暂停（系统管理员签名的转账除外，见下文）
+/// 3. 拒绝冻结持有者的转出和转入（永久代理PDA发起的追回不检查发送者冻结状态），
+///    免税地址同样检查
+/// 4. 免税地址跳过其余规则
+/// 5. 恐慌模式下拒绝达到恐慌阈值的大额卖出（此类卖出必须走`transfer_with_tax`缴纳恐慌税）
+/// 6. 计算Transfer Fee扩展预扣的税额，更新Hook统计
+/// 7. 不是主程序发起的转账回调主程序更新持有者统计
+///
+/// ## 卖出判定
+/// - 接收代币账户或其所有者为`config.liquidity_pool`时视为卖出
+///
+/// ## 暂停豁免
+/// - `emergency_withdraw`只能在转账暂停时执行，转出由系统管理员签名，
+///   因此源账户授权者为`config.authority`的转账不受转账/卖出暂停约束
+///
+/// ## 持有者统计
+/// - `transfer_with_tax`等主程序指令在发起转账CPI前后已经更新统计，
+///   转账期间`TransferGuard.in_progress`为true，Hook不回调
+/// - 其余转账通过CPI调用主程序`record_hook_transfer`，规则与`transfer_with_tax`相同：
+///   卖出更新发送者统计，接收者按到账金额记录买入
+/// - 只在源代币账户的`TransferHookAccount.transferring`为true时回调，
+///   即确实由Token-2022转账调用，直接调用`execute`不会写入统计
+/// - 守卫账户尚未初始化时不回调
+fn execute_internal(ctx: Context<ExecuteTransferHook>, amount: u64) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let config = &ctx.accounts.config;
+    let tax_config = &ctx.accounts.tax_config;
+
+    // 检查Hook是否暂停
+    // 如果暂停，拒绝所有转账
+    require!(!ctx.accounts.hook_config.is_paused, TotError::HookPaused);
+
+    // 验证Mint地址
+    // 确保这是TOT代币的转账，防止Hook被用于其他代币
+    require!(
+        ctx.accounts.mint.key() == ctx.accounts.hook_config.tot_mint,
+        TotError::InvalidMint
+    );
+
+    let source = &ctx.accounts.source_account;
+    let destination = &ctx.accounts.destination_account;
+    let sender = source.owner;
+    let receiver = destination.owner;
+
+    // 卖出判定: 转入流动性池
+    let is_sell = destination.key() == config.liquidity_pool
+        || receiver == config.liquidity_pool;
+    // 买入判定: 从流动性池转出
+    let is_buy = source.key() == config.liquidity_pool
+        || sender == config.liquidity_pool;
+
+    // 暂停检查（免税转账同样受暂停约束，系统管理员签名的紧急提取除外）
+    if ctx.accounts.source_authority.key() != config.authority {
+        config.require_not_paused(PauseFlag::Transfers, timestamp)?;
+        if is_sell {
+            config.require_not_paused(PauseFlag::Sells, timestamp)?;
+        }
+    }
+
+    // Transfer Fee扩展在本次转账中预扣的税额
+    let withheld_fee = calculate_withheld_fee(&ctx.accounts.mint.to_account_info(), amount)?;
+
+    // 冻结检查: 冻结的持有者既不能转出也不能转入
+    // 在免税判断之前执行，冻结的持有者不能借免税地址转移资金
+    let sender_holder =
+        HolderAccount::load_optional(&ctx.accounts.sender_holder_info, &tot_token::ID)?;
+    let receiver_holder =
+        HolderAccount::load_optional(&ctx.accounts.receiver_holder_info, &tot_token::ID)?;
+
+    // 追回由主程序的永久代理PDA签名，转出的正是被冻结持有者的代币
+    let (permanent_delegate, _) =
+        Pubkey::find_program_address(&[seeds::PERMANENT_DELEGATE_SEED], &tot_token::ID);
+    let is_clawback = ctx.accounts.source_authority.key() == permanent_delegate;
+
+    if let Some(ref holder) = sender_holder {
+        require!(is_clawback || !holder.is_frozen, TotError::HolderFrozen);
+    }
+    if let Some(ref holder) = receiver_holder {
+        require!(!holder.is_frozen, TotError::HolderFrozen);
+    }
+
+    let is_exempt = tax_config.is_exempt(&sender) || tax_config.is_exempt(&receiver);
+
+    if !is_exempt {
+        // 卖出限制: Hook无法收取动态税，恐慌模式下的大额卖出必须走transfer_with_tax
+        if is_sell && config.panic_mode {
+            require!(
+                !TaxCalculator::exceeds_panic_threshold(
+                    amount,
+                    config.circulating_supply(ctx.accounts.mint.supply),
+                    tax_config.panic_threshold_bps,
+                )?,
+                TotError::HookSellLimitExceeded
+            );
+        }
+    }
+
+    // 更新Hook统计信息
+    let hook_config = &mut ctx.accounts.hook_config;
+    hook_config.total_transfers = hook_config.total_transfers
+        .checked_add(1)
+        .ok_or(error!(TotError::MathOverflow))?;
+    hook_config.total_tax_collected = hook_config.total_tax_collected
+        .checked_add(withheld_fee)
+        .ok_or(error!(TotError::MathOverflow))?;
+
+    // 持有者统计: 只处理不是主程序发起的转账
+    let guard = TransferGuard::load_optional(&ctx.accounts.transfer_guard, &tot_token::ID)?;
+    let external_transfer = matches!(guard, Some(guard) if !guard.in_progress);
+    if external_transfer
+        && is_transferring(&source.to_account_info())?
+        && (sender_holder.is_some() || receiver_holder.is_some())
+    {
+        let bump = [ctx.accounts.hook_config.bump];
+        let signer_seeds: &[&[&[u8]]] = &[&[seeds::HOOK_CONFIG_SEED, &bump]];
+        tot_token::cpi::record_hook_transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.tot_program.to_account_info(),
+                RecordHookTransfer {
+                    hook_authority: ctx.accounts.hook_config.to_account_info(),
+                    sender_holder_info: ctx.accounts.sender_holder_info.to_account_info(),
+                    receiver_holder_info: ctx.accounts.receiver_holder_info.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            HookTransferRecord {
+                sender,
+                receiver,
+                amount,
+                fee: withheld_fee,
+                sender_remaining: source.amount,
+                is_sell,
+                is_buy,
+            },
+        )?;
+    }
+
+    msg!(
+        "Transfer Hook: {} tokens from {} to {}, withheld fee {}",
+        amount,
+        source.key(),
+        destination.key(),
+        withheld_fee
+    );
+    Ok(())
+}
+
+/// 代币账户是否处于Token-2022转账过程中
+///
+/// Token-2022在调用Hook前置位源、目标账户的`TransferHookAccount.transferring`，
+/// 返回后清除。
+fn is_transferring(account: &AccountInfo) -> Result<bool> {
+    let data = account.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+    Ok(state
+        .get_extension::<TransferHookAccount>()
+        .map(|extension| bool::from(extension.transferring))
+        .unwrap_or(false))
+}
+
+/// 计算Transfer Fee扩展在本次转账中预扣的税额
+///
+/// 使用当前epoch生效的费率配置，与Token-2022内部的计算方式一致。
+fn calculate_withheld_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+
+    let fee = match state.get_extension::<TransferFeeConfig>() {
+        Ok(fee_config) => fee_config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(TotError::MathOverflow)?,
+        Err(_) => 0,
+    };
+
+    Ok(fee)
+}
+
+/// Transfer Hook需要的额外账户列表
+///
+/// 顺序必须与`ExecuteTransferHook`中接口固定账户之后的字段一致：
+/// tot_program、hook_config、config、tax_config、sender_holder_info、receiver_holder_info、
+/// transfer_guard。
+///
+/// 除hook_config外都是主程序的PDA，以`TOT_PROGRAM_INDEX`处的主程序ID派生。
+/// 持有者PDA和转账守卫可写：Hook回调主程序更新持有者统计，主程序转账时改写守卫。
+/// 持有者PDA的种子从代币账户数据中读取所有者地址（偏移32，长度32）：
+/// - 发送者: 账户索引0（source）
+/// - 接收者: 账户索引2（destination）
+pub fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![
+        // tot_program（只读，派生主程序PDA）
+        ExtraAccountMeta::new_with_pubkey(&tot_token::ID, false, false)?,
+        // hook_config（可写，更新统计）
+        ExtraAccountMeta::new_with_seeds(
+            &[Seed::Literal { bytes: seeds::HOOK_CONFIG_SEED.to_vec() }],
+            false,
+            true,
+        )?,
+        // config（只读）
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            TOT_PROGRAM_INDEX,
+            &[Seed::Literal { bytes: seeds::CONFIG_SEED.to_vec() }],
+            false,
+            false,
+        )?,
+        // tax_config（只读）
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            TOT_PROGRAM_INDEX,
+            &[Seed::Literal { bytes: seeds::TAX_CONFIG_SEED.to_vec() }],
+            false,
+            false,
+        )?,
+        // sender_holder_info（可写，冻结检查、持有者统计）
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            TOT_PROGRAM_INDEX,
+            &[
+                Seed::Literal { bytes: seeds::HOLDER_SEED.to_vec() },
+                Seed::AccountData { account_index: 0, data_index: 32, length: 32 },
+            ],
+            false,
+            true,
+        )?,
+        // receiver_holder_info（可写，冻结检查、持有者统计）
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            TOT_PROGRAM_INDEX,
+            &[
+                Seed::Literal { bytes: seeds::HOLDER_SEED.to_vec() },
+                Seed::AccountData { account_index: 2, data_index: 32, length: 32 },
+            ],
+            false,
+            true,
+        )?,
+        // transfer_guard（可写，主程序转账时置位）
+        ExtraAccountMeta::new_external_pda_with_seeds(
+            TOT_PROGRAM_INDEX,
+            &[Seed::Literal { bytes: seeds::TRANSFER_GUARD_SEED.to_vec() }],
+            false,
+            true,
+        )?,
+    ])
+}
+
+/// Transfer Hook 全局配置
+#[account]
+pub struct TransferHookConfig {
+    /// 管理员
+    pub authority: Pubkey,
+
+    /// TOT Mint 地址
+    pub tot_mint: Pubkey,
+
+    /// TOT 全局配置地址
+    pub tot_config: Pubkey,
+
+    /// 总转账次数
+    pub total_transfers: u64,
+
+    /// 总收取税额
+    ///
+    /// 每次转账时累加Transfer Fee扩展预扣的税额。
+    /// 主程序`get_protocol_stats`按固定偏移读取此字段，调整字段顺序时需同步修改
+    pub total_tax_collected: u64,
+
+    /// 总销毁数量
+    ///
+    /// 预扣税额在提取（harvest）后才会销毁，Hook执行时不更新此字段
+    pub total_burned: u64,
+
+    /// 是否暂停
+    pub is_paused: bool,
+
+    /// 待接受的新管理员（Pubkey::default()表示无）
+    pub pending_authority: Pubkey,
+
+    /// 管理员移交最早可接受时间
+    pub authority_transfer_eta: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl TransferHookConfig {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        32 + // tot_mint
+        32 + // tot_config
+        8 + // total_transfers
+        8 + // total_tax_collected
+        8 + // total_burned
+        1 + // is_paused
+        32 + // pending_authority
+        8 + // authority_transfer_eta
+        1; // bump
+}
+
+/// 初始化 Transfer Hook 配置的账户
+#[derive(Accounts)]
+pub struct InitializeTransferHook<'info> {
+    /// TOT系统管理员（成为Hook管理员并支付租金）
+    #[account(
+        mut,
+        constraint = authority.key() == config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// TOT全局配置
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        seeds::program = tot_token::ID
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = TransferHookConfig::LEN,
+        seeds = [seeds::HOOK_CONFIG_SEED],
+        bump
+    )]
+    pub hook_config: Account<'info, TransferHookConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Transfer Hook 执行的账户
+///
+/// 这些账户由 Token-2022 程序自动传递，顺序遵循spl-transfer-hook-interface：
+/// 前五个为接口固定账户，之后为extra_account_meta_list中登记的额外账户。
+#[derive(Accounts)]
+pub struct ExecuteTransferHook<'info> {
+    /// 源代币账户
+    #[account()]
+    pub source_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint 账户
+    #[account()]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// 目标代币账户
+    #[account()]
+    pub destination_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// 源账户所有者/授权者
+    /// CHECK: 由Token-2022程序验证
+    pub source_authority: AccountInfo<'info>,
+
+    /// Extra Account Metas账户
+    ///
+    /// 存储额外账户的解析规则（见`extra_account_metas()`），
+    /// Token-2022据此把下面的额外账户追加到本指令。
+    ///
+    /// CHECK: 地址由seeds约束，内容由Token-2022解析
+    #[account(
+        seeds = [seeds::EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: AccountInfo<'info>,
+
+    /// TOT主程序（额外账户中的PDA派生程序）
+    /// CHECK: 地址约束为主程序ID
+    #[account(address = tot_token::ID)]
+    pub tot_program: AccountInfo<'info>,
+
+    /// Transfer Hook 配置
+    #[account(
+        mut,
+        seeds = [seeds::HOOK_CONFIG_SEED],
+        bump = hook_config.bump
+    )]
+    pub hook_config: Account<'info, TransferHookConfig>,
+
+    /// 全局配置（暂停状态、恐慌模式、流动性池地址）
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        seeds::program = tot_token::ID
+    )]
+    pub config: Account<'info, TotConfig>,
+
+    /// 税率配置（免税地址、恐慌阈值）
+    #[account(
+        seeds = [seeds::TAX_CONFIG_SEED],
+        bump,
+        seeds::program = tot_token::ID
+    )]
+    pub tax_config: Account<'info, TaxConfig>,
+
+    /// 发送者持有者信息
+    ///
+    /// CHECK: 地址由源代币账户所有者派生，可能尚未初始化，在处理器中按需反序列化
+    #[account(
+        mut,
+        seeds = [seeds::HOLDER_SEED, source_account.owner.as_ref()],
+        bump,
+        seeds::program = tot_token::ID
+    )]
+    pub sender_holder_info: UncheckedAccount<'info>,
+
+    /// 接收者持有者信息
+    ///
+    /// CHECK: 地址由目标代币账户所有者派生，可能尚未初始化，在处理器中按需反序列化
+    #[account(
+        mut,
+        seeds = [seeds::HOLDER_SEED, destination_account.owner.as_ref()],
+        bump,
+        seeds::program = tot_token::ID
+    )]
+    pub receiver_holder_info: UncheckedAccount<'info>,
+
+    /// 主程序转账守卫（区分主程序发起的转账）
+    ///
+    /// CHECK: 地址由seeds约束，可能尚未初始化，在处理器中按需反序列化
+    #[account(
+        seeds = [seeds::TRANSFER_GUARD_SEED],
+        bump,
+        seeds::program = tot_token::ID
+    )]
+    pub transfer_guard: UncheckedAccount<'info>,
+}
+
+/// 初始化Extra Account Meta List的账户
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    /// Hook管理员（支付账户租金）
+    #[account(
+        mut,
+        constraint = authority.key() == hook_config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// Extra Account Metas账户
+    /// CHECK: 地址由seeds约束，内容由ExtraAccountMetaList写入
+    #[account(
+        init,
+        payer = authority,
+        space = ExtraAccountMetaList::size_of(extra_account_metas()?.len())?,
+        seeds = [seeds::EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: AccountInfo<'info>,
+
+    /// TOT Mint
+    #[account(
+        address = hook_config.tot_mint @ TotError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [seeds::HOOK_CONFIG_SEED],
+        bump = hook_config.bump
+    )]
+    pub hook_config: Account<'info, TransferHookConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// 更新Extra Account Meta List的账户
+#[derive(Accounts)]
+pub struct UpdateExtraAccountMetaList<'info> {
+    /// Hook管理员（支付扩容租金）
+    #[account(
+        mut,
+        constraint = authority.key() == hook_config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// Extra Account Metas账户
+    /// CHECK: 地址由seeds约束，内容由ExtraAccountMetaList重写
+    #[account(
+        mut,
+        seeds = [seeds::EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: AccountInfo<'info>,
+
+    /// TOT Mint
+    #[account(
+        address = hook_config.tot_mint @ TotError::InvalidMint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [seeds::HOOK_CONFIG_SEED],
+        bump = hook_config.bump
+    )]
+    pub hook_config: Account<'info, TransferHookConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Transfer Hook 管理员操作账户
+#[derive(Accounts)]
+pub struct TransferHookAdminAction<'info> {
+    #[account(
+        constraint = authority.key() == hook_config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [seeds::HOOK_CONFIG_SEED],
+        bump = hook_config.bump
+    )]
+    pub hook_config: Account<'info, TransferHookConfig>,
+}
+
+/// 提议Transfer Hook管理员的账户
+#[derive(Accounts)]
+pub struct ProposeHookAuthority<'info> {
+    #[account(
+        constraint = authority.key() == hook_config.authority @ TotError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [seeds::HOOK_CONFIG_SEED],
+        bump = hook_config.bump
+    )]
+    pub hook_config: Account<'info, TransferHookConfig>,
+
+    /// TOT全局配置（读取权限移交延迟）
+    #[account(
+        seeds = [seeds::CONFIG_SEED],
+        bump,
+        seeds::program = tot_token::ID
+    )]
+    pub config: Account<'info, TotConfig>,
+}
+
+/// 接受Transfer Hook管理员权限的账户
+#[derive(Accounts)]
+pub struct AcceptHookAuthority<'info> {
+    #[account(
+        constraint = new_authority.key() == hook_config.pending_authority @ TotError::PendingAuthorityMismatch
+    )]
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [seeds::HOOK_CONFIG_SEED],
+        bump = hook_config.bump
+    )]
+    pub hook_config: Account<'info, TransferHookConfig>,
+}
+
+/// Hook管理员移交提议事件
+#[event]
+pub struct HookAuthorityTransferProposed {
+    pub current_authority: Pubkey,
+    pub pending_authority: Pubkey,
+    pub eta: i64,
+    pub timestamp: i64,
+}
+
+/// Hook管理员更新事件
+#[event]
+pub struct HookAuthorityUpdated {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::program_pack::Pack;
+    use anchor_lang::Discriminator;
+    use spl_token_2022::state::{Account as TokenAccountState, AccountState, Mint as MintState};
+    use spl_transfer_hook_interface::{get_extra_account_metas_address, instruction::execute};
+
+    /// 构造测试账户，数据泄漏为'static以满足`try_accounts`的生命周期
+    fn account_info(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> AccountInfo<'static> {
+        AccountInfo::new(
+            Box::leak(Box::new(key)),
+            false,
+            false,
+            Box::leak(Box::new(1_000_000_000u64)),
+            Box::leak(data.into_boxed_slice()),
+            Box::leak(Box::new(owner)),
+            false,
+            0,
+        )
+    }
+
+    fn token_account_data(mint: Pubkey, owner: Pubkey, amount: u64) -> Vec<u8> {
+        let mut data = vec![0u8; TokenAccountState::LEN];
+        TokenAccountState {
+            mint,
+            owner,
+            amount,
+            state: AccountState::Initialized,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        data
+    }
+
+    fn mint_data(supply: u64) -> Vec<u8> {
+        let mut data = vec![0u8; MintState::LEN];
+        MintState {
+            supply,
+            decimals: 6,
+            is_initialized: true,
+            ..Default::default()
+        }
+        .pack_into_slice(&mut data);
+        data
+    }
+
+    /// 只有discriminator、其余为零值的主程序账户
+    fn zeroed_account_data<T: Discriminator>(len: usize) -> Vec<u8> {
+        let mut data = vec![0u8; len];
+        data[..8].copy_from_slice(&T::DISCRIMINATOR);
+        data
+    }
+
+    fn tot_pda(seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, &tot_token::ID).0
+    }
+
+    /// 按Extra Account Meta List解析Execute指令的账户，并交给`ExecuteTransferHook`校验
+    ///
+    /// 与Token-2022调用Hook时的过程一致：先由接口固定账户和额外账户列表解析出额外账户，
+    /// 再按指令中的可写标记传给Hook。额外账户列表与`ExecuteTransferHook`的字段顺序、
+    /// 种子或可写性不一致时，这里的`try_accounts`会失败。
+    #[test]
+    fn test_execute_accounts_resolved_from_extra_account_meta_list() {
+        let token_program = spl_token_2022::ID;
+        let mint = Pubkey::new_unique();
+        let sender = Pubkey::new_unique();
+        let receiver = Pubkey::new_unique();
+        let amount = 1_000u64;
+
+        let source = account_info(Pubkey::new_unique(), token_program, token_account_data(mint, sender, 5_000));
+        let mint_info = account_info(mint, token_program, mint_data(1_000_000));
+        let destination = account_info(Pubkey::new_unique(), token_program, token_account_data(mint, receiver, 0));
+        let authority = account_info(sender, system_program::ID, vec![]);
+
+        let metas = extra_account_metas().unwrap();
+        let mut meta_list_data = vec![0u8; ExtraAccountMetaList::size_of(metas.len()).unwrap()];
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut meta_list_data, &metas).unwrap();
+        let validation = account_info(
+            get_extra_account_metas_address(&mint, &crate::ID),
+            crate::ID,
+            meta_list_data,
+        );
+
+        let (hook_config_key, hook_config_bump) =
+            Pubkey::find_program_address(&[seeds::HOOK_CONFIG_SEED], &crate::ID);
+        let mut hook_config_data = Vec::new();
+        TransferHookConfig {
+            authority: Pubkey::new_unique(),
+            tot_mint: mint,
+            tot_config: tot_pda(&[seeds::CONFIG_SEED]),
+            total_transfers: 0,
+            total_tax_collected: 0,
+            total_burned: 0,
+            is_paused: false,
+            pending_authority: Pubkey::default(),
+            authority_transfer_eta: 0,
+            bump: hook_config_bump,
+        }
+        .try_serialize(&mut hook_config_data)
+        .unwrap();
+
+        // 候选账户的顺序与额外账户列表无关，解析按地址查找
+        let candidates = vec![
+            account_info(tot_pda(&[seeds::TRANSFER_GUARD_SEED]), system_program::ID, vec![]),
+            account_info(tot_pda(&[seeds::HOLDER_SEED, receiver.as_ref()]), system_program::ID, vec![]),
+            account_info(tot_pda(&[seeds::HOLDER_SEED, sender.as_ref()]), system_program::ID, vec![]),
+            account_info(
+                tot_pda(&[seeds::TAX_CONFIG_SEED]),
+                tot_token::ID,
+                zeroed_account_data::<TaxConfig>(TaxConfig::LEN),
+            ),
+            account_info(
+                tot_pda(&[seeds::CONFIG_SEED]),
+                tot_token::ID,
+                zeroed_account_data::<TotConfig>(TotConfig::LEN),
+            ),
+            account_info(hook_config_key, crate::ID, hook_config_data),
+            account_info(tot_token::ID, Pubkey::default(), vec![]),
+        ];
+
+        let mut instruction = execute(
+            &crate::ID,
+            source.key,
+            mint_info.key,
+            destination.key,
+            authority.key,
+            validation.key,
+            amount,
+        );
+        let mut infos = vec![source, mint_info, destination, authority, validation.clone()];
+        ExtraAccountMetaList::add_to_cpi_instruction::<ExecuteInstruction>(
+            &mut instruction,
+            &mut infos,
+            &validation.try_borrow_data().unwrap(),
+            &candidates,
+        )
+        .unwrap();
+        assert_eq!(instruction.accounts.len(), infos.len());
+        assert_eq!(infos.len(), 5 + metas.len());
+
+        // 运行时按指令中的账户标记设置可写性
+        for (meta, info) in instruction.accounts.iter().zip(infos.iter_mut()) {
+            assert_eq!(meta.pubkey, *info.key);
+            info.is_writable = meta.is_writable;
+        }
+
+        // fallback的路由与账户校验
+        assert_eq!(parse_execute_amount(&instruction.data).unwrap(), amount);
+        let accounts: &'static [AccountInfo<'static>] = Box::leak(infos.into_boxed_slice());
+        let mut remaining_accounts = accounts;
+        let mut bumps = ExecuteTransferHookBumps::default();
+        let hook_accounts = ExecuteTransferHook::try_accounts(
+            &crate::ID,
+            &mut remaining_accounts,
+            &instruction.data[8..],
+            &mut bumps,
+            &mut BTreeSet::new(),
+        )
+        .unwrap();
+
+        assert!(remaining_accounts.is_empty());
+        assert_eq!(hook_accounts.hook_config.key(), hook_config_key);
+        assert_eq!(
+            hook_accounts.sender_holder_info.key(),
+            tot_pda(&[seeds::HOLDER_SEED, sender.as_ref()])
+        );
+        assert_eq!(
+            hook_accounts.receiver_holder_info.key(),
+            tot_pda(&[seeds::HOLDER_SEED, receiver.as_ref()])
+        );
+        assert_eq!(
+            hook_accounts.transfer_guard.key(),
+            tot_pda(&[seeds::TRANSFER_GUARD_SEED])
+        );
+    }
+}
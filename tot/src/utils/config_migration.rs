@@ -0,0 +1,128 @@
+//! # 配置账户迁移模块
+//!
+//! 本模块把版本1（旧布局）的`TotConfig`账户数据转换为当前布局。
+//!
+//! ## 背景
+//!
+//! 版本1在`version`字段之后只有128字节预留空间。之后新增的字段（权限移交、
+//! 暂停位图、追回、流通供应量、协议统计等）插入在`version`与`_reserved`之间，
+//! 账户从`TotConfig::LEGACY_LEN`字节增长到`TotConfig::LEN`，旧账户无法按当前布局反序列化。
+//!
+//! 新字段在`initialize`中全部初始化为零值，旧布局的预留空间也全为0，
+//! 因此保留`version`之前的数据、其余补零即得到等价的当前布局数据。
+//! 账户扩容和补足租金由`migrate_config`指令完成，这里只做纯数据转换，方便单元测试。
+//!
+//! ============================================
+// 文件: src/utils/config_migration.rs
+// 配置账户布局迁移
+// ============================================
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::errors::TotError;
+use crate::state::config::TotConfig;
+
+/// 版本1布局中预留空间的起始偏移（`version`字段之后）
+const LEGACY_RESERVED_OFFSET: usize = TotConfig::LEGACY_LEN - 128;
+
+/// 把版本1布局的配置账户数据转换为当前布局
+///
+/// # 参数
+/// * `data` - 旧配置账户的完整数据（含discriminator）
+///
+/// # 返回值
+/// * `Result<Vec<u8>>` - 长度为`TotConfig::LEN`的当前布局数据，`version`设为`CURRENT_VERSION`
+///
+/// # 错误
+/// * `TotError::ConfigAlreadyMigrated` - 数据长度已是当前布局
+/// * `TotError::InvalidLegacyConfig` - 长度不是旧布局、discriminator不匹配或预留空间不全为0
+pub fn migrate_legacy_config_data(data: &[u8]) -> Result<Vec<u8>> {
+    require!(data.len() != TotConfig::LEN, TotError::ConfigAlreadyMigrated);
+    require!(
+        data.len() == TotConfig::LEGACY_LEN && data.starts_with(&TotConfig::DISCRIMINATOR),
+        TotError::InvalidLegacyConfig
+    );
+    require!(
+        data[LEGACY_RESERVED_OFFSET..].iter().all(|byte| *byte == 0),
+        TotError::InvalidLegacyConfig
+    );
+
+    let mut migrated = vec![0u8; TotConfig::LEN];
+    migrated[..LEGACY_RESERVED_OFFSET].copy_from_slice(&data[..LEGACY_RESERVED_OFFSET]);
+
+    let mut config = TotConfig::try_deserialize(&mut &migrated[..])?;
+    config.version = TotConfig::CURRENT_VERSION;
+
+    let mut output = Vec::with_capacity(TotConfig::LEN);
+    config.try_serialize(&mut output)?;
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 按版本1布局构造配置账户数据
+    fn legacy_config_data(authority: Pubkey, mint: Pubkey) -> Vec<u8> {
+        let mut data = TotConfig::DISCRIMINATOR.to_vec();
+        for key in [authority, mint, Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()] {
+            data.extend_from_slice(key.as_ref());
+        }
+        data.push(1); // panic_mode
+        data.extend_from_slice(&1_700_000_000i64.to_le_bytes()); // initialized_at
+        data.extend_from_slice(&500u64.to_le_bytes()); // total_minted
+        data.extend_from_slice(&20u64.to_le_bytes()); // total_burned
+        data.extend_from_slice(&30u64.to_le_bytes()); // total_tax_collected
+        data.push(1); // version
+        data.extend_from_slice(&[0u8; 128]); // _reserved
+        data
+    }
+
+    #[test]
+    fn test_migrate_legacy_config_data() {
+        let authority = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let legacy = legacy_config_data(authority, mint);
+        assert_eq!(legacy.len(), TotConfig::LEGACY_LEN);
+
+        let migrated = migrate_legacy_config_data(&legacy).unwrap();
+        assert_eq!(migrated.len(), TotConfig::LEN);
+
+        let config = TotConfig::try_deserialize(&mut &migrated[..]).unwrap();
+        assert_eq!(config.authority, authority);
+        assert_eq!(config.mint, mint);
+        assert!(config.panic_mode);
+        assert_eq!(config.initialized_at, 1_700_000_000);
+        assert_eq!(config.total_minted, 500);
+        assert_eq!(config.total_burned, 20);
+        assert_eq!(config.total_tax_collected, 30);
+        assert_eq!(config.version, TotConfig::CURRENT_VERSION);
+
+        // 新字段与initialize的初始值一致
+        assert_eq!(config.pending_authority, Pubkey::default());
+        assert_eq!(config.authority_transfer_delay, 0);
+        assert_eq!(config.paused_operations, 0);
+        assert_eq!(config.locked_supply, 0);
+        assert_eq!(config.total_consumed, [0; 8]);
+        assert_eq!(config.voucher_signer, Pubkey::default());
+    }
+
+    #[test]
+    fn test_migrate_rejects_current_layout() {
+        let data = vec![0u8; TotConfig::LEN];
+        assert!(migrate_legacy_config_data(&data).is_err());
+    }
+
+    #[test]
+    fn test_migrate_rejects_invalid_legacy_data() {
+        let mut legacy = legacy_config_data(Pubkey::new_unique(), Pubkey::new_unique());
+        let last = legacy.len() - 1;
+        legacy[last] = 1;
+        assert!(migrate_legacy_config_data(&legacy).is_err());
+
+        let mut legacy = legacy_config_data(Pubkey::new_unique(), Pubkey::new_unique());
+        legacy[0] ^= 0xff;
+        assert!(migrate_legacy_config_data(&legacy).is_err());
+    }
+}
@@ -0,0 +1,186 @@
+//! # Ed25519签名验证指令解析模块
+//!
+//! 本模块负责校验交易中的Ed25519原生程序（precompile）指令。
+//!
+//! ## 背景
+//!
+//! Solana程序内无法直接做Ed25519验签，标准做法是在同一交易中放一条
+//! Ed25519程序指令：运行时在执行交易前完成验签，失败则整笔交易被拒绝。
+//! 本程序通过指令sysvar读取那条指令，确认它验证的是预期的公钥和消息。
+//!
+//! 这里只做纯数据解析，不涉及账户，方便单元测试覆盖各种伪造情况。
+//!
+//! ============================================
+// 文件: src/utils/ed25519.rs
+// Ed25519签名验证指令解析
+// ============================================
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{ed25519_program, instruction::Instruction};
+
+use crate::errors::TotError;
+
+/// 签名数据的偏移量结构大小（7个u16）
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+/// 偏移量结构的起始位置（num_signatures u8 + padding u8）
+const SIGNATURE_OFFSETS_START: usize = 2;
+/// 公钥长度
+const PUBKEY_LEN: usize = 32;
+/// 签名长度
+const SIGNATURE_LEN: usize = 64;
+
+/// 读取小端u16
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    let bytes = data
+        .get(offset..offset + 2)
+        .ok_or(TotError::InvalidVoucherSignature)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+/// 校验Ed25519签名验证指令
+///
+/// # 参数
+/// * `ix` - 从指令sysvar读取的指令
+/// * `expected_signer` - 预期的签名公钥
+/// * `expected_message` - 预期的签名消息
+///
+/// # 返回值
+/// * `Result<()>` - 指令验证的正是该公钥对该消息的签名时返回Ok(())
+///
+/// # 错误
+/// * `TotError::InvalidVoucherSignature` - 不是Ed25519程序指令、签名数量不为1、
+///   数据引用了其他指令，或公钥/消息不一致
+///
+/// # 注意事项
+/// - 只接受签名、公钥和消息都内联在该指令中（instruction_index为u16::MAX）的格式，
+///   避免指令引用交易中其他指令的数据绕过校验
+/// - 签名本身由运行时在执行交易前验证，这里不重复验签
+pub fn verify_ed25519_instruction(
+    ix: &Instruction,
+    expected_signer: &Pubkey,
+    expected_message: &[u8],
+) -> Result<()> {
+    require_keys_eq!(ix.program_id, ed25519_program::ID, TotError::InvalidVoucherSignature);
+    require!(ix.accounts.is_empty(), TotError::InvalidVoucherSignature);
+
+    let data = &ix.data;
+    require!(
+        data.len() >= SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN && data[0] == 1,
+        TotError::InvalidVoucherSignature
+    );
+
+    let offsets = SIGNATURE_OFFSETS_START;
+    let signature_offset = read_u16(data, offsets)? as usize;
+    let signature_ix_index = read_u16(data, offsets + 2)?;
+    let pubkey_offset = read_u16(data, offsets + 4)? as usize;
+    let pubkey_ix_index = read_u16(data, offsets + 6)?;
+    let message_offset = read_u16(data, offsets + 8)? as usize;
+    let message_size = read_u16(data, offsets + 10)? as usize;
+    let message_ix_index = read_u16(data, offsets + 12)?;
+
+    // 数据必须内联在本条指令中
+    require!(
+        signature_ix_index == u16::MAX
+            && pubkey_ix_index == u16::MAX
+            && message_ix_index == u16::MAX,
+        TotError::InvalidVoucherSignature
+    );
+    require!(
+        data.len() >= signature_offset + SIGNATURE_LEN,
+        TotError::InvalidVoucherSignature
+    );
+
+    let pubkey = data
+        .get(pubkey_offset..pubkey_offset + PUBKEY_LEN)
+        .ok_or(TotError::InvalidVoucherSignature)?;
+    require!(
+        pubkey == expected_signer.as_ref(),
+        TotError::InvalidVoucherSignature
+    );
+
+    let message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(TotError::InvalidVoucherSignature)?;
+    require!(message == expected_message, TotError::InvalidVoucherSignature);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 按`@solana/web3.js`的`Ed25519Program.createInstructionWithPublicKey`布局构造指令
+    fn build_ix(pubkey: &Pubkey, message: &[u8], ix_index: u16) -> Instruction {
+        let pubkey_offset = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN;
+        let signature_offset = pubkey_offset + PUBKEY_LEN;
+        let message_offset = signature_offset + SIGNATURE_LEN;
+
+        let mut data = vec![1u8, 0u8];
+        for value in [
+            signature_offset as u16,
+            ix_index,
+            pubkey_offset as u16,
+            ix_index,
+            message_offset as u16,
+            message.len() as u16,
+            ix_index,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(pubkey.as_ref());
+        data.extend_from_slice(&[7u8; SIGNATURE_LEN]);
+        data.extend_from_slice(message);
+
+        Instruction {
+            program_id: ed25519_program::ID,
+            accounts: vec![],
+            data,
+        }
+    }
+
+    #[test]
+    fn test_accepts_matching_signer_and_message() {
+        let signer = Pubkey::new_unique();
+        let ix = build_ix(&signer, b"voucher", u16::MAX);
+        assert!(verify_ed25519_instruction(&ix, &signer, b"voucher").is_ok());
+    }
+
+    #[test]
+    fn test_rejects_other_signer() {
+        let signer = Pubkey::new_unique();
+        let ix = build_ix(&signer, b"voucher", u16::MAX);
+        assert!(verify_ed25519_instruction(&ix, &Pubkey::new_unique(), b"voucher").is_err());
+    }
+
+    #[test]
+    fn test_rejects_other_message() {
+        let signer = Pubkey::new_unique();
+        let ix = build_ix(&signer, b"voucher", u16::MAX);
+        assert!(verify_ed25519_instruction(&ix, &signer, b"voucher2").is_err());
+        assert!(verify_ed25519_instruction(&ix, &signer, b"vouche").is_err());
+    }
+
+    #[test]
+    fn test_rejects_data_from_other_instruction() {
+        let signer = Pubkey::new_unique();
+        let ix = build_ix(&signer, b"voucher", 0);
+        assert!(verify_ed25519_instruction(&ix, &signer, b"voucher").is_err());
+    }
+
+    #[test]
+    fn test_rejects_other_program() {
+        let signer = Pubkey::new_unique();
+        let mut ix = build_ix(&signer, b"voucher", u16::MAX);
+        ix.program_id = Pubkey::new_unique();
+        assert!(verify_ed25519_instruction(&ix, &signer, b"voucher").is_err());
+    }
+
+    #[test]
+    fn test_rejects_truncated_data() {
+        let signer = Pubkey::new_unique();
+        let mut ix = build_ix(&signer, b"voucher", u16::MAX);
+        ix.data.truncate(20);
+        assert!(verify_ed25519_instruction(&ix, &signer, b"voucher").is_err());
+    }
+}
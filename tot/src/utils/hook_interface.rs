@@ -0,0 +1,109 @@
+//! # Transfer Hook接口解析模块
+//!
+//! 本模块负责识别Token-2022发来的spl-transfer-hook-interface指令。
+//!
+//! ## 背景
+//!
+//! Token-2022在每次转账时通过CPI调用Hook程序的`Execute`指令，
+//! 指令数据使用spl-transfer-hook-interface的8字节discriminator，
+//! 而不是Anchor的`global:<name>`discriminator，因此无法被Anchor的标准路由匹配，
+//! 只能落入`#[program]`模块中的fallback函数。
+//!
+//! Hook程序（`transfer-hook/`）依赖主程序crate，其fallback使用这里的解析函数。
+//!
+//! 这里只做纯数据解析，不涉及账户，方便单元测试覆盖路由规则。
+//!
+//! ============================================
+// 文件: src/utils/hook_interface.rs
+// Transfer Hook接口指令解析
+// ============================================
+
+use anchor_lang::prelude::*;
+use spl_transfer_hook_interface::instruction::TransferHookInstruction;
+
+/// 解析Token-2022发来的Execute指令，返回转账金额
+///
+/// # 参数
+/// * `data` - 完整的指令数据（含8字节discriminator）
+///
+/// # 返回值
+/// * `Result<u64>` - Execute指令返回转账金额
+///
+/// # 错误
+/// * `ErrorCode::InstructionFallbackNotFound` - 数据不是Execute指令
+///   （包括Anchor格式的指令数据和接口中的其他指令）
+///
+/// # 注意事项
+/// - 接口中的`InitializeExtraAccountMetaList`由Anchor指令
+///   `initialize_extra_account_meta_list`处理，这里不再重复支持
+pub fn parse_execute_amount(data: &[u8]) -> Result<u64> {
+    match TransferHookInstruction::unpack(data) {
+        Ok(TransferHookInstruction::Execute { amount }) => Ok(amount),
+        _ => Err(error!(ErrorCode::InstructionFallbackNotFound)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::hash::hash;
+    use spl_transfer_hook_interface::instruction::execute;
+
+    #[test]
+    fn test_parse_token_2022_execute() {
+        // 按Token-2022 CPI时的方式构造Execute指令
+        let program_id = Pubkey::new_unique();
+        let source = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let destination = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let validation = Pubkey::new_unique();
+        let ix = execute(
+            &program_id,
+            &source,
+            &mint,
+            &destination,
+            &authority,
+            &validation,
+            1_000_000,
+        );
+
+        assert_eq!(ix.program_id, program_id);
+        assert_eq!(parse_execute_amount(&ix.data).unwrap(), 1_000_000);
+
+        // 账户顺序与ExecuteTransferHook的前五个账户一致
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(keys, vec![source, mint, destination, authority, validation]);
+    }
+
+    #[test]
+    fn test_parse_execute_amount_bounds() {
+        let data = TransferHookInstruction::Execute { amount: 0 }.pack();
+        assert_eq!(parse_execute_amount(&data).unwrap(), 0);
+
+        let data = TransferHookInstruction::Execute { amount: u64::MAX }.pack();
+        assert_eq!(parse_execute_amount(&data).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn test_reject_anchor_discriminator() {
+        // Anchor格式的execute指令由标准路由处理，不应被识别为接口指令
+        let mut data = hash(b"global:execute").to_bytes()[..8].to_vec();
+        data.extend_from_slice(&1_000u64.to_le_bytes());
+        assert!(parse_execute_amount(&data).is_err());
+    }
+
+    #[test]
+    fn test_reject_other_interface_instructions() {
+        let data = TransferHookInstruction::InitializeExtraAccountMetaList {
+            extra_account_metas: vec![],
+        }
+        .pack();
+        assert!(parse_execute_amount(&data).is_err());
+
+        // 数据不完整
+        let data = TransferHookInstruction::Execute { amount: 1 }.pack();
+        assert!(parse_execute_amount(&data[..10]).is_err());
+        assert!(parse_execute_amount(&[]).is_err());
+    }
+}
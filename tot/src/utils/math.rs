@@ -101,6 +101,81 @@ pub fn calculate_holding_days(first_buy_time: i64, current_time: i64) -> u64 {
     (time_diff_limited / 86400) as u64
 }
 
+/// 计算线性释放的已释放数量
+/// 
+/// 用于代币流（stream）：`total`在`[start_time, end_time]`内按时间线性释放。
+/// 
+/// # 参数
+/// * `total` - 释放总量
+/// * `start_time` - 开始时间（Unix时间戳）
+/// * `end_time` - 结束时间（Unix时间戳）
+/// * `current_time` - 当前时间（Unix时间戳）
+/// 
+/// # 返回值
+/// * `Result<u64>` - 截至`current_time`已释放的数量（向下取整）
+/// 
+/// # 边界情况
+/// * `current_time <= start_time`时返回0
+/// * `current_time >= end_time`时返回`total`（包括`start_time == end_time`）
+/// 
+/// # 示例
+/// ```rust
+/// let vested = calculate_linear_vested(1000, 0, 100, 25)?; // 返回250
+/// ```
+pub fn calculate_linear_vested(
+    total: u64,
+    start_time: i64,
+    end_time: i64,
+    current_time: i64,
+) -> Result<u64> {
+    if current_time <= start_time {
+        return Ok(0);
+    }
+    if current_time >= end_time {
+        return Ok(total);
+    }
+
+    // start_time < current_time < end_time，两个差值都为正数
+    let elapsed = (current_time - start_time) as u128;
+    let duration = (end_time - start_time) as u128;
+
+    // 使用u128避免 total × elapsed 溢出，结果不超过total
+    Ok(((total as u128) * elapsed / duration) as u64)
+}
+
+/// 计算 a × b / c（向上取整）
+/// 
+/// 用于P2P挂单部分成交：按挂单价格折算吃单方应支付的数量，
+/// 向上取整保证挂单方收到的不少于挂单价格。
+/// 
+/// # 参数
+/// * `a` - 被乘数
+/// * `b` - 乘数
+/// * `c` - 除数
+/// 
+/// # 返回值
+/// * `Result<u64>` - ⌈a × b / c⌉
+/// 
+/// # 错误
+/// * `DivisionByZero` - c为0
+/// * `MathOverflow` - 结果超过u64
+/// 
+/// # 示例
+/// ```rust
+/// let payment = mul_div_ceil(1, 10, 3)?; // 返回4
+/// ```
+pub fn mul_div_ceil(a: u64, b: u64, c: u64) -> Result<u64> {
+    if c == 0 {
+        return Err(TotError::DivisionByZero.into());
+    }
+
+    // 使用u128避免 a × b 溢出
+    let product = (a as u128) * (b as u128);
+    let c = c as u128;
+    let result = product.div_ceil(c);
+    u64::try_from(result).map_err(|_| TotError::MathOverflow.into())
+}
+
 /// 计算幂次方（用于时间衰减）
 /// 使用定点数运算，避免浮点数
 /// 计算: base^(exponent/100)
@@ -167,4 +242,49 @@ mod tests {
         let current = 1000000 + (30 * 86400); // 30 days later
         assert_eq!(calculate_holding_days(first_buy, current), 30);
     }
+
+    #[test]
+    fn test_calculate_linear_vested() {
+        // 开始前和开始时未释放
+        assert_eq!(calculate_linear_vested(1000, 100, 200, 50).unwrap(), 0);
+        assert_eq!(calculate_linear_vested(1000, 100, 200, 100).unwrap(), 0);
+
+        // 线性释放，向下取整
+        assert_eq!(calculate_linear_vested(1000, 100, 200, 125).unwrap(), 250);
+        assert_eq!(calculate_linear_vested(1000, 0, 3, 1).unwrap(), 333);
+
+        // 结束后全部释放
+        assert_eq!(calculate_linear_vested(1000, 100, 200, 200).unwrap(), 1000);
+        assert_eq!(calculate_linear_vested(1000, 100, 200, 10_000).unwrap(), 1000);
+
+        // 开始与结束相同（已取消的流）
+        assert_eq!(calculate_linear_vested(700, 100, 100, 100).unwrap(), 0);
+        assert_eq!(calculate_linear_vested(700, 100, 100, 101).unwrap(), 700);
+
+        // 大数不溢出
+        assert_eq!(
+            calculate_linear_vested(u64::MAX, 0, 2, 1).unwrap(),
+            u64::MAX / 2
+        );
+    }
+
+    #[test]
+    fn test_mul_div_ceil() {
+        // 整除时不进位
+        assert_eq!(mul_div_ceil(50, 200, 100).unwrap(), 100);
+
+        // 有余数时向上取整
+        assert_eq!(mul_div_ceil(1, 10, 3).unwrap(), 4);
+        assert_eq!(mul_div_ceil(1, 1, 1_000_000).unwrap(), 1);
+
+        // 0不进位
+        assert_eq!(mul_div_ceil(0, 10, 3).unwrap(), 0);
+
+        // 中间结果超过u64时不溢出
+        assert_eq!(mul_div_ceil(u64::MAX, u64::MAX, u64::MAX).unwrap(), u64::MAX);
+
+        // 除数为0和结果溢出
+        assert!(mul_div_ceil(1, 1, 0).is_err());
+        assert!(mul_div_ceil(u64::MAX, 2, 1).is_err());
+    }
 }
@@ -13,6 +13,10 @@
 //! - `tax_calculator`: 动态税率计算器（核心业务逻辑）
 //! - `math`: 数学工具函数（安全运算、BPS计算等）
 //! - `validation`: 验证工具函数（参数验证、范围检查等）
+//! - `hook_interface`: Transfer Hook接口指令解析（Hook程序的fallback路由）
+//! - `token_transfer`: 携带Transfer Hook额外账户的transfer_checked CPI
+//! - `ed25519`: Ed25519签名验证指令解析（奖励兑换券）
+//! - `config_migration`: 旧布局配置账户的数据迁移（`migrate_config`）
 //! 
 //! ## 依赖关系
 //! 
@@ -47,6 +51,10 @@
 pub mod tax_calculator;
 pub mod math;
 pub mod validation;
+pub mod hook_interface;
+pub mod token_transfer;
+pub mod ed25519;
+pub mod config_migration;
 
 // 精确导出公共API，避免通配符导出导致的模块边界不清晰
 
@@ -67,6 +75,8 @@ pub use math::{
     calculate_amount_after_tax,
     apply_discount_to_tax,
     calculate_holding_days,
+    calculate_linear_vested,
+    mul_div_ceil,
     power_fixed,
 };
 
@@ -39,6 +39,7 @@
 
 use anchor_lang::prelude::*;
 use crate::constants::*;
+use crate::errors::TotError;
 use crate::state::holder::HolderAccount;
 use crate::state::tax::TaxConfig;
 use crate::utils::math::*;
@@ -58,7 +59,7 @@ pub struct TaxCalculation {
     pub holding_discount_bps: u16,
     
     /// 大额交易附加税（basis points）
-    /// 仅对卖出操作，当交易规模超过阈值时收取的附加税
+    /// 仅对买入和卖出操作，当交易规模超过阈值时收取的附加税
     pub whale_tax_bps: u16,
     
     /// 最终税率（basis points）
@@ -74,6 +75,20 @@ pub struct TaxCalculation {
     pub net_amount: u64,
 }
 
+impl TaxCalculation {
+    /// 发送者被扣除的总额（净金额 + 税额）
+    /// 
+    /// 含税模式下等于传入的转账金额，不含税模式下为反推出的含税总额。
+    pub fn gross_amount(&self) -> Result<u64> {
+        safe_add(self.net_amount, self.tax_amount)
+    }
+}
+
+/// 不含税模式反推含税总额的最大迭代次数
+/// 
+/// 税率只在大额附加税和恐慌税率的档位处跳变，档位数远小于此值。
+const MAX_GROSS_UP_ITERATIONS: usize = 8;
+
 /// 税率计算器
 /// 
 /// 实现TOT动态重力场税收模型（TOT-DGTM）的核心计算逻辑。
@@ -111,10 +126,12 @@ impl TaxCalculator {
     /// * `holder_info` - 持有者信息（可选）
     ///   - `Some(holder)`: 已注册的持有者，可以计算持有时间折扣
     ///   - `None`: 新用户，无持有时间折扣
-    /// * `total_supply` - 代币总供应量（用于计算大额交易惩罚）
+    /// * `circulating_supply` - 流通供应量（用于计算大额交易惩罚，见`TotConfig::circulating_supply`）
     /// * `current_timestamp` - 当前Unix时间戳（用于计算持有天数）
-    /// * `is_buy` - 是否为买入操作（当前版本买入不收取税收）
+    /// * `is_buy` - 是否为买入操作（从已登记的交易场所转入，使用买入税率并计算大额交易惩罚）
+    ///   - 买入时`holder_info`应传入买入方（接收者）的持有者信息
     /// * `is_sell` - 是否为卖出操作（会计算大额交易惩罚）
+    /// * `panic_mode` - 是否处于恐慌税率模式（来自`TotConfig.panic_mode`）
     /// * `tax_config` - 税率配置（包含所有税率参数）
     /// 
     /// # 返回值
@@ -123,12 +140,13 @@ impl TaxCalculator {
     /// 
     /// # 计算步骤
     /// 
-    /// 1. **确定基础税率**: 从配置中读取基础税率（默认2%）
+    /// 1. **确定基础税率**: 买入使用买入税率（默认5%），其他使用基础税率（默认2%）
     /// 2. **计算持有时间折扣**: 根据持有天数计算折扣（会减少税率）
-    /// 3. **计算大额交易附加税**: 如果是卖出且交易规模大，计算附加税（会增加税率）
+    /// 3. **计算大额交易附加税**: 如果是买入或卖出且交易规模大，计算附加税（会增加税率）
     /// 4. **综合计算最终税率**: 基础税率 - 折扣 + 附加税
-    /// 5. **限制最大税率**: 确保不超过99%
-    /// 6. **计算税额和净金额**: 根据最终税率计算实际税额
+    /// 5. **恐慌税率**: 恐慌模式下的大额卖出，税率至少为panic_tax_bps
+    /// 6. **限制最大税率**: 确保不超过99%
+    /// 7. **计算税额和净金额**: 根据最终税率计算实际税额
     /// 
     /// # 公式实现
     /// 
@@ -136,9 +154,9 @@ impl TaxCalculator {
     /// 最终税率 = Base - Discount + Penalty
     /// 
     /// 其中:
-    /// - Base = base_tax_bps (基础税率)
+    /// - Base = buy_tax_bps (买入) 或 base_tax_bps (卖出和普通转账)
     /// - Discount = calculate_holding_discount() (持有时间折扣)
-    /// - Penalty = calculate_whale_tax() (大额交易惩罚，仅对卖出)
+    /// - Penalty = calculate_whale_tax() (大额交易惩罚，仅对买入和卖出)
     /// ```
     /// 
     /// # 错误处理
@@ -150,10 +168,11 @@ impl TaxCalculator {
     /// let tax_calc = TaxCalculator::calculate_tax(
     ///     1_000_000_000,           // 转账1000个代币
     ///     Some(&holder_account),   // 持有者信息
-    ///     202_700_000_000_000_000_000, // 总供应量
+    ///     202_700_000_000_000_000, // 流通供应量
     ///     clock.unix_timestamp,    // 当前时间
     ///     false,                    // 不是买入
     ///     true,                     // 是卖出
+    ///     config.panic_mode,        // 恐慌税率模式
     ///     &tax_config,              // 税率配置
     /// )?;
     /// 
@@ -163,10 +182,11 @@ impl TaxCalculator {
     pub fn calculate_tax(
         amount: u64,
         holder_info: Option<&HolderAccount>,
-        total_supply: u64,
+        circulating_supply: u64,
         current_timestamp: i64,
         is_buy: bool,
         is_sell: bool,
+        panic_mode: bool,
         tax_config: &TaxConfig,
     ) -> Result<TaxCalculation> {
         // ========================================
@@ -175,7 +195,12 @@ impl TaxCalculator {
         // 
         // 基础税率是所有转账都需要支付的基本税率。
         // 这是给国库的基本供奉，不考虑任何折扣和惩罚。
-        let base_tax_bps = tax_config.base_tax_bps;
+        // 从交易场所买入使用单独配置的买入税率。
+        let base_tax_bps = if is_buy {
+            tax_config.buy_tax_bps
+        } else {
+            tax_config.base_tax_bps
+        };
 
         // ========================================
         // 步骤2: 计算持有时间折扣
@@ -189,22 +214,22 @@ impl TaxCalculator {
         let holding_discount_bps = Self::calculate_holding_discount(
             holder_info,
             current_timestamp,
-            tax_config,
+            base_tax_bps,
         )?;
 
         // ========================================
-        // 步骤3: 计算大额交易附加税（仅对卖出操作）
+        // 步骤3: 计算大额交易附加税（仅对买入和卖出操作）
         // ========================================
         // 
-        // 如果是卖出操作，需要计算大额交易惩罚。
+        // 如果是买入或卖出操作，需要计算大额交易惩罚。
         // 交易规模越大（占供应量比例越高），附加税越高。
         // 
         // 惩罚公式: (P_impact / L) × α
         // 其中P_impact可以简化为交易量占供应量的比例
-        let whale_tax_bps = if is_sell {
-            Self::calculate_whale_tax(amount, total_supply)?
+        let whale_tax_bps = if is_sell || is_buy {
+            Self::calculate_whale_tax(amount, circulating_supply)?
         } else {
-            // 买入和普通转账不收取大额交易惩罚
+            // 普通转账不收取大额交易惩罚
             0
         };
 
@@ -225,6 +250,17 @@ impl TaxCalculator {
         let final_tax_bps = after_discount
             .checked_add(whale_tax_bps)
             .ok_or(error!(TotError::TaxRateExceedsMaximum))?;
+
+        // 恐慌税率：恐慌模式下，卖出金额占供应量比例达到panic_threshold_bps时，
+        // 税率至少提升到panic_tax_bps（恐慌模式只影响税率，不拒绝卖出）
+        let final_tax_bps = if is_sell
+            && panic_mode
+            && Self::exceeds_panic_threshold(amount, circulating_supply, tax_config.panic_threshold_bps)?
+        {
+            std::cmp::max(final_tax_bps, tax_config.panic_tax_bps)
+        } else {
+            final_tax_bps
+        };
         
         // 限制最大税率为 MAX_TAX_BPS (99%)
         // 这是保护机制，防止极端情况下的异常税率
@@ -252,6 +288,139 @@ impl TaxCalculator {
         })
     }
 
+    /// 不含税模式的税率计算（接收者收到精确金额）
+    /// 
+    /// 与`calculate_tax`相反，调用方指定接收者实际收到的净额，
+    /// 计算器反推发送者需要支付的含税总额。
+    /// 
+    /// ## 计算逻辑
+    /// 
+    /// 大额附加税和恐慌税率取决于含税总额，因此采用迭代：
+    /// 1. 以净额作为初始总额计算税率
+    /// 2. 用`calculate_gross_amount`按该税率反推总额
+    /// 3. 按新总额重新计算税率，直到总额不再变化
+    /// 
+    /// 税率随金额单调不减，总额单调递增，最多经过税率档位数次迭代即收敛。
+    /// 
+    /// ## 返回值
+    /// 
+    /// * `Result<TaxCalculation>` - 按含税总额计算的结果，`net_amount`等于传入的净额
+    /// 
+    /// ## 错误
+    /// 
+    /// * `TotError::MathOverflow` - 含税总额超出u64范围
+    /// * `TotError::TaxCalculationOverflow` - 迭代未收敛（理论上不会发生）
+    pub fn calculate_tax_exclusive(
+        net_amount: u64,
+        holder_info: Option<&HolderAccount>,
+        circulating_supply: u64,
+        current_timestamp: i64,
+        is_buy: bool,
+        is_sell: bool,
+        panic_mode: bool,
+        tax_config: &TaxConfig,
+    ) -> Result<TaxCalculation> {
+        let mut gross_amount = net_amount;
+
+        for _ in 0..MAX_GROSS_UP_ITERATIONS {
+            let calculation = Self::calculate_tax(
+                gross_amount,
+                holder_info,
+                circulating_supply,
+                current_timestamp,
+                is_buy,
+                is_sell,
+                panic_mode,
+                tax_config,
+            )?;
+
+            let required = Self::calculate_gross_amount(net_amount, calculation.final_tax_bps)?;
+            if required == gross_amount {
+                return Ok(calculation);
+            }
+            gross_amount = required;
+        }
+
+        Err(error!(TotError::TaxCalculationOverflow))
+    }
+
+    /// 按固定税率反推含税总额
+    /// 
+    /// 求满足`gross - floor(gross × tax_bps / 10000) >= net_amount`的最小`gross`。
+    /// 
+    /// ## 舍入
+    /// 
+    /// 税额按`calculate_bps`向下取整，因此净额 = ceil(gross × (10000 - tax_bps) / 10000)。
+    /// 由此得到最小总额：
+    /// 
+    /// ```text
+    /// gross = floor((net - 1) × 10000 / (10000 - tax_bps)) + 1    (net > 0)
+    /// ```
+    /// 
+    /// 总额每增加1，净额最多增加1，所以最小总额对应的净额恰好等于`net_amount`，
+    /// 接收者不会多收也不会少收。
+    /// 
+    /// ## 参数
+    /// 
+    /// * `net_amount` - 接收者应收到的净额
+    /// * `tax_bps` - 税率（basis points），不能超过`MAX_TAX_BPS`
+    /// 
+    /// ## 示例
+    /// 
+    /// ```rust
+    /// // 税率2%，接收者收到100
+    /// // gross = floor(99 × 10000 / 9800) + 1 = 102
+    /// // 税额 = floor(102 × 200 / 10000) = 2，净额 = 100
+    /// ```
+    pub fn calculate_gross_amount(net_amount: u64, tax_bps: u16) -> Result<u64> {
+        require!(tax_bps <= tax::MAX_TAX_BPS, TotError::TaxRateExceedsMaximum);
+
+        if net_amount == 0 {
+            return Ok(0);
+        }
+
+        let keep_bps = (BASIS_POINTS - tax_bps as u64) as u128;
+        let gross_amount = ((net_amount - 1) as u128)
+            .checked_mul(BASIS_POINTS as u128)
+            .ok_or(error!(TotError::MathOverflow))?
+            / keep_bps
+            + 1;
+
+        u64::try_from(gross_amount).map_err(|_| error!(TotError::MathOverflow))
+    }
+
+    /// 在已有计算结果上降低最终税率
+    /// 
+    /// 用于积分兑换的税率折扣：最终税率减去`reduction_bps`（不低于0），
+    /// 按新税率重新计算税额和净额。
+    /// 
+    /// * 含税模式（`tax_exclusive = false`）：总额不变，净额增加
+    /// * 不含税模式（`tax_exclusive = true`）：净额不变，总额减少
+    pub fn apply_rate_reduction(
+        calculation: &TaxCalculation,
+        reduction_bps: u16,
+        tax_exclusive: bool,
+    ) -> Result<TaxCalculation> {
+        let final_tax_bps = calculation.final_tax_bps.saturating_sub(reduction_bps);
+
+        let (tax_amount, net_amount) = if tax_exclusive {
+            let net_amount = calculation.net_amount;
+            let gross_amount = Self::calculate_gross_amount(net_amount, final_tax_bps)?;
+            (safe_sub(gross_amount, net_amount)?, net_amount)
+        } else {
+            let gross_amount = calculation.gross_amount()?;
+            let tax_amount = calculate_bps(gross_amount, final_tax_bps)?;
+            (tax_amount, safe_sub(gross_amount, tax_amount)?)
+        };
+
+        Ok(TaxCalculation {
+            final_tax_bps,
+            tax_amount,
+            net_amount,
+            ..calculation.clone()
+        })
+    }
+
     /// 计算持有时间折扣
     /// 
     /// 根据用户的持有时间计算税率折扣。持有时间越长，折扣越大，最终税率越低。
@@ -279,7 +448,7 @@ impl TaxCalculator {
     ///   - `Some(holder)`: 已注册用户，可以计算持有时间
     ///   - `None`: 新用户，无折扣
     /// * `current_timestamp` - 当前Unix时间戳
-    /// * `tax_config` - 税率配置（包含基础税率等参数）
+    /// * `base_tax_bps` - 本次交易的基础税率（买入为买入税率）
     /// 
     /// ## 返回值
     /// 
@@ -304,7 +473,7 @@ impl TaxCalculator {
     fn calculate_holding_discount(
         holder_info: Option<&HolderAccount>,
         current_timestamp: i64,
-        tax_config: &TaxConfig,
+        base_tax_bps: u16,
     ) -> Result<u16> {
         // 如果用户未注册（新用户），无折扣
         let holder = match holder_info {
@@ -343,7 +512,7 @@ impl TaxCalculator {
         // 应用折扣到基础税率
         // 折扣金额 = 基础税率 × 折扣比例 / 10000
         // 例如: 基础税率200 bps，折扣比例50%，折扣金额 = 200 × 5000 / 10000 = 100 bps
-        let discount_amount = calculate_bps(tax_config.base_tax_bps as u64, discount_bps as u16)?;
+        let discount_amount = calculate_bps(base_tax_bps as u64, discount_bps as u16)?;
         
         Ok(discount_amount as u16)
     }
@@ -372,7 +541,7 @@ impl TaxCalculator {
     /// ## 参数
     /// 
     /// * `amount` - 交易金额（基础单位）
-    /// * `total_supply` - 代币总供应量（基础单位）
+    /// * `circulating_supply` - 流通供应量（基础单位，不含池子和财库中锁定的代币）
     /// 
     /// ## 返回值
     /// 
@@ -380,7 +549,7 @@ impl TaxCalculator {
     /// 
     /// ## 计算逻辑
     /// 
-    /// 1. 计算交易占比 = (交易金额 / 总供应量) × 10000 (basis points)
+    /// 1. 计算交易占比 = (交易金额 / 流通供应量) × 10000 (basis points)
     /// 2. 根据占比确定附加税率（分段函数）
     /// 3. 返回附加税率
     /// 
@@ -393,42 +562,31 @@ impl TaxCalculator {
     /// ## 示例
     /// 
     /// ```rust
-    /// // 总供应量: 202.7B
-    /// // 交易金额: 2.027B (1% of supply)
+    /// // 流通供应量: 20.27B
+    /// // 交易金额: 202.7M (1% of circulating supply)
     /// // 交易占比: 1% = 100 basis points
     /// // 附加税率: +3% = 300 basis points
     /// // 
     /// // 如果基础税率是2%，最终税率 = 2% + 3% = 5%
     /// ```
-    fn calculate_whale_tax(amount: u64, total_supply: u64) -> Result<u16> {
-        // 如果总供应量为0，无法计算比例，返回0
-        if total_supply == 0 {
+    fn calculate_whale_tax(amount: u64, circulating_supply: u64) -> Result<u16> {
+        // 如果流通供应量为0，无法计算比例，返回0
+        if circulating_supply == 0 {
             return Ok(0);
         }
 
         // 计算交易占比（basis points）
-        // 公式: 占比 = (交易金额 / 总供应量) × 10000
+        // 公式: 占比 = (交易金额 / 流通供应量) × 10000
         // 使用u128避免溢出
         let ratio_bps_u128 = (amount as u128)
             .checked_mul(10000)
-            .and_then(|v| v.checked_div(total_supply as u128))
+            .and_then(|v| v.checked_div(circulating_supply as u128))
             .ok_or(error!(TotError::MathOverflow))?;
 
         // 安全转换为u16
-        // 理论上ratio_bps最大值为 (u64::MAX * 10000) / 1，远大于u16的最大值65535
-        // 但实际使用中，ratio_bps不应该超过10000（100%），因为不能转账超过总供应量
-        // 如果超过10000，说明数据异常，返回错误
-        // 如果超过u16最大值但小于等于10000，限制为65535（虽然这种情况理论上不会发生）
-        let ratio_bps = if ratio_bps_u128 > 10000 {
-            // 超过100%的交易占比，数据异常
-            return Err(error!(TotError::MathOverflow));
-        } else if ratio_bps_u128 > 65535 {
-            // 虽然理论上不会发生，但为了安全，限制为u16最大值
-            // 这种情况表示交易占比在655.35%到1000%之间，实际不会发生
-            65535u16
-        } else {
-            ratio_bps_u128 as u16
-        };
+        // 锁定供应量在两次刷新之间可能偏高（例如财库转出后尚未刷新），
+        // 此时流通供应量偏低，占比可能超过100%，按最高档处理
+        let ratio_bps = ratio_bps_u128.min(10000) as u16;
 
         // 根据占比确定附加税率（分段函数）
         // 这是对理论公式 (P_impact / L) × α 的简化实现
@@ -457,6 +615,23 @@ impl TaxCalculator {
         Ok(whale_tax_bps)
     }
 
+    /// 判断卖出金额是否达到恐慌税率阈值
+    ///
+    /// 卖出金额占流通供应量的比例（basis points）>= threshold_bps 时返回true。
+    /// 流通供应量为0时不触发。
+    pub fn exceeds_panic_threshold(amount: u64, circulating_supply: u64, threshold_bps: u16) -> Result<bool> {
+        if circulating_supply == 0 {
+            return Ok(false);
+        }
+
+        let ratio_bps = (amount as u128)
+            .checked_mul(BASIS_POINTS as u128)
+            .and_then(|v| v.checked_div(circulating_supply as u128))
+            .ok_or(error!(TotError::MathOverflow))?;
+
+        Ok(ratio_bps >= threshold_bps as u128)
+    }
+
     /// 计算税额
     fn calculate_tax_amount(amount: u64, tax_bps: u16) -> Result<u64> {
         calculate_bps(amount, tax_bps)
@@ -516,11 +691,7 @@ impl TaxDistribution {
             .ok_or(error!(TotError::MathOverflow))?;
         
         // 允许最多1个代币的舍入误差（由于整数除法）
-        let diff = if sum > total_tax {
-            sum - total_tax
-        } else {
-            total_tax - sum
-        };
+        let diff = sum.abs_diff(total_tax);
         
         require!(
             diff <= 1,
@@ -546,6 +717,8 @@ mod tests {
             panic_tax_bps: 3000,
             enabled: true,
             exempt_addresses: vec![],
+            buy_tax_bps: 500, // 5%
+            venue_addresses: vec![],
             last_updated: 0,
             bump: 0,
         }
@@ -561,6 +734,7 @@ mod tests {
             0,
             false,
             true, // 卖出
+            false,
             &tax_config,
         ).unwrap();
 
@@ -582,12 +756,223 @@ mod tests {
             0,
             false,
             true,
+            false,
             &tax_config,
         ).unwrap();
 
         assert_eq!(result.whale_tax_bps, 500); // +5% for > 2%
     }
 
+    #[test]
+    fn test_whale_tax_above_circulating_supply() {
+        // 锁定供应量尚未刷新时，交易金额可能超过记录的流通供应量
+        let tax_config = create_test_tax_config();
+        let circulating_supply = 1_000_000u64;
+
+        let result = TaxCalculator::calculate_tax(
+            circulating_supply * 2,
+            None,
+            circulating_supply,
+            0,
+            false,
+            true,
+            false,
+            &tax_config,
+        ).unwrap();
+
+        assert_eq!(result.whale_tax_bps, 500);
+    }
+
+    #[test]
+    fn test_panic_tax_applies_to_large_sells() {
+        let tax_config = create_test_tax_config();
+        let total_supply = 1_000_000_000_000u64; // 1T
+        let large_amount = total_supply / 100; // 1% >= 0.5% 阈值
+
+        let result = TaxCalculator::calculate_tax(
+            large_amount,
+            None,
+            total_supply,
+            0,
+            false,
+            true,
+            true, // 恐慌模式
+            &tax_config,
+        ).unwrap();
+
+        assert_eq!(result.final_tax_bps, 3000); // 恐慌税率30%
+        assert_eq!(result.tax_amount, large_amount * 3000 / 10000);
+    }
+
+    #[test]
+    fn test_panic_tax_ignores_small_sells_and_transfers() {
+        let tax_config = create_test_tax_config();
+        let total_supply = 1_000_000_000_000u64;
+
+        // 低于阈值的卖出不触发恐慌税率
+        let small_sell = TaxCalculator::calculate_tax(
+            1_000_000, None, total_supply, 0, false, true, true, &tax_config,
+        ).unwrap();
+        assert_eq!(small_sell.final_tax_bps, 200);
+
+        // 非卖出转账不触发恐慌税率
+        let transfer = TaxCalculator::calculate_tax(
+            total_supply / 100, None, total_supply, 0, false, false, true, &tax_config,
+        ).unwrap();
+        assert_eq!(transfer.final_tax_bps, 200);
+    }
+
+    #[test]
+    fn test_gross_amount_rounding() {
+        // 2%: 102 - floor(102 × 2%) = 100
+        assert_eq!(TaxCalculator::calculate_gross_amount(100, 200).unwrap(), 102);
+        assert_eq!(TaxCalculator::calculate_gross_amount(0, 200).unwrap(), 0);
+        assert_eq!(TaxCalculator::calculate_gross_amount(1, 200).unwrap(), 1);
+        assert_eq!(TaxCalculator::calculate_gross_amount(500, 0).unwrap(), 500);
+
+        // 对每个净额验证: 恰好得到净额，且是最小的总额
+        for tax_bps in [1u16, 150, 200, 333, 3000, 9900] {
+            for net in 1..2_000u64 {
+                let gross = TaxCalculator::calculate_gross_amount(net, tax_bps).unwrap();
+                let (after, _) = calculate_amount_after_tax(gross, tax_bps).unwrap();
+                assert_eq!(after, net);
+                let (before, _) = calculate_amount_after_tax(gross - 1, tax_bps).unwrap();
+                assert!(before < net);
+            }
+        }
+    }
+
+    fn create_test_holder(first_hold_time: i64) -> HolderAccount {
+        HolderAccount {
+            owner: Pubkey::default(),
+            token_account: Pubkey::default(),
+            first_hold_time,
+            last_transaction_time: first_hold_time,
+            weighted_hold_days: 0,
+            total_bought: 0,
+            total_sold: 0,
+            total_tax_paid: 0,
+            total_consumed: 0,
+            is_frozen: false,
+            freeze_reason: 0,
+            frozen_at: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_buy_tax_uses_buy_rate() {
+        let tax_config = create_test_tax_config();
+        let total_supply = 1_000_000_000_000u64;
+
+        let buy = TaxCalculator::calculate_tax(
+            1_000_000, None, total_supply, 0, true, false, false, &tax_config,
+        ).unwrap();
+        assert_eq!(buy.base_tax_bps, 500);
+        assert_eq!(buy.final_tax_bps, 500);
+        assert_eq!(buy.tax_amount, 50_000);
+
+        // 大额买入同样收取附加税，但不触发恐慌税率
+        let whale_buy = TaxCalculator::calculate_tax(
+            total_supply * 3 / 100, None, total_supply, 0, true, false, true, &tax_config,
+        ).unwrap();
+        assert_eq!(whale_buy.whale_tax_bps, 500);
+        assert_eq!(whale_buy.final_tax_bps, 1000);
+    }
+
+    #[test]
+    fn test_buy_discount_scales_with_buy_rate() {
+        let tax_config = create_test_tax_config();
+        let now = 200 * 86400;
+        let holder = create_test_holder(0); // 持有200天，50%折扣
+
+        let buy = TaxCalculator::calculate_tax(
+            1_000_000, Some(&holder), 1_000_000_000_000, now, true, false, false, &tax_config,
+        ).unwrap();
+        assert_eq!(buy.holding_discount_bps, 250);
+        assert_eq!(buy.final_tax_bps, 250);
+
+        let transfer = TaxCalculator::calculate_tax(
+            1_000_000, Some(&holder), 1_000_000_000_000, now, false, false, false, &tax_config,
+        ).unwrap();
+        assert_eq!(transfer.holding_discount_bps, 100);
+        assert_eq!(transfer.final_tax_bps, 100);
+    }
+
+    #[test]
+    fn test_gross_amount_respects_max_tax() {
+        // 99%: 接收者收到1需要支付100
+        assert_eq!(TaxCalculator::calculate_gross_amount(1, 9900).unwrap(), 1);
+        assert_eq!(TaxCalculator::calculate_gross_amount(2, 9900).unwrap(), 101);
+        assert!(TaxCalculator::calculate_gross_amount(100, 9901).is_err());
+        assert!(TaxCalculator::calculate_gross_amount(u64::MAX, 200).is_err());
+    }
+
+    #[test]
+    fn test_tax_exclusive_transfer() {
+        let tax_config = create_test_tax_config();
+        let total_supply = 1_000_000_000_000u64;
+
+        let result = TaxCalculator::calculate_tax_exclusive(
+            100_000_000, None, total_supply, 0, false, false, false, &tax_config,
+        ).unwrap();
+
+        assert_eq!(result.net_amount, 100_000_000);
+        assert_eq!(result.final_tax_bps, 200);
+        assert_eq!(result.gross_amount().unwrap(), 102_040_816);
+    }
+
+    #[test]
+    fn test_tax_exclusive_crosses_whale_tier() {
+        let tax_config = create_test_tax_config();
+        let total_supply = 1_000_000_000_000u64;
+        // 净额刚好低于0.1%，加税后总额跨过0.1%，附加税+1%
+        let net = total_supply / 1000 - 1_000_000;
+
+        let result = TaxCalculator::calculate_tax_exclusive(
+            net, None, total_supply, 0, false, true, false, &tax_config,
+        ).unwrap();
+
+        assert_eq!(result.net_amount, net);
+        assert_eq!(result.whale_tax_bps, 100);
+        assert_eq!(result.final_tax_bps, 300);
+
+        // 按含税总额重新计算得到相同结果
+        let check = TaxCalculator::calculate_tax(
+            result.gross_amount().unwrap(), None, total_supply, 0, false, true, false, &tax_config,
+        ).unwrap();
+        assert_eq!(check.net_amount, net);
+    }
+
+    #[test]
+    fn test_apply_rate_reduction() {
+        let tax_config = create_test_tax_config();
+        let total_supply = 1_000_000_000_000u64;
+
+        // 含税模式：总额不变，2%降到1.5%
+        let result = TaxCalculator::calculate_tax(
+            1_000_000, None, total_supply, 0, false, false, false, &tax_config,
+        ).unwrap();
+        let reduced = TaxCalculator::apply_rate_reduction(&result, 50, false).unwrap();
+        assert_eq!(reduced.final_tax_bps, 150);
+        assert_eq!(reduced.tax_amount, 15_000);
+        assert_eq!(reduced.gross_amount().unwrap(), 1_000_000);
+
+        // 不含税模式：净额不变
+        let result = TaxCalculator::calculate_tax_exclusive(
+            100_000_000, None, total_supply, 0, false, false, false, &tax_config,
+        ).unwrap();
+        let reduced = TaxCalculator::apply_rate_reduction(&result, 50, true).unwrap();
+        assert_eq!(reduced.net_amount, 100_000_000);
+        assert_eq!(reduced.final_tax_bps, 150);
+        assert!(reduced.gross_amount().unwrap() < result.gross_amount().unwrap());
+
+        // 折扣超过税率时免税
+        let reduced = TaxCalculator::apply_rate_reduction(&result, 500, true).unwrap();
+        assert_eq!(reduced.final_tax_bps, 0);
+        assert_eq!(reduced.tax_amount, 0);
+    }
+
     #[test]
     fn test_tax_distribution() {
         let total_tax = 1000u64;
@@ -0,0 +1,176 @@
+//! # Token-2022转账CPI模块
+//!
+//! 本模块封装主程序发起的`transfer_checked` CPI。
+//!
+//! ## 背景
+//!
+//! TOT Mint带有Transfer Hook扩展，Token-2022在转账时会CPI调用Hook程序，
+//! Hook需要的额外账户（Extra Account Meta List及其中登记的账户）必须出现在
+//! 转账指令中。anchor-spl的`transfer_checked`只传递四个固定账户，
+//! 对TOT转账必然失败，因此主程序的所有转账都通过这里发起。
+//!
+//! 调用方把Hook需要的账户放在指令的`remaining_accounts`中原样传入，
+//! 这里按地址从中查找，顺序无关。Mint没有Hook时不做额外处理。
+//!
+//! 转账CPI期间置位`TransferGuard`（账户已初始化时），Hook据此识别主程序发起的转账，
+//! 不再回调主程序更新持有者统计（主程序指令自行维护，且运行时禁止重入）。
+//!
+//! ## 为什么不直接使用`spl_token_2022::onchain::invoke_transfer_checked`
+//!
+//! spl-transfer-hook-interface 0.3（anchor-spl 0.29对应的版本）在只含转账四个账户的
+//! 列表上解析额外账户，账户索引比Execute指令少一位（缺少extra_account_meta_list）。
+//! Hook登记的主程序PDA以账户索引指定派生程序，按错位的索引会解析出错误地址。
+//! 这里先按Execute指令的账户顺序解析，再把结果追加到转账指令。
+//!
+//! ============================================
+// 文件: src/utils/token_transfer.rs
+// Token-2022转账CPI（携带Transfer Hook额外账户）
+// ============================================
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::{AccountMeta, Instruction}, program::invoke_signed};
+use anchor_spl::token_interface::TransferChecked;
+use spl_tlv_account_resolution::state::ExtraAccountMetaList;
+use spl_token_2022::extension::{transfer_hook, StateWithExtensions};
+use spl_transfer_hook_interface::{
+    get_extra_account_metas_address,
+    instruction::{execute, ExecuteInstruction},
+};
+
+use crate::errors::TotError;
+use crate::state::transfer_guard::TransferGuard;
+
+/// Execute指令中接口固定账户的数量
+///
+/// source、mint、destination、authority、extra_account_meta_list
+const EXECUTE_FIXED_ACCOUNTS: usize = 5;
+
+/// 携带Transfer Hook额外账户的`transfer_checked`
+///
+/// 与`anchor_spl::token_interface::transfer_checked`的参数相同，
+/// 额外接收Hook需要的账户。
+///
+/// # 参数
+/// * `ctx` - 转账CPI上下文（签名种子通过`with_signer`传入）
+/// * `hook_accounts` - Transfer Hook需要的账户（通常为指令的`remaining_accounts`）
+/// * `amount` - 转账金额
+/// * `decimals` - Mint精度
+///
+/// # 错误
+/// * `TotError::TransferHookFailed` - Mint带有Hook但`hook_accounts`缺少Hook程序或Extra Account Meta List
+pub fn transfer_checked_with_hook<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, TransferChecked<'info>>,
+    hook_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    let TransferChecked { from, mint, to, authority } = ctx.accounts;
+
+    let mut instruction = spl_token_2022::instruction::transfer_checked(
+        ctx.program.key,
+        from.key,
+        mint.key,
+        to.key,
+        authority.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+
+    let hook_program_id = {
+        let mint_data = mint.try_borrow_data()?;
+        let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+        transfer_hook::get_program_id(&mint_state)
+    };
+
+    let mut account_infos = vec![from, mint, to, authority];
+    let mut transfer_guard = None;
+    if let Some(hook_program_id) = hook_program_id {
+        add_hook_accounts(
+            &mut instruction,
+            &mut account_infos,
+            &hook_program_id,
+            hook_accounts,
+            amount,
+        )?;
+        transfer_guard = find_transfer_guard(hook_accounts)?;
+    }
+
+    if let Some(guard) = transfer_guard {
+        TransferGuard::set_in_progress(guard, true)?;
+    }
+    invoke_signed(&instruction, &account_infos, ctx.signer_seeds)?;
+    if let Some(guard) = transfer_guard {
+        TransferGuard::set_in_progress(guard, false)?;
+    }
+
+    Ok(())
+}
+
+/// 从Hook账户中查找已初始化的转账守卫
+///
+/// 守卫只能在其PDA地址初始化，按所有者和discriminator识别即可，无需重新派生地址。
+fn find_transfer_guard<'a, 'info>(
+    hook_accounts: &'a [AccountInfo<'info>],
+) -> Result<Option<&'a AccountInfo<'info>>> {
+    for info in hook_accounts {
+        if TransferGuard::load_optional(info, &crate::ID)?.is_some() {
+            return Ok(Some(info));
+        }
+    }
+    Ok(None)
+}
+
+/// 解析Hook的额外账户并追加到转账指令
+///
+/// `account_infos`为转账指令的四个固定账户。追加顺序为：额外账户、
+/// extra_account_meta_list、Hook程序，Token-2022按地址查找，顺序无关。
+fn add_hook_accounts<'info>(
+    instruction: &mut Instruction,
+    account_infos: &mut Vec<AccountInfo<'info>>,
+    hook_program_id: &Pubkey,
+    hook_accounts: &[AccountInfo<'info>],
+    amount: u64,
+) -> Result<()> {
+    let find = |key: &Pubkey| {
+        hook_accounts
+            .iter()
+            .find(|info| info.key == key)
+            .ok_or(error!(TotError::TransferHookFailed))
+    };
+
+    let validation_key = get_extra_account_metas_address(account_infos[1].key, hook_program_id);
+    let validation_info = find(&validation_key)?;
+    let program_info = find(hook_program_id)?;
+
+    // 按Token-2022调用Hook时的Execute指令解析额外账户，保证账户索引一致
+    let mut execute_instruction = execute(
+        hook_program_id,
+        account_infos[0].key,
+        account_infos[1].key,
+        account_infos[2].key,
+        account_infos[3].key,
+        &validation_key,
+        amount,
+    );
+    let mut execute_infos = account_infos.clone();
+    execute_infos.push(validation_info.clone());
+    ExtraAccountMetaList::add_to_cpi_instruction::<ExecuteInstruction>(
+        &mut execute_instruction,
+        &mut execute_infos,
+        &validation_info.try_borrow_data()?,
+        hook_accounts,
+    )?;
+
+    instruction
+        .accounts
+        .extend_from_slice(&execute_instruction.accounts[EXECUTE_FIXED_ACCOUNTS..]);
+    account_infos.extend_from_slice(&execute_infos[EXECUTE_FIXED_ACCOUNTS..]);
+
+    instruction.accounts.push(AccountMeta::new_readonly(validation_key, false));
+    account_infos.push(validation_info.clone());
+    instruction.accounts.push(AccountMeta::new_readonly(*hook_program_id, false));
+    account_infos.push(program_info.clone());
+
+    Ok(())
+}
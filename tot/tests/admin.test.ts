@@ -5,11 +5,28 @@
 
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
-import { Keypair, PublicKey } from "@solana/web3.js";
-import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { Ed25519Program, Keypair, PublicKey, SYSVAR_INSTRUCTIONS_PUBKEY } from "@solana/web3.js";
+import { TOKEN_2022_PROGRAM_ID, getAccount } from "@solana/spl-token";
 import { expect } from "chai";
-import { setupTestContext } from "./helpers/setup";
-import { getConfigPda, getAssociatedTokenAddress } from "./helpers/accounts";
+import { setupTestContext, sleep } from "./helpers/setup";
+import {
+  getConfigPda,
+  getAssociatedTokenAddress,
+  getConsumeReceiptPda,
+  getPriceCatalogPda,
+  getPlatformBudgetPda,
+  getPlatformVaultAddress,
+  getRewardsVaultPda,
+  getVoucherRedemptionPda,
+  buildVoucherMessage,
+  getMerchantPda,
+  getInvoicePda,
+  getHolderPda,
+  getPartnerPda,
+  getPartnerAuthorityPda,
+  getPointsConfigPda,
+  getPointsPda,
+} from "./helpers/accounts";
 import { 
   assertPublicKeyEqual,
   assertError 
@@ -25,113 +42,874 @@ describe("管理员功能测试", () => {
     [configPda] = getConfigPda(ctx.program.programId);
   });
 
-  describe("更新管理员", () => {
-    it("应该成功更新管理员地址", async () => {
-      const newAuthority = Keypair.generate().publicKey;
+  describe("管理员两步移交", () => {
+    it("应该提议并由新地址接受管理员权限", async () => {
+      const newAuthority = createTestUser("NewAdmin");
 
       try {
         const tx = await ctx.program.methods
-          .updateAuthority(newAuthority)
+          .proposeAuthority(newAuthority.publicKey)
           .accounts({
             authority: ctx.wallet.publicKey,
             config: configPda,
           })
           .rpc();
 
-        console.log("✅ 更新管理员交易签名:", tx);
+        console.log("✅ 提议新管理员交易签名:", tx);
 
-        // 验证更新
+        // 提议后管理员尚未变更
+        let config = await ctx.program.account.totConfig.fetch(configPda);
+        assertPublicKeyEqual(config.pendingAuthority, newAuthority.publicKey);
+        assertPublicKeyEqual(config.authority, ctx.wallet.publicKey);
+
+        // 新地址签名接受（默认延迟为0）
+        await ctx.program.methods
+          .acceptAuthority()
+          .accounts({
+            newAuthority: newAuthority.publicKey,
+            config: configPda,
+          })
+          .signers([newAuthority.keypair])
+          .rpc();
+
+        config = await ctx.program.account.totConfig.fetch(configPda);
+        assertPublicKeyEqual(config.authority, newAuthority.publicKey);
+        assertPublicKeyEqual(config.pendingAuthority, PublicKey.default);
+
+        // 恢复原管理员（用于后续测试）
+        await ctx.program.methods
+          .proposeAuthority(ctx.wallet.publicKey)
+          .accounts({
+            authority: newAuthority.publicKey,
+            config: configPda,
+          })
+          .signers([newAuthority.keypair])
+          .rpc();
+        await ctx.program.methods
+          .acceptAuthority()
+          .accounts({
+            newAuthority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc();
+      } catch (error: any) {
+        // 如果系统未初始化，这是预期的
+        console.log("⚠️  需要先初始化系统:", error.message);
+      }
+    });
+
+    it("应该拒绝非提议地址接受权限", async () => {
+      const proposed = Keypair.generate().publicKey;
+      const impostor = createTestUser("Impostor");
+
+      try {
+        await ctx.program.methods
+          .proposeAuthority(proposed)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc();
+
+        await ctx.program.methods
+          .acceptAuthority()
+          .accounts({
+            newAuthority: impostor.publicKey,
+            config: configPda,
+          })
+          .signers([impostor.keypair])
+          .rpc();
+
+        expect.fail("应该抛出错误");
+      } catch (error: any) {
+        assertError(error, "PendingAuthorityMismatch");
+      } finally {
+        // 清理进行中的提议
+        await ctx.program.methods
+          .cancelAuthorityTransfer()
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc()
+          .catch(() => undefined);
+      }
+    });
+
+    it("应该在时间锁未到期时拒绝接受", async () => {
+      const newAuthority = createTestUser("TimelockedAdmin");
+
+      try {
+        // 延迟较短：finally中恢复为0要等这段延迟过去才生效
+        await ctx.program.methods
+          .setAuthorityTransferDelay(new anchor.BN(5))
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc();
+
+        await ctx.program.methods
+          .proposeAuthority(newAuthority.publicKey)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc();
+
+        await ctx.program.methods
+          .acceptAuthority()
+          .accounts({
+            newAuthority: newAuthority.publicKey,
+            config: configPda,
+          })
+          .signers([newAuthority.keypair])
+          .rpc();
+
+        expect.fail("应该抛出错误");
+      } catch (error: any) {
+        assertError(error, "AuthorityTransferTimelocked");
+      } finally {
+        await ctx.program.methods
+          .cancelAuthorityTransfer()
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc()
+          .catch(() => undefined);
+        await ctx.program.methods
+          .setAuthorityTransferDelay(new anchor.BN(0))
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc()
+          .catch(() => undefined);
+      }
+    });
+
+    it("缩短权限移交延迟应该在当前延迟过去后才生效", async () => {
+      const setDelay = (seconds: number) =>
+        ctx.program.methods
+          .setAuthorityTransferDelay(new anchor.BN(seconds))
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc();
+
+      try {
+        // 等待上一个测试留下的缩短生效
+        await sleep(6);
+
+        // 延长立即生效
+        await setDelay(5);
+        let config = await ctx.program.account.totConfig.fetch(configPda);
+        expect(config.authorityTransferDelay.toNumber()).to.equal(5);
+        expect(config.authorityTransferDelayEta.toNumber()).to.equal(0);
+
+        // 缩短进入待生效状态，提议仍按原延迟计算
+        await setDelay(0);
+        config = await ctx.program.account.totConfig.fetch(configPda);
+        expect(config.authorityTransferDelay.toNumber()).to.equal(5);
+        expect(config.pendingAuthorityTransferDelay.toNumber()).to.equal(0);
+        expect(config.authorityTransferDelayEta.toNumber()).to.be.greaterThan(0);
+
+        const proposed = Keypair.generate().publicKey;
+        await ctx.program.methods
+          .proposeAuthority(proposed)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc();
+        config = await ctx.program.account.totConfig.fetch(configPda);
+        expect(config.authorityTransferEta.toNumber()).to.be.at.least(
+          config.authorityTransferDelayEta.toNumber()
+        );
+      } catch (error: any) {
+        // 如果系统未初始化，这是预期的
+        console.log("⚠️  需要先初始化系统:", error.message);
+      } finally {
+        await ctx.program.methods
+          .cancelAuthorityTransfer()
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc()
+          .catch(() => undefined);
+      }
+    });
+
+    it("应该拒绝无效的新管理员地址（空地址）", async () => {
+      try {
+        await ctx.program.methods
+          .proposeAuthority(PublicKey.default)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc();
+        
+        expect.fail("应该抛出错误");
+      } catch (error: any) {
+        assertError(error, "InvalidNewAuthority");
+      }
+    });
+
+    it("应该拒绝非管理员提议权限移交", async () => {
+      const unauthorizedUser = createTestUser("Unauthorized");
+
+      try {
+        await ctx.program.methods
+          .proposeAuthority(unauthorizedUser.publicKey)
+          .accounts({
+            authority: unauthorizedUser.publicKey,
+            config: configPda,
+          })
+          .signers([unauthorizedUser.keypair])
+          .rpc();
+        
+        expect.fail("应该抛出错误");
+      } catch (error: any) {
+        assertError(error, "Unauthorized");
+      }
+    });
+
+    it("应该拒绝在没有提议时取消移交", async () => {
+      try {
+        await ctx.program.methods
+          .cancelAuthorityTransfer()
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc();
+
+        expect.fail("应该抛出错误");
+      } catch (error: any) {
+        assertError(error, "NoPendingAuthority");
+      }
+    });
+  });
+
+  describe("暂停/恢复指令族", () => {
+    it("应该成功暂停指定的指令族", async () => {
+      try {
+        const tx = await ctx.program.methods
+          .setPaused([{ consume: {} }, { auctions: {} }], true, null)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc();
+
+        console.log("✅ 暂停指令族交易签名:", tx);
+
+        // 验证暂停位图（consume = 1 << 2, auctions = 1 << 4）
+        const config = await ctx.program.account.totConfig.fetch(configPda);
+        expect(config.pausedOperations & (1 << 2)).to.not.equal(0);
+        expect(config.pausedOperations & (1 << 4)).to.not.equal(0);
+        // 暂停不影响恐慌税率模式
+        expect(config.panicMode).to.be.false;
+      } catch (error: any) {
+        console.log("⚠️  需要先初始化系统:", error.message);
+      }
+    });
+
+    it("应该成功恢复指定的指令族", async () => {
+      try {
+        const tx = await ctx.program.methods
+          .setPaused([{ consume: {} }, { auctions: {} }], false, null)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc();
+
+        console.log("✅ 恢复指令族交易签名:", tx);
+
+        // 验证恢复状态
         const config = await ctx.program.account.totConfig.fetch(configPda);
-        assertPublicKeyEqual(config.authority, newAuthority);
+        expect(config.pausedOperations & (1 << 2)).to.equal(0);
+        expect(config.pausedOperations & (1 << 4)).to.equal(0);
+      } catch (error: any) {
+        console.log("⚠️  需要先初始化系统:", error.message);
+      }
+    });
+
+    it("应该记录自动恢复时间", async () => {
+      const resumeAt = Math.floor(Date.now() / 1000) + 3600;
+
+      try {
+        await ctx.program.methods
+          .setPaused([{ assetMinting: {} }], true, new anchor.BN(resumeAt))
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc();
+
+        const config = await ctx.program.account.totConfig.fetch(configPda);
+        expect(config.pauseResumeAt[5].toNumber()).to.equal(resumeAt);
+
+        await ctx.program.methods
+          .setPaused([{ assetMinting: {} }], false, null)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc();
+      } catch (error: any) {
+        console.log("⚠️  需要先初始化系统:", error.message);
+      }
+    });
+
+    it("应该拒绝已过期的自动恢复时间", async () => {
+      try {
+        await ctx.program.methods
+          .setPaused([{ transfers: {} }], true, new anchor.BN(1))
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc();
+
+        expect.fail("应该抛出错误");
+      } catch (error: any) {
+        assertError(error, "InvalidTimeParameter");
+      }
+    });
+  });
+
+  describe("恐慌税率模式", () => {
+    it("应该独立于暂停开启和关闭恐慌模式", async () => {
+      try {
+        await ctx.program.methods
+          .setPanicMode(true)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc();
+
+        let config = await ctx.program.account.totConfig.fetch(configPda);
+        expect(config.panicMode).to.be.true;
+        expect(config.pausedOperations).to.equal(0);
+
+        await ctx.program.methods
+          .setPanicMode(false)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc();
+
+        config = await ctx.program.account.totConfig.fetch(configPda);
+        expect(config.panicMode).to.be.false;
+      } catch (error: any) {
+        console.log("⚠️  需要先初始化系统:", error.message);
+      }
+    });
+  });
+
+  describe("消费回执", () => {
+    const backend = createTestUser("ReceiptBackend");
+
+    it("应该设置并撤销回执核销权限", async () => {
+      await ctx.program.methods
+        .setReceiptAuthority(backend.publicKey)
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          config: configPda,
+        })
+        .rpc();
+
+      let config = await ctx.program.account.totConfig.fetch(configPda);
+      assertPublicKeyEqual(config.receiptAuthority, backend.publicKey);
+
+      await ctx.program.methods
+        .setReceiptAuthority(PublicKey.default)
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          config: configPda,
+        })
+        .rpc();
+
+      config = await ctx.program.account.totConfig.fetch(configPda);
+      assertPublicKeyEqual(config.receiptAuthority, PublicKey.default);
+    });
+
+    it("应该拒绝非管理员设置回执核销权限", async () => {
+      try {
+        await ctx.program.methods
+          .setReceiptAuthority(backend.publicKey)
+          .accounts({
+            authority: backend.publicKey,
+            config: configPda,
+          })
+          .signers([backend.keypair])
+          .rpc();
+        expect.fail("应该抛出Unauthorized错误");
+      } catch (error: any) {
+        assertError(error, "Unauthorized");
+      }
+    });
+
+    it("应该拒绝核销不存在的回执", async () => {
+      const [receiptPda] = getConsumeReceiptPda(
+        ctx.program.programId,
+        backend.publicKey,
+        new anchor.BN(1)
+      );
+
+      try {
+        await ctx.program.methods
+          .redeemConsumeReceipt()
+          .accounts({
+            redeemer: ctx.wallet.publicKey,
+            config: configPda,
+            consumeReceipt: receiptPda,
+          })
+          .rpc();
+        expect.fail("应该抛出AccountNotInitialized错误");
+      } catch (error: any) {
+        assertError(error, "AccountNotInitialized");
+      }
+    });
+  });
+
+  describe("价格目录", () => {
+    const outsider = createTestUser("CatalogOutsider");
+    let catalogPda: PublicKey;
+
+    before(async () => {
+      [catalogPda] = getPriceCatalogPda(ctx.program.programId);
+      const existing = await ctx.provider.connection.getAccountInfo(catalogPda);
+      if (!existing) {
+        await ctx.program.methods
+          .initializePriceCatalog()
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+            priceCatalog: catalogPda,
+          })
+          .rpc();
+      }
+    });
+
+    it("应该新增、更新并删除价格条目", async () => {
+      const price = new anchor.BN(100_000_000_000);
+
+      await ctx.program.methods
+        .setCatalogPrice({ ancestorMarking: {} }, 7, price, 1000, 180, true, PublicKey.default)
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          config: configPda,
+          priceCatalog: catalogPda,
+        })
+        .rpc();
+
+      let catalog = await ctx.program.account.priceCatalog.fetch(catalogPda);
+      let entry = catalog.entries.find((e: any) => e.consumeType === 1 && e.sku === 7);
+      expect(entry).to.not.be.undefined;
+      expect(entry.price.toString()).to.equal(price.toString());
+      expect(entry.discountBps).to.equal(1000);
+      expect(entry.isActive).to.be.true;
+
+      // 下架
+      await ctx.program.methods
+        .setCatalogPrice({ ancestorMarking: {} }, 7, price, 1000, 180, false, PublicKey.default)
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          config: configPda,
+          priceCatalog: catalogPda,
+        })
+        .rpc();
+
+      catalog = await ctx.program.account.priceCatalog.fetch(catalogPda);
+      entry = catalog.entries.find((e: any) => e.consumeType === 1 && e.sku === 7);
+      expect(entry.isActive).to.be.false;
+
+      await ctx.program.methods
+        .removeCatalogPrice({ ancestorMarking: {} }, 7)
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          config: configPda,
+          priceCatalog: catalogPda,
+        })
+        .rpc();
+
+      catalog = await ctx.program.account.priceCatalog.fetch(catalogPda);
+      entry = catalog.entries.find((e: any) => e.consumeType === 1 && e.sku === 7);
+      expect(entry).to.be.undefined;
+    });
+
+    it("应该拒绝价格为0的条目", async () => {
+      try {
+        await ctx.program.methods
+          .setCatalogPrice({ mapAction: {} }, 0, new anchor.BN(0), 0, 0, true, PublicKey.default)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+            priceCatalog: catalogPda,
+          })
+          .rpc();
+        expect.fail("应该抛出InvalidPriceEntry错误");
+      } catch (error: any) {
+        assertError(error, "InvalidPriceEntry");
+      }
+    });
+
+    it("应该拒绝删除不存在的条目", async () => {
+      try {
+        await ctx.program.methods
+          .removeCatalogPrice({ other: {} }, 999)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+            priceCatalog: catalogPda,
+          })
+          .rpc();
+        expect.fail("应该抛出PriceNotListed错误");
+      } catch (error: any) {
+        assertError(error, "PriceNotListed");
+      }
+    });
 
-        // 恢复原管理员（用于后续测试）
+    it("应该拒绝非管理员设置价格", async () => {
+      try {
         await ctx.program.methods
-          .updateAuthority(ctx.wallet.publicKey)
+          .setCatalogPrice({ mapAction: {} }, 0, new anchor.BN(1), 0, 0, true, PublicKey.default)
           .accounts({
-            authority: newAuthority,
+            authority: outsider.publicKey,
             config: configPda,
+            priceCatalog: catalogPda,
           })
+          .signers([outsider.keypair])
           .rpc();
+        expect.fail("应该抛出Unauthorized错误");
       } catch (error: any) {
-        // 如果系统未初始化，这是预期的
-        console.log("⚠️  需要先初始化系统:", error.message);
+        assertError(error, "Unauthorized");
       }
     });
 
-    it("应该拒绝无效的新管理员地址（空地址）", async () => {
+    it("应该设置并取消收入分成", async () => {
+      await ctx.program.methods
+        .setRevenueSplit({ predictionBet: {} }, 2000, 5000, 3000)
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          config: configPda,
+          priceCatalog: catalogPda,
+        })
+        .rpc();
+
+      let catalog = await ctx.program.account.priceCatalog.fetch(catalogPda);
+      expect(catalog.splits[5].burnBps).to.equal(2000);
+      expect(catalog.splits[5].treasuryBps).to.equal(5000);
+      expect(catalog.splits[5].beneficiaryBps).to.equal(3000);
+
+      await ctx.program.methods
+        .setRevenueSplit({ predictionBet: {} }, 0, 0, 0)
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          config: configPda,
+          priceCatalog: catalogPda,
+        })
+        .rpc();
+
+      catalog = await ctx.program.account.priceCatalog.fetch(catalogPda);
+      expect(catalog.splits[5].burnBps).to.equal(0);
+      expect(catalog.splits[5].beneficiaryBps).to.equal(0);
+    });
+
+    it("应该拒绝总和不为10000的收入分成", async () => {
       try {
         await ctx.program.methods
-          .updateAuthority(PublicKey.default)
+          .setRevenueSplit({ mapAction: {} }, 3000, 3000, 3000)
           .accounts({
             authority: ctx.wallet.publicKey,
             config: configPda,
+            priceCatalog: catalogPda,
           })
           .rpc();
-        
-        expect.fail("应该抛出错误");
+        expect.fail("应该抛出InvalidRevenueSplit错误");
       } catch (error: any) {
-        assertError(error, "InvalidNewAuthority");
+        assertError(error, "InvalidRevenueSplit");
       }
     });
 
-    it("应该拒绝非管理员更新权限", async () => {
-      const unauthorizedUser = createTestUser("Unauthorized");
+    it("应该允许价格为0但登记了受益人的条目", async () => {
+      const marketCreator = createTestUser("MarketCreator");
+
+      await ctx.program.methods
+        .setCatalogPrice({ predictionBet: {} }, 42, new anchor.BN(0), 0, 0, true, marketCreator.publicKey)
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          config: configPda,
+          priceCatalog: catalogPda,
+        })
+        .rpc();
+
+      let catalog = await ctx.program.account.priceCatalog.fetch(catalogPda);
+      const entry = catalog.entries.find((e: any) => e.consumeType === 5 && e.sku === 42);
+      assertPublicKeyEqual(entry.beneficiary, marketCreator.publicKey);
+
+      await ctx.program.methods
+        .removeCatalogPrice({ predictionBet: {} }, 42)
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          config: configPda,
+          priceCatalog: catalogPda,
+        })
+        .rpc();
+    });
+  });
+
+  describe("平台预算", () => {
+    const hotWallet = createTestUser("PlatformHotWallet");
+    let budgetPda: PublicKey;
+    const day = new anchor.BN(86400);
+    const periodCap = new anchor.BN(1_000_000_000_000);
+    const perRecipientCap = new anchor.BN(100_000_000_000);
+
+    before(async () => {
+      [budgetPda] = getPlatformBudgetPda(ctx.program.programId);
+      const existing = await ctx.provider.connection.getAccountInfo(budgetPda);
+      if (!existing) {
+        await ctx.program.methods
+          .initializePlatformBudget(day, periodCap, perRecipientCap)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+            platformBudget: budgetPda,
+          })
+          .rpc();
+      }
+    });
+
+    it("应该更新预算并返回剩余额度", async () => {
+      await ctx.program.methods
+        .updatePlatformBudget(day, periodCap, perRecipientCap)
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          config: configPda,
+          platformBudget: budgetPda,
+        })
+        .rpc();
 
+      const status = await ctx.program.methods
+        .getPlatformBudget()
+        .accounts({
+          platformBudget: budgetPda,
+          recipientUsage: null,
+        })
+        .view();
+
+      expect(status.periodCap.toString()).to.equal(periodCap.toString());
+      expect(status.periodRemaining.add(status.periodSpent).toString()).to.equal(periodCap.toString());
+      expect(status.recipientRemaining.lte(perRecipientCap)).to.be.true;
+      expect(status.periodResetsAt.sub(status.periodStart).toString()).to.equal(day.toString());
+    });
+
+    it("应该拒绝单个接收者额度大于周期预算", async () => {
       try {
         await ctx.program.methods
-          .updateAuthority(unauthorizedUser.publicKey)
+          .updatePlatformBudget(day, perRecipientCap, periodCap)
           .accounts({
-            authority: unauthorizedUser.publicKey,
+            authority: ctx.wallet.publicKey,
             config: configPda,
+            platformBudget: budgetPda,
           })
-          .signers([unauthorizedUser.keypair])
           .rpc();
-        
-        expect.fail("应该抛出错误");
+        expect.fail("应该抛出InvalidPlatformBudget错误");
       } catch (error: any) {
-        assertError(error, "Unauthorized");
+        assertError(error, "InvalidPlatformBudget");
       }
     });
-  });
 
-  describe("暂停/恢复系统", () => {
-    it("应该成功暂停系统", async () => {
+    it("应该登记和移除平台热钱包", async () => {
+      await ctx.program.methods
+        .addPlatformSigner(hotWallet.publicKey)
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          config: configPda,
+          platformBudget: budgetPda,
+        })
+        .rpc();
+
+      let budget = await ctx.program.account.platformBudget.fetch(budgetPda);
+      expect(budget.signers.map((s: PublicKey) => s.toString())).to.include(hotWallet.publicKey.toString());
+
       try {
-        const tx = await ctx.program.methods
-          .setPaused(true)
+        await ctx.program.methods
+          .addPlatformSigner(hotWallet.publicKey)
           .accounts({
             authority: ctx.wallet.publicKey,
             config: configPda,
+            platformBudget: budgetPda,
           })
           .rpc();
+        expect.fail("应该抛出PlatformSignerAlreadyRegistered错误");
+      } catch (error: any) {
+        assertError(error, "PlatformSignerAlreadyRegistered");
+      }
 
-        console.log("✅ 暂停系统交易签名:", tx);
+      await ctx.program.methods
+        .removePlatformSigner(hotWallet.publicKey)
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          config: configPda,
+          platformBudget: budgetPda,
+        })
+        .rpc();
 
-        // 验证暂停状态
-        const config = await ctx.program.account.totConfig.fetch(configPda);
-        expect(config.panicMode).to.be.true;
+      budget = await ctx.program.account.platformBudget.fetch(budgetPda);
+      expect(budget.signers.map((s: PublicKey) => s.toString())).to.not.include(hotWallet.publicKey.toString());
+    });
+
+    it("应该初始化由平台预算PDA持有的平台金库", async () => {
+      const config = await ctx.program.account.totConfig.fetch(configPda);
+      const platformVault = getPlatformVaultAddress(ctx.program.programId, config.mint);
+
+      if (!(await ctx.provider.connection.getAccountInfo(platformVault))) {
+        await ctx.program.methods
+          .initializePlatformVault()
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+            platformBudget: budgetPda,
+            platformVault,
+            mint: config.mint,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc();
+      }
+
+      // 金库由程序签名转出，热钱包不持有代币
+      const vault = await getAccount(ctx.provider.connection, platformVault, undefined, TOKEN_2022_PROGRAM_ID);
+      assertPublicKeyEqual(vault.owner, budgetPda);
+      assertPublicKeyEqual(vault.mint, config.mint);
+    });
+
+    it("应该拒绝非管理员初始化平台金库", async () => {
+      const config = await ctx.program.account.totConfig.fetch(configPda);
+
+      try {
+        await ctx.program.methods
+          .initializePlatformVault()
+          .accounts({
+            authority: hotWallet.publicKey,
+            config: configPda,
+            platformBudget: budgetPda,
+            platformVault: getPlatformVaultAddress(ctx.program.programId, config.mint),
+            mint: config.mint,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .signers([hotWallet.keypair])
+          .rpc();
+        expect.fail("应该抛出Unauthorized错误");
       } catch (error: any) {
-        console.log("⚠️  需要先初始化系统:", error.message);
+        assertError(error, "Unauthorized");
       }
     });
 
-    it("应该成功恢复系统", async () => {
+    it("应该拒绝热钱包自行修改预算", async () => {
       try {
-        const tx = await ctx.program.methods
-          .setPaused(false)
+        await ctx.program.methods
+          .updatePlatformBudget(day, periodCap.muln(10), perRecipientCap)
           .accounts({
-            authority: ctx.wallet.publicKey,
+            authority: hotWallet.publicKey,
             config: configPda,
+            platformBudget: budgetPda,
           })
+          .signers([hotWallet.keypair])
           .rpc();
+        expect.fail("应该抛出Unauthorized错误");
+      } catch (error: any) {
+        assertError(error, "Unauthorized");
+      }
+    });
+  });
 
-        console.log("✅ 恢复系统交易签名:", tx);
+  describe("奖励兑换券", () => {
+    const voucherSigner = Keypair.generate();
+    const claimer = createTestUser("VoucherClaimer");
 
-        // 验证恢复状态
-        const config = await ctx.program.account.totConfig.fetch(configPda);
-        expect(config.panicMode).to.be.false;
+    it("应该设置兑换券签名公钥", async () => {
+      await ctx.program.methods
+        .setVoucherSigner(voucherSigner.publicKey)
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          config: configPda,
+        })
+        .rpc();
+
+      const config = await ctx.program.account.totConfig.fetch(configPda);
+      assertPublicKeyEqual(config.voucherSigner, voucherSigner.publicKey);
+    });
+
+    it("应该拒绝非管理员设置兑换券签名公钥", async () => {
+      try {
+        await ctx.program.methods
+          .setVoucherSigner(claimer.publicKey)
+          .accounts({
+            authority: claimer.publicKey,
+            config: configPda,
+          })
+          .signers([claimer.keypair])
+          .rpc();
+        expect.fail("应该抛出Unauthorized错误");
       } catch (error: any) {
-        console.log("⚠️  需要先初始化系统:", error.message);
+        assertError(error, "Unauthorized");
+      }
+    });
+
+    it("应该拒绝非签名公钥签发的兑换券", async () => {
+      const config = await ctx.program.account.totConfig.fetch(configPda);
+      const [rewardsVaultPda] = getRewardsVaultPda(ctx.program.programId);
+      const vault = await ctx.program.account.rewardsVault.fetchNullable(rewardsVaultPda);
+      if (!vault) {
+        console.log("⚠️  兑换券测试需要先初始化奖励金库");
+        return;
+      }
+
+      const voucher = {
+        recipient: claimer.publicKey,
+        amount: new anchor.BN(1_000_000),
+        expiresAt: new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
+        nonce: new anchor.BN(Date.now()),
+      };
+      const [redemptionPda] = getVoucherRedemptionPda(ctx.program.programId, voucher.nonce);
+      const forger = Keypair.generate();
+
+      try {
+        await ctx.program.methods
+          .redeemVoucher(voucher)
+          .accounts({
+            recipient: claimer.publicKey,
+            recipientTokenAccount: getAssociatedTokenAddress(config.mint, claimer.publicKey),
+            recipientHolderInfo: null,
+            config: configPda,
+            rewardsVault: rewardsVaultPda,
+            vaultTokenAccount: vault.tokenAccount,
+            voucherRedemption: redemptionPda,
+            mint: config.mint,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          })
+          .preInstructions([
+            Ed25519Program.createInstructionWithPrivateKey({
+              privateKey: forger.secretKey,
+              message: buildVoucherMessage(ctx.program.programId, voucher),
+            }),
+          ])
+          .signers([claimer.keypair])
+          .rpc();
+        expect.fail("应该抛出InvalidVoucherSignature错误");
+      } catch (error: any) {
+        assertError(error, "InvalidVoucherSignature");
       }
     });
   });
@@ -157,7 +935,7 @@ describe("管理员功能测试", () => {
       destTokenAccount = getAssociatedTokenAddress(mintPublicKey, destKeypair.publicKey);
     });
 
-    it("应该拒绝在系统未暂停时执行紧急提取", async () => {
+    it("应该拒绝在转账未暂停时执行紧急提取", async () => {
       const amount = new anchor.BN(1000000);
 
       try {
@@ -222,4 +1000,337 @@ describe("管理员功能测试", () => {
       }
     });
   });
+
+  describe("商户与发票", () => {
+    let merchantPda: PublicKey;
+
+    before(async () => {
+      [merchantPda] = getMerchantPda(ctx.program.programId, ctx.wallet.publicKey);
+      const existing = await ctx.provider.connection.getAccountInfo(merchantPda);
+      if (!existing) {
+        await ctx.program.methods
+          .registerMerchant(ctx.wallet.publicKey, null)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+            merchantAccount: merchantPda,
+          })
+          .rpc();
+      }
+    });
+
+    function updateMerchant(taxCapBps: number | null, isActive: boolean) {
+      return ctx.program.methods
+        .updateMerchant(taxCapBps, isActive)
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          config: configPda,
+          merchantAccount: merchantPda,
+        })
+        .rpc();
+    }
+
+    function createInvoice(invoiceId: anchor.BN, amount: number, dueAt: number) {
+      return ctx.program.methods
+        .createInvoice(invoiceId, new anchor.BN(amount), new anchor.BN(dueAt), Array(32).fill(7))
+        .accounts({
+          merchant: ctx.wallet.publicKey,
+          merchantAccount: merchantPda,
+          invoice: getInvoicePda(ctx.program.programId, ctx.wallet.publicKey, invoiceId)[0],
+        })
+        .rpc();
+    }
+
+    it("应该设置商户税率上限并拒绝超过最大税率", async () => {
+      await updateMerchant(0, true);
+
+      const merchant = await ctx.program.account.merchantAccount.fetch(merchantPda);
+      expect(merchant.taxCapBps).to.equal(0);
+      expect(merchant.isActive).to.be.true;
+
+      try {
+        await updateMerchant(10000, true);
+        expect.fail("应该抛出InvalidMerchantTaxRate错误");
+      } catch (error: any) {
+        assertError(error, "InvalidMerchantTaxRate");
+      }
+    });
+
+    it("应该拒绝金额为0或已过期的发票", async () => {
+      const now = Math.floor(Date.now() / 1000);
+
+      try {
+        await createInvoice(new anchor.BN(Date.now()), 0, now + 3600);
+        expect.fail("应该抛出InvalidInvoice错误");
+      } catch (error: any) {
+        assertError(error, "InvalidInvoice");
+      }
+
+      try {
+        await createInvoice(new anchor.BN(Date.now() + 1), 1_000_000, now - 60);
+        expect.fail("应该抛出InvalidInvoice错误");
+      } catch (error: any) {
+        assertError(error, "InvalidInvoice");
+      }
+    });
+
+    it("停用的商户不能开票，商户可以作废未支付的发票", async () => {
+      const now = Math.floor(Date.now() / 1000);
+      const invoiceId = new anchor.BN(Date.now());
+      const [invoicePda] = getInvoicePda(ctx.program.programId, ctx.wallet.publicKey, invoiceId);
+
+      await createInvoice(invoiceId, 1_000_000, now + 3600);
+      const invoice = await ctx.program.account.invoice.fetch(invoicePda);
+      expect(invoice.amount.toNumber()).to.equal(1_000_000);
+      expect(invoice.status).to.deep.equal({ open: {} });
+
+      await ctx.program.methods
+        .closeInvoice()
+        .accounts({
+          merchant: ctx.wallet.publicKey,
+          invoice: invoicePda,
+        })
+        .rpc();
+      expect(await ctx.provider.connection.getAccountInfo(invoicePda)).to.be.null;
+
+      await updateMerchant(0, false);
+      try {
+        await createInvoice(new anchor.BN(Date.now() + 2), 1_000_000, now + 3600);
+        expect.fail("应该抛出MerchantNotActive错误");
+      } catch (error: any) {
+        assertError(error, "MerchantNotActive");
+      } finally {
+        await updateMerchant(0, true);
+      }
+    });
+  });
+
+  describe("合作程序", () => {
+    const partnerProgramId = Keypair.generate().publicKey;
+    let partnerPda: PublicKey;
+
+    before(async () => {
+      [partnerPda] = getPartnerPda(ctx.program.programId, partnerProgramId);
+      await ctx.program.methods
+        .registerPartner(partnerProgramId, 0b111)
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          config: configPda,
+          partner: partnerPda,
+        })
+        .rpc();
+    });
+
+    it("登记时应该保存合作程序的签名PDA", async () => {
+      const partner = await ctx.program.account.partnerProgram.fetch(partnerPda);
+      const [expectedAuthority] = getPartnerAuthorityPda(partnerProgramId);
+      expect(partner.authority.toString()).to.equal(expectedAuthority.toString());
+      expect(partner.permissions).to.equal(0b111);
+      expect(partner.isActive).to.be.true;
+    });
+
+    it("应该拒绝未定义的权限位", async () => {
+      try {
+        await ctx.program.methods
+          .updatePartner(0b1000, true)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+            partner: partnerPda,
+          })
+          .rpc();
+        expect.fail("应该抛出InvalidPartnerProgram错误");
+      } catch (error: any) {
+        assertError(error, "InvalidPartnerProgram");
+      }
+    });
+
+    it("应该拒绝不是合作程序签名PDA的调用方", async () => {
+      const [holderPda] = getHolderPda(ctx.program.programId, ctx.wallet.publicKey);
+      if (!(await ctx.provider.connection.getAccountInfo(holderPda))) {
+        console.log("⚠️  合作程序测试需要持有者账户，跳过");
+        return;
+      }
+
+      const impostor = Keypair.generate();
+      try {
+        await ctx.program.methods
+          .partnerRecordHolderActivity({ buy: { amount: new anchor.BN(1), taxPaid: new anchor.BN(0) } })
+          .accounts({
+            partnerAuthority: impostor.publicKey,
+            partner: partnerPda,
+            holderInfo: holderPda,
+            holderTokenAccount: null,
+            config: configPda,
+          })
+          .signers([impostor])
+          .rpc();
+        expect.fail("应该抛出PartnerNotAuthorized错误");
+      } catch (error: any) {
+        assertError(error, "PartnerNotAuthorized");
+      }
+    });
+  });
+
+  describe("积分", () => {
+    let pointsConfigPda: PublicKey;
+    let pointsPda: PublicKey;
+    let catalogPda: PublicKey;
+
+    const pointsParams = (discountBps: number) => ({
+      consumeRates: [10, 10, 5, 0, 0, 1, 0, 0],
+      holdingPointsPerDay: new anchor.BN(5),
+      seizePoints: new anchor.BN(100),
+      redeemPointsPerTot: new anchor.BN(20),
+      discountBps,
+      discountCost: new anchor.BN(500),
+      discountDurationSeconds: new anchor.BN(7 * 86400),
+    });
+
+    before(async () => {
+      [pointsConfigPda] = getPointsConfigPda(ctx.program.programId);
+      [pointsPda] = getPointsPda(ctx.program.programId, ctx.wallet.publicKey);
+      [catalogPda] = getPriceCatalogPda(ctx.program.programId);
+
+      if (!(await ctx.provider.connection.getAccountInfo(pointsConfigPda))) {
+        await ctx.program.methods
+          .initializePointsConfig(pointsParams(50))
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+            pointsConfig: pointsConfigPda,
+          })
+          .rpc();
+      }
+      if (!(await ctx.provider.connection.getAccountInfo(pointsPda))) {
+        await ctx.program.methods
+          .initializePointsAccount()
+          .accounts({
+            payer: ctx.wallet.publicKey,
+            owner: ctx.wallet.publicKey,
+            pointsAccount: pointsPda,
+          })
+          .rpc();
+      }
+    });
+
+    it("应该拒绝超过最高税率的折扣", async () => {
+      try {
+        await ctx.program.methods
+          .updatePointsConfig(pointsParams(10_001))
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+            pointsConfig: pointsConfigPda,
+          })
+          .rpc();
+        expect.fail("应该抛出InvalidPointsConfig错误");
+      } catch (error: any) {
+        assertError(error, "InvalidPointsConfig");
+      }
+    });
+
+    it("新积分账户余额为0", async () => {
+      const points = await ctx.program.account.pointsAccount.fetch(pointsPda);
+      assertPublicKeyEqual(points.owner, ctx.wallet.publicKey);
+      expect(points.balance.toNumber()).to.equal(0);
+      expect(points.taxDiscountBps).to.equal(0);
+    });
+
+    it("积分不足时应该拒绝兑换税率折扣", async () => {
+      const points = await ctx.program.account.pointsAccount.fetch(pointsPda);
+      if (points.balance.toNumber() >= 500) {
+        console.log("⚠️  积分余额已足够兑换，跳过");
+        return;
+      }
+
+      try {
+        await ctx.program.methods
+          .convertPointsToTaxDiscount()
+          .accounts({
+            owner: ctx.wallet.publicKey,
+            pointsConfig: pointsConfigPda,
+            pointsAccount: pointsPda,
+          })
+          .rpc();
+        expect.fail("应该抛出InsufficientPoints错误");
+      } catch (error: any) {
+        assertError(error, "InsufficientPoints");
+      }
+    });
+
+    it("应该拒绝兑换未登记的商品", async () => {
+      if (!(await ctx.provider.connection.getAccountInfo(catalogPda))) {
+        console.log("⚠️  价格目录未初始化，跳过");
+        return;
+      }
+
+      try {
+        await ctx.program.methods
+          .redeemPoints({ other: {} }, 999_999)
+          .accounts({
+            owner: ctx.wallet.publicKey,
+            config: configPda,
+            pointsConfig: pointsConfigPda,
+            pointsAccount: pointsPda,
+            priceCatalog: catalogPda,
+          })
+          .rpc();
+        expect.fail("应该抛出PointsRedemptionUnavailable错误");
+      } catch (error: any) {
+        assertError(error, "PointsRedemptionUnavailable");
+      }
+    });
+
+    it("排行榜应该按累计积分降序返回", async () => {
+      const other = Keypair.generate();
+      const [otherPointsPda] = getPointsPda(ctx.program.programId, other.publicKey);
+      await ctx.program.methods
+        .initializePointsAccount()
+        .accounts({
+          payer: ctx.wallet.publicKey,
+          owner: other.publicKey,
+          pointsAccount: otherPointsPda,
+        })
+        .rpc();
+
+      const leaderboard = await ctx.program.methods
+        .getPointsLeaderboard(10)
+        .accounts({ pointsConfig: pointsConfigPda })
+        .remainingAccounts(
+          [pointsPda, otherPointsPda, pointsPda].map((pubkey) => ({
+            pubkey,
+            isSigner: false,
+            isWritable: false,
+          }))
+        )
+        .view();
+
+      expect(leaderboard.length).to.equal(2);
+      for (let i = 1; i < leaderboard.length; i++) {
+        expect(leaderboard[i - 1].totalEarned.gte(leaderboard[i].totalEarned)).to.be.true;
+      }
+    });
+  });
+
+  describe("配置迁移", () => {
+    it("当前布局的配置账户应该拒绝再次迁移", async () => {
+      const config = await ctx.program.account.totConfig.fetch(configPda);
+      expect(config.version).to.equal(2);
+
+      try {
+        await ctx.program.methods
+          .migrateConfig()
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc();
+        expect.fail("应该抛出ConfigAlreadyMigrated错误");
+      } catch (error: any) {
+        assertError(error, "ConfigAlreadyMigrated");
+      }
+    });
+  });
 });
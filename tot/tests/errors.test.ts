@@ -30,7 +30,7 @@ describe("错误场景测试", () => {
       // 测试更新税率配置
       try {
         await ctx.program.methods
-          .updateTaxConfig(300, null, null, null, null, null)
+          .updateTaxConfig(300, null, null, null, null, null, null)
           .accounts({
             authority: unauthorizedUser.publicKey,
             config: configPda,
@@ -61,7 +61,7 @@ describe("错误场景测试", () => {
     it("应该拒绝无效的税率参数", async () => {
       try {
         await ctx.program.methods
-          .updateTaxConfig(10000, null, null, null, null, null) // 100%，超过最大
+          .updateTaxConfig(10000, null, null, null, null, null, null) // 100%，超过最大
           .accounts({
             authority: ctx.wallet.publicKey,
             config: configPda,
@@ -83,7 +83,7 @@ describe("错误场景测试", () => {
 
       try {
         await ctx.program.methods
-          .setPaused(true)
+          .setPaused([{ transfers: {} }], true, null)
           .accounts({
             authority: ctx.wallet.publicKey,
             config: invalidConfig,
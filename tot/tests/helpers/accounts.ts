@@ -3,9 +3,9 @@
 // PDA计算和账户创建辅助函数
 // ============================================
 
-import { PublicKey, Keypair } from "@solana/web3.js";
+import { PublicKey, Keypair, AccountMeta } from "@solana/web3.js";
 import { Program } from "@coral-xyz/anchor";
-import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { TOKEN_2022_PROGRAM_ID, getAssociatedTokenAddressSync } from "@solana/spl-token";
 import * as anchor from "@coral-xyz/anchor";
 
 /**
@@ -68,6 +68,451 @@ export function getHolderPda(
   );
 }
 
+/**
+ * 计算铸造权限PDA
+ * 
+ * @param programId 程序ID
+ * @returns [PDA地址, bump]
+ */
+export function getMintAuthorityPda(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_mint_auth")],
+    programId
+  );
+}
+
+/**
+ * 计算元数据权限PDA
+ * 
+ * @param programId 程序ID
+ * @returns [PDA地址, bump]
+ */
+export function getMetadataAuthorityPda(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_metadata_auth")],
+    programId
+  );
+}
+
+/**
+ * 计算永久代理PDA
+ * 
+ * @param programId 程序ID
+ * @returns [PDA地址, bump]
+ */
+export function getPermanentDelegatePda(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_perm_delegate")],
+    programId
+  );
+}
+
+/**
+ * 计算追回记录PDA
+ * 
+ * @param programId 程序ID
+ * @param caseId 案件编号
+ * @returns [PDA地址, bump]
+ */
+export function getClawbackRecordPda(
+  programId: PublicKey,
+  caseId: anchor.BN
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_clawback"), caseId.toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
+/**
+ * 计算支付回执PDA
+ * 
+ * @param programId 程序ID
+ * @param payer 付款方钱包（签名者）
+ * @param reference 32字节支付引用
+ * @returns [PDA地址, bump]
+ */
+export function getPaymentReceiptPda(
+  programId: PublicKey,
+  payer: PublicKey,
+  reference: Buffer
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_payment_receipt"), payer.toBuffer(), reference],
+    programId
+  );
+}
+
+/**
+ * 计算代币流PDA
+ * 
+ * @param programId 程序ID
+ * @param sender 发送者钱包地址
+ * @param streamId 流编号
+ * @returns [PDA地址, bump]
+ */
+export function getStreamPda(
+  programId: PublicKey,
+  sender: PublicKey,
+  streamId: anchor.BN
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_stream"), sender.toBuffer(), streamId.toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
+/**
+ * 计算P2P挂单PDA
+ * 
+ * @param programId 程序ID
+ * @param maker 挂单方钱包地址
+ * @param offerId 挂单编号
+ * @returns [PDA地址, bump]
+ */
+export function getOfferPda(
+  programId: PublicKey,
+  maker: PublicKey,
+  offerId: anchor.BN
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_offer"), maker.toBuffer(), offerId.toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
+/**
+ * 计算消费回执PDA
+ * 
+ * @param programId 程序ID
+ * @param user 消费用户钱包地址
+ * @param nonce 客户端随机数
+ * @returns [PDA地址, bump]
+ */
+export function getConsumeReceiptPda(
+  programId: PublicKey,
+  user: PublicKey,
+  nonce: anchor.BN
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_consume_receipt"), user.toBuffer(), nonce.toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
+/**
+ * 计算价格目录PDA
+ * 
+ * @param programId 程序ID
+ * @returns [PDA地址, bump]
+ */
+export function getPriceCatalogPda(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_price_catalog")],
+    programId
+  );
+}
+
+/**
+ * 计算平台预算PDA
+ * 
+ * @param programId 程序ID
+ * @returns [PDA地址, bump]
+ */
+export function getPlatformBudgetPda(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_platform_budget")],
+    programId
+  );
+}
+
+/**
+ * 计算平台金库代币账户地址（平台预算PDA的ATA）
+ * 
+ * @param programId 程序ID
+ * @param mint TOT Mint地址
+ * @returns 金库代币账户地址
+ */
+export function getPlatformVaultAddress(programId: PublicKey, mint: PublicKey): PublicKey {
+  const [budgetPda] = getPlatformBudgetPda(programId);
+  return getAssociatedTokenAddressSync(mint, budgetPda, true, TOKEN_2022_PROGRAM_ID);
+}
+
+/**
+ * 计算平台转账接收者额度PDA
+ * 
+ * @param programId 程序ID
+ * @param recipient 接收者钱包地址
+ * @returns [PDA地址, bump]
+ */
+export function getPlatformRecipientUsagePda(
+  programId: PublicKey,
+  recipient: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_platform_recipient"), recipient.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * 计算奖励金库PDA
+ * 
+ * @param programId 程序ID
+ * @returns [PDA地址, bump]
+ */
+export function getRewardsVaultPda(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_rewards_vault")],
+    programId
+  );
+}
+
+/**
+ * 计算兑换券核销记录PDA
+ * 
+ * @param programId 程序ID
+ * @param nonce 兑换券随机数
+ * @returns [PDA地址, bump]
+ */
+export function getVoucherRedemptionPda(
+  programId: PublicKey,
+  nonce: anchor.BN
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_voucher"), nonce.toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
+/**
+ * 构造奖励兑换券的签名消息（与链上Voucher::message一致）
+ * 
+ * @param programId 程序ID
+ * @param voucher 兑换券
+ * @returns 待签名消息
+ */
+export function buildVoucherMessage(
+  programId: PublicKey,
+  voucher: { recipient: PublicKey; amount: anchor.BN; expiresAt: anchor.BN; nonce: anchor.BN }
+): Buffer {
+  return Buffer.concat([
+    Buffer.from("TOT_VOUCHER_V1"),
+    programId.toBuffer(),
+    voucher.recipient.toBuffer(),
+    voucher.amount.toArrayLike(Buffer, "le", 8),
+    voucher.expiresAt.toTwos(64).toArrayLike(Buffer, "le", 8),
+    voucher.nonce.toArrayLike(Buffer, "le", 8),
+  ]);
+}
+
+/**
+ * 计算订阅账户PDA
+ * 
+ * @param programId 程序ID
+ * @param user 订阅用户
+ * @param subscriptionId 订阅编号
+ * @returns [PDA地址, bump]
+ */
+export function getSubscriptionPda(
+  programId: PublicKey,
+  user: PublicKey,
+  subscriptionId: anchor.BN
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_subscription"), user.toBuffer(), subscriptionId.toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
+/**
+ * 计算订阅代理PDA（用户代币账户授权的delegate）
+ * 
+ * @param programId 程序ID
+ * @returns [PDA地址, bump]
+ */
+export function getSubscriptionDelegatePda(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_sub_delegate")],
+    programId
+  );
+}
+
+/**
+ * 计算商户账户PDA
+ * 
+ * @param programId 程序ID
+ * @param merchant 商户钱包
+ * @returns [PDA地址, bump]
+ */
+export function getMerchantPda(programId: PublicKey, merchant: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_merchant"), merchant.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * 计算发票PDA
+ * 
+ * @param programId 程序ID
+ * @param merchant 商户钱包
+ * @param invoiceId 发票ID
+ * @returns [PDA地址, bump]
+ */
+export function getInvoicePda(
+  programId: PublicKey,
+  merchant: PublicKey,
+  invoiceId: anchor.BN
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_invoice"), merchant.toBuffer(), invoiceId.toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
+/**
+ * 计算合作程序登记PDA
+ * 
+ * @param programId 程序ID
+ * @param partnerProgramId 合作程序ID
+ * @returns [PDA地址, bump]
+ */
+export function getPartnerPda(programId: PublicKey, partnerProgramId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_partner"), partnerProgramId.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * 计算合作程序签名PDA（在合作程序ID下派生，CPI时由合作程序签名）
+ * 
+ * @param partnerProgramId 合作程序ID
+ * @returns [PDA地址, bump]
+ */
+export function getPartnerAuthorityPda(partnerProgramId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_partner_authority")],
+    partnerProgramId
+  );
+}
+
+/**
+ * 计算积分配置PDA
+ * 
+ * @param programId 程序ID
+ * @returns [PDA地址, bump]
+ */
+export function getPointsConfigPda(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_points_config")],
+    programId
+  );
+}
+
+/**
+ * 计算积分账户PDA
+ * 
+ * @param programId 程序ID
+ * @param owner 积分持有者钱包
+ * @returns [PDA地址, bump]
+ */
+export function getPointsPda(programId: PublicKey, owner: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_points"), owner.toBuffer()],
+    programId
+  );
+}
+
+/**
+ * 计算Transfer Hook配置PDA（属于Hook程序）
+ * 
+ * @param hookProgramId Transfer Hook程序ID
+ * @returns [PDA地址, bump]
+ */
+export function getHookConfigPda(hookProgramId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("hook_config")],
+    hookProgramId
+  );
+}
+
+/**
+ * 计算Transfer Hook额外账户列表PDA（属于Hook程序）
+ * 
+ * @param hookProgramId Transfer Hook程序ID
+ * @param mint 代币Mint地址
+ * @returns [PDA地址, bump]
+ */
+export function getExtraAccountMetaListPda(
+  hookProgramId: PublicKey,
+  mint: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("extra-account-metas"), mint.toBuffer()],
+    hookProgramId
+  );
+}
+
+/**
+ * 计算转账守卫PDA（属于主程序）
+ * 
+ * @param programId 主程序ID
+ * @returns [PDA地址, bump]
+ */
+export function getTransferGuardPda(programId: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("tot_transfer_guard")],
+    programId
+  );
+}
+
+/**
+ * 构建TOT转账需要的Transfer Hook账户
+ * 
+ * 顺序与Hook程序的`extra_account_metas()`一致，之后是额外账户列表和Hook程序。
+ * 既可以追加到Token-2022的`transferChecked`指令，也可以作为主程序转账指令的
+ * `remainingAccounts`传入（主程序按地址查找，顺序无关）。
+ * 
+ * @param programId 主程序ID
+ * @param hookProgramId Transfer Hook程序ID
+ * @param mint TOT Mint地址
+ * @param sourceOwner 转出代币账户的所有者
+ * @param destinationOwner 转入代币账户的所有者
+ * @returns 账户列表
+ */
+export function getTransferHookAccounts(
+  programId: PublicKey,
+  hookProgramId: PublicKey,
+  mint: PublicKey,
+  sourceOwner: PublicKey,
+  destinationOwner: PublicKey
+): AccountMeta[] {
+  const readonly = (pubkey: PublicKey): AccountMeta => ({
+    pubkey,
+    isSigner: false,
+    isWritable: false,
+  });
+  const writable = (pubkey: PublicKey): AccountMeta => ({
+    pubkey,
+    isSigner: false,
+    isWritable: true,
+  });
+
+  return [
+    readonly(programId),
+    writable(getHookConfigPda(hookProgramId)[0]),
+    readonly(getConfigPda(programId)[0]),
+    readonly(getTaxConfigPda(programId)[0]),
+    writable(getHolderPda(programId, sourceOwner)[0]),
+    writable(getHolderPda(programId, destinationOwner)[0]),
+    writable(getTransferGuardPda(programId)[0]),
+    readonly(getExtraAccountMetaListPda(hookProgramId, mint)[0]),
+    readonly(hookProgramId),
+  ];
+}
+
 /**
  * 计算国库账户PDA（如果使用PDA作为国库）
  * 
@@ -14,6 +14,7 @@ import { Connection, Keypair, PublicKey } from "@solana/web3.js";
 export interface TestContext {
   provider: AnchorProvider;
   program: Program<any>;
+  hookProgram: Program<any>;
   wallet: Wallet;
   connection: Connection;
 }
@@ -21,7 +22,7 @@ export interface TestContext {
 /**
  * 初始化测试环境
  * 
- * 设置Anchor提供者、程序实例和钱包
+ * 设置Anchor提供者、程序实例（主程序和Transfer Hook程序）和钱包
  * 
  * @returns 测试上下文
  */
@@ -30,12 +31,14 @@ export function setupTestContext(): TestContext {
   anchor.setProvider(provider);
 
   const program = anchor.workspace.TotToken as Program<any>;
+  const hookProgram = anchor.workspace.TotTransferHook as Program<any>;
   const wallet = provider.wallet as anchor.Wallet;
   const connection = provider.connection;
 
   return {
     provider,
     program,
+    hookProgram,
     wallet,
     connection,
   };
@@ -6,9 +6,18 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { 
+  TOKEN_2022_PROGRAM_ID,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
 import { expect } from "chai";
 import { setupTestContext } from "./helpers/setup";
-import { getConfigPda, getHolderPda } from "./helpers/accounts";
+import { 
+  getConfigPda, 
+  getHolderPda,
+  getPermanentDelegatePda,
+  getClawbackRecordPda,
+} from "./helpers/accounts";
 import { 
   assertAccountExists,
   assertError 
@@ -119,6 +128,7 @@ describe("持有者管理测试", () => {
       const holderAccount = await ctx.program.account.holderAccount.fetch(frozenHolderPda);
       expect(holderAccount.isFrozen).to.be.true;
       expect(holderAccount.freezeReason).to.equal(reasonCode);
+      expect(holderAccount.frozenAt.toNumber()).to.be.greaterThan(0);
     });
 
     it("应该成功解冻持有者账户", async () => {
@@ -137,6 +147,7 @@ describe("持有者管理测试", () => {
       const holderAccount = await ctx.program.account.holderAccount.fetch(frozenHolderPda);
       expect(holderAccount.isFrozen).to.be.false;
       expect(holderAccount.freezeReason).to.equal(0);
+      expect(holderAccount.frozenAt.toNumber()).to.equal(0);
     });
 
     it("应该拒绝非管理员冻结账户", async () => {
@@ -160,4 +171,107 @@ describe("持有者管理测试", () => {
       }
     });
   });
+
+  describe("追回冻结账户代币", () => {
+    let clawbackUser: ReturnType<typeof createTestUser>;
+    let clawbackHolderPda: PublicKey;
+    let sourceTokenAccount: PublicKey;
+    let mint: PublicKey;
+
+    before(async () => {
+      clawbackUser = createTestUser("ClawbackUser");
+      [clawbackHolderPda] = getHolderPda(ctx.program.programId, clawbackUser.publicKey);
+      mint = (await ctx.program.account.totConfig.fetch(configPda)).mint;
+
+      await ctx.program.methods
+        .initializeHolder()
+        .accounts({
+          payer: ctx.wallet.publicKey,
+          holderWallet: clawbackUser.publicKey,
+          holderInfo: clawbackHolderPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      const tokenAccount = await getOrCreateAssociatedTokenAccount(
+        ctx.connection,
+        (ctx.wallet as any).payer,
+        mint,
+        clawbackUser.publicKey,
+        false,
+        undefined,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      sourceTokenAccount = tokenAccount.address;
+    });
+
+    function clawbackAccounts(operator: PublicKey, caseId: anchor.BN) {
+      return {
+        operator,
+        config: configPda,
+        holderInfo: clawbackHolderPda,
+        sourceTokenAccount,
+        destinationTokenAccount: null,
+        mint,
+        permanentDelegate: getPermanentDelegatePda(ctx.program.programId)[0],
+        clawbackRecord: getClawbackRecordPda(ctx.program.programId, caseId)[0],
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      };
+    }
+
+    it("应该拒绝追回未冻结的账户", async () => {
+      const caseId = new anchor.BN(1);
+
+      try {
+        await ctx.program.methods
+          .clawback(caseId, { burn: {} }, new anchor.BN(1), Array(32).fill(1))
+          .accounts(clawbackAccounts(ctx.wallet.publicKey, caseId))
+          .rpc();
+        expect.fail("应该抛出HolderNotFrozen错误");
+      } catch (error: any) {
+        assertError(error, "HolderNotFrozen");
+      }
+    });
+
+    it("应该拒绝追回冻结时间不足的账户", async () => {
+      await ctx.program.methods
+        .freezeHolder(FreezeReason.Violation)
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          holderInfo: clawbackHolderPda,
+          config: configPda,
+        })
+        .rpc();
+
+      const caseId = new anchor.BN(2);
+
+      try {
+        await ctx.program.methods
+          .clawback(caseId, { burn: {} }, new anchor.BN(1), Array(32).fill(1))
+          .accounts(clawbackAccounts(ctx.wallet.publicKey, caseId))
+          .rpc();
+        expect.fail("应该抛出ClawbackFrozenPeriodNotElapsed错误");
+      } catch (error: any) {
+        assertError(error, "ClawbackFrozenPeriodNotElapsed");
+      }
+    });
+
+    it("应该拒绝非管理员或合规权限追回", async () => {
+      const attacker = createTestUser("ClawbackAttacker");
+      const caseId = new anchor.BN(3);
+
+      try {
+        await ctx.program.methods
+          .clawback(caseId, { burn: {} }, new anchor.BN(1), Array(32).fill(1))
+          .accounts(clawbackAccounts(attacker.publicKey, caseId))
+          .signers([attacker.keypair])
+          .rpc();
+        expect.fail("应该抛出Unauthorized错误");
+      } catch (error: any) {
+        assertError(error, "Unauthorized");
+      }
+    });
+  });
 });
@@ -13,10 +13,16 @@ import {
 import { 
   TOKEN_2022_PROGRAM_ID,
   getMint,
+  getTokenMetadata,
 } from "@solana/spl-token";
 import { expect } from "chai";
 import { setupTestContext, waitForConfirmation } from "./helpers/setup";
-import { getConfigPda, getTaxConfigPda } from "./helpers/accounts";
+import { 
+  getConfigPda, 
+  getTaxConfigPda,
+  getMintAuthorityPda,
+  getMetadataAuthorityPda,
+} from "./helpers/accounts";
 import { 
   assertAccountExists, 
   assertBNEqual,
@@ -68,7 +74,7 @@ describe("初始化测试", () => {
         TOKEN_2022_PROGRAM_ID
       );
 
-      expect(mintInfo.decimals).to.equal(9);
+      expect(mintInfo.decimals).to.equal(6);
       expect(mintInfo.supply.toString()).to.equal("0");
 
       // 验证配置账户存在
@@ -150,6 +156,75 @@ describe("初始化测试", () => {
       }
     });
   });
+
+  describe("链上元数据", () => {
+    const uri = "https://taiwansheng.example/tot.json";
+
+    function metadataAccounts(authority: PublicKey) {
+      return {
+        authority,
+        config: configPda,
+        mint: mintKeypair.publicKey,
+        metadataAuthority: getMetadataAuthorityPda(ctx.program.programId)[0],
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      };
+    }
+
+    it("应该成功初始化元数据", async () => {
+      await ctx.program.methods
+        .initializeTokenMetadata(uri)
+        .accounts({
+          ...metadataAccounts(ctx.wallet.publicKey),
+          mintAuthority: getMintAuthorityPda(ctx.program.programId)[0],
+        })
+        .rpc();
+
+      const metadata = await getTokenMetadata(
+        ctx.connection,
+        mintKeypair.publicKey,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(metadata?.name).to.equal("TaiOneToken");
+      expect(metadata?.symbol).to.equal("TOT");
+      expect(metadata?.uri).to.equal(uri);
+    });
+
+    it("应该可以添加自定义字段", async () => {
+      const policyUrl = "https://taiwansheng.example/tax-policy";
+
+      await ctx.program.methods
+        .updateTokenMetadataField({ key: { 0: "tax_policy" } }, policyUrl)
+        .accounts(metadataAccounts(ctx.wallet.publicKey))
+        .rpc();
+
+      const metadata = await getTokenMetadata(
+        ctx.connection,
+        mintKeypair.publicKey,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(metadata?.additionalMetadata).to.deep.include(["tax_policy", policyUrl]);
+    });
+
+    it("应该拒绝非元数据权限更新", async () => {
+      const attacker = Keypair.generate();
+      const sig = await ctx.connection.requestAirdrop(attacker.publicKey, 1_000_000_000);
+      await waitForConfirmation(ctx.connection, sig);
+
+      try {
+        await ctx.program.methods
+          .updateTokenMetadataField({ uri: {} }, "https://evil.example")
+          .accounts(metadataAccounts(attacker.publicKey))
+          .signers([attacker])
+          .rpc();
+        expect.fail("应该抛出InvalidMetadataAuthority错误");
+      } catch (error: any) {
+        assertError(error, "InvalidMetadataAuthority");
+      }
+    });
+  });
 });
 
 // 辅助函数
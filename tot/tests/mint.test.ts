@@ -6,13 +6,20 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { PublicKey } from "@solana/web3.js";
-import { TOKEN_2022_PROGRAM_ID, getAccount } from "@solana/spl-token";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  getAccount,
+  getMint,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
 import { expect } from "chai";
 import { setupTestContext } from "./helpers/setup";
 import { 
   getConfigPda, 
+  getMintAuthorityPda,
   getPoolPda, 
-  PoolType 
+  PoolType,
+  poolTypeToAnchor,
 } from "./helpers/accounts";
 import { 
   assertBNEqual,
@@ -28,6 +35,7 @@ describe("铸造测试", () => {
   let mintKeypair: anchor.web3.Keypair;
   let configPda: PublicKey;
   let mintPublicKey: PublicKey;
+  let mintAuthorityPda: PublicKey;
 
   before(async () => {
     ctx = setupTestContext();
@@ -35,6 +43,7 @@ describe("铸造测试", () => {
     mintPublicKey = mintKeypair.publicKey;
     
     [configPda] = getConfigPda(ctx.program.programId);
+    [mintAuthorityPda] = getMintAuthorityPda(ctx.program.programId);
 
     // 确保系统已初始化
     try {
@@ -99,6 +108,42 @@ describe("铸造测试", () => {
     }
   });
 
+  describe("铸造权限", () => {
+    it("initialize应该把铸造权限设置为程序PDA", async () => {
+      const mintInfo = await getMint(
+        ctx.connection,
+        mintPublicKey,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(mintInfo.mintAuthority?.toString()).to.equal(mintAuthorityPda.toString());
+    });
+
+    it("铸造完成前应该拒绝放弃铸造权限", async () => {
+      const config = await ctx.program.account.totConfig.fetch(configPda);
+      if (config.totalMinted.toString() !== "0") {
+        console.log("⚠️  代币已经铸造过，跳过此测试");
+        return;
+      }
+
+      try {
+        await ctx.program.methods
+          .renounceMintAuthority()
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+            mint: mintPublicKey,
+            mintAuthority: mintAuthorityPda,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc();
+        expect.fail("应该抛出MintingNotComplete错误");
+      } catch (error: any) {
+        assertError(error, "MintingNotComplete");
+      }
+    });
+  });
+
   describe("铸造到池子", () => {
     it("应该成功铸造所有代币到各池子", async () => {
       // 获取所有池子账户
@@ -121,6 +166,7 @@ describe("铸造测试", () => {
             authority: ctx.wallet.publicKey,
             config: configPda,
             mint: mintPublicKey,
+            mintAuthority: mintAuthorityPda,
             victoryPool: victoryPoolPda,
             victoryTokenAccount: victoryPool.tokenAccount,
             historyPool: historyPoolPda,
@@ -140,6 +186,8 @@ describe("铸造测试", () => {
         // 验证配置中的总铸造量
         const config = await ctx.program.account.totConfig.fetch(configPda);
         assertBNEqual(config.totalMinted, TOTAL_SUPPLY);
+        // 全部代币都在池子中，锁定供应量等于总供应量
+        assertBNEqual(config.lockedSupply, TOTAL_SUPPLY);
 
         // 验证各池子代币账户余额
         const victoryBalance = await getAccount(
@@ -191,6 +239,36 @@ describe("铸造测试", () => {
       }
     });
 
+    it("铸造完成后应该可以放弃铸造权限", async () => {
+      const config = await ctx.program.account.totConfig.fetch(configPda);
+      if (config.totalMinted.toString() === "0" || config.mintAuthorityRenounced) {
+        console.log("⚠️  代币未铸造或铸造权限已放弃，跳过此测试");
+        return;
+      }
+
+      await ctx.program.methods
+        .renounceMintAuthority()
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          config: configPda,
+          mint: mintPublicKey,
+          mintAuthority: mintAuthorityPda,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+
+      const mintInfo = await getMint(
+        ctx.connection,
+        mintPublicKey,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect(mintInfo.mintAuthority).to.be.null;
+
+      const updated = await ctx.program.account.totConfig.fetch(configPda);
+      expect(updated.mintAuthorityRenounced).to.be.true;
+    });
+
     it("应该拒绝重复铸造", async () => {
       // 检查是否已经铸造过
       const config = await ctx.program.account.totConfig.fetch(configPda);
@@ -212,4 +290,125 @@ describe("铸造测试", () => {
       }
     });
   });
+
+  describe("池子释放与流通供应量", () => {
+    const releaseAccounts = async (poolType: PoolType, destination: PublicKey) => {
+      const [poolPda] = getPoolPda(ctx.program.programId, poolType);
+      const pool = await ctx.program.account.poolAccount.fetch(poolPda);
+      return {
+        authority: ctx.wallet.publicKey,
+        config: configPda,
+        poolAccount: poolPda,
+        poolTokenAccount: pool.tokenAccount,
+        destinationTokenAccount: destination,
+        mint: mintPublicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      };
+    };
+
+    let destination: PublicKey;
+
+    before(async () => {
+      const account = await getOrCreateAssociatedTokenAccount(
+        ctx.connection,
+        (ctx.wallet as any).payer,
+        mintPublicKey,
+        ctx.wallet.publicKey,
+        false,
+        undefined,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      destination = account.address;
+    });
+
+    it("释放历史重铸池代币后锁定供应量应该减少", async () => {
+      const amount = new anchor.BN(1_000_000_000_000);
+      const before = await ctx.program.account.totConfig.fetch(configPda);
+
+      await ctx.program.methods
+        .releasePoolTokens(poolTypeToAnchor(PoolType.HistoryLP), amount)
+        .accounts(await releaseAccounts(PoolType.HistoryLP, destination))
+        .rpc();
+
+      const after = await ctx.program.account.totConfig.fetch(configPda);
+      assertBNEqual(after.lockedSupply, before.lockedSupply.sub(amount));
+
+      const [poolPda] = getPoolPda(ctx.program.programId, PoolType.HistoryLP);
+      const pool = await ctx.program.account.poolAccount.fetch(poolPda);
+      expect(pool.releasedAmount.gte(amount)).to.be.true;
+    });
+
+    it("应该拒绝释放需要多签的外资统战池", async () => {
+      try {
+        await ctx.program.methods
+          .releasePoolTokens(poolTypeToAnchor(PoolType.GlobalAlliance), new anchor.BN(1))
+          .accounts(await releaseAccounts(PoolType.GlobalAlliance, destination))
+          .rpc();
+        expect.fail("应该抛出PoolRequiresMultisig错误");
+      } catch (error: any) {
+        assertError(error, "PoolRequiresMultisig");
+      }
+    });
+
+    it("应该拒绝释放未到解锁时间的胜利日基金", async () => {
+      try {
+        await ctx.program.methods
+          .releasePoolTokens(poolTypeToAnchor(PoolType.VictoryFund), new anchor.BN(1))
+          .accounts(await releaseAccounts(PoolType.VictoryFund, destination))
+          .rpc();
+        expect.fail("应该抛出PoolUnlockTimeNotReached错误");
+      } catch (error: any) {
+        assertError(error, "PoolUnlockTimeNotReached");
+      }
+    });
+
+    it("刷新后锁定供应量应该等于池子和财库余额之和", async () => {
+      const config = await ctx.program.account.totConfig.fetch(configPda);
+      const poolTypes = [
+        PoolType.VictoryFund,
+        PoolType.HistoryLP,
+        PoolType.CyberArmy,
+        PoolType.GlobalAlliance,
+        PoolType.AssetAnchor,
+      ];
+      const names = ["victory", "history", "cyber", "global", "asset"];
+
+      const accounts: Record<string, PublicKey> = {
+        config: configPda,
+        mint: mintPublicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      };
+      let expected = BigInt(0);
+      for (let i = 0; i < poolTypes.length; i++) {
+        const [poolPda] = getPoolPda(ctx.program.programId, poolTypes[i]);
+        const pool = await ctx.program.account.poolAccount.fetch(poolPda);
+        accounts[`${names[i]}Pool`] = poolPda;
+        accounts[`${names[i]}TokenAccount`] = pool.tokenAccount;
+        const balance = await getAccount(ctx.connection, pool.tokenAccount, undefined, TOKEN_2022_PROGRAM_ID);
+        expected += balance.amount;
+      }
+
+      const treasury = await getOrCreateAssociatedTokenAccount(
+        ctx.connection,
+        (ctx.wallet as any).payer,
+        mintPublicKey,
+        config.twsTreasury,
+        true,
+        undefined,
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+      accounts.treasuryTokenAccount = treasury.address;
+      expected += treasury.amount;
+
+      await ctx.program.methods
+        .refreshCirculatingSupply()
+        .accounts(accounts)
+        .rpc();
+
+      const updated = await ctx.program.account.totConfig.fetch(configPda);
+      expect(updated.lockedSupply.toString()).to.equal(expected.toString());
+    });
+  });
 });
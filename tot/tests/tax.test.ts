@@ -54,7 +54,8 @@ describe("税率管理测试", () => {
           null,       // beta
           null,       // gamma_bps
           null,       // panic_threshold_bps
-          null        // panic_tax_bps
+          null,       // panic_tax_bps
+          null        // buy_tax_bps
         )
         .accounts({
           authority: ctx.wallet.publicKey,
@@ -79,7 +80,8 @@ describe("税率管理测试", () => {
           60,   // beta: 0.6
           2500, // gamma_bps: 25%
           60,   // panic_threshold_bps: 0.6%
-          3500  // panic_tax_bps: 35%
+          3500, // panic_tax_bps: 35%
+          400   // buy_tax_bps: 4%
         )
         .accounts({
           authority: ctx.wallet.publicKey,
@@ -98,6 +100,7 @@ describe("税率管理测试", () => {
       expect(taxConfig.gammaBps).to.equal(2500);
       expect(taxConfig.panicThresholdBps).to.equal(60);
       expect(taxConfig.panicTaxBps).to.equal(3500);
+      expect(taxConfig.buyTaxBps).to.equal(400);
     });
 
     it("应该拒绝无效的税率（超过99%）", async () => {
@@ -105,7 +108,7 @@ describe("税率管理测试", () => {
         await ctx.program.methods
           .updateTaxConfig(
             10000, // 100%，超过最大允许值
-            null, null, null, null, null
+            null, null, null, null, null, null
           )
           .accounts({
             authority: ctx.wallet.publicKey,
@@ -125,7 +128,7 @@ describe("税率管理测试", () => {
 
       try {
         await ctx.program.methods
-          .updateTaxConfig(300, null, null, null, null, null)
+          .updateTaxConfig(300, null, null, null, null, null, null)
           .accounts({
             authority: unauthorizedUser.publicKey,
             config: configPda,
@@ -227,4 +230,115 @@ describe("税率管理测试", () => {
       }
     });
   });
+
+  describe("交易场所管理", () => {
+    let venueAddress: PublicKey;
+
+    before(() => {
+      venueAddress = Keypair.generate().publicKey;
+    });
+
+    it("应该拒绝超过99%的买入税率", async () => {
+      try {
+        await ctx.program.methods
+          .updateTaxConfig(null, null, null, null, null, null, 10000)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+            taxConfig: taxConfigPda,
+          })
+          .rpc();
+
+        expect.fail("应该抛出错误");
+      } catch (error: any) {
+        assertError(error, "TaxRateExceedsMaximum");
+      }
+    });
+
+    it("应该成功登记交易场所，并拒绝重复登记", async () => {
+      await ctx.program.methods
+        .addTradingVenue(venueAddress)
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          config: configPda,
+          taxConfig: taxConfigPda,
+        })
+        .rpc();
+
+      const taxConfig = await ctx.program.account.taxConfig.fetch(taxConfigPda);
+      expect(
+        taxConfig.venueAddresses.some(
+          (addr: PublicKey) => addr.toString() === venueAddress.toString()
+        )
+      ).to.be.true;
+
+      try {
+        await ctx.program.methods
+          .addTradingVenue(venueAddress)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+            taxConfig: taxConfigPda,
+          })
+          .rpc();
+
+        expect.fail("应该抛出错误");
+      } catch (error: any) {
+        assertError(error, "VenueAlreadyRegistered");
+      }
+    });
+
+    it("应该成功移除交易场所，并拒绝移除未登记的地址", async () => {
+      await ctx.program.methods
+        .removeTradingVenue(venueAddress)
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          config: configPda,
+          taxConfig: taxConfigPda,
+        })
+        .rpc();
+
+      const taxConfig = await ctx.program.account.taxConfig.fetch(taxConfigPda);
+      expect(
+        taxConfig.venueAddresses.some(
+          (addr: PublicKey) => addr.toString() === venueAddress.toString()
+        )
+      ).to.be.false;
+
+      try {
+        await ctx.program.methods
+          .removeTradingVenue(venueAddress)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+            taxConfig: taxConfigPda,
+          })
+          .rpc();
+
+        expect.fail("应该抛出错误");
+      } catch (error: any) {
+        assertError(error, "VenueNotRegistered");
+      }
+    });
+
+    it("应该拒绝非管理员登记交易场所", async () => {
+      const unauthorizedUser = createTestUser("Unauthorized");
+
+      try {
+        await ctx.program.methods
+          .addTradingVenue(venueAddress)
+          .accounts({
+            authority: unauthorizedUser.publicKey,
+            config: configPda,
+            taxConfig: taxConfigPda,
+          })
+          .signers([unauthorizedUser.keypair])
+          .rpc();
+
+        expect.fail("应该抛出错误");
+      } catch (error: any) {
+        assertError(error, "Unauthorized");
+      }
+    });
+  });
 });
@@ -5,10 +5,14 @@
 
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
-import { Keypair, PublicKey } from "@solana/web3.js";
+import { Keypair, PublicKey, Transaction } from "@solana/web3.js";
 import { 
   TOKEN_2022_PROGRAM_ID,
+  NATIVE_MINT,
   getAccount,
+  getMint,
+  getTransferHook,
+  createTransferCheckedInstruction,
   createAssociatedTokenAccount,
   mintTo,
 } from "@solana/spl-token";
@@ -18,7 +22,20 @@ import {
   getConfigPda, 
   getTaxConfigPda,
   getHolderPda,
-  getAssociatedTokenAddress 
+  getAssociatedTokenAddress,
+  getHookConfigPda,
+  getExtraAccountMetaListPda,
+  getTransferHookAccounts,
+  getTransferGuardPda,
+  getPaymentReceiptPda,
+  getStreamPda,
+  getOfferPda,
+  getPlatformBudgetPda,
+  getPlatformVaultAddress,
+  getPlatformRecipientUsagePda,
+  getPriceCatalogPda,
+  getSubscriptionPda,
+  getSubscriptionDelegatePda,
 } from "./helpers/accounts";
 import { 
   assertBNEqual,
@@ -55,6 +72,19 @@ describe("转账测试", () => {
     // 确保系统已初始化（简化处理，实际需要完整初始化流程）
   });
 
+  /**
+   * TOT转账需要的Transfer Hook账户（作为主程序指令的remainingAccounts传入）
+   */
+  function hookAccounts(mint: PublicKey, sourceOwner: PublicKey, destinationOwner: PublicKey) {
+    return getTransferHookAccounts(
+      ctx.program.programId,
+      ctx.hookProgram.programId,
+      mint,
+      sourceOwner,
+      destinationOwner
+    );
+  }
+
   describe("带税转账", () => {
     it("应该成功执行普通转账（非卖出）", async () => {
       // 注意：这个测试需要完整的系统初始化，包括：
@@ -78,7 +108,7 @@ describe("转账测试", () => {
 
         // 执行转账
         const tx = await ctx.program.methods
-          .transferWithTax(amount, isSell)
+          .transferWithTax(amount, isSell, null)
           .accounts({
             sender: sender.publicKey,
             senderTokenAccount: senderTokenAccount,
@@ -87,10 +117,11 @@ describe("转账测试", () => {
             config: configPda,
             taxConfig: taxConfigPda,
             senderHolderInfo: senderHolderPda,
-            receiverHolderInfo: null,
+            receiverHolderInfo: getHolderPda(ctx.program.programId, receiver.publicKey)[0],
             taxCollector: taxCollectorAccount,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
           })
+          .remainingAccounts(hookAccounts(mintPublicKey, sender.publicKey, receiver.publicKey))
           .signers([sender.keypair])
           .rpc();
 
@@ -126,7 +157,7 @@ describe("转账测试", () => {
         const taxCollectorAccount = getAssociatedTokenAddress(mintPublicKey, taxCollector.publicKey);
 
         await ctx.program.methods
-          .transferWithTax(amount, false)
+          .transferWithTax(amount, false, null)
           .accounts({
             sender: frozenUser.publicKey,
             senderTokenAccount: frozenTokenAccount,
@@ -135,10 +166,11 @@ describe("转账测试", () => {
             config: configPda,
             taxConfig: taxConfigPda,
             senderHolderInfo: frozenHolderPda,
-            receiverHolderInfo: null,
+            receiverHolderInfo: getHolderPda(ctx.program.programId, receiver.publicKey)[0],
             taxCollector: taxCollectorAccount,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
           })
+          .remainingAccounts(hookAccounts(mintPublicKey, frozenUser.publicKey, receiver.publicKey))
           .signers([frozenUser.keypair])
           .rpc();
         
@@ -148,18 +180,18 @@ describe("转账测试", () => {
       }
     });
 
-    it("应该拒绝恐慌模式下的卖出", async () => {
+    it("应该拒绝卖出暂停时的卖出", async () => {
       // 这个测试需要：
-      // 1. 系统处于恐慌模式
+      // 1. 卖出指令族处于暂停状态
       // 2. 尝试执行卖出操作
 
       const amount = new anchor.BN(1000000);
       const isSell = true;
 
       try {
-        // 先设置系统为暂停状态
+        // 先暂停卖出
         await ctx.program.methods
-          .setPaused(true)
+          .setPaused([{ sells: {} }], true, null)
           .accounts({
             authority: ctx.wallet.publicKey,
             config: configPda,
@@ -172,7 +204,7 @@ describe("转账测试", () => {
         const taxCollectorAccount = getAssociatedTokenAddress(mintPublicKey, taxCollector.publicKey);
 
         await ctx.program.methods
-          .transferWithTax(amount, isSell)
+          .transferWithTax(amount, isSell, null)
           .accounts({
             sender: sender.publicKey,
             senderTokenAccount: senderTokenAccount,
@@ -181,21 +213,22 @@ describe("转账测试", () => {
             config: configPda,
             taxConfig: taxConfigPda,
             senderHolderInfo: senderHolderPda,
-            receiverHolderInfo: null,
+            receiverHolderInfo: getHolderPda(ctx.program.programId, receiver.publicKey)[0],
             taxCollector: taxCollectorAccount,
             tokenProgram: TOKEN_2022_PROGRAM_ID,
           })
+          .remainingAccounts(hookAccounts(mintPublicKey, sender.publicKey, receiver.publicKey))
           .signers([sender.keypair])
           .rpc();
         
         expect.fail("应该抛出错误");
       } catch (error: any) {
-        assertError(error, "SystemPaused");
+        assertError(error, "OperationPaused");
       } finally {
         // 恢复系统状态
         try {
           await ctx.program.methods
-            .setPaused(false)
+            .setPaused([{ sells: {} }], false, null)
             .accounts({
               authority: ctx.wallet.publicKey,
               config: configPda,
@@ -206,5 +239,1012 @@ describe("转账测试", () => {
         }
       }
     });
+
+    it("应该拒绝不属于接收者的持有者账户", async () => {
+      // 买入时按接收者持有者计算持有时间折扣，不能借用其他长期持有者的账户
+      const totMint = (await ctx.program.account.totConfig.fetch(configPda)).mint;
+      const receiverTokenAccount = getAssociatedTokenAddress(totMint, receiver.publicKey);
+      if (!(await ctx.connection.getAccountInfo(receiverTokenAccount))) {
+        console.log("⚠️  持有者账户约束测试需要接收者代币账户");
+        return;
+      }
+      const [senderHolderPda] = getHolderPda(ctx.program.programId, sender.publicKey);
+
+      try {
+        await ctx.program.methods
+          .transferWithTax(new anchor.BN(1000), false, null)
+          .accounts({
+            sender: sender.publicKey,
+            senderTokenAccount: getAssociatedTokenAddress(totMint, sender.publicKey),
+            receiverTokenAccount,
+            mint: totMint,
+            config: configPda,
+            taxConfig: taxConfigPda,
+            senderHolderInfo: senderHolderPda,
+            receiverHolderInfo: getHolderPda(ctx.program.programId, taxCollector.publicKey)[0],
+            taxCollector: getAssociatedTokenAddress(totMint, taxCollector.publicKey),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .remainingAccounts(hookAccounts(totMint, sender.publicKey, receiver.publicKey))
+          .signers([sender.keypair])
+          .rpc();
+        expect.fail("应该抛出ConstraintSeeds错误");
+      } catch (error: any) {
+        assertError(error, "ConstraintSeeds");
+      }
+    });
+  });
+
+  describe("批量转账", () => {
+    let totMint: PublicKey;
+    let senderHolderPda: PublicKey;
+    let senderTokenAccount: PublicKey;
+    let taxCollectorAccount: PublicKey;
+
+    before(async () => {
+      totMint = (await ctx.program.account.totConfig.fetch(configPda)).mint;
+      [senderHolderPda] = getHolderPda(ctx.program.programId, ctx.wallet.publicKey);
+      senderTokenAccount = getAssociatedTokenAddress(totMint, ctx.wallet.publicKey);
+      taxCollectorAccount = getAssociatedTokenAddress(totMint, taxCollector.publicKey);
+    });
+
+    function batchAccounts() {
+      return {
+        sender: ctx.wallet.publicKey,
+        senderTokenAccount,
+        mint: totMint,
+        config: configPda,
+        taxConfig: taxConfigPda,
+        senderHolderInfo: senderHolderPda,
+        taxCollector: taxCollectorAccount,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      };
+    }
+
+    function recipientAccounts(owner: PublicKey) {
+      const [holderPda] = getHolderPda(ctx.program.programId, owner);
+      return [
+        { pubkey: getAssociatedTokenAddress(totMint, owner), isSigner: false, isWritable: true },
+        { pubkey: holderPda, isSigner: false, isWritable: true },
+      ];
+    }
+
+    it("应该拒绝空的接收者列表", async () => {
+      try {
+        await ctx.program.methods
+          .batchTransfer([])
+          .accounts(batchAccounts())
+          .rpc();
+        expect.fail("应该抛出InvalidBatchSize错误");
+      } catch (error: any) {
+        assertError(error, "InvalidBatchSize");
+      }
+    });
+
+    it("应该拒绝超过上限的接收者数量", async () => {
+      const users = Array.from({ length: 9 }, (_, i) => createTestUser(`BatchUser${i}`));
+
+      try {
+        await ctx.program.methods
+          .batchTransfer(users.map((user) => ({
+            recipient: user.publicKey,
+            amount: new anchor.BN(1000),
+          })))
+          .accounts(batchAccounts())
+          .remainingAccounts(users.flatMap((user) => recipientAccounts(user.publicKey)))
+          .rpc();
+        expect.fail("应该抛出InvalidBatchSize错误");
+      } catch (error: any) {
+        assertError(error, "InvalidBatchSize");
+      }
+    });
+
+    it("应该拒绝与接收者不匹配的remaining accounts", async () => {
+      try {
+        // 两个接收者只传入一组账户
+        await ctx.program.methods
+          .batchTransfer([
+            { recipient: sender.publicKey, amount: new anchor.BN(1000) },
+            { recipient: receiver.publicKey, amount: new anchor.BN(1000) },
+          ])
+          .accounts(batchAccounts())
+          .remainingAccounts(recipientAccounts(sender.publicKey))
+          .rpc();
+        expect.fail("应该抛出BatchAccountsMismatch错误");
+      } catch (error: any) {
+        assertError(error, "BatchAccountsMismatch");
+      }
+    });
+
+    it("应该拒绝不是接收者持有者PDA的持有者账户", async () => {
+      try {
+        await getAccount(ctx.connection, senderTokenAccount, undefined, TOKEN_2022_PROGRAM_ID);
+      } catch (error: any) {
+        console.log("⚠️  批量转账测试需要完整的系统初始化:", error.message);
+        return;
+      }
+
+      // 代币账户正确，持有者位置传入空账户，企图跳过冻结检查和折扣计算
+      const [tokenAccount] = recipientAccounts(ctx.wallet.publicKey);
+      try {
+        await ctx.program.methods
+          .batchTransfer([{ recipient: ctx.wallet.publicKey, amount: new anchor.BN(1000) }])
+          .accounts(batchAccounts())
+          .remainingAccounts([
+            tokenAccount,
+            { pubkey: Keypair.generate().publicKey, isSigner: false, isWritable: true },
+          ])
+          .rpc();
+        expect.fail("应该抛出BatchAccountsMismatch错误");
+      } catch (error: any) {
+        assertError(error, "BatchAccountsMismatch");
+      }
+    });
+  });
+
+  describe("支付引用", () => {
+    let totMint: PublicKey;
+    let budgetPda: PublicKey;
+    let platformVault: PublicKey;
+    let userTokenAccount: PublicKey;
+
+    before(async () => {
+      totMint = (await ctx.program.account.totConfig.fetch(configPda)).mint;
+      [budgetPda] = getPlatformBudgetPda(ctx.program.programId);
+      platformVault = getPlatformVaultAddress(ctx.program.programId, totMint);
+      userTokenAccount = getAssociatedTokenAddress(totMint, receiver.publicKey);
+    });
+
+    function platformTransfer(
+      reference: Buffer | null,
+      paymentReceipt: PublicKey | null,
+      amount: anchor.BN = new anchor.BN(1000)
+    ) {
+      return ctx.program.methods
+        .platformTransfer(amount, reference ? Array.from(reference) : null)
+        .accounts({
+          platform: ctx.wallet.publicKey,
+          platformVault,
+          userTokenAccount,
+          mint: totMint,
+          config: configPda,
+          userHolderInfo: null,
+          platformBudget: budgetPda,
+          recipientUsage: getPlatformRecipientUsagePda(ctx.program.programId, receiver.publicKey)[0],
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          paymentReceipt,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(hookAccounts(totMint, budgetPda, receiver.publicKey))
+        .rpc();
+    }
+
+    it("应该为支付引用创建回执，并拒绝重放", async () => {
+      const reference = Keypair.generate().publicKey.toBuffer();
+      const [receiptPda] = getPaymentReceiptPda(ctx.program.programId, budgetPda, reference);
+
+      try {
+        await platformTransfer(reference, receiptPda);
+      } catch (error: any) {
+        // 如果前置条件不满足（平台金库余额等），跳过
+        console.log("⚠️  支付引用测试需要完整的系统初始化:", error.message);
+        return;
+      }
+
+      const receipt = await ctx.program.account.paymentReceipt.fetch(receiptPda);
+      expect(Buffer.from(receipt.reference)).to.deep.equal(reference);
+      expect(receipt.payer.toString()).to.equal(budgetPda.toString());
+      expect(receipt.recipient.toString()).to.equal(receiver.publicKey.toString());
+      assertBNEqual(receipt.amount, new anchor.BN(1000));
+
+      // 后端超时重试：同一引用不能再次付款
+      try {
+        await platformTransfer(reference, receiptPda);
+        expect.fail("应该抛出DuplicatePaymentReference错误");
+      } catch (error: any) {
+        assertError(error, "DuplicatePaymentReference");
+      }
+    });
+
+    it("应该拒绝缺少回执账户的支付引用", async () => {
+      const reference = Keypair.generate().publicKey.toBuffer();
+
+      try {
+        await platformTransfer(reference, null);
+        expect.fail("应该抛出InvalidPaymentReceipt错误");
+      } catch (error: any) {
+        assertError(error, "InvalidPaymentReceipt");
+      }
+    });
+
+    it("应该拒绝不是由引用派生的回执账户", async () => {
+      const reference = Keypair.generate().publicKey.toBuffer();
+      const [otherReceiptPda] = getPaymentReceiptPda(
+        ctx.program.programId,
+        budgetPda,
+        Keypair.generate().publicKey.toBuffer()
+      );
+
+      try {
+        await platformTransfer(reference, otherReceiptPda);
+        expect.fail("应该抛出InvalidPaymentReceipt错误");
+      } catch (error: any) {
+        assertError(error, "InvalidPaymentReceipt");
+      }
+    });
+
+    it("应该拒绝其他付款方派生的回执账户", async () => {
+      const reference = Keypair.generate().publicKey.toBuffer();
+      const [senderReceiptPda] = getPaymentReceiptPda(ctx.program.programId, sender.publicKey, reference);
+
+      try {
+        await platformTransfer(reference, senderReceiptPda);
+        expect.fail("应该抛出InvalidPaymentReceipt错误");
+      } catch (error: any) {
+        assertError(error, "InvalidPaymentReceipt");
+      }
+    });
+
+    it("其他付款方抢先使用同一引用不应该阻止平台付款", async () => {
+      const reference = Keypair.generate().publicKey.toBuffer();
+      const [senderReceiptPda] = getPaymentReceiptPda(ctx.program.programId, sender.publicKey, reference);
+      const [platformReceiptPda] = getPaymentReceiptPda(ctx.program.programId, budgetPda, reference);
+      expect(senderReceiptPda.toString()).to.not.equal(platformReceiptPda.toString());
+
+      // 他人先用同一引用付款（或向平台的回执地址转入lamports）
+      try {
+        const [senderHolderPda] = getHolderPda(ctx.program.programId, sender.publicKey);
+        await ctx.program.methods
+          .transferWithTax(new anchor.BN(1000), false, Array.from(reference))
+          .accounts({
+            sender: sender.publicKey,
+            senderTokenAccount: getAssociatedTokenAddress(totMint, sender.publicKey),
+            receiverTokenAccount: userTokenAccount,
+            mint: totMint,
+            config: configPda,
+            taxConfig: taxConfigPda,
+            senderHolderInfo: senderHolderPda,
+            receiverHolderInfo: getHolderPda(ctx.program.programId, receiver.publicKey)[0],
+            taxCollector: getAssociatedTokenAddress(totMint, taxCollector.publicKey),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+            paymentReceipt: senderReceiptPda,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .remainingAccounts(hookAccounts(totMint, sender.publicKey, receiver.publicKey))
+          .signers([sender.keypair])
+          .rpc();
+      } catch (error: any) {
+        // 发送者没有余额时抢先付款失败，不影响下面的断言
+        console.log("⚠️  抢先付款未执行:", error.message);
+      }
+
+      try {
+        await platformTransfer(reference, platformReceiptPda);
+      } catch (error: any) {
+        // 平台付款不应该因为同一引用的其他回执失败
+        expect(error.toString()).to.not.include("DuplicatePaymentReference");
+        console.log("⚠️  支付引用测试需要完整的系统初始化:", error.message);
+        return;
+      }
+
+      const receipt = await ctx.program.account.paymentReceipt.fetch(platformReceiptPda);
+      expect(receipt.payer.toString()).to.equal(budgetPda.toString());
+      expect(Buffer.from(receipt.reference)).to.deep.equal(reference);
+    });
+
+    it("应该拒绝超出单个接收者额度的平台转账", async () => {
+      const [budgetPda] = getPlatformBudgetPda(ctx.program.programId);
+      const budget = await ctx.program.account.platformBudget.fetchNullable(budgetPda);
+      if (!budget) {
+        console.log("⚠️  平台预算测试需要先初始化平台预算");
+        return;
+      }
+
+      try {
+        await platformTransfer(null, null, budget.perRecipientCap.addn(1));
+        expect.fail("应该抛出RecipientBudgetExceeded错误");
+      } catch (error: any) {
+        assertError(error, "RecipientBudgetExceeded");
+      }
+    });
+  });
+
+  describe("代币流", () => {
+    let totMint: PublicKey;
+    let senderHolderPda: PublicKey;
+    let senderTokenAccount: PublicKey;
+
+    before(async () => {
+      totMint = (await ctx.program.account.totConfig.fetch(configPda)).mint;
+      [senderHolderPda] = getHolderPda(ctx.program.programId, ctx.wallet.publicKey);
+      senderTokenAccount = getAssociatedTokenAddress(totMint, ctx.wallet.publicKey);
+    });
+
+    function createStream(streamId: anchor.BN, startTime: number, endTime: number) {
+      const [streamPda] = getStreamPda(ctx.program.programId, ctx.wallet.publicKey, streamId);
+      return ctx.program.methods
+        .createStream(
+          streamId,
+          receiver.publicKey,
+          new anchor.BN(1_000_000),
+          new anchor.BN(startTime),
+          new anchor.BN(endTime)
+        )
+        .accounts({
+          sender: ctx.wallet.publicKey,
+          senderTokenAccount,
+          senderHolderInfo: senderHolderPda,
+          stream: streamPda,
+          escrowTokenAccount: getAssociatedTokenAddress(totMint, streamPda),
+          mint: totMint,
+          config: configPda,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .remainingAccounts(hookAccounts(totMint, ctx.wallet.publicKey, streamPda))
+        .rpc();
+    }
+
+    it("应该拒绝结束时间不晚于开始时间的代币流", async () => {
+      const now = Math.floor(Date.now() / 1000);
+
+      try {
+        await createStream(new anchor.BN(Date.now()), now + 3600, now + 3600);
+        expect.fail("应该抛出InvalidStreamSchedule错误");
+      } catch (error: any) {
+        assertError(error, "InvalidStreamSchedule");
+      }
+    });
+
+    it("应该拒绝已经结束的代币流", async () => {
+      const now = Math.floor(Date.now() / 1000);
+
+      try {
+        await createStream(new anchor.BN(Date.now()), now - 7200, now - 3600);
+        expect.fail("应该抛出InvalidStreamSchedule错误");
+      } catch (error: any) {
+        assertError(error, "InvalidStreamSchedule");
+      }
+    });
+
+    it("发送者取消后应该取回未释放部分，且不能重复取消", async () => {
+      const streamId = new anchor.BN(Date.now());
+      const [streamPda] = getStreamPda(ctx.program.programId, ctx.wallet.publicKey, streamId);
+      const now = Math.floor(Date.now() / 1000);
+
+      try {
+        await createStream(streamId, now + 3600, now + 7200);
+      } catch (error: any) {
+        // 如果前置条件不满足（发送者余额、持有者账户等），跳过
+        console.log("⚠️  代币流测试需要完整的系统初始化:", error.message);
+        return;
+      }
+
+      const cancelAccounts = {
+        sender: ctx.wallet.publicKey,
+        senderTokenAccount,
+        stream: streamPda,
+        escrowTokenAccount: getAssociatedTokenAddress(totMint, streamPda),
+        mint: totMint,
+        config: configPda,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      };
+
+      // 尚未开始释放，全部退回发送者
+      await ctx.program.methods
+        .cancelStream()
+        .accounts(cancelAccounts)
+        .remainingAccounts(hookAccounts(totMint, streamPda, ctx.wallet.publicKey))
+        .rpc();
+
+      const stream = await ctx.program.account.streamAccount.fetch(streamPda);
+      expect(stream.isCancelled).to.be.true;
+      assertBNEqual(stream.totalAmount, new anchor.BN(0));
+
+      try {
+        await ctx.program.methods
+          .cancelStream()
+          .accounts(cancelAccounts)
+          .remainingAccounts(hookAccounts(totMint, streamPda, ctx.wallet.publicKey))
+          .rpc();
+        expect.fail("应该抛出StreamAlreadyCancelled错误");
+      } catch (error: any) {
+        assertError(error, "StreamAlreadyCancelled");
+      }
+    });
+  });
+
+  describe("订阅", () => {
+    let totMint: PublicKey;
+    let userHolderPda: PublicKey;
+    let userTokenAccount: PublicKey;
+    let treasuryTokenAccount: PublicKey;
+    let subscriptionDelegate: PublicKey;
+    let treasury: PublicKey;
+
+    before(async () => {
+      const config = await ctx.program.account.totConfig.fetch(configPda);
+      totMint = config.mint;
+      treasury = config.twsTreasury;
+      [userHolderPda] = getHolderPda(ctx.program.programId, ctx.wallet.publicKey);
+      userTokenAccount = getAssociatedTokenAddress(totMint, ctx.wallet.publicKey);
+      treasuryTokenAccount = getAssociatedTokenAddress(totMint, treasury);
+      [subscriptionDelegate] = getSubscriptionDelegatePda(ctx.program.programId);
+    });
+
+    function createSubscription(subscriptionId: anchor.BN, amount: number, periodSeconds: number, maxPeriods: number) {
+      const [subscriptionPda] = getSubscriptionPda(ctx.program.programId, ctx.wallet.publicKey, subscriptionId);
+      return ctx.program.methods
+        .createSubscription(
+          subscriptionId,
+          { other: {} },
+          0,
+          new anchor.BN(amount),
+          new anchor.BN(periodSeconds),
+          maxPeriods
+        )
+        .accounts({
+          user: ctx.wallet.publicKey,
+          userTokenAccount,
+          userHolderInfo: userHolderPda,
+          subscription: subscriptionPda,
+          subscriptionDelegate,
+          priceCatalog: getPriceCatalogPda(ctx.program.programId)[0],
+          mint: totMint,
+          config: configPda,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+    }
+
+    function chargeSubscription(subscriptionPda: PublicKey) {
+      return ctx.program.methods
+        .chargeSubscription()
+        .accounts({
+          subscription: subscriptionPda,
+          userTokenAccount,
+          userHolderInfo: userHolderPda,
+          subscriptionDelegate,
+          treasuryTokenAccount,
+          beneficiaryTokenAccount: null,
+          priceCatalog: getPriceCatalogPda(ctx.program.programId)[0],
+          mint: totMint,
+          config: configPda,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .remainingAccounts(hookAccounts(totMint, ctx.wallet.publicKey, treasury))
+        .rpc();
+    }
+
+    it("应该拒绝最大期数为0的订阅计划", async () => {
+      try {
+        await createSubscription(new anchor.BN(Date.now()), 1_000_000, 86400, 0);
+        expect.fail("应该抛出InvalidSubscriptionPlan错误");
+      } catch (error: any) {
+        assertError(error, "InvalidSubscriptionPlan");
+      }
+    });
+
+    it("到期扣一期后未到期应该拒绝，取消后应该收回授权", async () => {
+      const subscriptionId = new anchor.BN(Date.now());
+      const [subscriptionPda] = getSubscriptionPda(ctx.program.programId, ctx.wallet.publicKey, subscriptionId);
+
+      try {
+        await createSubscription(subscriptionId, 1_000_000, 86400, 3);
+      } catch (error: any) {
+        // 如果前置条件不满足（持有者账户、价格目录等），跳过
+        console.log("⚠️  订阅测试需要完整的系统初始化:", error.message);
+        return;
+      }
+
+      const approved = await getAccount(ctx.connection, userTokenAccount, undefined, TOKEN_2022_PROGRAM_ID);
+      expect(approved.delegate?.toBase58()).to.equal(subscriptionDelegate.toBase58());
+
+      // 第一期创建后立即到期
+      await chargeSubscription(subscriptionPda);
+      const subscription = await ctx.program.account.subscription.fetch(subscriptionPda);
+      expect(subscription.periodsCharged).to.equal(1);
+
+      try {
+        await chargeSubscription(subscriptionPda);
+        expect.fail("应该抛出SubscriptionNotDue错误");
+      } catch (error: any) {
+        assertError(error, "SubscriptionNotDue");
+      }
+
+      await ctx.program.methods
+        .cancelSubscription()
+        .accounts({
+          user: ctx.wallet.publicKey,
+          subscription: subscriptionPda,
+          userTokenAccount,
+          subscriptionDelegate,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .rpc();
+
+      const closed = await ctx.connection.getAccountInfo(subscriptionPda);
+      expect(closed).to.be.null;
+    });
+  });
+
+  describe("P2P挂单", () => {
+    let totMint: PublicKey;
+    let makerHolderPda: PublicKey;
+    let makerTokenAccount: PublicKey;
+
+    before(async () => {
+      totMint = (await ctx.program.account.totConfig.fetch(configPda)).mint;
+      [makerHolderPda] = getHolderPda(ctx.program.programId, ctx.wallet.publicKey);
+      makerTokenAccount = getAssociatedTokenAddress(totMint, ctx.wallet.publicKey);
+    });
+
+    function createOffer(offerId: anchor.BN, askMint: PublicKey, askAmount: anchor.BN) {
+      const [offerPda] = getOfferPda(ctx.program.programId, ctx.wallet.publicKey, offerId);
+      return ctx.program.methods
+        .createOffer(offerId, new anchor.BN(1_000_000), askAmount)
+        .accounts({
+          maker: ctx.wallet.publicKey,
+          makerTokenAccount,
+          makerHolderInfo: makerHolderPda,
+          offer: offerPda,
+          escrowTokenAccount: getAssociatedTokenAddress(totMint, offerPda),
+          mint: totMint,
+          askMint,
+          config: configPda,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        })
+        .remainingAccounts(hookAccounts(totMint, ctx.wallet.publicKey, offerPda))
+        .rpc();
+    }
+
+    it("应该拒绝以TOT本身为要价的挂单", async () => {
+      try {
+        await createOffer(new anchor.BN(Date.now()), totMint, new anchor.BN(1000));
+        expect.fail("应该抛出InvalidOfferTerms错误");
+      } catch (error: any) {
+        assertError(error, "InvalidOfferTerms");
+      }
+    });
+
+    it("应该拒绝要价为0的挂单", async () => {
+      try {
+        await createOffer(new anchor.BN(Date.now()), NATIVE_MINT, new anchor.BN(0));
+        expect.fail("应该抛出InvalidOfferTerms错误");
+      } catch (error: any) {
+        assertError(error, "InvalidOfferTerms");
+      }
+    });
+
+    it("撤单后应该取回全部TOT，且不能再次撤单", async () => {
+      const offerId = new anchor.BN(Date.now());
+      const [offerPda] = getOfferPda(ctx.program.programId, ctx.wallet.publicKey, offerId);
+
+      try {
+        await createOffer(offerId, NATIVE_MINT, new anchor.BN(5_000_000));
+      } catch (error: any) {
+        // 如果前置条件不满足（挂单方余额、持有者账户等），跳过
+        console.log("⚠️  P2P挂单测试需要完整的系统初始化:", error.message);
+        return;
+      }
+
+      const offer = await ctx.program.account.tradeOffer.fetch(offerPda);
+      expect(offer.askMint.toString()).to.equal(NATIVE_MINT.toString());
+      assertBNEqual(offer.totRemaining, offer.totAmount);
+
+      const cancelAccounts = {
+        maker: ctx.wallet.publicKey,
+        makerTokenAccount,
+        offer: offerPda,
+        escrowTokenAccount: getAssociatedTokenAddress(totMint, offerPda),
+        mint: totMint,
+        config: configPda,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      };
+
+      await ctx.program.methods
+        .cancelOffer()
+        .accounts(cancelAccounts)
+        .remainingAccounts(hookAccounts(totMint, offerPda, ctx.wallet.publicKey))
+        .rpc();
+
+      const cancelled = await ctx.program.account.tradeOffer.fetch(offerPda);
+      expect(cancelled.isCancelled).to.be.true;
+      assertBNEqual(cancelled.totRemaining, new anchor.BN(0));
+
+      try {
+        await ctx.program.methods
+          .cancelOffer()
+          .accounts(cancelAccounts)
+          .remainingAccounts(hookAccounts(totMint, offerPda, ctx.wallet.publicKey))
+          .rpc();
+        expect.fail("应该抛出OfferClosed错误");
+      } catch (error: any) {
+        assertError(error, "OfferClosed");
+      }
+    });
+  });
+
+  describe("Transfer Hook额外账户列表", () => {
+    let hookConfigPda: PublicKey;
+    let extraAccountMetaListPda: PublicKey;
+    let totMint: PublicKey;
+
+    before(async () => {
+      [hookConfigPda] = getHookConfigPda(ctx.hookProgram.programId);
+      totMint = (await ctx.program.account.totConfig.fetch(configPda)).mint;
+      [extraAccountMetaListPda] = getExtraAccountMetaListPda(ctx.hookProgram.programId, totMint);
+
+      // 确保Hook配置已初始化（签名者必须是TOT系统管理员）
+      try {
+        await ctx.hookProgram.methods
+          .initializeTransferHook(totMint, configPda)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+            hookConfig: hookConfigPda,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc();
+      } catch (error: any) {
+        if (!error.toString().includes("already in use")) {
+          throw error;
+        }
+      }
+    });
+
+    it("应该成功初始化额外账户列表", async () => {
+      await ctx.hookProgram.methods
+        .initializeExtraAccountMetaList()
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          extraAccountMetaList: extraAccountMetaListPda,
+          mint: totMint,
+          hookConfig: hookConfigPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      const info = await ctx.connection.getAccountInfo(extraAccountMetaListPda);
+      expect(info).to.not.be.null;
+      expect(info!.owner.toString()).to.equal(ctx.hookProgram.programId.toString());
+      // TLV头（8+4）+ 列表长度（4）+ 7个额外账户（35字节×7）
+      expect(info!.data.length).to.equal(8 + 4 + 4 + 35 * 7);
+    });
+
+    it("应该可以重写额外账户列表", async () => {
+      await ctx.hookProgram.methods
+        .updateExtraAccountMetaList()
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          extraAccountMetaList: extraAccountMetaListPda,
+          mint: totMint,
+          hookConfig: hookConfigPda,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+      const info = await ctx.connection.getAccountInfo(extraAccountMetaListPda);
+      expect(info!.data.length).to.equal(8 + 4 + 4 + 35 * 7);
+    });
+
+    it("应该拒绝非Hook管理员更新额外账户列表", async () => {
+      const attacker = createTestUser("HookAttacker");
+
+      try {
+        await ctx.hookProgram.methods
+          .updateExtraAccountMetaList()
+          .accounts({
+            authority: attacker.publicKey,
+            extraAccountMetaList: extraAccountMetaListPda,
+            mint: totMint,
+            hookConfig: hookConfigPda,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([attacker.keypair])
+          .rpc();
+        expect.fail("应该抛出Unauthorized错误");
+      } catch (error: any) {
+        assertError(error, "Unauthorized");
+      }
+    });
+  });
+
+  describe("Transfer Hook转账", () => {
+    let totMint: PublicKey;
+    let decimals: number;
+    let walletTokenAccount: PublicKey;
+    let receiverTokenAccount: PublicKey;
+
+    before(async () => {
+      totMint = (await ctx.program.account.totConfig.fetch(configPda)).mint;
+      decimals = (await getMint(ctx.connection, totMint, undefined, TOKEN_2022_PROGRAM_ID)).decimals;
+      walletTokenAccount = getAssociatedTokenAddress(totMint, ctx.wallet.publicKey);
+      receiverTokenAccount = getAssociatedTokenAddress(totMint, receiver.publicKey);
+
+      // 确保转账守卫已初始化（Hook据此为钱包转账更新持有者统计）
+      try {
+        await ctx.program.methods
+          .initializeTransferGuard()
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+            transferGuard: getTransferGuardPda(ctx.program.programId)[0],
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc();
+      } catch (error: any) {
+        if (!error.toString().includes("already in use")) {
+          throw error;
+        }
+      }
+    });
+
+    /**
+     * 直接调用Token-2022的transferChecked（不经过主程序）
+     */
+    async function transferChecked(amount: bigint, withHookAccounts: boolean) {
+      const ix = createTransferCheckedInstruction(
+        walletTokenAccount,
+        totMint,
+        receiverTokenAccount,
+        ctx.wallet.publicKey,
+        amount,
+        decimals,
+        [],
+        TOKEN_2022_PROGRAM_ID
+      );
+      if (withHookAccounts) {
+        ix.keys.push(...hookAccounts(totMint, ctx.wallet.publicKey, receiver.publicKey));
+      }
+      return ctx.provider.sendAndConfirm(new Transaction().add(ix));
+    }
+
+    it("Mint的Transfer Hook扩展应该指向Hook程序", async () => {
+      const mint = await getMint(ctx.connection, totMint, undefined, TOKEN_2022_PROGRAM_ID);
+      const transferHook = getTransferHook(mint);
+      expect(transferHook).to.not.be.null;
+      expect(transferHook!.programId.toString()).to.equal(ctx.hookProgram.programId.toString());
+    });
+
+    it("携带额外账户的transferChecked应该经过Hook完成转账", async () => {
+      const amount = BigInt(1_000_000);
+      const [hookConfigPda] = getHookConfigPda(ctx.hookProgram.programId);
+
+      let before: any;
+      try {
+        await getAccount(ctx.connection, walletTokenAccount, undefined, TOKEN_2022_PROGRAM_ID);
+        before = await ctx.hookProgram.account.transferHookConfig.fetch(hookConfigPda);
+      } catch (error: any) {
+        // 如果前置条件不满足（钱包余额、Hook配置等），跳过
+        console.log("⚠️  Hook转账测试需要完整的系统初始化:", error.message);
+        return;
+      }
+
+      const receiverBefore = await getAccount(ctx.connection, receiverTokenAccount, undefined, TOKEN_2022_PROGRAM_ID)
+        .then((account) => account.amount)
+        .catch(() => BigInt(0));
+
+      await transferChecked(amount, true);
+
+      const receiverAfter = await getAccount(ctx.connection, receiverTokenAccount, undefined, TOKEN_2022_PROGRAM_ID);
+      expect(receiverAfter.amount > receiverBefore).to.be.true;
+
+      const after = await ctx.hookProgram.account.transferHookConfig.fetch(hookConfigPda);
+      assertBNEqual(after.totalTransfers, before.totalTransfers.addn(1));
+    });
+
+    it("钱包直接转账应该由Hook更新接收者的持有者统计", async () => {
+      const amount = BigInt(1_000_000);
+      const [receiverHolderPda] = getHolderPda(ctx.program.programId, receiver.publicKey);
+
+      let before: any;
+      try {
+        await getAccount(ctx.connection, walletTokenAccount, undefined, TOKEN_2022_PROGRAM_ID);
+        before = await ctx.program.account.holderAccount.fetch(receiverHolderPda);
+      } catch (error: any) {
+        // 如果前置条件不满足（钱包余额、接收者持有者账户等），跳过
+        console.log("⚠️  Hook持有者统计测试需要完整的系统初始化:", error.message);
+        return;
+      }
+
+      await transferChecked(amount, true);
+
+      const after = await ctx.program.account.holderAccount.fetch(receiverHolderPda);
+      expect(after.totalBought.gt(before.totalBought)).to.be.true;
+
+      const guard = await ctx.program.account.transferGuard.fetch(
+        getTransferGuardPda(ctx.program.programId)[0]
+      );
+      expect(guard.inProgress).to.be.false;
+    });
+
+    it("主程序发起的转账不应该由Hook重复计入持有者统计", async () => {
+      const amount = new anchor.BN(1_000_000);
+      const [senderHolderPda] = getHolderPda(ctx.program.programId, ctx.wallet.publicKey);
+      const [receiverHolderPda] = getHolderPda(ctx.program.programId, receiver.publicKey);
+
+      let before: any;
+      try {
+        before = await ctx.program.account.holderAccount.fetch(receiverHolderPda);
+        await ctx.program.methods
+          .transferWithTax(amount, false, null)
+          .accounts({
+            sender: ctx.wallet.publicKey,
+            senderTokenAccount: walletTokenAccount,
+            receiverTokenAccount,
+            mint: totMint,
+            config: configPda,
+            taxConfig: taxConfigPda,
+            senderHolderInfo: senderHolderPda,
+            receiverHolderInfo: receiverHolderPda,
+            taxCollector: getAssociatedTokenAddress(totMint, taxCollector.publicKey),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .remainingAccounts(hookAccounts(totMint, ctx.wallet.publicKey, receiver.publicKey))
+          .rpc();
+      } catch (error: any) {
+        console.log("⚠️  Hook持有者统计测试需要完整的系统初始化:", error.message);
+        return;
+      }
+
+      // 只由transfer_with_tax按净额记一次买入，不超过转账金额
+      const after = await ctx.program.account.holderAccount.fetch(receiverHolderPda);
+      const bought = after.totalBought.sub(before.totalBought);
+      expect(bought.gtn(0)).to.be.true;
+      expect(bought.lte(amount)).to.be.true;
+    });
+
+    it("缺少额外账户的transferChecked应该失败", async () => {
+      let succeeded = false;
+      try {
+        await transferChecked(BigInt(1), false);
+        succeeded = true;
+      } catch (error: any) {
+        // Token-2022无法调用Hook，整笔转账回滚
+      }
+      expect(succeeded).to.be.false;
+    });
+
+    it("转账暂停时紧急提取应该通过Hook完成转账", async () => {
+      const amount = new anchor.BN(1_000_000);
+
+      try {
+        await getAccount(ctx.connection, walletTokenAccount, undefined, TOKEN_2022_PROGRAM_ID);
+      } catch (error: any) {
+        console.log("⚠️  紧急提取测试需要完整的系统初始化:", error.message);
+        return;
+      }
+
+      const receiverBefore = await getAccount(ctx.connection, receiverTokenAccount, undefined, TOKEN_2022_PROGRAM_ID)
+        .then((account) => account.amount)
+        .catch(() => BigInt(0));
+
+      await ctx.program.methods
+        .setPaused([{ transfers: {} }], true, null)
+        .accounts({
+          authority: ctx.wallet.publicKey,
+          config: configPda,
+        })
+        .rpc();
+
+      try {
+        // 普通钱包转账被Hook拒绝
+        let plainTransferSucceeded = false;
+        try {
+          await transferChecked(BigInt(1), true);
+          plainTransferSucceeded = true;
+        } catch (error: any) {
+          // 转账指令族已暂停
+        }
+        expect(plainTransferSucceeded).to.be.false;
+
+        // 管理员签名的紧急提取不受Hook的暂停检查约束
+        await ctx.program.methods
+          .emergencyWithdraw(amount)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+            sourceAccount: walletTokenAccount,
+            destinationAccount: receiverTokenAccount,
+            mint: totMint,
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .remainingAccounts(hookAccounts(totMint, ctx.wallet.publicKey, receiver.publicKey))
+          .rpc();
+
+        const receiverAfter = await getAccount(ctx.connection, receiverTokenAccount, undefined, TOKEN_2022_PROGRAM_ID);
+        expect(receiverAfter.amount > receiverBefore).to.be.true;
+      } finally {
+        await ctx.program.methods
+          .setPaused([{ transfers: {} }], false, null)
+          .accounts({
+            authority: ctx.wallet.publicKey,
+            config: configPda,
+          })
+          .rpc();
+      }
+    });
+
+    it("主程序转账未传入Hook账户时应该拒绝", async () => {
+      const [senderHolderPda] = getHolderPda(ctx.program.programId, ctx.wallet.publicKey);
+
+      try {
+        await ctx.program.methods
+          .transferWithTax(new anchor.BN(1_000_000), false, null)
+          .accounts({
+            sender: ctx.wallet.publicKey,
+            senderTokenAccount: walletTokenAccount,
+            receiverTokenAccount,
+            mint: totMint,
+            config: configPda,
+            taxConfig: taxConfigPda,
+            senderHolderInfo: senderHolderPda,
+            receiverHolderInfo: getHolderPda(ctx.program.programId, receiver.publicKey)[0],
+            taxCollector: getAssociatedTokenAddress(totMint, taxCollector.publicKey),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .rpc();
+        expect.fail("应该抛出TransferHookFailed错误");
+      } catch (error: any) {
+        assertError(error, "TransferHookFailed");
+      }
+    });
+  });
+
+  describe("协议统计", () => {
+    const fetchStats = async () => {
+      const [hookConfigPda] = getHookConfigPda(ctx.hookProgram.programId);
+      return ctx.program.methods
+        .getProtocolStats()
+        .accounts({
+          config: configPda,
+          mint: mintPublicKey,
+          hookConfig: hookConfigPda,
+        })
+        .view();
+    };
+
+    it("流通供应量与锁定供应量之和应该等于Mint总供应量", async () => {
+      const stats = await fetchStats();
+      if (stats.lockedSupply.gte(stats.mintSupply)) {
+        // 尚无流通供应量时按总供应量计算
+        assertBNEqual(stats.circulatingSupply, stats.mintSupply);
+      } else {
+        assertBNEqual(stats.circulatingSupply.add(stats.lockedSupply), stats.mintSupply);
+      }
+      expect(stats.totalConsumed).to.have.lengthOf(8);
+    });
+
+    it("带税转账后收税和销毁统计应该增加", async () => {
+      const before = await fetchStats();
+      const [senderHolderPda] = getHolderPda(ctx.program.programId, sender.publicKey);
+
+      try {
+        await ctx.program.methods
+          .transferWithTax(new anchor.BN(1_000_000), false, null)
+          .accounts({
+            sender: sender.publicKey,
+            senderTokenAccount: getAssociatedTokenAddress(mintPublicKey, sender.publicKey),
+            receiverTokenAccount: getAssociatedTokenAddress(mintPublicKey, receiver.publicKey),
+            mint: mintPublicKey,
+            config: configPda,
+            taxConfig: taxConfigPda,
+            senderHolderInfo: senderHolderPda,
+            receiverHolderInfo: getHolderPda(ctx.program.programId, receiver.publicKey)[0],
+            taxCollector: getAssociatedTokenAddress(mintPublicKey, taxCollector.publicKey),
+            tokenProgram: TOKEN_2022_PROGRAM_ID,
+          })
+          .remainingAccounts(hookAccounts(mintPublicKey, sender.publicKey, receiver.publicKey))
+          .signers([sender.keypair])
+          .rpc();
+      } catch (error: any) {
+        console.log("⚠️  协议统计测试需要完整的系统初始化:", error.message);
+        return;
+      }
+
+      const after = await fetchStats();
+      expect(after.totalTaxCollected.gt(before.totalTaxCollected)).to.be.true;
+      expect(after.totalBurned.gt(before.totalBurned)).to.be.true;
+    });
   });
 });